@@ -0,0 +1,123 @@
+//! [ResolveOptions::adaptive_extension_probing] over [MemoryFileSystem], with the `metadata`
+//! calls of each mode counted and printed before they are measured:
+//! `cargo bench --bench extension_probing`.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use oxc_resolver::{
+    FileMetadata, FileSystem, MemoryFileSystem, ResolveError, ResolveOptions, ResolverGeneric,
+};
+
+/// Counts the `metadata` and `symlink_metadata` calls.
+struct CountingFileSystem {
+    fs: MemoryFileSystem,
+    calls: AtomicUsize,
+}
+
+impl FileSystem for CountingFileSystem {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.fs.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.fs.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.fs.symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.fs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.fs.read_dir(path)
+    }
+}
+
+/// A TypeScript project importing 100 extensionless files in each of 10 directories, with
+/// `.js` and `.jsx` tried before `.ts`.
+fn project() -> (Arc<CountingFileSystem>, Vec<String>) {
+    let fs = MemoryFileSystem::default();
+    fs.add_file("/project/src/index.ts", "");
+    let specifiers = (0..10)
+        .flat_map(|directory| (0..100).map(move |file| format!("./dir{directory}/file{file}")))
+        .inspect(|specifier| fs.add_file(format!("/project/src/{}.ts", &specifier[2..]), ""))
+        .collect();
+    (Arc::new(CountingFileSystem { fs, calls: AtomicUsize::new(0) }), specifiers)
+}
+
+fn resolver(
+    fs: &Arc<CountingFileSystem>,
+    adaptive_extension_probing: bool,
+) -> ResolverGeneric<Arc<CountingFileSystem>> {
+    let options = ResolveOptions {
+        extensions: vec![".js".into(), ".jsx".into(), ".ts".into(), ".tsx".into()],
+        adaptive_extension_probing,
+        ..ResolveOptions::default()
+    };
+    ResolverGeneric::new_with_file_system(Arc::clone(fs), options)
+}
+
+fn resolve_all(resolver: &ResolverGeneric<Arc<CountingFileSystem>>, specifiers: &[String]) {
+    for specifier in specifiers {
+        resolver.resolve("/project/src", specifier).unwrap();
+    }
+}
+
+fn bench_extension_probing(c: &mut Criterion) {
+    let (fs, specifiers) = project();
+    let mut calls = [0; 2];
+    for (adaptive, calls) in [false, true].into_iter().zip(&mut calls) {
+        fs.calls.store(0, Ordering::Relaxed);
+        resolve_all(&resolver(&fs, adaptive), &specifiers);
+        *calls = fs.calls.load(Ordering::Relaxed);
+        println!("adaptive_extension_probing: {adaptive}, metadata calls: {calls}");
+    }
+    assert!(calls[1] < calls[0], "{calls:?}");
+
+    let mut group = c.benchmark_group("extension_probing");
+    for adaptive in [false, true] {
+        let resolver = resolver(&fs, adaptive);
+        let name = if adaptive { "adaptive" } else { "configured" };
+        group.bench_with_input(BenchmarkId::from_parameter(name), &resolver, |b, resolver| {
+            b.iter(|| {
+                resolver.clear_cache();
+                resolve_all(resolver, &specifiers);
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(extension_probing, bench_extension_probing);
+criterion_main!(extension_probing);
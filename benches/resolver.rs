@@ -448,12 +448,87 @@ fn bench_tsconfig_paths_aliases(c: &mut Criterion) {
     );
 }
 
+fn bench_many_aliases(c: &mut Criterion) {
+    use oxc_resolver::{AliasValue, ResolveOptions, ResolverGeneric};
+
+    let mut group = c.benchmark_group("many_aliases_memory");
+    let alias_count = 1000usize;
+    let cwd = env::current_dir().unwrap();
+    let f = cwd.join("fixtures/enhanced-resolve/test/fixtures");
+    // Generated code imports many specifiers under a handful of prefixes.
+    let keys = (0..alias_count)
+        .map(|i| format!("@gen/{}/module{i:04}", ["a", "b", "c", "d"][i % 4]))
+        .collect::<Vec<_>>();
+    let targets = ["a.js", "b.js", "c.js"];
+    let alias = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let target = f.join(targets[i % targets.len()]);
+            (key.clone(), vec![AliasValue::from(target.to_string_lossy().as_ref())])
+        })
+        .collect();
+    let resolver = ResolverGeneric::new_with_file_system(
+        BenchMemoryFS::new(),
+        ResolveOptions { alias, ..ResolveOptions::default() },
+    );
+
+    // Check validity by resolving every alias once.
+    for key in &keys {
+        assert!(resolver.resolve(&f, key).is_ok(), "alias_count={alias_count} request={key}");
+    }
+
+    group.bench_with_input(BenchmarkId::from_parameter("query each alias"), &keys, |b, keys| {
+        b.iter(|| {
+            for key in keys {
+                _ = resolver.resolve(&f, key);
+            }
+        });
+    });
+
+    group.bench_with_input(BenchmarkId::from_parameter("relative requests"), &f, |b, f| {
+        b.iter(|| {
+            for request in ["./a.js", "./b.js", "./c.js", "./lib/index"] {
+                _ = resolver.resolve(f, request);
+            }
+        });
+    });
+}
+
+fn bench_deeply_nested(c: &mut Criterion) {
+    use oxc_resolver::{ResolveOptions, ResolverGeneric};
+
+    let mut group = c.benchmark_group("deeply_nested_memory");
+    let depth = 25usize;
+    let (fs, nested, requests) = BenchMemoryFS::with_deeply_nested_fixture(depth, 200);
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    // Check validity by resolving every package once.
+    for request in &requests {
+        assert!(resolver.resolve(&nested, request).is_ok(), "depth={depth} request={request}");
+    }
+
+    group.bench_with_input(
+        BenchmarkId::from_parameter("bare specifiers"),
+        &requests,
+        |b, requests| {
+            b.iter(|| {
+                for request in requests {
+                    _ = resolver.resolve(&nested, request);
+                }
+            });
+        },
+    );
+}
+
 criterion_group!(
     resolver,
     bench_resolver_memory,
     bench_resolver_real,
     bench_package_json_deserialization,
-    bench_tsconfig_paths_aliases
+    bench_tsconfig_paths_aliases,
+    bench_many_aliases,
+    bench_deeply_nested
 );
 criterion_main!(resolver);
 
@@ -521,6 +596,31 @@ mod memory_fs {
             (fs, importer, tsconfig_path, requests)
         }
 
+        /// Generated `depth` levels below a directory with `package_count` packages in its
+        /// `node_modules`, returns the deepest directory and a request for each package.
+        pub fn with_deeply_nested_fixture(
+            depth: usize,
+            package_count: usize,
+        ) -> (Self, PathBuf, Vec<String>) {
+            let mut fs = Self::new();
+            let root = std::env::current_dir().unwrap().join("fixtures/bench-deeply-nested");
+            let nested = (0..depth).fold(root.clone(), |dir, i| dir.join(format!("d{i}")));
+            fs.add_file(nested.join("index.js"));
+            let requests = (0..package_count)
+                .map(|i| {
+                    let name = format!("pkg{i:04}");
+                    fs.add_file(root.join("node_modules").join(&name).join("index.js"));
+                    name
+                })
+                .collect();
+            (fs, nested, requests)
+        }
+
+        fn add_file(&mut self, path: PathBuf) {
+            self.add_parent_directories(&path);
+            self.files.insert(path, vec![]);
+        }
+
         fn add_parent_directories(&mut self, path: &Path) {
             // Add all parent directories of a path
             for ancestor in path.ancestors().skip(1) {
@@ -0,0 +1,244 @@
+//! Hermetic benchmarks of the hot paths of the resolver, over [MemoryFileSystem].
+//!
+//! Every scenario checks its resolutions before it is measured, so the suite doubles as an
+//! integration test: `cargo bench --bench scenarios`.
+
+use std::path::{Path, PathBuf};
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use oxc_resolver::{
+    AliasValue, MemoryFileSystem, ResolveOptions, ResolverGeneric, TsconfigDiscovery,
+    TsconfigOptions, TsconfigReferences,
+};
+
+/// A resolver and the requests it is measured with.
+pub struct Scenario {
+    pub name: &'static str,
+    pub resolver: ResolverGeneric<MemoryFileSystem>,
+    /// `(importer, specifier, expected path)`
+    pub requests: Vec<(PathBuf, String, PathBuf)>,
+    /// Resolve with [ResolverGeneric::resolve_file], which applies the tsconfig of the importer.
+    pub resolve_file: bool,
+}
+
+impl Scenario {
+    fn resolve(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        let resolution = if self.resolve_file {
+            self.resolver.resolve_file(importer, specifier)
+        } else {
+            self.resolver.resolve(importer, specifier)
+        };
+        resolution.ok().map(|resolution| resolution.full_path())
+    }
+
+    /// Check that every request resolves to its expected path.
+    ///
+    /// # Panics
+    ///
+    /// * When a request resolves to another path or fails.
+    pub fn verify(&self) {
+        for (importer, specifier, expected) in &self.requests {
+            let resolved = self.resolve(importer, specifier);
+            assert_eq!(
+                resolved.as_ref(),
+                Some(expected),
+                "{}: resolve({}, {specifier:?})",
+                self.name,
+                importer.display()
+            );
+        }
+    }
+
+    fn resolve_all(&self) {
+        for (importer, specifier, _) in &self.requests {
+            _ = self.resolve(importer, specifier);
+        }
+    }
+}
+
+/// All scenarios, for running them outside of criterion, e.g.
+/// `scenarios().iter().for_each(Scenario::verify)`.
+#[must_use]
+pub fn scenarios() -> Vec<Scenario> {
+    vec![
+        node_modules_walk(),
+        exports_wildcards(),
+        tsconfig_paths(),
+        relative_extensions(),
+        aliases(),
+        pnpm_symlinks(),
+    ]
+}
+
+/// Bare specifiers imported from 10 directories below the `node_modules` containing them.
+fn node_modules_walk() -> Scenario {
+    let fs = MemoryFileSystem::default();
+    let importer = (0..10).fold(PathBuf::from("/project"), |dir, i| dir.join(format!("level{i}")));
+    fs.add_file(importer.join("index.js"), "");
+    let requests = (0..100)
+        .map(|i| {
+            let package = format!("/project/node_modules/pkg{i}");
+            fs.add_file(format!("{package}/package.json"), r#"{ "main": "./lib/main.js" }"#);
+            fs.add_file(format!("{package}/lib/main.js"), "");
+            let expected = PathBuf::from(format!("{package}/lib/main.js"));
+            (importer.clone(), format!("pkg{i}"), expected)
+        })
+        .collect();
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    Scenario { name: "node_modules_walk", resolver, requests, resolve_file: false }
+}
+
+/// A package with 500 `exports` wildcard keys.
+fn exports_wildcards() -> Scenario {
+    let fs = MemoryFileSystem::default();
+    let exports = (0..500)
+        .map(|i| format!(r#""./feature{i}/*": "./dist/feature{i}/*.js""#))
+        .collect::<Vec<_>>()
+        .join(",");
+    fs.add_file(
+        "/project/node_modules/pkg/package.json",
+        format!(r#"{{ "name": "pkg", "exports": {{ {exports} }} }}"#),
+    );
+    let requests = (0..500)
+        .step_by(5)
+        .map(|i| {
+            let expected = format!("/project/node_modules/pkg/dist/feature{i}/module.js");
+            fs.add_file(&expected, "");
+            (PathBuf::from("/project/src"), format!("pkg/feature{i}/module"), expected.into())
+        })
+        .collect();
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    Scenario { name: "exports_wildcards", resolver, requests, resolve_file: false }
+}
+
+/// A tsconfig with 300 `compilerOptions.paths` entries.
+fn tsconfig_paths() -> Scenario {
+    let fs = MemoryFileSystem::default();
+    let paths = (0..300)
+        .map(|i| format!(r#""@module{i}/*": ["./src/module{i}/*"]"#))
+        .collect::<Vec<_>>()
+        .join(",");
+    fs.add_file(
+        "/project/tsconfig.json",
+        format!(r#"{{ "compilerOptions": {{ "paths": {{ {paths} }} }} }}"#),
+    );
+    let importer = PathBuf::from("/project/src/index.ts");
+    fs.add_file(&importer, "");
+    let requests = (0..300)
+        .step_by(3)
+        .map(|i| {
+            let expected = format!("/project/src/module{i}/util.ts");
+            fs.add_file(&expected, "");
+            (importer.clone(), format!("@module{i}/util"), expected.into())
+        })
+        .collect();
+    let options = ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: "/project/tsconfig.json".into(),
+            references: TsconfigReferences::Disabled,
+        })),
+        extensions: vec![".ts".into(), ".js".into()],
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options);
+    Scenario { name: "tsconfig_paths", resolver, requests, resolve_file: true }
+}
+
+/// Relative imports across deep directories, probing 8 extensions.
+fn relative_extensions() -> Scenario {
+    let extensions = [".tsx", ".ts", ".mts", ".cts", ".jsx", ".js", ".mjs", ".json"];
+    let fs = MemoryFileSystem::default();
+    let importer = PathBuf::from("/project/src/a/b/c/d/e/f");
+    fs.add_file(importer.join("index.ts"), "");
+    let requests = (0..100)
+        .map(|i| {
+            let extension = extensions[i % extensions.len()];
+            let expected = format!("/project/src/shared/g/h/file{i}{extension}");
+            fs.add_file(&expected, "");
+            (importer.clone(), format!("../../../../../../shared/g/h/file{i}"), expected.into())
+        })
+        .collect();
+    let options = ResolveOptions {
+        extensions: extensions.into_iter().map(String::from).collect(),
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options);
+    Scenario { name: "relative_extensions", resolver, requests, resolve_file: false }
+}
+
+/// 500 aliases, a few of them pointing at other aliases.
+fn aliases() -> Scenario {
+    let fs = MemoryFileSystem::default();
+    let mut alias = (0..500)
+        .map(|i| {
+            let target = format!("/project/src/components/component{i}");
+            fs.add_file(format!("{target}/index.js"), "");
+            (format!("@components/component{i}"), vec![AliasValue::from(target.as_str())])
+        })
+        .collect::<Vec<_>>();
+    alias.push(("@ui".into(), vec![AliasValue::from("@components")]));
+    alias.push(("@components".into(), vec![AliasValue::from("/project/src/components")]));
+    let importer = PathBuf::from("/project/src");
+    let requests = (0..500)
+        .step_by(5)
+        .flat_map(|i| {
+            let expected = PathBuf::from(format!("/project/src/components/component{i}/index.js"));
+            [
+                (importer.clone(), format!("@components/component{i}"), expected.clone()),
+                (importer.clone(), format!("@ui/component{i}"), expected),
+            ]
+        })
+        .collect();
+    let resolver = ResolverGeneric::new_with_file_system(
+        fs,
+        ResolveOptions { alias, ..ResolveOptions::default() },
+    );
+    Scenario { name: "aliases", resolver, requests, resolve_file: false }
+}
+
+/// A pnpm layout, packages symlinked from `node_modules/.pnpm` and importing their dependencies.
+fn pnpm_symlinks() -> Scenario {
+    let fs = MemoryFileSystem::default();
+    let store = "/project/node_modules/.pnpm";
+    let importer = PathBuf::from("/project/src");
+    let mut requests = vec![];
+    for i in 0..50 {
+        let package = format!("{store}/pkg{i}@1.0.0/node_modules/pkg{i}");
+        fs.add_file(format!("{package}/package.json"), r#"{ "exports": "./index.js" }"#);
+        fs.add_file(format!("{package}/index.js"), "");
+        fs.add_symlink(format!("/project/node_modules/pkg{i}"), &package);
+        requests.push((importer.clone(), format!("pkg{i}"), format!("{package}/index.js").into()));
+
+        // Each package depends on the next one.
+        let dependency = format!("pkg{}", (i + 1) % 50);
+        fs.add_symlink(
+            format!("{store}/pkg{i}@1.0.0/node_modules/{dependency}"),
+            format!("{store}/{dependency}@1.0.0/node_modules/{dependency}"),
+        );
+        let expected = format!("{store}/{dependency}@1.0.0/node_modules/{dependency}/index.js");
+        requests.push((package.into(), dependency, expected.into()));
+    }
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    Scenario { name: "pnpm_symlinks", resolver, requests, resolve_file: false }
+}
+
+fn bench_scenarios(c: &mut Criterion) {
+    let scenarios = scenarios();
+    scenarios.iter().for_each(Scenario::verify);
+    for scenario in scenarios {
+        let mut group = c.benchmark_group(scenario.name);
+        group.bench_with_input(BenchmarkId::from_parameter("cold"), &scenario, |b, scenario| {
+            b.iter(|| {
+                scenario.resolver.clear_cache();
+                scenario.resolve_all();
+            });
+        });
+        group.bench_with_input(BenchmarkId::from_parameter("warm"), &scenario, |b, scenario| {
+            b.iter(|| scenario.resolve_all());
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(scenarios_group, bench_scenarios);
+criterion_main!(scenarios_group);
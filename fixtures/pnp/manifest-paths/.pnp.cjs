@@ -0,0 +1 @@
+const RAW_RUNTIME_STATE = '{"enableTopLevelFallback":false,"ignorePatternData":null,"dependencyTreeRoots":[{"name":"root","reference":"workspace:."}],"fallbackPool":[],"fallbackExclusionList":[],"packageRegistryData":[[null,[[null,{"packageLocation":"./","packageDependencies":[["dep","npm:1.0.0"],["root","workspace:."]]}]]],["root",[["workspace:.",{"packageLocation":"./","packageDependencies":[["dep","npm:1.0.0"],["root","workspace:."]]}]]],["dep",[["npm:1.0.0",{"packageLocation":"./vendor/dep-1.0.0/","packageDependencies":[["dep","npm:1.0.0"]]}]]]]}';
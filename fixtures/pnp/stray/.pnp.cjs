@@ -0,0 +1 @@
+const RAW_RUNTIME_STATE = '{"enableTopLevelFallback":true,"ignorePatternData":null,"dependencyTreeRoots":[{"name":"stray","reference":"workspace:."}],"fallbackPool":[],"fallbackExclusionList":[],"packageRegistryData":[[null,[[null,{"packageLocation":"./","packageDependencies":[["stray","workspace:."]]}]]],["stray",[["workspace:.",{"packageLocation":"./","packageDependencies":[["stray","workspace:."]]}]]]]}';
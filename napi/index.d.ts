@@ -5,6 +5,22 @@ export declare class ResolverFactory {
   static default(): ResolverFactory
   /** Clone the resolver using the same underlying cache. */
   cloneWithOptions(options: NapiResolveOptions): ResolverFactory
+  /**
+   * Add or replace a file of `virtualFileSystem`.
+   *
+   * The cache is cleared so that following resolutions see the file, the warning of
+   * `clearCache` applies.
+   *
+   * Throws when the resolver was not created with `virtualFileSystem`.
+   */
+  addVirtualFile(path: string, content: string): void
+  /**
+   * The options of the resolver with the defaults applied, e.g. to derive the options of
+   * `cloneWithOptions` from them.
+   *
+   * Restrictions created from a `regex` are left out as they can not be converted back.
+   */
+  options(): NapiResolveOptions
   /**
    * Clear the underlying cache.
    *
@@ -41,6 +57,14 @@ export declare class ResolverFactory {
    * Uses TypeScript's `moduleResolution: "bundler"` algorithm.
    */
   resolveDtsAsync(file: string, request: string): Promise<ResolveResult>
+  /**
+   * Resolve the `subpaths` of the package `packageName` installed for `fromDirectory` against
+   * its `exports`, or its files when it has none, in a single call.
+   *
+   * The package is located once for all subpaths. When it cannot be located, every subpath
+   * reports the error.
+   */
+  getPackageEntries(packageName: string, fromDirectory: string, options?: PackageEntriesOptions | undefined | null): Array<PackageEntry>
 }
 
 /** Node.js builtin module when `Options::builtin_modules` is enabled. */
@@ -59,12 +83,38 @@ export interface Builtin {
   isRuntimeModule: boolean
 }
 
+/** Value for [NapiResolveOptions::case_sensitivity]. */
+export declare const enum CaseSensitivity {
+  Sensitive = 0,
+  Insensitive = 1,
+  /** Insensitive when the file system ignores case. */
+  Auto = 2
+}
+
+export declare const enum DiagnosticSeverity {
+  Warning = 'warning',
+  Error = 'error'
+}
+
 export declare const enum EnforceExtension {
   Auto = 0,
   Enabled = 1,
   Disabled = 2
 }
 
+/** Import map for [ResolveOptions::import_map]. */
+export interface ImportMap {
+  /** Mapping of specifiers to addresses for every importer. */
+  imports?: Record<string, string>
+  /** Mappings for the importers inside a directory, keyed by the directory ending with `/`. */
+  scopes?: Record<string, Record<string, string>>
+  /**
+   * Directory which relative keys, scopes and addresses are resolved against, usually the
+   * directory of the import map file.
+   */
+  base?: string
+}
+
 export declare const enum ModuleType {
   Module = 'module',
   CommonJs = 'commonjs',
@@ -112,6 +162,13 @@ export interface NapiResolveOptions {
    * Default `[]`
    */
   conditionNames?: Array<string>
+  /**
+   * The JSON files to use as description files, tried in order in each directory.
+   * All of them are read as `package.json`.
+   *
+   * Default `["package.json"]`
+   */
+  descriptionFiles?: Array<string>
   /**
    * If true, it will not allow extension-less files.
    * So by default `require('./foo')` works if `./foo` has a `.js` extension,
@@ -160,6 +217,13 @@ export interface NapiResolveOptions {
    * Default `{}`
    */
   fallback?: Record<string, Array<string | undefined | null>>
+  /**
+   * [Import map](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps) applied
+   * to the specifier passed to the resolver, before tsconfig paths and `alias`.
+   *
+   * Default `None`
+   */
+  importMap?: ImportMap
   /**
    * Request passed to resolve is already fully specified and extensions or main files are not resolved for it (they are still resolved for internal requests).
    *
@@ -168,6 +232,16 @@ export interface NapiResolveOptions {
    * Default `false`
    */
   fullySpecified?: boolean
+  /**
+   * Whether `fullySpecified` also applies to requests into packages in `modules` which have no
+   * `exports` field.
+   *
+   * When disabled, extensions and main files are probed inside such packages, so `lodash/get`
+   * resolves to `lodash/get.js`, while the user's own relative requests stay fully specified.
+   *
+   * Default `true`
+   */
+  fullySpecifiedAppliesToNodeModules?: boolean
   /**
    * A list of main fields in description files
    *
@@ -210,6 +284,12 @@ export interface NapiResolveOptions {
    * Default `[]`
    */
   restrictions?: Array<Restriction>
+  /**
+   * A list of file name suffixes that a resolved path must not end with, e.g. `[".env", ".pem"]`.
+   *
+   * Default `[]`
+   */
+  forbiddenExtensions?: Array<string>
   /**
    * A list of directories where requests of server-relative URLs (starting with '/') are resolved.
    * On non-Windows systems these requests are resolved as an absolute path first.
@@ -258,12 +338,171 @@ export interface NapiResolveOptions {
    * Default: `false`
    */
   allowPackageExportsInDirectoryResolve?: boolean
+  /**
+   * Probe the package.json `directories.lib` field when resolving a directory.
+   *
+   * Default: `false`
+   */
+  useDirectoriesLib?: boolean
+  /**
+   * Report resolutions that only succeeded because of extension probing or main file fallback
+   * through `ResolveResult#probed`.
+   *
+   * Default: `false`
+   */
+  extensionProbeReport?: boolean
+  /**
+   * Require `exports` targets to point at an existing file exactly as written, like Node.js does,
+   * instead of probing extensions and main files.
+   *
+   * Default: `false`
+   */
+  exportsRequireExactTarget?: boolean
+  /**
+   * Only fall back to tsconfig `baseUrl` when no `compilerOptions.paths` pattern matched the
+   * specifier, like tsc does.
+   *
+   * Default: `false`
+   */
+  tsconfigPathsStrictFallback?: boolean
+  /**
+   * Only apply tsconfig `paths`, `baseUrl` and `rootDirs` to imports from directories covered
+   * by the `files`, `include` and `exclude` of the tsconfig.
+   *
+   * Default: `false`
+   */
+  tsconfigRespectInclude?: boolean
+  /**
+   * The number of nested resolutions, e.g. through aliases, browser field remaps and `exports`
+   * targets, after which a resolution fails.
+   *
+   * Default: `64`
+   */
+  maxResolveDepth?: number
+  /**
+   * Try the extension which was last found in a directory first when probing `extensions`
+   * there. Resolutions are the same, with fewer failed stats.
+   *
+   * Default: `false`
+   */
+  adaptiveExtensionProbing?: boolean
+  /**
+   * The TypeScript version, e.g. `5.4.0`, selecting the `typesVersions` entry of a
+   * package.json. When set, `typesVersions` redirects the paths of packages after `exports`.
+   *
+   * Default: `None`
+   */
+  typescriptVersion?: string
+  /**
+   * Fail a bare specifier which is not found as ignored instead of not found when the
+   * importing package declares it optional, in `peerDependenciesMeta` or
+   * `optionalDependencies`. `fallback` is tried first.
+   *
+   * Default: `false`
+   */
+  ignoreMissingOptionalPeers?: boolean
+  /**
+   * Detect the module type of `.js` and extensionless files outside of packages with a
+   * `"type"` field from their top-level `import` and `export` statements.
+   * Requires `moduleType`.
+   *
+   * Default: `false`
+   */
+  moduleTypeDetection?: boolean
+  /**
+   * Resolve the root of a package with an `exports` field from its `module` field first, like
+   * webpack 4. Subpaths are still resolved by `exports`.
+   *
+   * Default: `false`
+   */
+  preferModuleFieldOverExports?: boolean
+  /**
+   * Whether paths are looked up ignoring case, so that e.g. `import 'React'` finds
+   * `node_modules/react` on case-sensitive filesystems too.
+   *
+   * Default: `CaseSensitivity.Sensitive`
+   */
+  caseSensitivity?: CaseSensitivity
+  /**
+   * Directories whose package.json `imports` are tried in order for `#` specifiers which the
+   * closest package.json does not map, e.g. the root of a workspace.
+   *
+   * Default `[]`
+   */
+  importsFallbackScopes?: Array<string>
+  /**
+   * Bare specifiers which are left unresolved because the runtime provides them, reported
+   * with `ResolveResult#external`. Entries are matched like the keys of `alias`.
+   *
+   * Default `[]`
+   */
+  external?: Array<string>
+  /**
+   * Resolve against virtual files instead of the file system, see `ResolverFactory#addVirtualFile`.
+   *
+   * Only read by the `ResolverFactory` constructor, `cloneWithOptions` shares the file system
+   * of the cloned resolver.
+   *
+   * Default `None`
+   */
+  virtualFileSystem?: VirtualFileSystemOptions
+  /**
+   * Only use a Yarn PnP manifest located in this directory or below it,
+   * a manifest found above it is ignored and `node_modules` are used instead.
+   *
+   * Default `None`
+   */
+  yarnPnpRoot?: string
+  /**
+   * Yarn PnP manifests (`.pnp.cjs` files) to use instead of the one found from `cwd`,
+   * like enhanced-resolve's `pnpApi`.
+   * The closest manifest covering the directory being resolved from is used.
+   *
+   * Default `[]`
+   */
+  pnpManifestPaths?: Array<string>
+}
+
+/** A problem with a combination of options, see `validateOptions`. */
+export interface OptionsDiagnostic {
+  severity: DiagnosticSeverity
+  /** Names of the involved options, e.g. `["fullySpecified", "enforceExtension"]`. */
+  fields: Array<string>
+  message: string
+  /** How to fix the options. */
+  suggestion?: string
+}
+
+/** Options of `ResolverFactory#getPackageEntries`. */
+export interface PackageEntriesOptions {
+  /** Condition names used in place of `conditionNames`. */
+  conditions?: Array<string>
+  /**
+   * Subpaths of the package to resolve, e.g. `[".", "./utils"]`.
+   *
+   * Subpaths matched by an `exports` pattern such as `./features/*` are listed one by one.
+   *
+   * Default `["."]`
+   */
+  subpaths?: Array<string>
+}
+
+/** A subpath resolved by `ResolverFactory#getPackageEntries`. */
+export interface PackageEntry {
+  subpath: string
+  path?: string
+  error?: string
 }
 
 export interface ResolveResult {
   path?: string
   error?: string
   builtin?: Builtin
+  /**
+   * The bare specifier matched by `ResolveOptions#external`, after `alias` and tsconfig
+   * `paths`.
+   */
+  external?: string
   /**
    * Module type for this path.
    *
@@ -276,6 +515,14 @@ export interface ResolveResult {
   moduleType?: ModuleType
   /** `package.json` path for the given module. */
   packageJsonPath?: string
+  /**
+   * Whether the resolution relied on extension probing or main file fallback.
+   *
+   * Enable with `ResolveOptions#extensionProbeReport`.
+   */
+  probed?: boolean
+  /** Whether the `node-addons` condition was taken when resolving `exports` or `imports`. */
+  viaNodeAddons?: boolean
 }
 
 /**
@@ -309,3 +556,18 @@ export interface TsconfigOptions {
    */
   references?: 'auto'
 }
+
+/**
+ * Check for option combinations which are accepted but do not behave as intended.
+ *
+ * Returns an empty array for sound options.
+ */
+export declare function validateOptions(options: NapiResolveOptions): Array<OptionsDiagnostic>
+
+/** Virtual files and symlinks, keyed by their absolute path. */
+export interface VirtualFileSystemOptions {
+  /** File contents. */
+  files: Record<string, string> | Map<string, string>
+  /** Symlink targets, a relative target is relative to the directory of the symlink. */
+  symlinks?: Record<string, string> | Map<string, string>
+}
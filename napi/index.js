@@ -596,9 +596,12 @@ if (!nativeBinding) {
 
 module.exports = nativeBinding
 module.exports.ResolverFactory = nativeBinding.ResolverFactory
+module.exports.CaseSensitivity = nativeBinding.CaseSensitivity
+module.exports.DiagnosticSeverity = nativeBinding.DiagnosticSeverity
 module.exports.EnforceExtension = nativeBinding.EnforceExtension
 module.exports.ModuleType = nativeBinding.ModuleType
 module.exports.sync = nativeBinding.sync
+module.exports.validateOptions = nativeBinding.validateOptions
 
 if (process.versions.pnp) {
   process.env.OXC_RESOLVER_YARN_PNP = '1'
@@ -0,0 +1,62 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use oxc_resolver::{FileMetadata, FileSystem, FileSystemOs, MemoryFileSystem, ResolveError};
+
+/// The file system of a `ResolverFactory`, the OS or the files of `virtualFileSystem`.
+pub enum NapiFileSystem {
+    Os(FileSystemOs),
+    Virtual(Arc<MemoryFileSystem>),
+}
+
+impl NapiFileSystem {
+    fn inner(&self) -> &dyn FileSystem {
+        match self {
+            Self::Os(fs) => fs,
+            Self::Virtual(fs) => fs.as_ref(),
+        }
+    }
+}
+
+impl FileSystem for NapiFileSystem {
+    #[cfg(feature = "yarn_pnp")]
+    fn new(yarn_pnp: bool) -> Self {
+        Self::Os(FileSystemOs::new(yarn_pnp))
+    }
+
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        Self::Os(FileSystemOs::new())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.inner().read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.inner().read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.inner().metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.inner().symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.inner().read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.inner().canonicalize(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.inner().read_dir(path)
+    }
+}
@@ -13,11 +13,16 @@ use std::{
 use napi::{Either, Task, bindgen_prelude::AsyncTask};
 use napi_derive::napi;
 use oxc_resolver::{
-    Resolution, ResolveError, ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions,
+    MemoryFileSystem, Resolution, ResolveError, ResolveOptions, Resolver, ResolverGeneric,
+    ResolverImpl, TsconfigDiscovery, TsconfigOptions,
 };
 
-use self::options::{NapiResolveOptions, StrOrStrList};
+use self::{
+    file_system::NapiFileSystem,
+    options::{NapiResolveOptions, StrOrStrList, VirtualFileSystemOptions},
+};
 
+mod file_system;
 mod options;
 #[cfg(feature = "tracing-subscriber")]
 mod tracing;
@@ -27,6 +32,9 @@ pub struct ResolveResult {
     pub path: Option<String>,
     pub error: Option<String>,
     pub builtin: Option<Builtin>,
+    /// The bare specifier matched by `ResolveOptions#external`, after `alias` and tsconfig
+    /// `paths`.
+    pub external: Option<String>,
     /// Module type for this path.
     ///
     /// Enable with `ResolveOptions#moduleType`.
@@ -38,6 +46,14 @@ pub struct ResolveResult {
 
     /// `package.json` path for the given module.
     pub package_json_path: Option<String>,
+
+    /// Whether the resolution relied on extension probing or main file fallback.
+    ///
+    /// Enable with `ResolveOptions#extensionProbeReport`.
+    pub probed: Option<bool>,
+
+    /// Whether the `node-addons` condition was taken when resolving `exports` or `imports`.
+    pub via_node_addons: Option<bool>,
 }
 
 /// Node.js builtin module when `Options::builtin_modules` is enabled.
@@ -75,6 +91,89 @@ impl From<oxc_resolver::ModuleType> for ModuleType {
     }
 }
 
+/// Options of `ResolverFactory#getPackageEntries`.
+#[napi(object)]
+pub struct PackageEntriesOptions {
+    /// Condition names used in place of `conditionNames`.
+    pub conditions: Option<Vec<String>>,
+
+    /// Subpaths of the package to resolve, e.g. `[".", "./utils"]`.
+    ///
+    /// Subpaths matched by an `exports` pattern such as `./features/*` are listed one by one.
+    ///
+    /// Default `["."]`
+    pub subpaths: Option<Vec<String>>,
+}
+
+/// A subpath resolved by `ResolverFactory#getPackageEntries`.
+#[napi(object)]
+pub struct PackageEntry {
+    pub subpath: String,
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+#[napi(string_enum = "lowercase")]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// A problem with a combination of options, see `validateOptions`.
+#[napi(object)]
+pub struct OptionsDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// Names of the involved options, e.g. `["fullySpecified", "enforceExtension"]`.
+    pub fields: Vec<String>,
+    pub message: String,
+    /// How to fix the options.
+    pub suggestion: Option<String>,
+}
+
+impl From<oxc_resolver::OptionsDiagnostic> for OptionsDiagnostic {
+    fn from(value: oxc_resolver::OptionsDiagnostic) -> Self {
+        Self {
+            severity: match value.severity {
+                oxc_resolver::DiagnosticSeverity::Warning => DiagnosticSeverity::Warning,
+                oxc_resolver::DiagnosticSeverity::Error => DiagnosticSeverity::Error,
+            },
+            fields: value.fields.into_iter().map(camel_case).collect(),
+            message: camel_case_quoted(&value.message),
+            suggestion: value.suggestion.as_deref().map(camel_case_quoted),
+        }
+    }
+}
+
+/// The JS name of an option, e.g. `fullySpecified` for `fully_specified`.
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut camel_case = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let mut chars = word.chars();
+        camel_case.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+        camel_case.push_str(chars.as_str());
+    }
+    camel_case
+}
+
+/// `text` with the option names quoted in backticks in their JS spelling, see [camel_case].
+fn camel_case_quoted(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, part)| if i % 2 == 1 { camel_case(part) } else { part.to_string() })
+        .collect::<Vec<_>>()
+        .join("`")
+}
+
+/// Check for option combinations which are accepted but do not behave as intended.
+///
+/// Returns an empty array for sound options.
+#[napi]
+pub fn validate_options(options: NapiResolveOptions) -> napi::Result<Vec<OptionsDiagnostic>> {
+    let options = ResolverFactory::normalize_options(options)?;
+    Ok(options.validate().into_iter().map(OptionsDiagnostic::from).collect())
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[napi]
 pub fn sync(path: String, request: String) -> ResolveResult {
@@ -83,8 +182,10 @@ pub fn sync(path: String, request: String) -> ResolveResult {
     resolve(&resolver, &path, &request)
 }
 
+type NapiResolver = ResolverGeneric<NapiFileSystem>;
+
 pub struct ResolveTask {
-    resolver: Arc<Resolver>,
+    resolver: Arc<NapiResolver>,
     directory: PathBuf,
     request: String,
 }
@@ -104,7 +205,7 @@ impl Task for ResolveTask {
 }
 
 pub struct ResolveFileTask {
-    resolver: Arc<Resolver>,
+    resolver: Arc<NapiResolver>,
     file: PathBuf,
     request: String,
 }
@@ -124,7 +225,7 @@ impl Task for ResolveFileTask {
 }
 
 pub struct ResolveDtsTask {
-    resolver: Arc<Resolver>,
+    resolver: Arc<NapiResolver>,
     file: PathBuf,
     request: String,
 }
@@ -145,7 +246,9 @@ impl Task for ResolveDtsTask {
 
 #[napi]
 pub struct ResolverFactory {
-    resolver: Arc<Resolver>,
+    resolver: Arc<NapiResolver>,
+    /// The files of `virtualFileSystem`, shared by the resolvers of `cloneWithOptions`.
+    virtual_file_system: Option<Arc<MemoryFileSystem>>,
 }
 
 #[napi]
@@ -156,17 +259,27 @@ impl ResolverFactory {
         {
             tracing::init_tracing();
         }
-        let options = match options {
-            Some(op) => Self::normalize_options(op)?,
-            None => ResolveOptions::default(),
+        let Some(mut op) = options else { return Ok(Self::default()) };
+        let virtual_file_system =
+            op.virtual_file_system.take().map(|options| Arc::new(Self::virtual_files(options)));
+        let options = Self::normalize_options(op)?;
+        let resolver = match &virtual_file_system {
+            Some(fs) => ResolverGeneric::new_with_file_system(
+                NapiFileSystem::Virtual(Arc::clone(fs)),
+                options,
+            ),
+            None => ResolverGeneric::new(options),
         };
-        Ok(Self { resolver: Arc::new(Resolver::new(options)) })
+        Ok(Self { resolver: Arc::new(resolver), virtual_file_system })
     }
 
     #[napi]
     #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
-        Self { resolver: Arc::new(Resolver::new(ResolveOptions::default())) }
+        Self {
+            resolver: Arc::new(ResolverGeneric::new(ResolveOptions::default())),
+            virtual_file_system: None,
+        }
     }
 
     /// Clone the resolver using the same underlying cache.
@@ -174,9 +287,38 @@ impl ResolverFactory {
     pub fn clone_with_options(&self, options: NapiResolveOptions) -> napi::Result<Self> {
         Ok(Self {
             resolver: Arc::new(self.resolver.clone_with_options(Self::normalize_options(options)?)),
+            virtual_file_system: self.virtual_file_system.clone(),
         })
     }
 
+    /// Add or replace a file of `virtualFileSystem`.
+    ///
+    /// The cache is cleared so that following resolutions see the file, the warning of
+    /// `clearCache` applies.
+    ///
+    /// Throws when the resolver was not created with `virtualFileSystem`.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn add_virtual_file(&self, path: String, content: String) -> napi::Result<()> {
+        let Some(fs) = &self.virtual_file_system else {
+            return Err(napi::Error::from_reason(
+                "addVirtualFile requires a resolver created with `virtualFileSystem`",
+            ));
+        };
+        fs.add_file(path, content);
+        self.resolver.clear_cache();
+        Ok(())
+    }
+
+    /// The options of the resolver with the defaults applied, e.g. to derive the options of
+    /// `cloneWithOptions` from them.
+    ///
+    /// Restrictions created from a `regex` are left out as they can not be converted back.
+    #[napi]
+    pub fn options(&self) -> NapiResolveOptions {
+        NapiResolveOptions::from(self.resolver.options())
+    }
+
     /// Clear the underlying cache.
     ///
     /// Warning: The caller must ensure that there're no ongoing resolution operations when calling this method. Otherwise, it may cause those operations to return an incorrect result.
@@ -246,6 +388,52 @@ impl ResolverFactory {
         AsyncTask::new(ResolveDtsTask { resolver, file: path, request })
     }
 
+    /// Resolve the `subpaths` of the package `packageName` installed for `fromDirectory` against
+    /// its `exports`, or its files when it has none, in a single call.
+    ///
+    /// The package is located once for all subpaths. When it cannot be located, every subpath
+    /// reports the error.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn get_package_entries(
+        &self,
+        package_name: String,
+        from_directory: String,
+        options: Option<PackageEntriesOptions>,
+    ) -> Vec<PackageEntry> {
+        let PackageEntriesOptions { conditions, subpaths } =
+            options.unwrap_or(PackageEntriesOptions { conditions: None, subpaths: None });
+        let subpaths = subpaths.unwrap_or_else(|| vec![".".to_string()]);
+        let subpath_refs = subpaths.iter().map(String::as_str).collect::<Vec<_>>();
+        let entries = self.resolver.resolve_package_entries(
+            &from_directory,
+            &package_name,
+            &subpath_refs,
+            conditions.as_deref(),
+        );
+        match entries {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| {
+                    let result = map_resolution_to_result(entry.resolution);
+                    PackageEntry { subpath: entry.subpath, path: result.path, error: result.error }
+                })
+                .collect(),
+            Err(error) => subpaths
+                .into_iter()
+                .map(|subpath| PackageEntry { subpath, path: None, error: Some(error.to_string()) })
+                .collect(),
+        }
+    }
+
+    fn virtual_files(options: VirtualFileSystemOptions) -> MemoryFileSystem {
+        let fs = MemoryFileSystem::new(options.files.0);
+        for (path, target) in options.symlinks.unwrap_or_default().0 {
+            fs.add_symlink(path, target);
+        }
+        fs
+    }
+
     fn normalize_options(op: NapiResolveOptions) -> napi::Result<ResolveOptions> {
         let default = ResolveOptions::default();
         // merging options
@@ -285,6 +473,8 @@ impl ResolverFactory {
                 .map(|o| o.into_iter().map(|x| StrOrStrList(x).into()).collect::<Vec<_>>())
                 .unwrap_or(default.alias_fields),
             condition_names: op.condition_names.unwrap_or(default.condition_names),
+            condition_names_provider: None,
+            description_files: op.description_files.unwrap_or(default.description_files),
             enforce_extension: op
                 .enforce_extension
                 .map(|enforce_extension| enforce_extension.into())
@@ -320,7 +510,11 @@ impl ResolverFactory {
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or(default.fallback),
+            import_map: op.import_map.map(oxc_resolver::ImportMap::from),
             fully_specified: op.fully_specified.unwrap_or(default.fully_specified),
+            fully_specified_applies_to_node_modules: op
+                .fully_specified_applies_to_node_modules
+                .unwrap_or(default.fully_specified_applies_to_node_modules),
             main_fields: op
                 .main_fields
                 .map(|o| StrOrStrList(o).into())
@@ -340,6 +534,7 @@ impl ResolverFactory {
                 })
                 .transpose()?
                 .unwrap_or(default.restrictions),
+            forbidden_extensions: op.forbidden_extensions.unwrap_or(default.forbidden_extensions),
             roots: op
                 .roots
                 .map(|roots| roots.into_iter().map(PathBuf::from).collect::<Vec<_>>())
@@ -351,26 +546,100 @@ impl ResolverFactory {
             allow_package_exports_in_directory_resolve: op
                 .allow_package_exports_in_directory_resolve
                 .unwrap_or(default.allow_package_exports_in_directory_resolve),
+            use_directories_lib: op.use_directories_lib.unwrap_or(default.use_directories_lib),
+            extension_probe_report: op
+                .extension_probe_report
+                .unwrap_or(default.extension_probe_report),
+            exports_require_exact_target: op
+                .exports_require_exact_target
+                .unwrap_or(default.exports_require_exact_target),
+            tsconfig_paths_strict_fallback: op
+                .tsconfig_paths_strict_fallback
+                .unwrap_or(default.tsconfig_paths_strict_fallback),
+            tsconfig_respect_include: op
+                .tsconfig_respect_include
+                .unwrap_or(default.tsconfig_respect_include),
+            // The napi binding does not report a `ResolveContext`.
+            collect_package_json_chain: default.collect_package_json_chain,
+            record_matched_entry: default.record_matched_entry,
+            report_file_directory_ambiguity: default.report_file_directory_ambiguity,
+            max_resolve_depth: op
+                .max_resolve_depth
+                .map_or(default.max_resolve_depth, |depth| depth as usize),
+            adaptive_extension_probing: op
+                .adaptive_extension_probing
+                .unwrap_or(default.adaptive_extension_probing),
+            typescript_version: op
+                .typescript_version
+                .map(|version| {
+                    semver::Version::parse(&version).map_err(|error| {
+                        napi::Error::from_reason(format!(
+                            "Invalid typescriptVersion '{version}': {error}"
+                        ))
+                    })
+                })
+                .transpose()?,
+            ignore_missing_optional_peers: op
+                .ignore_missing_optional_peers
+                .unwrap_or(default.ignore_missing_optional_peers),
+            module_type_detection: op
+                .module_type_detection
+                .unwrap_or(default.module_type_detection),
+            prefer_module_field_over_exports: op
+                .prefer_module_field_over_exports
+                .unwrap_or(default.prefer_module_field_over_exports),
+            case_sensitivity: op
+                .case_sensitivity
+                .map_or(default.case_sensitivity, oxc_resolver::CaseSensitivity::from),
+            imports_fallback_scopes: op
+                .imports_fallback_scopes
+                .map(|scopes| scopes.into_iter().map(PathBuf::from).collect::<Vec<_>>())
+                .unwrap_or(default.imports_fallback_scopes),
+            external: op.external.unwrap_or(default.external),
+            dependency_sink: None,
             #[cfg(feature = "yarn_pnp")]
             yarn_pnp: default.yarn_pnp,
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp_root: op.yarn_pnp_root.map(PathBuf::from),
+            #[cfg(feature = "yarn_pnp")]
+            pnp_manifest_paths: op
+                .pnp_manifest_paths
+                .map(|paths| paths.into_iter().map(PathBuf::from).collect::<Vec<_>>())
+                .unwrap_or(default.pnp_manifest_paths),
         })
     }
 }
 
 fn map_resolution_to_result(result: Result<Resolution, ResolveError>) -> ResolveResult {
     match result {
-        Ok(resolution) => ResolveResult {
-            path: Some(resolution.full_path().to_string_lossy().to_string()),
-            error: None,
-            builtin: None,
-            module_type: resolution.module_type().map(ModuleType::from),
-            package_json_path: resolution
-                .package_json()
-                .and_then(|p| p.path().to_str())
-                .map(|p| p.to_string()),
-        },
+        Ok(resolution) => {
+            // JavaScript strings can't hold a non-UTF-8 path without corrupting it.
+            let path = match resolution.full_path().into_os_string().into_string() {
+                Ok(path) => path,
+                Err(path) => {
+                    return map_resolution_to_result(Err(ResolveError::NonUtf8Path(path.into())));
+                }
+            };
+            ResolveResult {
+                path: Some(path),
+                error: None,
+                builtin: None,
+                external: None,
+                module_type: resolution.module_type().map(ModuleType::from),
+                package_json_path: resolution
+                    .package_json()
+                    .and_then(|p| p.path().to_str())
+                    .map(|p| p.to_string()),
+                probed: Some(resolution.probed()),
+                via_node_addons: Some(resolution.via_node_addons()),
+            }
+        }
         Err(err) => {
             let error = err.to_string();
+            let external = match &err {
+                ResolveError::External { resolved_name, .. } => Some(resolved_name.clone()),
+                _ => None,
+            };
             ResolveResult {
                 path: None,
                 builtin: match err {
@@ -379,22 +648,251 @@ fn map_resolution_to_result(result: Result<Resolution, ResolveError>) -> Resolve
                     }
                     _ => None,
                 },
+                external,
                 module_type: None,
                 error: Some(error),
                 package_json_path: None,
+                probed: None,
+                via_node_addons: None,
             }
         }
     }
 }
 
-fn resolve(resolver: &Resolver, path: &Path, request: &str) -> ResolveResult {
+fn resolve(resolver: &ResolverImpl, path: &Path, request: &str) -> ResolveResult {
     map_resolution_to_result(resolver.resolve(path, request))
 }
 
-fn resolve_file(resolver: &Resolver, path: &Path, request: &str) -> ResolveResult {
+fn resolve_file(resolver: &ResolverImpl, path: &Path, request: &str) -> ResolveResult {
     map_resolution_to_result(resolver.resolve_file(path, request))
 }
 
-fn resolve_dts(resolver: &Resolver, file: &Path, request: &str) -> ResolveResult {
+fn resolve_dts(resolver: &ResolverImpl, file: &Path, request: &str) -> ResolveResult {
     map_resolution_to_result(resolver.resolve_dts(file, request))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use napi::Either;
+
+    use super::{
+        OptionsDiagnostic, ResolverFactory,
+        options::{
+            CaseSensitivity, EnforceExtension, ImportMap, NapiResolveOptions, Restriction,
+            TsconfigOptions,
+        },
+    };
+
+    fn napi_options() -> NapiResolveOptions {
+        NapiResolveOptions {
+            tsconfig: Some(Either::B(TsconfigOptions {
+                config_file: "/app/tsconfig.json".into(),
+                references: Some("auto".into()),
+            })),
+            alias: Some(HashMap::from([("a".into(), vec![Some("./b".into()), None])])),
+            alias_fields: Some(vec![Either::B(vec!["browser".into()])]),
+            condition_names: Some(vec!["import".into(), "node".into()]),
+            description_files: Some(vec!["package.json".into(), "bower.json".into()]),
+            enforce_extension: Some(EnforceExtension::Enabled),
+            exports_fields: Some(vec![Either::B(vec!["a".into(), "exports".into()])]),
+            imports_fields: Some(vec![Either::B(vec!["imports".into()])]),
+            extension_alias: Some(HashMap::from([(".js".into(), vec![".ts".into()])])),
+            extensions: Some(vec![".ts".into(), ".js".into()]),
+            fallback: Some(HashMap::from([("c".into(), vec![Some("./d".into())])])),
+            import_map: Some(ImportMap {
+                imports: Some(HashMap::from([("e".into(), "./f.js".into())])),
+                scopes: Some(HashMap::from([(
+                    "/app/".into(),
+                    HashMap::from([("g".into(), "./h.js".into())]),
+                )])),
+                base: Some("/app".into()),
+            }),
+            fully_specified: Some(true),
+            fully_specified_applies_to_node_modules: Some(false),
+            main_fields: Some(Either::B(vec!["module".into(), "main".into()])),
+            main_files: Some(vec!["main".into()]),
+            modules: Some(Either::B(vec!["node_modules".into(), "/app/vendor".into()])),
+            resolve_to_context: Some(true),
+            prefer_relative: Some(true),
+            prefer_absolute: Some(true),
+            restrictions: Some(vec![Restriction { path: Some("/app".into()), regex: None }]),
+            forbidden_extensions: Some(vec![".env".into()]),
+            roots: Some(vec!["/app/public".into()]),
+            symlinks: Some(false),
+            node_path: Some(false),
+            builtin_modules: Some(true),
+            module_type: Some(true),
+            allow_package_exports_in_directory_resolve: Some(true),
+            use_directories_lib: Some(true),
+            extension_probe_report: Some(true),
+            exports_require_exact_target: Some(true),
+            tsconfig_paths_strict_fallback: Some(true),
+            tsconfig_respect_include: Some(true),
+            max_resolve_depth: Some(8),
+            adaptive_extension_probing: Some(true),
+            typescript_version: Some("5.4.0".into()),
+            ignore_missing_optional_peers: Some(true),
+            module_type_detection: Some(true),
+            prefer_module_field_over_exports: Some(true),
+            case_sensitivity: Some(CaseSensitivity::Insensitive),
+            imports_fallback_scopes: Some(vec!["/workspace".into()]),
+            external: Some(vec!["react$".into(), "@scope/*".into()]),
+            virtual_file_system: None,
+            yarn_pnp_root: Some("/app".into()),
+            pnp_manifest_paths: Some(vec!["/app/.pnp.cjs".into()]),
+        }
+    }
+
+    fn either_list(value: Option<&Either<String, Vec<String>>>) -> Option<Vec<String>> {
+        value.map(|value| match value {
+            Either::A(value) => vec![value.clone()],
+            Either::B(values) => values.clone(),
+        })
+    }
+
+    fn either_lists(values: Option<&Vec<Either<String, Vec<String>>>>) -> Option<Vec<Vec<String>>> {
+        values.map(|values| values.iter().filter_map(|value| either_list(Some(value))).collect())
+    }
+
+    #[test]
+    fn options_round_trip() {
+        let expected = napi_options();
+        let options = ResolverFactory::normalize_options(napi_options()).unwrap();
+        let actual = NapiResolveOptions::from(&options);
+
+        let Some(Either::B(tsconfig)) = &actual.tsconfig else { panic!("{:?}", actual.tsconfig) };
+        assert_eq!(tsconfig.config_file, "/app/tsconfig.json");
+        assert_eq!(tsconfig.references.as_deref(), Some("auto"));
+        assert_eq!(actual.alias, expected.alias);
+        assert_eq!(
+            either_lists(actual.alias_fields.as_ref()),
+            either_lists(expected.alias_fields.as_ref())
+        );
+        assert_eq!(actual.condition_names, expected.condition_names);
+        assert_eq!(actual.description_files, expected.description_files);
+        assert_eq!(actual.enforce_extension, expected.enforce_extension);
+        assert_eq!(
+            either_lists(actual.exports_fields.as_ref()),
+            either_lists(expected.exports_fields.as_ref())
+        );
+        assert_eq!(
+            either_lists(actual.imports_fields.as_ref()),
+            either_lists(expected.imports_fields.as_ref())
+        );
+        assert_eq!(actual.extension_alias, expected.extension_alias);
+        assert_eq!(actual.extensions, expected.extensions);
+        assert_eq!(actual.fallback, expected.fallback);
+        let (import_map, expected_import_map) =
+            (actual.import_map.as_ref().unwrap(), expected.import_map.as_ref().unwrap());
+        assert_eq!(import_map.imports, expected_import_map.imports);
+        assert_eq!(import_map.scopes, expected_import_map.scopes);
+        assert_eq!(import_map.base, expected_import_map.base);
+        assert_eq!(actual.fully_specified, expected.fully_specified);
+        assert_eq!(
+            actual.fully_specified_applies_to_node_modules,
+            expected.fully_specified_applies_to_node_modules
+        );
+        assert_eq!(
+            either_list(actual.main_fields.as_ref()),
+            either_list(expected.main_fields.as_ref())
+        );
+        assert_eq!(actual.main_files, expected.main_files);
+        assert_eq!(either_list(actual.modules.as_ref()), either_list(expected.modules.as_ref()));
+        assert_eq!(actual.resolve_to_context, expected.resolve_to_context);
+        assert_eq!(actual.prefer_relative, expected.prefer_relative);
+        assert_eq!(actual.prefer_absolute, expected.prefer_absolute);
+        let restrictions = actual.restrictions.as_ref().unwrap();
+        assert_eq!(restrictions.len(), 1);
+        assert_eq!(restrictions[0].path.as_deref(), Some("/app"));
+        assert_eq!(restrictions[0].regex, None);
+        assert_eq!(actual.forbidden_extensions, expected.forbidden_extensions);
+        assert_eq!(actual.roots, expected.roots);
+        assert_eq!(actual.symlinks, expected.symlinks);
+        assert_eq!(actual.node_path, expected.node_path);
+        assert_eq!(actual.builtin_modules, expected.builtin_modules);
+        assert_eq!(actual.module_type, expected.module_type);
+        assert_eq!(
+            actual.allow_package_exports_in_directory_resolve,
+            expected.allow_package_exports_in_directory_resolve
+        );
+        assert_eq!(actual.use_directories_lib, expected.use_directories_lib);
+        assert_eq!(actual.extension_probe_report, expected.extension_probe_report);
+        assert_eq!(actual.exports_require_exact_target, expected.exports_require_exact_target);
+        assert_eq!(actual.tsconfig_paths_strict_fallback, expected.tsconfig_paths_strict_fallback);
+        assert_eq!(actual.tsconfig_respect_include, expected.tsconfig_respect_include);
+        assert_eq!(actual.max_resolve_depth, expected.max_resolve_depth);
+        assert_eq!(actual.adaptive_extension_probing, expected.adaptive_extension_probing);
+        assert_eq!(actual.typescript_version, expected.typescript_version);
+        assert_eq!(actual.ignore_missing_optional_peers, expected.ignore_missing_optional_peers);
+        assert_eq!(actual.module_type_detection, expected.module_type_detection);
+        assert_eq!(
+            actual.prefer_module_field_over_exports,
+            expected.prefer_module_field_over_exports
+        );
+        assert_eq!(actual.case_sensitivity, expected.case_sensitivity);
+        assert_eq!(actual.imports_fallback_scopes, expected.imports_fallback_scopes);
+        assert_eq!(actual.external, expected.external);
+        assert!(actual.virtual_file_system.is_none());
+        #[cfg(feature = "yarn_pnp")]
+        {
+            assert_eq!(actual.yarn_pnp_root, expected.yarn_pnp_root);
+            assert_eq!(actual.pnp_manifest_paths, expected.pnp_manifest_paths);
+        }
+    }
+
+    #[test]
+    fn options_exceptions() {
+        let options = NapiResolveOptions {
+            tsconfig: Some(Either::A("auto".into())),
+            restrictions: Some(vec![
+                Restriction { path: None, regex: Some("\\.js$".into()) },
+                Restriction { path: Some("/app".into()), regex: None },
+            ]),
+            ..napi_options()
+        };
+        let options = ResolverFactory::normalize_options(options).unwrap();
+        let actual = NapiResolveOptions::from(&options);
+        assert!(matches!(&actual.tsconfig, Some(Either::A(auto)) if auto == "auto"));
+        // `regex` restrictions are functions which can not be converted back.
+        let restrictions = actual.restrictions.unwrap();
+        assert_eq!(restrictions.len(), 1);
+        assert_eq!(restrictions[0].path.as_deref(), Some("/app"));
+    }
+
+    #[test]
+    fn factory_options() {
+        let factory = ResolverFactory::new(Some(NapiResolveOptions {
+            enforce_extension: None,
+            tsconfig: None,
+            ..napi_options()
+        }))
+        .unwrap();
+        let options = factory.options();
+        // Sanitized, `Auto` is `Disabled` without an empty extension.
+        assert_eq!(options.enforce_extension, Some(EnforceExtension::Disabled));
+        assert!(options.tsconfig.is_none());
+
+        let clone = factory.clone_with_options(options).unwrap();
+        assert_eq!(clone.options().extensions, factory.options().extensions);
+    }
+
+    #[test]
+    fn diagnostics_use_js_names() {
+        let options = oxc_resolver::ResolveOptions {
+            extension_alias: vec![(".js".into(), vec![".ts".into()])],
+            resolve_to_context: true,
+            ..oxc_resolver::ResolveOptions::default()
+        };
+        let diagnostics =
+            options.validate().into_iter().map(OptionsDiagnostic::from).collect::<Vec<_>>();
+        assert_eq!(diagnostics[0].fields, ["resolveToContext", "extensionAlias"]);
+        assert!(
+            diagnostics[0]
+                .message
+                .starts_with("`extensionAlias` is not applied, `resolveToContext`")
+        );
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("remove `extensionAlias`"));
+    }
+}
@@ -1,6 +1,17 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
-
-use napi::Either;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use napi::{
+    Either, Env, ValueType,
+    bindgen_prelude::{
+        FromNapiValue, Function, JsObjectValue, JsValue, Object, ToNapiValue, TypeName, Unknown,
+        ValidateNapiValue,
+    },
+    sys,
+};
 use napi_derive::napi;
 use regress::Regex;
 
@@ -41,6 +52,12 @@ pub struct NapiResolveOptions {
     /// Default `[]`
     pub condition_names: Option<Vec<String>>,
 
+    /// The JSON files to use as description files, tried in order in each directory.
+    /// All of them are read as `package.json`.
+    ///
+    /// Default `["package.json"]`
+    pub description_files: Option<Vec<String>>,
+
     /// If true, it will not allow extension-less files.
     /// So by default `require('./foo')` works if `./foo` has a `.js` extension,
     /// but with this enabled only `require('./foo.js')` will work.
@@ -85,6 +102,12 @@ pub struct NapiResolveOptions {
     /// Default `{}`
     pub fallback: Option<HashMap<String, Vec<Option<String>>>>,
 
+    /// [Import map](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps) applied
+    /// to the specifier passed to the resolver, before tsconfig paths and `alias`.
+    ///
+    /// Default `None`
+    pub import_map: Option<ImportMap>,
+
     /// Request passed to resolve is already fully specified and extensions or main files are not resolved for it (they are still resolved for internal requests).
     ///
     /// See also webpack configuration [resolve.fullySpecified](https://webpack.js.org/configuration/module/#resolvefullyspecified)
@@ -92,6 +115,15 @@ pub struct NapiResolveOptions {
     /// Default `false`
     pub fully_specified: Option<bool>,
 
+    /// Whether `fullySpecified` also applies to requests into packages in `modules` which have no
+    /// `exports` field.
+    ///
+    /// When disabled, extensions and main files are probed inside such packages, so `lodash/get`
+    /// resolves to `lodash/get.js`, while the user's own relative requests stay fully specified.
+    ///
+    /// Default `true`
+    pub fully_specified_applies_to_node_modules: Option<bool>,
+
     /// A list of main fields in description files
     ///
     /// Default `["main"]`.
@@ -129,6 +161,11 @@ pub struct NapiResolveOptions {
     /// Default `[]`
     pub restrictions: Option<Vec<Restriction>>,
 
+    /// A list of file name suffixes that a resolved path must not end with, e.g. `[".env", ".pem"]`.
+    ///
+    /// Default `[]`
+    pub forbidden_extensions: Option<Vec<String>>,
+
     /// A list of directories where requests of server-relative URLs (starting with '/') are resolved.
     /// On non-Windows systems these requests are resolved as an absolute path first.
     ///
@@ -170,6 +207,112 @@ pub struct NapiResolveOptions {
     ///
     /// Default: `false`
     pub allow_package_exports_in_directory_resolve: Option<bool>,
+
+    /// Probe the package.json `directories.lib` field when resolving a directory.
+    ///
+    /// Default: `false`
+    pub use_directories_lib: Option<bool>,
+
+    /// Report resolutions that only succeeded because of extension probing or main file fallback
+    /// through `ResolveResult#probed`.
+    ///
+    /// Default: `false`
+    pub extension_probe_report: Option<bool>,
+
+    /// Require `exports` targets to point at an existing file exactly as written, like Node.js does,
+    /// instead of probing extensions and main files.
+    ///
+    /// Default: `false`
+    pub exports_require_exact_target: Option<bool>,
+
+    /// Only fall back to tsconfig `baseUrl` when no `compilerOptions.paths` pattern matched the
+    /// specifier, like tsc does.
+    ///
+    /// Default: `false`
+    pub tsconfig_paths_strict_fallback: Option<bool>,
+
+    /// Only apply tsconfig `paths`, `baseUrl` and `rootDirs` to imports from directories covered
+    /// by the `files`, `include` and `exclude` of the tsconfig.
+    ///
+    /// Default: `false`
+    pub tsconfig_respect_include: Option<bool>,
+
+    /// The number of nested resolutions, e.g. through aliases, browser field remaps and `exports`
+    /// targets, after which a resolution fails.
+    ///
+    /// Default: `64`
+    pub max_resolve_depth: Option<u32>,
+
+    /// Try the extension which was last found in a directory first when probing `extensions`
+    /// there. Resolutions are the same, with fewer failed stats.
+    ///
+    /// Default: `false`
+    pub adaptive_extension_probing: Option<bool>,
+
+    /// The TypeScript version, e.g. `5.4.0`, selecting the `typesVersions` entry of a
+    /// package.json. When set, `typesVersions` redirects the paths of packages after `exports`.
+    ///
+    /// Default: `None`
+    pub typescript_version: Option<String>,
+
+    /// Fail a bare specifier which is not found as ignored instead of not found when the
+    /// importing package declares it optional, in `peerDependenciesMeta` or
+    /// `optionalDependencies`. `fallback` is tried first.
+    ///
+    /// Default: `false`
+    pub ignore_missing_optional_peers: Option<bool>,
+
+    /// Detect the module type of `.js` and extensionless files outside of packages with a
+    /// `"type"` field from their top-level `import` and `export` statements.
+    /// Requires `moduleType`.
+    ///
+    /// Default: `false`
+    pub module_type_detection: Option<bool>,
+
+    /// Resolve the root of a package with an `exports` field from its `module` field first, like
+    /// webpack 4. Subpaths are still resolved by `exports`.
+    ///
+    /// Default: `false`
+    pub prefer_module_field_over_exports: Option<bool>,
+
+    /// Whether paths are looked up ignoring case, so that e.g. `import 'React'` finds
+    /// `node_modules/react` on case-sensitive filesystems too.
+    ///
+    /// Default: `CaseSensitivity.Sensitive`
+    pub case_sensitivity: Option<CaseSensitivity>,
+
+    /// Directories whose package.json `imports` are tried in order for `#` specifiers which the
+    /// closest package.json does not map, e.g. the root of a workspace.
+    ///
+    /// Default `[]`
+    pub imports_fallback_scopes: Option<Vec<String>>,
+
+    /// Bare specifiers which are left unresolved because the runtime provides them, reported
+    /// with `ResolveResult#external`. Entries are matched like the keys of `alias`.
+    ///
+    /// Default `[]`
+    pub external: Option<Vec<String>>,
+
+    /// Resolve against virtual files instead of the file system, see `ResolverFactory#addVirtualFile`.
+    ///
+    /// Only read by the `ResolverFactory` constructor, `cloneWithOptions` shares the file system
+    /// of the cloned resolver.
+    ///
+    /// Default `None`
+    pub virtual_file_system: Option<VirtualFileSystemOptions>,
+
+    /// Only use a Yarn PnP manifest located in this directory or below it,
+    /// a manifest found above it is ignored and `node_modules` are used instead.
+    ///
+    /// Default `None`
+    pub yarn_pnp_root: Option<String>,
+
+    /// Yarn PnP manifests (`.pnp.cjs` files) to use instead of the one found from `cwd`,
+    /// like enhanced-resolve's `pnpApi`.
+    /// The closest manifest covering the directory being resolved from is used.
+    ///
+    /// Default `[]`
+    pub pnp_manifest_paths: Option<Vec<String>>,
 }
 
 #[napi]
@@ -180,6 +323,16 @@ pub enum EnforceExtension {
     Disabled,
 }
 
+/// Value for [NapiResolveOptions::case_sensitivity].
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseSensitivity {
+    Sensitive,
+    Insensitive,
+    /// Insensitive when the file system ignores case.
+    Auto,
+}
+
 /// Alias Value for [ResolveOptions::alias] and [ResolveOptions::fallback].
 /// Use struct because napi don't support structured union now
 #[napi(object)]
@@ -189,6 +342,21 @@ pub struct Restriction {
     pub regex: Option<String>,
 }
 
+/// Import map for [ResolveOptions::import_map].
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ImportMap {
+    /// Mapping of specifiers to addresses for every importer.
+    pub imports: Option<HashMap<String, String>>,
+
+    /// Mappings for the importers inside a directory, keyed by the directory ending with `/`.
+    pub scopes: Option<HashMap<String, HashMap<String, String>>>,
+
+    /// Directory which relative keys, scopes and addresses are resolved against, usually the
+    /// directory of the import map file.
+    pub base: Option<String>,
+}
+
 /// Tsconfig Options
 ///
 /// Derived from [tsconfig-paths-webpack-plugin](https://github.com/dividab/tsconfig-paths-webpack-plugin#options)
@@ -208,6 +376,57 @@ pub struct TsconfigOptions {
     pub references: Option<String>,
 }
 
+/// Virtual files and symlinks, keyed by their absolute path.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VirtualFileSystemOptions {
+    /// File contents.
+    #[napi(ts_type = "Record<string, string> | Map<string, string>")]
+    pub files: VirtualEntries,
+
+    /// Symlink targets, a relative target is relative to the directory of the symlink.
+    #[napi(ts_type = "Record<string, string> | Map<string, string>")]
+    pub symlinks: Option<VirtualEntries>,
+}
+
+/// The entries of a plain object or a `Map` of strings.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualEntries(pub Vec<(String, String)>);
+
+impl TypeName for VirtualEntries {
+    fn type_name() -> &'static str {
+        "Record<string, string> | Map<string, string>"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+impl ValidateNapiValue for VirtualEntries {}
+
+impl FromNapiValue for VirtualEntries {
+    unsafe fn from_napi_value(env: sys::napi_env, value: sys::napi_value) -> napi::Result<Self> {
+        let object = unsafe { Object::from_napi_value(env, value)? };
+        let global = Env::from(env).get_global()?;
+        if object.instanceof(global.get_named_property::<Unknown>("Map")?)? {
+            let array = global.get_named_property::<Object>("Array")?;
+            let from =
+                array.get_named_property::<Function<Object, Vec<(String, String)>>>("from")?;
+            return Ok(Self(from.call(object)?));
+        }
+        let entries = unsafe { HashMap::<String, String>::from_napi_value(env, value)? };
+        Ok(Self(entries.into_iter().collect()))
+    }
+}
+
+impl ToNapiValue for VirtualEntries {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        let entries = val.0.into_iter().collect::<HashMap<_, _>>();
+        unsafe { HashMap::to_napi_value(env, entries) }
+    }
+}
+
 impl TryFrom<Restriction> for oxc_resolver::Restriction {
     type Error = napi::Error;
 
@@ -229,6 +448,36 @@ impl TryFrom<Restriction> for oxc_resolver::Restriction {
     }
 }
 
+impl From<oxc_resolver::CaseSensitivity> for CaseSensitivity {
+    fn from(val: oxc_resolver::CaseSensitivity) -> Self {
+        match val {
+            oxc_resolver::CaseSensitivity::Sensitive => Self::Sensitive,
+            oxc_resolver::CaseSensitivity::Insensitive => Self::Insensitive,
+            oxc_resolver::CaseSensitivity::Auto => Self::Auto,
+        }
+    }
+}
+
+impl From<CaseSensitivity> for oxc_resolver::CaseSensitivity {
+    fn from(val: CaseSensitivity) -> Self {
+        match val {
+            CaseSensitivity::Sensitive => oxc_resolver::CaseSensitivity::Sensitive,
+            CaseSensitivity::Insensitive => oxc_resolver::CaseSensitivity::Insensitive,
+            CaseSensitivity::Auto => oxc_resolver::CaseSensitivity::Auto,
+        }
+    }
+}
+
+impl From<oxc_resolver::EnforceExtension> for EnforceExtension {
+    fn from(val: oxc_resolver::EnforceExtension) -> Self {
+        match val {
+            oxc_resolver::EnforceExtension::Auto => Self::Auto,
+            oxc_resolver::EnforceExtension::Enabled => Self::Enabled,
+            oxc_resolver::EnforceExtension::Disabled => Self::Disabled,
+        }
+    }
+}
+
 impl From<EnforceExtension> for oxc_resolver::EnforceExtension {
     fn from(val: EnforceExtension) -> Self {
         match val {
@@ -239,6 +488,48 @@ impl From<EnforceExtension> for oxc_resolver::EnforceExtension {
     }
 }
 
+impl From<ImportMap> for oxc_resolver::ImportMap {
+    fn from(val: ImportMap) -> Self {
+        Self {
+            imports: val.imports.unwrap_or_default().into_iter().collect(),
+            scopes: val
+                .scopes
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(scope, imports)| (scope, imports.into_iter().collect()))
+                .collect(),
+            base: val.base.map(PathBuf::from).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&oxc_resolver::ImportMap> for ImportMap {
+    fn from(val: &oxc_resolver::ImportMap) -> Self {
+        Self {
+            imports: Some(val.imports.clone().into_iter().collect()),
+            scopes: Some(
+                val.scopes
+                    .iter()
+                    .map(|(scope, imports)| (scope.clone(), imports.clone().into_iter().collect()))
+                    .collect(),
+            ),
+            base: Some(path_to_string(&val.base)),
+        }
+    }
+}
+
+impl From<&oxc_resolver::TsconfigOptions> for TsconfigOptions {
+    fn from(val: &oxc_resolver::TsconfigOptions) -> Self {
+        Self {
+            config_file: path_to_string(&val.config_file),
+            references: match val.references {
+                oxc_resolver::TsconfigReferences::Auto => Some("auto".to_string()),
+                oxc_resolver::TsconfigReferences::Disabled => None,
+            },
+        }
+    }
+}
+
 impl TryFrom<TsconfigOptions> for oxc_resolver::TsconfigOptions {
     type Error = napi::Error;
 
@@ -269,3 +560,107 @@ impl From<StrOrStrList> for Vec<String> {
         }
     }
 }
+
+/// The napi shape of resolver options, see `ResolverFactory#options`.
+///
+/// Options without a napi counterpart are left out: `Restriction::Fn`, including the
+/// restrictions created from a `regex`, `conditionNamesProvider`, `dependencySink` and the
+/// options of `ResolveContext`. `virtualFileSystem` is a property of the factory, not of the
+/// options, and is `None`.
+impl From<&oxc_resolver::ResolveOptions> for NapiResolveOptions {
+    fn from(options: &oxc_resolver::ResolveOptions) -> Self {
+        let alias = |alias: &oxc_resolver::Alias| {
+            alias
+                .iter()
+                .map(|(key, values)| {
+                    let values = values
+                        .iter()
+                        .map(|value| match value {
+                            oxc_resolver::AliasValue::Path(path) => Some(path.clone()),
+                            oxc_resolver::AliasValue::Ignore => None,
+                        })
+                        .collect();
+                    (key.clone(), values)
+                })
+                .collect()
+        };
+        let fields =
+            |fields: &[Vec<String>]| fields.iter().map(|field| Either::B(field.clone())).collect();
+        let paths = |paths: &[PathBuf]| paths.iter().map(|path| path_to_string(path)).collect();
+        Self {
+            tsconfig: options.tsconfig.as_ref().map(|tsconfig| match tsconfig {
+                oxc_resolver::TsconfigDiscovery::Auto => Either::A("auto".to_string()),
+                oxc_resolver::TsconfigDiscovery::Manual(tsconfig) => Either::B(tsconfig.into()),
+            }),
+            alias: Some(alias(&options.alias)),
+            alias_fields: Some(fields(&options.alias_fields)),
+            condition_names: Some(options.condition_names.clone()),
+            description_files: Some(options.description_files.clone()),
+            enforce_extension: Some(options.enforce_extension.into()),
+            exports_fields: Some(fields(&options.exports_fields)),
+            imports_fields: Some(fields(&options.imports_fields)),
+            extension_alias: Some(options.extension_alias.iter().cloned().collect()),
+            extensions: Some(options.extensions.clone()),
+            fallback: Some(alias(&options.fallback)),
+            import_map: options.import_map.as_ref().map(ImportMap::from),
+            fully_specified: Some(options.fully_specified),
+            fully_specified_applies_to_node_modules: Some(
+                options.fully_specified_applies_to_node_modules,
+            ),
+            main_fields: Some(Either::B(options.main_fields.clone())),
+            main_files: Some(options.main_files.clone()),
+            modules: Some(Either::B(options.modules.clone())),
+            resolve_to_context: Some(options.resolve_to_context),
+            prefer_relative: Some(options.prefer_relative),
+            prefer_absolute: Some(options.prefer_absolute),
+            restrictions: Some(
+                options
+                    .restrictions
+                    .iter()
+                    .filter_map(|restriction| match restriction {
+                        oxc_resolver::Restriction::Path(path) => {
+                            Some(Restriction { path: Some(path_to_string(path)), regex: None })
+                        }
+                        oxc_resolver::Restriction::Fn(_) => None,
+                    })
+                    .collect(),
+            ),
+            forbidden_extensions: Some(options.forbidden_extensions.clone()),
+            roots: Some(paths(&options.roots)),
+            symlinks: Some(options.symlinks),
+            node_path: Some(options.node_path),
+            builtin_modules: Some(options.builtin_modules),
+            module_type: Some(options.module_type),
+            allow_package_exports_in_directory_resolve: Some(
+                options.allow_package_exports_in_directory_resolve,
+            ),
+            use_directories_lib: Some(options.use_directories_lib),
+            extension_probe_report: Some(options.extension_probe_report),
+            exports_require_exact_target: Some(options.exports_require_exact_target),
+            tsconfig_paths_strict_fallback: Some(options.tsconfig_paths_strict_fallback),
+            tsconfig_respect_include: Some(options.tsconfig_respect_include),
+            max_resolve_depth: Some(u32::try_from(options.max_resolve_depth).unwrap_or(u32::MAX)),
+            adaptive_extension_probing: Some(options.adaptive_extension_probing),
+            typescript_version: options.typescript_version.as_ref().map(ToString::to_string),
+            ignore_missing_optional_peers: Some(options.ignore_missing_optional_peers),
+            module_type_detection: Some(options.module_type_detection),
+            prefer_module_field_over_exports: Some(options.prefer_module_field_over_exports),
+            case_sensitivity: Some(options.case_sensitivity.into()),
+            imports_fallback_scopes: Some(paths(&options.imports_fallback_scopes)),
+            external: Some(options.external.clone()),
+            virtual_file_system: None,
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp_root: options.yarn_pnp_root.as_deref().map(path_to_string),
+            #[cfg(not(feature = "yarn_pnp"))]
+            yarn_pnp_root: None,
+            #[cfg(feature = "yarn_pnp")]
+            pnp_manifest_paths: Some(paths(&options.pnp_manifest_paths)),
+            #[cfg(not(feature = "yarn_pnp"))]
+            pnp_manifest_paths: None,
+        }
+    }
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
@@ -1,7 +1,7 @@
 import * as path from "node:path";
 import { assert, describe, it } from "vite-plus/test";
 
-import { ResolverFactory } from "../index.js";
+import { ResolverFactory, validateOptions } from "../index.js";
 import { normalizePath } from "./utils.mjs";
 
 const fixtureDir = path.resolve(
@@ -54,3 +54,32 @@ describe("option", () => {
     it("should allow field array", () => createTest(["main"]));
   });
 });
+
+describe("validateOptions", () => {
+  it("should return no diagnostics for the default options", () => {
+    assert.deepEqual(validateOptions({}), []);
+  });
+  it("should report contradictory options", () => {
+    const diagnostics = validateOptions({ fullySpecified: true, enforceExtension: 1 });
+    assert.equal(diagnostics.length, 1);
+    assert.equal(diagnostics[0].severity, "error");
+    assert.deepEqual(diagnostics[0].fields, ["fullySpecified", "enforceExtension"]);
+  });
+});
+
+describe("options", () => {
+  it("should return the options with the defaults applied", () => {
+    const resolver = new ResolverFactory({ extensions: [".ts"], alias: { a: ["./b", null] } });
+    const options = resolver.options();
+    assert.deepEqual(options.extensions, [".ts"]);
+    assert.deepEqual(options.alias, { a: ["./b", null] });
+    assert.deepEqual(options.mainFields, ["main"]);
+    assert.equal(options.symlinks, true);
+  });
+  it("should be accepted by cloneWithOptions", () => {
+    const resolver = new ResolverFactory({ conditionNames: ["import"] });
+    const clone = resolver.cloneWithOptions({ ...resolver.options(), extensions: [".mjs"] });
+    assert.deepEqual(clone.options().conditionNames, ["import"]);
+    assert.deepEqual(clone.options().extensions, [".mjs"]);
+  });
+});
@@ -0,0 +1,73 @@
+import { assert, describe, it } from "vite-plus/test";
+
+import { ResolverFactory } from "../index.js";
+
+const files = {
+  "/app/node_modules/pkg/package.json": JSON.stringify({
+    name: "pkg",
+    exports: { ".": { import: "./esm.js", default: "./cjs.js" } },
+  }),
+  "/app/node_modules/pkg/esm.js": "",
+  "/app/node_modules/pkg/cjs.js": "",
+  "/app/tsconfig.json": JSON.stringify({ compilerOptions: { paths: { "@/*": ["./src/*"] } } }),
+  "/app/src/util.ts": "",
+  "/app/src/index.ts": "",
+};
+
+// Virtual paths are posix paths.
+describe.skipIf(process.platform === "win32")("virtualFileSystem", () => {
+  it("resolves exports and tsconfig paths from virtual files", () => {
+    const resolver = new ResolverFactory({
+      conditionNames: ["import"],
+      extensions: [".ts", ".js"],
+      tsconfig: { configFile: "/app/tsconfig.json" },
+      virtualFileSystem: { files },
+    });
+    assert.equal(resolver.sync("/app", "pkg").path, "/app/node_modules/pkg/esm.js");
+    assert.equal(resolver.sync("/app", "@/util").path, "/app/src/util.ts");
+    assert.equal(resolver.resolveFileSync("/app/src/index.ts", "./util").path, "/app/src/util.ts");
+  });
+
+  it("accepts a Map and symlinks", () => {
+    const resolver = new ResolverFactory({
+      virtualFileSystem: {
+        files: new Map([["/app/packages/linked/index.js", ""]]),
+        symlinks: new Map([["/app/node_modules/linked", "../packages/linked"]]),
+      },
+    });
+    assert.equal(resolver.sync("/app", "linked").path, "/app/packages/linked/index.js");
+  });
+
+  it("addVirtualFile clears the cache", async () => {
+    const resolver = new ResolverFactory({ virtualFileSystem: { files } });
+    assert.isDefined(resolver.sync("/app", "./new").error);
+    resolver.addVirtualFile("/app/new.js", "");
+    assert.equal(resolver.sync("/app", "./new").path, "/app/new.js");
+    assert.equal((await resolver.async("/app", "./new")).path, "/app/new.js");
+
+    // Clones share the virtual files.
+    const clone = resolver.cloneWithOptions({ extensions: [".ts"] });
+    clone.addVirtualFile("/app/other.ts", "");
+    assert.equal(clone.sync("/app", "./other").path, "/app/other.ts");
+    assert.equal(resolver.sync("/app", "./other.ts").path, "/app/other.ts");
+  });
+
+  it("getPackageEntries resolves several subpaths in one call", () => {
+    const resolver = new ResolverFactory({ virtualFileSystem: { files } });
+    assert.deepEqual(resolver.getPackageEntries("pkg", "/app/src"), [
+      { subpath: ".", path: "/app/node_modules/pkg/cjs.js" },
+    ]);
+    const entries = resolver.getPackageEntries("pkg", "/app", {
+      conditions: ["import"],
+      subpaths: [".", "./missing"],
+    });
+    assert.equal(entries[0].path, "/app/node_modules/pkg/esm.js");
+    assert.isDefined(entries[1].error);
+    assert.isDefined(resolver.getPackageEntries("missing", "/app")[0].error);
+  });
+
+  it("addVirtualFile throws without virtualFileSystem", () => {
+    const resolver = new ResolverFactory();
+    assert.throws(() => resolver.addVirtualFile("/app/new.js", ""), /virtualFileSystem/);
+  });
+});
@@ -1,23 +1,26 @@
 use std::{borrow::Cow, path::Path};
 
 use compact_str::CompactString;
+use rustc_hash::FxHashMap;
 
 use crate::{
-    Alias, AliasValue, CachedPath, ResolveError, ResolverImpl, TsConfig,
+    Alias, AliasValue, CachedPath, ResolveError, ResolverImpl, StepOutcome, TsConfig,
     context::ResolveContext as Ctx,
     path::{PathUtil, SLASH_START},
+    prefix_trie::PrefixTrie,
 };
 
 #[derive(Clone, Default)]
 pub struct CompiledAlias {
     entries: Vec<CompiledAliasEntry>,
-    /// First byte of each entry's key (or wildcard prefix), parallel to `entries`. Packed
-    /// contiguously so the per-specifier scan touches one or two cache lines instead of striding
-    /// over the ~100-byte entries. `0` marks an entry that must always be evaluated (an empty
-    /// key/prefix can match any specifier); a key genuinely starting with NUL also maps to `0`,
-    /// which just skips its fast-reject.
-    first_bytes: Box<[u8]>,
-    /// 256-bit set of `first_bytes` values for an O(1) "no entry can match" exit.
+    /// Indices of the `$` (exact match) entries, by key.
+    exact: FxHashMap<Box<[u8]>, Vec<u32>>,
+    /// Indices of the prefix entries by key, and of the wildcard entries by their prefix, so the
+    /// candidate entries for a specifier are found in a single descent.
+    prefixes: PrefixTrie,
+    /// 256-bit set of the entries' first key bytes for an O(1) "no entry can match" exit.
+    /// `0` marks an entry that must always be evaluated (an empty key/prefix can match any
+    /// specifier); a key genuinely starting with NUL also maps to `0`.
     byte_mask: [u64; 4],
 }
 
@@ -32,34 +35,58 @@ impl CompiledAlias {
         self.mask_contains(0) || specifier.first().is_some_and(|&byte| self.mask_contains(byte))
     }
 
-    /// Entries whose first byte is compatible with `specifier`, in declaration order. This is
-    /// only the fast-reject; callers still confirm with [`CompiledAliasEntry::key_matches`].
-    fn matching<'a>(&'a self, specifier: &'a [u8]) -> impl Iterator<Item = &'a CompiledAliasEntry> {
-        let first = specifier.first().copied();
-        self.first_bytes
-            .iter()
-            .zip(&self.entries)
-            .filter(move |&(&byte, _)| byte == 0 || Some(byte) == first)
-            .map(|(_, entry)| entry)
+    /// Indices of the entries whose key (or wildcard prefix) is compatible with `specifier`, in
+    /// declaration order. Callers still confirm with [`CompiledAliasEntry::key_matches`].
+    ///
+    /// The trie nodes and the exact entries each hold their indices in ascending order, so they
+    /// are merged by taking the smallest index after the last one on every step, without
+    /// collecting them.
+    fn candidates<'a>(&'a self, specifier: &'a [u8]) -> impl Iterator<Item = u32> + 'a {
+        let exact = self.exact.get(specifier).map_or(&[][..], Vec::as_slice);
+        let mut next = 0;
+        std::iter::from_fn(move || {
+            let index = self
+                .prefixes
+                .prefixes_of(specifier)
+                .chain(std::iter::once(exact))
+                .filter_map(|indices| indices.get(indices.partition_point(|&i| i < next)))
+                .min()
+                .copied()?;
+            next = index + 1;
+            Some(index)
+        })
+    }
+
+    /// Whether any entry matches `specifier`, including the suffix of wildcard entries, for
+    /// [crate::ResolveOptions::external] whose entries have no values to resolve.
+    pub(crate) fn any_entry_matches(&self, specifier: &str) -> bool {
+        self.may_match(specifier.as_bytes())
+            && self.candidates(specifier.as_bytes()).any(|index| {
+                let entry = &self.entries[index as usize];
+                match &entry.match_kind {
+                    AliasMatchKind::Wildcard { prefix, suffix } => specifier
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.ends_with(suffix.as_str())),
+                    AliasMatchKind::Exact | AliasMatchKind::Prefix => {
+                        entry.key_matches(specifier.as_bytes())
+                    }
+                }
+            })
     }
 
     /// Whether any entry's key matches `specifier` (raw bytes), gated by the first-byte mask.
-    /// This runs for every file candidate when `alias` is configured, so on the common path
-    /// (no always-evaluated entry) it jumps straight to candidate entries with `memchr` instead
-    /// of scanning `first_bytes` with a branch per entry.
+    /// This runs for every file candidate when `alias` is configured, so it only descends the
+    /// prefix trie without collecting candidates in declaration order.
     pub(crate) fn any_key_matches(&self, specifier: &[u8]) -> bool {
         if !self.may_match(specifier) {
             return false;
         }
-        if self.mask_contains(0) {
-            return self.matching(specifier).any(|entry| entry.key_matches(specifier));
-        }
-        // `may_match` returned true without a `0` entry, so the specifier is non-empty.
-        let Some(&first) = specifier.first() else {
-            return false;
-        };
-        memchr::memchr_iter(first, &self.first_bytes)
-            .any(|index| self.entries[index].key_matches(specifier))
+        self.exact.contains_key(specifier)
+            || self
+                .prefixes
+                .prefixes_of(specifier)
+                .flatten()
+                .any(|&index| self.entries[index as usize].key_matches(specifier))
     }
 }
 
@@ -68,11 +95,6 @@ pub struct CompiledAliasEntry {
     key: CompactString,
     match_kind: AliasMatchKind,
     specifiers: Vec<AliasValue>,
-    /// First byte of the key (or wildcard prefix), cached so the alias loop can fast-reject
-    /// non-matching entries without dispatching into the match-kind branch. `None` means an
-    /// empty key/prefix — which can match any specifier (e.g. a `*` wildcard) — so the entry
-    /// is always evaluated.
-    match_first_byte: Option<u8>,
 }
 
 #[derive(Clone)]
@@ -83,51 +105,55 @@ pub enum AliasMatchKind {
 }
 
 pub fn compile_alias(aliases: &Alias) -> CompiledAlias {
-    let entries: Vec<CompiledAliasEntry> = aliases
-        .iter()
-        .map(|(key, specifiers)| {
-            let (key, match_kind) = key.strip_suffix('$').map_or_else(
-                || {
-                    if let Some((prefix, suffix)) = key.split_once('*') {
-                        (
-                            CompactString::new(key),
-                            AliasMatchKind::Wildcard {
-                                prefix: CompactString::new(prefix),
-                                suffix: CompactString::new(suffix),
-                            },
-                        )
-                    } else {
-                        (CompactString::new(key), AliasMatchKind::Prefix)
-                    }
-                },
-                |stripped_key| (CompactString::new(stripped_key), AliasMatchKind::Exact),
-            );
-            let match_first_byte = match &match_kind {
-                AliasMatchKind::Exact | AliasMatchKind::Prefix => key.as_bytes().first().copied(),
-                AliasMatchKind::Wildcard { prefix, .. } => prefix.as_bytes().first().copied(),
-            };
-            CompiledAliasEntry { key, match_kind, specifiers: specifiers.clone(), match_first_byte }
-        })
-        .collect();
-    let first_bytes: Box<[u8]> =
-        entries.iter().map(|entry| entry.match_first_byte.unwrap_or(0)).collect();
-    let mut byte_mask = [0u64; 4];
-    for &byte in &first_bytes {
-        byte_mask[usize::from(byte >> 6)] |= 1u64 << (byte & 63);
+    let mut compiled = CompiledAlias::default();
+    for (index, (key, specifiers)) in aliases.iter().enumerate() {
+        let (key, match_kind) = key.strip_suffix('$').map_or_else(
+            || {
+                if let Some((prefix, suffix)) = key.split_once('*') {
+                    (
+                        CompactString::new(key),
+                        AliasMatchKind::Wildcard {
+                            prefix: CompactString::new(prefix),
+                            suffix: CompactString::new(suffix),
+                        },
+                    )
+                } else {
+                    (CompactString::new(key), AliasMatchKind::Prefix)
+                }
+            },
+            |stripped_key| (CompactString::new(stripped_key), AliasMatchKind::Exact),
+        );
+        #[expect(clippy::cast_possible_truncation, reason = "less than u32::MAX aliases")]
+        let index = index as u32;
+        let match_key = match &match_kind {
+            AliasMatchKind::Exact => {
+                compiled.exact.entry(key.as_bytes().into()).or_default().push(index);
+                key.as_bytes()
+            }
+            AliasMatchKind::Prefix => {
+                compiled.prefixes.insert(key.as_bytes(), index);
+                key.as_bytes()
+            }
+            AliasMatchKind::Wildcard { prefix, .. } => {
+                compiled.prefixes.insert(prefix.as_bytes(), index);
+                prefix.as_bytes()
+            }
+        };
+        let byte = match_key.first().copied().unwrap_or(0);
+        compiled.byte_mask[usize::from(byte >> 6)] |= 1u64 << (byte & 63);
+        compiled.entries.push(CompiledAliasEntry {
+            key,
+            match_kind,
+            specifiers: specifiers.clone(),
+        });
     }
-    CompiledAlias { entries, first_bytes, byte_mask }
+    compiled
 }
 
 impl CompiledAliasEntry {
     /// Whether this entry's key matches `specifier` (raw bytes). Matching on bytes lets a caller
     /// gate on a path's `OsStr` without paying for UTF-8 validation up front.
     pub(crate) fn key_matches(&self, specifier: &[u8]) -> bool {
-        // Fast-reject on the required first byte; `None` matches any specifier.
-        if let Some(required) = self.match_first_byte
-            && Some(required) != specifier.first().copied()
-        {
-            return false;
-        }
         match &self.match_kind {
             AliasMatchKind::Exact => self.key.as_bytes() == specifier,
             // Prefix/suffix is validated later in `load_alias_value`.
@@ -152,7 +178,8 @@ impl ResolverImpl {
         if !aliases.may_match(specifier.as_bytes()) {
             return Ok(None);
         }
-        for alias in aliases.matching(specifier.as_bytes()) {
+        for index in aliases.candidates(specifier.as_bytes()) {
+            let alias = &aliases.entries[index as usize];
             if !alias.key_matches(specifier.as_bytes()) {
                 continue;
             }
@@ -161,28 +188,38 @@ impl ResolverImpl {
             // failed to resolve.
             // <https://github.com/webpack/enhanced-resolve/blob/570337b969eee46120a18b62b72809a3246147da/lib/AliasPlugin.js#L65>
             let mut should_stop = false;
+            // Like AliasPlugin, the remaining values are tried after an error, which is only
+            // reported when none of them resolves.
+            let mut error = None;
             for r in &alias.specifiers {
                 match r {
-                    AliasValue::Path(alias_value) => {
-                        if let Some(path) = self.load_alias_value(
-                            cached_path,
-                            alias_key,
-                            &alias.match_kind,
-                            alias_value,
-                            specifier,
-                            tsconfig,
-                            ctx,
-                            &mut should_stop,
-                        )? {
-                            return Ok(Some(path));
+                    AliasValue::Path(alias_value) => match self.load_alias_value(
+                        cached_path,
+                        alias_key,
+                        &alias.match_kind,
+                        alias_value,
+                        specifier,
+                        tsconfig,
+                        ctx,
+                        &mut should_stop,
+                    ) {
+                        Ok(Some(path)) => return Ok(Some(path)),
+                        Ok(None) => {}
+                        Err(err) if err.is_ignore() => return Err(err),
+                        Err(err) => {
+                            error.get_or_insert(err);
                         }
-                    }
+                    },
+                    // Only reached when none of the values before it resolved, like webpack.
                     AliasValue::Ignore => {
                         let cached_path = cached_path.normalize_with(alias_key, &self.cache);
                         return Err(ResolveError::Ignored(cached_path.to_path_buf()));
                     }
                 }
             }
+            if let Some(err) = error {
+                return Err(err);
+            }
             if should_stop {
                 return Err(ResolveError::MatchedAliasNotFound(
                     specifier.to_string(),
@@ -230,7 +267,7 @@ impl ResolverImpl {
                         let alias_path = Path::new(alias_value).normalize();
                         // Must not append anything to alias_value if it is a file.
                         let cached_alias_path = self.cache.value(&alias_path);
-                        if self.is_file(&cached_alias_path, ctx) {
+                        if self.is_file_impl(&cached_alias_path, ctx) {
                             return Ok(None);
                         }
                         // Remove the leading slash so the final path is concatenated.
@@ -247,11 +284,25 @@ impl ResolverImpl {
 
             *should_stop = true;
             ctx.with_fully_specified(false);
-            return match self.require(cached_path, new_specifier.as_ref(), tsconfig, ctx) {
+            let alias_chain_len = ctx.push_alias(request, &new_specifier);
+            ctx.add_step(
+                "alias",
+                Path::new(new_specifier.as_ref()),
+                StepOutcome::Matched(request.to_string()),
+            );
+            let resolved = self.require(cached_path, new_specifier.as_ref(), tsconfig, ctx);
+            if resolved.is_err() {
+                ctx.truncate_alias_chain(alias_chain_len);
+            }
+            return match resolved {
                 Err(ResolveError::NotFound(_) | ResolveError::MatchedAliasNotFound(_, _)) => {
                     Ok(None)
                 }
-                Ok(path) => Ok(Some(path)),
+                Ok(path) => {
+                    // A nested alias which resolved has already set its specifier.
+                    ctx.aliased_specifier.get_or_insert_with(|| new_specifier.into_owned());
+                    Ok(Some(path))
+                }
                 Err(err) => Err(err),
             };
         }
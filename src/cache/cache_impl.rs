@@ -2,24 +2,30 @@ use std::{
     borrow::Cow,
     cfg_select,
     collections::HashSet as StdHashSet,
+    ffi::OsStr,
     hash::{BuildHasherDefault, Hash, Hasher},
     io,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, OnceLock},
+    time::Duration,
 };
 
 use dashmap::{DashMap, mapref::entry::Entry};
-#[cfg(feature = "yarn_pnp")]
-use once_cell::sync::OnceCell;
-use rustc_hash::FxHasher;
+use rustc_hash::{FxHashMap, FxHasher};
 
 use super::{
-    cached_path::{CachedPath, CachedPathImpl},
+    cached_path::{CachedPath, CachedPathImpl, Symlinks},
     hasher::IdentityHasher,
+    snapshot::{self, CacheSnapshot},
+    statistics::{CacheCounters, CacheStatistics},
 };
 use crate::{
-    FileMetadata, FileSystem, PackageJson, ResolveError, ResolveOptions, TsConfig,
-    context::ResolveContext as Ctx, path::PathUtil,
+    CaseSensitivity, ConditionNamesProvider, FileMetadata, FileSystem, ModuleType, PackageJson,
+    ResolveError, ResolveOptions, TsConfig,
+    context::ResolveContext as Ctx,
+    module_syntax::detect_module_type,
+    path::PathUtil,
+    timings::{Counter, Phase},
 };
 
 /// Cache implementation used for caching filesystem access.
@@ -30,8 +36,99 @@ pub struct Cache {
     pub(crate) tsconfigs_raw: DashMap<PathBuf, Arc<TsConfig>, BuildHasherDefault<FxHasher>>,
     /// Cache for built/resolved tsconfigs (used for resolution).
     pub(crate) tsconfigs_built: DashMap<PathBuf, Arc<TsConfig>, BuildHasherDefault<FxHasher>>,
+    /// Tsconfig of [crate::TsconfigDiscovery::Manual] for each config file, and whether its
+    /// references are followed.
+    pub(crate) manual_tsconfigs:
+        DashMap<(PathBuf, bool), Arc<TsConfig>, BuildHasherDefault<FxHasher>>,
+    /// Yarn PnP manifest found from each `cwd`, `None` when there is none, and each manifest of
+    /// [ResolveOptions::pnp_manifest_paths] keyed by its own path. The manifest found from the
+    /// working directory of the process is keyed by the empty path.
+    #[cfg(feature = "yarn_pnp")]
+    pub(crate) yarn_pnp_manifests:
+        DashMap<PathBuf, Option<Arc<pnp::Manifest>>, BuildHasherDefault<FxHasher>>,
+    /// The closest directory holding one of [LOCKFILES] of each directory, which decides
+    /// whether a Yarn PnP manifest applies to it.
     #[cfg(feature = "yarn_pnp")]
-    pub(crate) yarn_pnp_manifest: OnceCell<pnp::Manifest>,
+    pub(crate) lockfile_directories:
+        DashMap<CachedPath, Option<CachedPath>, BuildHasherDefault<IdentityHasher>>,
+    /// Metadata with size and modification time of each file `lstat`ed or followed to, which the
+    /// one byte [CachedMeta](super::cached_meta::CachedMeta) of a path drops. Kept apart so
+    /// that directories and missing paths, the bulk of the cache, do not pay for it.
+    pub(crate) file_metadata: DashMap<CachedPath, FileMetadata, BuildHasherDefault<IdentityHasher>>,
+    /// [ModuleDirectories] of each directory for each name in [ResolveOptions::modules].
+    pub(crate) module_directories:
+        DashMap<ModuleDirectoriesKey, Option<Arc<ModuleDirectories>>, BuildHasherDefault<FxHasher>>,
+    /// Description file found from each directory for [ResolveOptions::description_files] and
+    /// [ResolveOptions::symlinks] other than the defaults, which are memoized on [CachedPath]
+    /// instead.
+    pub(crate) description_files:
+        DashMap<DescriptionFilesKey, Option<Arc<PackageJson>>, BuildHasherDefault<FxHasher>>,
+    /// Result of [ResolveOptions::condition_names_provider] for each directory, with the
+    /// provider, whose address is part of the key, kept alive so the address is not reused.
+    pub(crate) condition_names:
+        DashMap<ConditionNamesKey, ConditionNamesValue, BuildHasherDefault<FxHasher>>,
+    /// The extension last found in each directory, see [ResolveOptions::adaptive_extension_probing].
+    pub(crate) extension_hints: DashMap<CachedPath, Box<str>, BuildHasherDefault<IdentityHasher>>,
+    /// Entry names of each directory for [ResolveOptions::adaptive_extension_probing] and
+    /// [CaseSensitivity::Insensitive], `None` when the directory cannot be listed.
+    pub(crate) directory_entries:
+        DashMap<CachedPath, Option<Arc<DirectoryEntries>>, BuildHasherDefault<IdentityHasher>>,
+    /// [TsConfig::includes_directory] of each directory and tsconfig path, for
+    /// [ResolveOptions::tsconfig_respect_include].
+    pub(crate) tsconfig_coverage:
+        DashMap<(CachedPath, PathBuf), bool, BuildHasherDefault<FxHasher>>,
+    /// Cumulative `exports` resolution time of each package.json, see
+    /// [crate::ResolverImpl::package_exports_timings].
+    pub(crate) package_exports_timings: DashMap<PathBuf, Duration, BuildHasherDefault<FxHasher>>,
+    /// Results of the [crate::AsyncFileSystem] operations of [crate::ResolverImpl::resolve_async].
+    #[cfg(feature = "async")]
+    pub(crate) file_system_results: Arc<crate::resolve_async::FileSystemResults>,
+    /// [Cache::fs] answering from [Cache::file_system_results], when the file system is async.
+    #[cfg(feature = "async")]
+    prefetched: Option<crate::resolve_async::PrefetchedFileSystem>,
+    /// See [Cache::statistics].
+    pub(crate) counters: CacheCounters,
+    /// See [Cache::load_snapshot_mmap].
+    pub(crate) snapshot: OnceLock<Arc<CacheSnapshot>>,
+    /// Paths are looked up ignoring case, see [Cache::with_case_sensitivity].
+    pub(crate) case_insensitive: bool,
+}
+
+/// Lockfiles of package managers, whose directory is the root of a project.
+#[cfg(feature = "yarn_pnp")]
+pub const LOCKFILES: [&str; 6] = [
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "bun.lockb",
+];
+
+/// A directory and a name in [ResolveOptions::modules].
+pub type ModuleDirectoriesKey = (CachedPath, Cow<'static, str>);
+
+/// A directory, the names in [ResolveOptions::description_files] joined with `\0` and
+/// [ResolveOptions::symlinks], which decides the [PackageJson::realpath].
+pub type DescriptionFilesKey = (CachedPath, Box<str>, bool);
+
+/// A directory and the address of a [ResolveOptions::condition_names_provider].
+pub type ConditionNamesKey = (CachedPath, usize);
+
+/// The provider of a [ConditionNamesKey] and its result.
+pub type ConditionNamesValue = (ConditionNamesProvider, Option<Arc<[String]>>);
+
+/// The entries of a directory by their lower cased names, see [Cache::may_exist].
+///
+/// Names which only differ in case map to an empty string.
+pub type DirectoryEntries = FxHashMap<Box<str>, Box<str>>;
+
+/// The existing module directories of a directory and its ancestors, closest first.
+///
+/// Chains of sibling directories share the nodes of their common ancestors.
+pub struct ModuleDirectories {
+    pub(crate) directory: CachedPath,
+    pub(crate) parent: Option<Arc<Self>>,
 }
 
 impl Cache {
@@ -39,11 +136,409 @@ impl Cache {
         self.paths.clear();
         self.tsconfigs_raw.clear();
         self.tsconfigs_built.clear();
+        self.manual_tsconfigs.clear();
+        #[cfg(feature = "yarn_pnp")]
+        self.yarn_pnp_manifests.clear();
+        #[cfg(feature = "yarn_pnp")]
+        self.lockfile_directories.clear();
+        self.file_metadata.clear();
+        self.module_directories.clear();
+        self.description_files.clear();
+        self.condition_names.clear();
+        self.extension_hints.clear();
+        self.directory_entries.clear();
+        self.tsconfig_coverage.clear();
+        #[cfg(feature = "async")]
+        self.file_system_results.clear();
+    }
+
+    /// Forget what is cached about the file or directory at `path`, e.g. after a watcher reported
+    /// a change to it, keeping the rest of the cache warm.
+    ///
+    /// This drops
+    /// * the file system metadata of `path`, and of the paths which canonicalize to it;
+    /// * the package.json read from `path`, and the package.json lookups below the directory of a
+    ///   created or removed package.json;
+    /// * every tsconfig when `path` is a tsconfig, because any other tsconfig may extend it;
+    /// * the module directory lookups which `path` takes part in;
+    /// * the [ResolveOptions::condition_names_provider] results of the directory of `path` and
+    ///   the directories below it.
+    ///
+    /// The paths below `path` are kept, see [Cache::invalidate_dir_recursive].
+    ///
+    /// Returns whether anything was dropped.
+    ///
+    /// Warning: The caller must ensure that there're no ongoing resolution operations when calling
+    /// this method, like for [Cache::clear].
+    pub fn invalidate_path(&self, path: &Path) -> bool {
+        self.invalidate(path, false)
+    }
+
+    /// [Cache::invalidate_path] for `path` and every path below it, e.g. after a directory was
+    /// deleted or moved.
+    ///
+    /// Returns whether anything was dropped.
+    ///
+    /// Warning: The caller must ensure that there're no ongoing resolution operations when calling
+    /// this method, like for [Cache::clear].
+    pub fn invalidate_dir_recursive(&self, path: &Path) -> bool {
+        self.invalidate(path, true)
+    }
+
+    fn invalidate(&self, path: &Path, recursive: bool) -> bool {
+        let path = path.normalize();
+        if let Some(snapshot) = self.snapshot.get() {
+            snapshot.invalidate(&path, recursive);
+        }
+        self.forget(&path, recursive)
+    }
+
+    /// [Cache::invalidate] leaving the snapshot alone, which a [Cache::scratch] cache shares.
+    pub(crate) fn forget(&self, path: &Path, recursive: bool) -> bool {
+        let matches = |p: &Path| if recursive { p.starts_with(path) } else { p == path };
+        let parent = path.parent().unwrap_or(path);
+        let file_name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+        let is_tsconfig = matches!(file_name, "tsconfig.json" | "jsconfig.json")
+            || self.tsconfigs_raw.iter().any(|entry| matches(entry.key()))
+            || self.tsconfigs_built.iter().any(|entry| matches(entry.key()));
+        let is_stale_package_json = |package_json: &Option<Arc<PackageJson>>| {
+            package_json.as_ref().is_some_and(|package_json| {
+                matches(package_json.path()) || matches(package_json.realpath())
+            })
+        };
+        let mut removed = false;
+
+        self.paths.retain(|cached_path, ()| {
+            let stale = matches(cached_path.path())
+                || cached_path.canonicalized.get().is_some_and(|(_, real, _)| matches(real))
+                || cached_path.package_json.get().is_some_and(|package_json| {
+                    is_stale_package_json(package_json)
+                        || (file_name == "package.json" && cached_path.path().starts_with(parent))
+                })
+                || (is_tsconfig
+                    && (cached_path.tsconfig.get().is_some()
+                        || cached_path.resolved_tsconfig.get().is_some()))
+                // The memo of `CachedPath::cached_node_modules`.
+                || (file_name == "node_modules" && cached_path.path() == parent);
+            removed |= stale;
+            !stale
+        });
+
+        if is_tsconfig
+            && !(self.tsconfigs_raw.is_empty()
+                && self.tsconfigs_built.is_empty()
+                && self.manual_tsconfigs.is_empty())
+        {
+            self.tsconfigs_raw.clear();
+            self.tsconfigs_built.clear();
+            self.manual_tsconfigs.clear();
+            self.tsconfig_coverage.clear();
+            removed = true;
+        }
+
+        self.file_metadata.retain(|path, _| {
+            let stale = matches(path.path())
+                || path.canonicalized.get().is_some_and(|(_, real, _)| matches(real));
+            removed |= stale;
+            !stale
+        });
+
+        self.description_files.retain(|(directory, file_names, _), package_json| {
+            let stale = is_stale_package_json(package_json)
+                || (file_names.split('\0').any(|name| name == file_name)
+                    && directory.path().starts_with(parent));
+            removed |= stale;
+            !stale
+        });
+
+        self.module_directories.retain(|(directory, name), module_directories| {
+            let mut stale = *name == file_name && directory.path().starts_with(parent);
+            let mut node = module_directories.as_deref();
+            while !stale && let Some(module_directory) = node {
+                stale = matches(module_directory.directory.path());
+                node = module_directory.parent.as_deref();
+            }
+            removed |= stale;
+            !stale
+        });
+
+        #[cfg(feature = "yarn_pnp")]
+        self.lockfile_directories.retain(|directory, lockfile_directory| {
+            let stale = (LOCKFILES.contains(&file_name) && directory.path().starts_with(parent))
+                || lockfile_directory.as_ref().is_some_and(|directory| matches(directory.path()));
+            removed |= stale;
+            !stale
+        });
+
+        self.condition_names.retain(|(directory, _), _| {
+            let stale = directory.path().starts_with(parent);
+            removed |= stale;
+            !stale
+        });
+
+        self.directory_entries.retain(|directory, _| {
+            let stale = directory.path() == parent || matches(directory.path());
+            removed |= stale;
+            !stale
+        });
+
+        #[cfg(feature = "async")]
+        self.file_system_results.retain(|(_, operation_path), _| {
+            let stale = matches(operation_path);
+            removed |= stale;
+            !stale
+        });
+
+        removed
+    }
+
+    /// Populate the cache with the metadata of every file and directory below `root`, and the
+    /// package.json lookup of every directory, e.g. when a bundler starts so that its
+    /// first resolutions find a warm cache.
+    ///
+    /// Symbolic links are followed like with the default [ResolveOptions::symlinks], but the
+    /// directories they point to are not walked into.
+    /// See [crate::ResolverImpl::warm_from_directory] for warming with the options of a resolver.
+    ///
+    /// Entries which are already cached are skipped, so calling this again is cheap and returns
+    /// the number of entries which were not cached yet. A `tracing` debug event is emitted for
+    /// each directory walked.
+    ///
+    /// Returns the number of entries populated: the paths whose metadata was loaded and the
+    /// description files which were parsed.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when `root` can not be read, including when the
+    ///   [FileSystem] does not implement [FileSystem::read_dir].
+    pub fn warm_from_directory(&self, root: &Path) -> Result<usize, ResolveError> {
+        self.warm_from_directory_with(root, &ResolveOptions::default())
     }
 
-    /// The underlying filesystem as a trait object.
+    /// [Cache::warm_from_directory] with the [ResolveOptions::symlinks] and
+    /// [ResolveOptions::description_files] of `options`.
+    pub(crate) fn warm_from_directory_with(
+        &self,
+        root: &Path,
+        options: &ResolveOptions,
+    ) -> Result<usize, ResolveError> {
+        let root = self.value(root);
+        let mut populated = usize::from(self.warm_metadata(&root, options.symlinks).0);
+        let mut directories = vec![(root, true)];
+        let mut ctx = Ctx::default();
+        while let Some((directory, is_root)) = directories.pop() {
+            let entries = match self.fs().read_dir(directory.path()) {
+                Ok(entries) => entries,
+                Err(error) if is_root => return Err(error.into()),
+                Err(_) => continue,
+            };
+            let mut count = 0;
+            let mut has_description_file = false;
+            for entry in entries {
+                let cached_path = self.value(&entry);
+                let (loaded, meta) = self.warm_metadata(&cached_path, options.symlinks);
+                count += usize::from(loaded);
+                let Some(meta) = meta else { continue };
+                if meta.is_file() {
+                    has_description_file |= entry
+                        .file_name()
+                        .and_then(OsStr::to_str)
+                        .is_some_and(|name| options.description_files.iter().any(|n| n == name));
+                } else if meta.is_dir()
+                    && !cached_path.link_metadata(self).is_some_and(FileMetadata::is_symlink)
+                {
+                    directories.push((cached_path, false));
+                }
+            }
+            // The lookup is also cached for directories without a description file, which
+            // share the one of their parent. Invalid description files are reported by the
+            // resolutions reading them.
+            if !self.has_description_file_lookup(&directory, options)
+                && self.find_package_json_impl(&directory, options, &mut ctx).is_ok()
+            {
+                count += usize::from(has_description_file);
+            }
+            tracing::debug!(directory = ?directory.path(), populated = count, "warmed directory");
+            populated += count;
+        }
+        Ok(populated)
+    }
+
+    /// Write the file system metadata in the cache to an artifact at `path`, which other
+    /// processes map with [Cache::load_snapshot_mmap] instead of asking the file system again.
+    ///
+    /// `token` is stored with the artifact and must be passed to the load, e.g. a hash of the
+    /// lockfile which changes whenever `node_modules` does. The artifact is replaced atomically,
+    /// so processes which mapped the previous one are not disturbed.
+    ///
+    /// Returns the number of paths written.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when the artifact can not be written.
+    pub fn save_snapshot_mmap(&self, path: &Path, token: &str) -> Result<usize, ResolveError> {
+        let entries = self
+            .paths
+            .iter()
+            .map(|entry| {
+                let (link, followed) = entry.key().meta.bits();
+                (entry.key().clone(), link, followed)
+            })
+            .filter(|(_, link, followed)| (link | followed) != 0)
+            .collect::<Vec<_>>();
+        let bytes = CacheSnapshot::encode(
+            entries
+                .iter()
+                .map(|(cached_path, link, followed)| (cached_path.path(), *link, *followed)),
+            token,
+        )?;
+        snapshot::write(path, &bytes)?;
+        Ok(entries.len())
+    }
+
+    /// Map the artifact written by [Cache::save_snapshot_mmap] at `path` as a read-only layer
+    /// beneath the cache: a path which is not cached yet takes its metadata from the artifact
+    /// when the artifact has it, and from the [FileSystem] otherwise. New metadata is only
+    /// cached in memory, the mapping is never written to and its pages are shared by every
+    /// process mapping the same artifact.
+    ///
+    /// The layer stays for the lifetime of the cache, also across [Cache::clear]. Paths passed to
+    /// [Cache::invalidate_path] and [Cache::invalidate_dir_recursive] are no longer answered
+    /// from it.
+    ///
+    /// The artifact is read into memory on platforms without `mmap` support.
+    ///
+    /// Returns `false` without loading anything when the artifact was written with another
+    /// `token`, or when an artifact is loaded already.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when `path` can not be read or is not an artifact.
+    ///
+    /// # Safety
+    ///
+    /// The artifact at `path` must not be truncated or modified in place, by this or any other
+    /// process, for the lifetime of the cache. Replace it with [Cache::save_snapshot_mmap], which
+    /// renames a new file over `path`. The mapping is private but not a copy, so the cache would
+    /// read the modified bytes.
+    pub unsafe fn load_snapshot_mmap(
+        &self,
+        path: &Path,
+        token: &str,
+    ) -> Result<bool, ResolveError> {
+        if self.snapshot.get().is_some() {
+            return Ok(false);
+        }
+        // SAFETY: The caller keeps the artifact unmodified for the lifetime of the cache, which
+        // owns the snapshot.
+        let Some(snapshot) = (unsafe { CacheSnapshot::open(path, token) })? else {
+            return Ok(false);
+        };
+        tracing::debug!(path = ?path, paths = snapshot.len(), "mapped cache snapshot");
+        Ok(self.snapshot.set(Arc::new(snapshot)).is_ok())
+    }
+
+    /// Load the metadata of `path` into the cache, also returning whether it was not cached yet.
+    fn warm_metadata(&self, path: &CachedPath, symlinks: bool) -> (bool, Option<FileMetadata>) {
+        let mut loaded = false;
+        let meta = path.meta.followed_or_init(|| {
+            loaded = true;
+            self.load_followed_metadata(path, symlinks)
+        });
+        (loaded, meta)
+    }
+
+    /// Whether the description file of a directory is memoized on [CachedPath::package_json]
+    /// rather than in [Cache::description_files].
+    fn memoizes_package_json(options: &ResolveOptions) -> bool {
+        options.symlinks
+            && matches!(options.description_files.as_slice(), [file_name] if file_name == "package.json")
+    }
+
+    fn description_files_key(
+        directory: &CachedPath,
+        options: &ResolveOptions,
+    ) -> DescriptionFilesKey {
+        let file_names = options.description_files.join("\0").into_boxed_str();
+        (directory.clone(), file_names, options.symlinks)
+    }
+
+    /// Whether the description file lookup of `directory` is cached.
+    fn has_description_file_lookup(
+        &self,
+        directory: &CachedPath,
+        options: &ResolveOptions,
+    ) -> bool {
+        if Self::memoizes_package_json(options) {
+            return directory.package_json.get().is_some();
+        }
+        let key = Self::description_files_key(directory, options);
+        self.description_files.contains_key(&key)
+    }
+
+    /// Whether `tsconfig` covers files in `directory`, see [TsConfig::includes_directory].
+    pub(crate) fn tsconfig_includes_directory(
+        &self,
+        tsconfig: &TsConfig,
+        directory: &CachedPath,
+    ) -> bool {
+        let key = (directory.clone(), tsconfig.path().to_path_buf());
+        if let Some(covered) = self.tsconfig_coverage.get(&key) {
+            return *covered;
+        }
+        let covered = tsconfig.includes_directory(directory.path());
+        self.tsconfig_coverage.insert(key, covered);
+        covered
+    }
+
+    /// The result of `provider` for `directory`, see [ResolveOptions::condition_names_provider].
+    pub(crate) fn condition_names(
+        &self,
+        directory: &CachedPath,
+        provider: &ConditionNamesProvider,
+    ) -> Option<Arc<[String]>> {
+        let key = (directory.clone(), Arc::as_ptr(&provider.0).cast::<()>() as usize);
+        if let Some(entry) = self.condition_names.get(&key) {
+            return entry.1.clone();
+        }
+        // The provider is called without holding a lock of the map.
+        let condition_names: Option<Arc<[String]>> = (provider.0)(directory.path()).map(Arc::from);
+        self.condition_names.insert(key, (provider.clone(), condition_names.clone()));
+        condition_names
+    }
+
+    /// Hit and miss counts since [Cache::enable_statistics] or [Cache::reset_statistics] was
+    /// called, all zero while the statistics are not enabled.
+    ///
+    /// [Cache::clear] does not reset the counts.
+    pub fn statistics(&self) -> CacheStatistics {
+        self.counters.snapshot()
+    }
+
+    /// Start counting the hits and misses of [Cache::statistics], which are not counted by
+    /// default to keep the lookups free of shared writes.
+    pub fn enable_statistics(&self) {
+        self.counters.enable();
+    }
+
+    /// Reset the counts of [Cache::statistics] to zero, e.g. at the start of a benchmark.
+    pub fn reset_statistics(&self) {
+        self.counters.reset();
+    }
+
+    pub(crate) fn add_package_exports_timing(&self, package_json_path: &Path, elapsed: Duration) {
+        *self.package_exports_timings.entry(package_json_path.to_path_buf()).or_default() +=
+            elapsed;
+    }
+
+    /// The underlying filesystem as a trait object, answering from
+    /// [Cache::file_system_results] first when it is async.
     #[inline]
-    fn fs(&self) -> &dyn FileSystem {
+    pub(crate) fn fs(&self) -> &dyn FileSystem {
+        #[cfg(feature = "async")]
+        if let Some(prefetched) = &self.prefetched {
+            return prefetched;
+        }
         &*self.fs
     }
 
@@ -72,6 +567,13 @@ impl Cache {
             }
         }
         let parent = path.parent().map(|p| self.value(p));
+        if self.case_insensitive
+            && let Some(parent) = &parent
+            && let Some(name) = path.file_name()
+            && let Some(name) = self.case_folded_name(parent.clone(), name)
+        {
+            return self.value(&parent.path().join(&*name));
+        }
         let is_node_modules = path.file_name().is_some_and(|name| name == "node_modules");
         let inside_node_modules =
             is_node_modules || parent.as_ref().is_some_and(|parent| parent.inside_node_modules);
@@ -83,6 +585,9 @@ impl Cache {
             inside_node_modules,
             parent_weak,
         )));
+        if let Some((link, followed)) = self.snapshot.get().and_then(|s| s.get(path)) {
+            cached_path.meta.seed(link, followed);
+        }
         // The shard guard above is dropped before the parent recursion, so a concurrent call may
         // have inserted this same path in the meantime. Dedup via the entry API so every path keeps
         // a single shared `Arc`, which the `canonicalized` / `node_modules` weak-pointer caches rely
@@ -96,8 +601,19 @@ impl Cache {
         }
     }
 
-    pub(crate) fn canonicalize(&self, path: &CachedPath) -> Result<PathBuf, ResolveError> {
-        let cached_path = self.canonicalize_impl(path)?;
+    /// [Cache::canonicalize_impl], recording each symlink traversed into the file dependencies of
+    /// `ctx`, so retargeting a link invalidates the resolutions through it.
+    pub(crate) fn canonicalize(
+        &self,
+        path: &CachedPath,
+        ctx: &mut Ctx,
+    ) -> Result<PathBuf, ResolveError> {
+        let (cached_path, symlinks) = self.canonicalize_with_symlinks(path)?;
+        if ctx.file_dependencies.is_some() {
+            for link in symlinks.iter().flat_map(|symlinks| symlinks.iter()) {
+                ctx.add_file_dependency(&self.value(link));
+            }
+        }
         let path = cached_path.to_path_buf();
         cfg_select! {
             target_os = "windows" => crate::windows::strip_windows_prefix(path),
@@ -106,25 +622,106 @@ impl Cache {
     }
 
     pub(crate) fn is_file(&self, path: &CachedPath, symlinks: bool, ctx: &mut Ctx) -> bool {
+        ctx.count(Counter::MetadataLookup);
         if self.followed_metadata(path, symlinks).is_some_and(FileMetadata::is_file) {
-            ctx.add_file_dependency(path.path());
+            ctx.add_file_dependency(path);
             true
         } else {
-            ctx.add_missing_dependency(path.path());
+            Self::add_missing(path, ctx);
             false
         }
     }
 
     pub(crate) fn is_dir(&self, path: &CachedPath, symlinks: bool, ctx: &mut Ctx) -> bool {
+        ctx.count(Counter::MetadataLookup);
         self.followed_metadata(path, symlinks).map_or_else(
             || {
-                ctx.add_missing_dependency(path.path());
+                Self::add_missing(path, ctx);
                 false
             },
             FileMetadata::is_dir,
         )
     }
 
+    fn add_missing(path: &CachedPath, ctx: &mut Ctx) {
+        ctx.add_missing_dependency(path);
+        if path.meta.is_too_long() && ctx.path_too_long.is_none() {
+            ctx.path_too_long = Some(path.to_path_buf());
+        }
+    }
+
+    /// The position in `extensions` of the extension last found in `directory`, when it is not
+    /// the first one, see [ResolveOptions::adaptive_extension_probing].
+    pub(crate) fn extension_hint(
+        &self,
+        directory: &CachedPath,
+        extensions: &[String],
+    ) -> Option<usize> {
+        let hint = self.extension_hints.get(directory)?;
+        extensions.iter().position(|extension| **extension == **hint).filter(|&index| index > 0)
+    }
+
+    /// Remember `extension` as found in `directory`, only the extensions which are not tried first
+    /// are kept.
+    pub(crate) fn set_extension_hint(&self, directory: &CachedPath, extension: &str, first: bool) {
+        if first {
+            if self.extension_hints.contains_key(directory) {
+                self.extension_hints.remove(directory);
+            }
+        } else if self.extension_hints.get(directory).is_none_or(|hint| **hint != *extension) {
+            self.extension_hints.insert(directory.clone(), extension.into());
+        }
+    }
+
+    /// Whether `path` may exist according to the entries of its directory, which are listed once
+    /// per directory, see [ResolveOptions::adaptive_extension_probing].
+    ///
+    /// Names are compared lower cased, so a case-insensitive file system only gets false
+    /// positives, which are stat'ed as usual. `true` when the directory cannot be listed.
+    pub(crate) fn may_exist(&self, path: &CachedPath) -> bool {
+        let Some(name) = path.path().file_name() else { return true };
+        // Too long names are left to `metadata`, which reports them.
+        if name.len() > 255 {
+            return true;
+        }
+        let Some(directory) = path.parent(self) else { return true };
+        let entries = self.directory_entries(directory);
+        entries.is_none_or(|entries| entries.contains_key(&*lowercase(&name.to_string_lossy())))
+    }
+
+    /// The name of the entry of `directory` which matches `name` ignoring case, when it is spelled
+    /// differently than `name` and no other entry matches.
+    fn case_folded_name(&self, directory: CachedPath, name: &OsStr) -> Option<Box<str>> {
+        let name = name.to_str()?;
+        let entries = self.directory_entries(directory)?;
+        let entry = entries.get(&*lowercase(name))?;
+        (!entry.is_empty() && &**entry != name).then(|| entry.clone())
+    }
+
+    fn directory_entries(&self, directory: CachedPath) -> Option<Arc<DirectoryEntries>> {
+        if let Some(entries) = self.directory_entries.get(&directory) {
+            return entries.clone();
+        }
+        let entries = self.fs().read_dir(directory.path()).ok().map(|entries| {
+            let mut names = DirectoryEntries::default();
+            for name in entries.iter().filter_map(|entry| entry.file_name()) {
+                let name = name.to_string_lossy();
+                names
+                    .entry(lowercase(&name).into())
+                    .and_modify(|entry| *entry = Box::default())
+                    .or_insert_with(|| name.into());
+            }
+            Arc::new(names)
+        });
+        self.directory_entries.insert(directory, entries.clone());
+        entries
+    }
+
+    /// Whether `path` exists, symlinks followed.
+    pub(crate) fn exists(&self, path: &CachedPath) -> bool {
+        self.followed_metadata(path, true).is_some()
+    }
+
     /// `stat`-equivalent metadata (symlinks followed) for `path`, cached in the `followed` slot.
     ///
     /// For a non-symlink the cached `lstat` already answers this, so no extra syscall is issued.
@@ -137,17 +734,50 @@ impl Cache {
     /// additive: a custom [`FileSystem`] whose `canonicalize` and `metadata` disagree still gets
     /// the same answer `stat` gave before.
     fn followed_metadata(&self, path: &CachedPath, symlinks: bool) -> Option<FileMetadata> {
-        path.meta.followed_or_init(|| match path.link_metadata(self.fs()) {
+        let mut hit = true;
+        let meta = path.meta.followed_or_init(|| {
+            hit = false;
+            self.load_followed_metadata(path, symlinks)
+        });
+        self.counters.file(hit);
+        meta
+    }
+
+    /// [Cache::followed_metadata] of a path which is not cached yet.
+    fn load_followed_metadata(&self, path: &CachedPath, symlinks: bool) -> Option<FileMetadata> {
+        match path.link_metadata(self) {
             Some(meta) if meta.is_symlink() => {
                 let followed = if symlinks {
-                    self.canonicalize_impl(path).ok().and_then(|c| c.link_metadata(self.fs()))
+                    self.canonicalize_impl(path).ok().and_then(|c| {
+                        c.link_metadata(self)
+                            .map(|meta| self.file_metadata.get(&c).map_or(meta, |m| *m))
+                    })
                 } else {
                     None
                 };
-                followed.or_else(|| self.fs.metadata(path.path()).ok())
+                let followed = followed.or_else(|| self.fs().metadata(path.path()).ok());
+                if let Some(meta) = followed.filter(|meta| meta.is_file()) {
+                    self.file_metadata.insert(path.clone(), meta);
+                }
+                followed
             }
             // A non-symlink's `lstat` already is its `stat`; `None` stays `None`.
             other => other,
+        }
+    }
+
+    /// `stat`-equivalent metadata of a file, with the size and modification time reported by the
+    /// [`FileSystem`]. Returns `None` when `path` is not a file.
+    pub(crate) fn file_metadata(&self, path: &CachedPath, symlinks: bool) -> Option<FileMetadata> {
+        let meta = self.followed_metadata(path, symlinks).filter(|meta| meta.is_file())?;
+        Some(self.file_metadata.get(path).map_or(meta, |meta| *meta))
+    }
+
+    /// Module type of the file at `path` detected from its contents, see
+    /// [ResolveOptions::module_type_detection]. `None` when the file cannot be read.
+    pub(crate) fn detected_module_type(&self, path: &CachedPath) -> Option<ModuleType> {
+        *path.detected_module_type.get_or_init(|| {
+            self.fs().read(path.path()).ok().map(|source| detect_module_type(&source))
         })
     }
 
@@ -162,14 +792,16 @@ impl Cache {
         options: &ResolveOptions,
         ctx: &mut Ctx,
     ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
-        self.find_package_json(path, options, ctx).map(|option_package_json| {
-            option_package_json.filter(|package_json| {
-                package_json
-                    .path()
-                    .parent()
-                    .is_some_and(|p| p.as_os_str() == path.path().as_os_str())
-            })
-        })
+        let timer = ctx.start_phase(Phase::PackageJsonIo);
+        let package_json = self.lookup_package_json(path, options, ctx);
+        ctx.stop_phase(timer);
+        let package_json = package_json?.filter(|package_json| {
+            package_json.path().parent().is_some_and(|p| p.as_os_str() == path.path().as_os_str())
+        });
+        if let Some(package_json) = &package_json {
+            ctx.add_package_json(package_json.path());
+        }
+        Ok(package_json)
     }
 
     /// Find package.json of a path by traversing parent directories.
@@ -182,6 +814,23 @@ impl Cache {
         path: &CachedPath,
         options: &ResolveOptions,
         ctx: &mut Ctx,
+    ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        let timer = ctx.start_phase(Phase::PackageJsonIo);
+        let package_json = self.lookup_package_json(path, options, ctx);
+        ctx.stop_phase(timer);
+        let package_json = package_json?;
+        if let Some(package_json) = &package_json {
+            ctx.add_package_json(package_json.path());
+        }
+        Ok(package_json)
+    }
+
+    /// [Cache::find_package_json] without recording into [Ctx::package_json_chain].
+    fn lookup_package_json(
+        &self,
+        path: &CachedPath,
+        options: &ResolveOptions,
+        ctx: &mut Ctx,
     ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
         let mut path = path.clone();
         // Go up directories when the querying path is not a directory
@@ -206,47 +855,72 @@ impl Cache {
         options: &ResolveOptions,
         ctx: &mut Ctx,
     ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
-        // Change to `std::sync::OnceLock::get_or_try_init` when it is stable.
-        path.package_json
-            .get_or_try_init(|| {
-                let package_json_path = path.path.join("package.json");
-                let Ok(package_json_bytes) = self.fs.read(&package_json_path) else {
-                    if let Some(deps) = &mut ctx.missing_dependencies {
-                        deps.push(package_json_path);
-                    }
-                    return path.parent(self).map_or(Ok(None), |parent| {
-                        self.find_package_json_impl(&parent, options, ctx)
-                    });
-                };
-                let real_path = if options.symlinks {
-                    self.canonicalize(path)?.join("package.json")
-                } else {
-                    package_json_path.clone()
-                };
-                // Move `package_json_path` into `parse` instead of cloning it: the parsed
-                // `PackageJson` stores the path verbatim (`package_json.path()`), and on error
-                // `JSONError.path` carries the same path, so the file-dependency record reads it
-                // back without a second allocation.
-                // https://github.com/webpack/enhanced-resolve/blob/58464fc7cb56673c9aa849e68e6300239601e615/lib/DescriptionFileUtils.js#L68-L82
-                match PackageJson::parse(
-                    self.fs(),
-                    package_json_path,
-                    real_path,
-                    package_json_bytes,
-                ) {
-                    Ok(package_json) => {
-                        ctx.add_file_dependency(package_json.path());
-                        Ok(Some(Arc::new(package_json)))
-                    }
-                    Err(error) => {
-                        if let Some(deps) = &mut ctx.file_dependencies {
-                            deps.push(error.path.clone());
-                        }
-                        Err(ResolveError::Json(error))
-                    }
-                }
-            })
-            .cloned()
+        if Self::memoizes_package_json(options) {
+            if let Some(package_json) = path.package_json.get() {
+                self.counters.package_json(true);
+                return Ok(package_json.clone());
+            }
+            self.counters.package_json(false);
+            // Change to `std::sync::OnceLock::get_or_try_init` when it is stable.
+            return path
+                .package_json
+                .get_or_try_init(|| self.read_description_file(path, options, ctx))
+                .cloned();
+        }
+        let key = Self::description_files_key(path, options);
+        if let Some(package_json) = self.description_files.get(&key) {
+            self.counters.package_json(true);
+            return Ok(package_json.clone());
+        }
+        self.counters.package_json(false);
+        let package_json = self.read_description_file(path, options, ctx)?;
+        self.description_files.insert(key, package_json.clone());
+        Ok(package_json)
+    }
+
+    /// Read the first of [ResolveOptions::description_files] in `path`, or find one from its
+    /// parent when there is none.
+    fn read_description_file(
+        &self,
+        path: &CachedPath,
+        options: &ResolveOptions,
+        ctx: &mut Ctx,
+    ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        let found = options.description_files.iter().find_map(|file_name| {
+            let package_json_path = path.path.join(file_name);
+            let cached_package_json = self.value(&package_json_path);
+            // Only read a regular file. A directory (e.g. from a failed extraction) or a
+            // special file such as a FIFO, which would block the read, is skipped as if the
+            // package.json were absent.
+            let is_file = self
+                .followed_metadata(&cached_package_json, options.symlinks)
+                .is_some_and(FileMetadata::is_file);
+            let package_json_bytes =
+                if is_file { self.fs().read(&package_json_path).ok() } else { None };
+            if package_json_bytes.is_none() {
+                ctx.add_missing_dependency(&cached_package_json);
+            }
+            package_json_bytes
+                .map(|bytes| (file_name, package_json_path, cached_package_json, bytes))
+        });
+        let Some((file_name, package_json_path, cached_package_json, package_json_bytes)) = found
+        else {
+            return path
+                .parent(self)
+                .map_or(Ok(None), |parent| self.find_package_json_impl(&parent, options, ctx));
+        };
+        let real_path = if options.symlinks {
+            self.canonicalize(path, ctx)?.join(file_name)
+        } else {
+            package_json_path.clone()
+        };
+        // The package.json is a file dependency whether or not it parses.
+        // https://github.com/webpack/enhanced-resolve/blob/58464fc7cb56673c9aa849e68e6300239601e615/lib/DescriptionFileUtils.js#L68-L82
+        ctx.add_file_dependency(&cached_package_json);
+        ctx.count(Counter::PackageJsonParse);
+        PackageJson::parse(self.fs(), package_json_path, real_path, package_json_bytes)
+            .map(|package_json| Some(Arc::new(package_json)))
+            .map_err(ResolveError::Json)
     }
 
     pub(crate) fn get_tsconfig<F: FnOnce(&mut TsConfig) -> Result<(), ResolveError>>(
@@ -257,35 +931,75 @@ impl Cache {
     ) -> Result<Arc<TsConfig>, ResolveError> {
         // For root=true (caller tsconfig), check built cache first
         if root && let Some(tsconfig) = self.tsconfigs_built.get(path) {
+            self.counters.tsconfig(true);
             return Ok(Arc::clone(tsconfig.value()));
         }
 
         // Check raw cache (callback applied, not built) - only for root=false
         // For root=true, we need to run the callback to ensure extends are processed
         if !root && let Some(tsconfig) = self.tsconfigs_raw.get(path) {
+            self.counters.tsconfig(true);
             return Ok(Arc::clone(tsconfig.value()));
         }
+        self.counters.tsconfig(false);
 
         // Not in any cache, parse from file.
+        let mut tsconfig = self.read_tsconfig(root, path)?;
+
+        // Run callback (extends/references processing)
+        callback(&mut tsconfig)?;
+
+        // Cache raw version (callback applied, not built)
+        tsconfig.set_should_build(false);
+        if root {
+            self.tsconfigs_raw.insert(path.to_path_buf(), Arc::new(tsconfig.clone()));
+            // Build and cache built version
+            tsconfig.set_should_build(true);
+            let tsconfig = Arc::new(tsconfig.build());
+            self.tsconfigs_built.insert(path.to_path_buf(), Arc::clone(&tsconfig));
+            Ok(tsconfig)
+        } else {
+            // Return unbuilt version
+            let tsconfig = Arc::new(tsconfig);
+            self.tsconfigs_raw.insert(path.to_path_buf(), Arc::clone(&tsconfig));
+            Ok(tsconfig)
+        }
+    }
+
+    /// Read and parse the tsconfig at `path` without caching it.
+    ///
+    /// `path` is a file, a directory containing `tsconfig.json` or else `jsconfig.json`, or a file
+    /// name without the `.json` extension.
+    pub(crate) fn read_tsconfig(&self, root: bool, path: &Path) -> Result<TsConfig, ResolveError> {
         // Classify file/dir via the cached `lstat` (which the canonicalization below reuses)
         // instead of a standalone `stat`. For a regular file/dir the two agree; only follow the
         // link with a `stat` when `path` is actually a symlink, preserving the symlink-following
         // classification while saving one metadata syscall per tsconfig in the common case.
         let cached_path = self.value(path);
-        let meta = match cached_path.link_metadata(self.fs()) {
-            Some(m) if m.is_symlink() => self.fs.metadata(path).ok(),
+        let meta = match cached_path.link_metadata(self) {
+            Some(m) if m.is_symlink() => self.fs().metadata(path).ok(),
             other => other,
         };
         let tsconfig_path = if meta.is_some_and(|m| m.is_file) {
             Cow::Borrowed(path)
         } else if meta.is_some_and(|m| m.is_dir) {
-            Cow::Owned(path.join("tsconfig.json"))
+            // `jsconfig.json` is ignored when there is a `tsconfig.json`, like in editors.
+            let tsconfig_path = path.join("tsconfig.json");
+            let jsconfig_path = path.join("jsconfig.json");
+            let mut ctx = Ctx::default();
+            if !self.is_file(&self.value(&tsconfig_path), true, &mut ctx)
+                && self.is_file(&self.value(&jsconfig_path), true, &mut ctx)
+            {
+                Cow::Owned(jsconfig_path)
+            } else {
+                Cow::Owned(tsconfig_path)
+            }
         } else {
             let mut os_string = path.to_path_buf().into_os_string();
             os_string.push(".json");
             Cow::Owned(PathBuf::from(os_string))
         };
-        let tsconfig_string = self.fs.read_to_string(&tsconfig_path).map_err(|err| {
+        let tsconfig_string = self.fs().read_to_string(&tsconfig_path).map_err(|err| {
             if err.kind() == io::ErrorKind::NotFound {
                 ResolveError::TsconfigNotFound(path.to_path_buf())
             } else {
@@ -296,73 +1010,148 @@ impl Cache {
             }
         })?;
         let canonical_path = self
-            .canonicalize(&self.value(&tsconfig_path))
+            .canonicalize(&self.value(&tsconfig_path), &mut Ctx::default())
             .unwrap_or_else(|_| tsconfig_path.to_path_buf());
-        let mut tsconfig = TsConfig::parse(root, &tsconfig_path, &canonical_path, tsconfig_string)
-            .map_err(|error| ResolveError::TsconfigLoadFailed {
+        TsConfig::parse(root, &tsconfig_path, &canonical_path, tsconfig_string).map_err(|error| {
+            ResolveError::TsconfigLoadFailed {
                 path: tsconfig_path.to_path_buf(),
                 source: Box::new(ResolveError::from_serde_json_error(
                     tsconfig_path.to_path_buf(),
                     &error,
                 )),
-            })?;
-
-        // Run callback (extends/references processing)
-        callback(&mut tsconfig)?;
-
-        // Cache raw version (callback applied, not built)
-        tsconfig.set_should_build(false);
-        if root {
-            self.tsconfigs_raw.insert(path.to_path_buf(), Arc::new(tsconfig.clone()));
-            // Build and cache built version
-            tsconfig.set_should_build(true);
-            let tsconfig = Arc::new(tsconfig.build());
-            self.tsconfigs_built.insert(path.to_path_buf(), Arc::clone(&tsconfig));
-            Ok(tsconfig)
-        } else {
-            // Return unbuilt version
-            let tsconfig = Arc::new(tsconfig);
-            self.tsconfigs_raw.insert(path.to_path_buf(), Arc::clone(&tsconfig));
-            Ok(tsconfig)
-        }
+            }
+        })
     }
 
+    /// Find the closest Yarn PnP manifest from `cwd`, or from the working directory of the
+    /// process, in `root` or below it.
+    ///
+    /// Both outcomes are cached per `cwd`, so a project without a manifest only pays for the
+    /// lookup once. The working directory of the process is only read by the first lookup.
+    /// A resolver with another [ResolveOptions::yarn_pnp_root] does not share this cache.
     #[cfg(feature = "yarn_pnp")]
     pub(crate) fn get_yarn_pnp_manifest(
         &self,
         cwd: Option<&Path>,
-    ) -> Result<&pnp::Manifest, ResolveError> {
-        self.yarn_pnp_manifest.get_or_try_init(|| {
-            let cwd = match cwd {
-                Some(path) => Cow::Borrowed(path),
-                None => match std::env::current_dir() {
-                    Ok(path) => Cow::Owned(path),
-                    Err(err) => return Err(ResolveError::from(err)),
-                },
-            };
-            let manifest = match pnp::find_pnp_manifest(&cwd) {
-                Ok(manifest) => match manifest {
-                    Some(manifest) => manifest,
-                    None => {
-                        return Err(ResolveError::FailedToFindYarnPnpManifest(cwd.to_path_buf()));
-                    }
-                },
-                Err(err) => return Err(ResolveError::YarnPnpError(err)),
-            };
-            Ok(manifest)
-        })
+        root: Option<&Path>,
+    ) -> Result<Option<Arc<pnp::Manifest>>, ResolveError> {
+        let key = cwd.unwrap_or_else(|| Path::new(""));
+        if let Some(manifest) = self.yarn_pnp_manifests.get(key) {
+            return Ok(manifest.clone());
+        }
+        let cwd = match cwd {
+            Some(path) => Cow::Borrowed(path),
+            None => Cow::Owned(std::env::current_dir()?),
+        };
+        let manifest = cwd
+            .ancestors()
+            .take_while(|dir| root.is_none_or(|root| dir.starts_with(root)))
+            .map(|dir| dir.join(".pnp.cjs"))
+            .find(|path| self.fs().metadata(path).is_ok_and(FileMetadata::is_file))
+            .map(|path| pnp::load_pnp_manifest(&path).map(Arc::new))
+            .transpose()
+            .map_err(ResolveError::YarnPnpError)?;
+        self.yarn_pnp_manifests.insert(key.to_path_buf(), manifest.clone());
+        Ok(manifest)
+    }
+
+    /// Load the Yarn PnP manifest at `path`, one of [ResolveOptions::pnp_manifest_paths].
+    #[cfg(feature = "yarn_pnp")]
+    pub(crate) fn get_yarn_pnp_manifest_at(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<pnp::Manifest>, ResolveError> {
+        if let Some(manifest) = self.yarn_pnp_manifests.get(path).and_then(|m| m.clone()) {
+            return Ok(manifest);
+        }
+        let manifest = Arc::new(pnp::load_pnp_manifest(path).map_err(ResolveError::YarnPnpError)?);
+        self.yarn_pnp_manifests.insert(path.to_path_buf(), Some(Arc::clone(&manifest)));
+        Ok(manifest)
     }
 }
 
 impl Cache {
     pub fn new(fs: Arc<dyn FileSystem>) -> Self {
+        #[cfg(feature = "async")]
+        let file_system_results = Arc::default();
+        #[cfg(feature = "async")]
+        let prefetched = fs.as_async().is_some().then(|| {
+            crate::resolve_async::PrefetchedFileSystem::new(
+                Arc::clone(&file_system_results),
+                Some(Arc::clone(&fs)),
+            )
+        });
         Self {
             fs,
             paths: DashMap::with_hasher(BuildHasherDefault::default()),
             tsconfigs_raw: DashMap::with_hasher(BuildHasherDefault::default()),
             tsconfigs_built: DashMap::with_hasher(BuildHasherDefault::default()),
+            manual_tsconfigs: DashMap::with_hasher(BuildHasherDefault::default()),
             #[cfg(feature = "yarn_pnp")]
-            yarn_pnp_manifest: OnceCell::new(),
+            yarn_pnp_manifests: DashMap::with_hasher(BuildHasherDefault::default()),
+            #[cfg(feature = "yarn_pnp")]
+            lockfile_directories: DashMap::with_hasher(BuildHasherDefault::default()),
+            file_metadata: DashMap::with_hasher(BuildHasherDefault::default()),
+            module_directories: DashMap::with_hasher(BuildHasherDefault::default()),
+            description_files: DashMap::with_hasher(BuildHasherDefault::default()),
+            condition_names: DashMap::with_hasher(BuildHasherDefault::default()),
+            extension_hints: DashMap::with_hasher(BuildHasherDefault::default()),
+            directory_entries: DashMap::with_hasher(BuildHasherDefault::default()),
+            tsconfig_coverage: DashMap::with_hasher(BuildHasherDefault::default()),
+            package_exports_timings: DashMap::with_hasher(BuildHasherDefault::default()),
+            #[cfg(feature = "async")]
+            file_system_results,
+            #[cfg(feature = "async")]
+            prefetched,
+            counters: CacheCounters::default(),
+            snapshot: OnceLock::new(),
+            case_insensitive: false,
+        }
+    }
+
+    /// Look up paths ignoring case according to `case_sensitivity`, so that e.g. `./Foo` finds
+    /// `foo.js` and resolves to its spelling on disk.
+    ///
+    /// [CaseSensitivity::Auto] checks whether `probe`, an existing path, is also found with its
+    /// case flipped.
+    #[must_use]
+    pub fn with_case_sensitivity(
+        mut self,
+        case_sensitivity: CaseSensitivity,
+        probe: &Path,
+    ) -> Self {
+        self.case_insensitive = match case_sensitivity {
+            CaseSensitivity::Sensitive => false,
+            CaseSensitivity::Insensitive => true,
+            CaseSensitivity::Auto => {
+                let path = probe.to_string_lossy();
+                let flipped = path
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_lowercase() {
+                            c.to_ascii_uppercase()
+                        } else {
+                            c.to_ascii_lowercase()
+                        }
+                    })
+                    .collect::<String>();
+                flipped != path
+                    && self.fs().metadata(probe).is_ok()
+                    && self.fs().metadata(Path::new(&flipped)).is_ok()
+            }
+        };
+        self
+    }
+
+    /// An empty cache over `fs` which looks up paths like this cache and shares its snapshot,
+    /// for [crate::ResolverImpl::resolve_async] to find the file system operations of a
+    /// resolution without leaving the memos of the operations it missed in this cache.
+    #[cfg(feature = "async")]
+    pub(crate) fn scratch(&self, fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            snapshot: self.snapshot.clone(),
+            case_insensitive: self.case_insensitive,
+            ..Self::new(fs)
         }
     }
 
@@ -370,6 +1159,14 @@ impl Cache {
     ///
     /// <https://github.com/parcel-bundler/parcel/blob/4d27ec8b8bd1792f536811fef86e74a31fa0e704/crates/parcel-resolver/src/cache.rs#L232>
     pub(crate) fn canonicalize_impl(&self, path: &CachedPath) -> Result<CachedPath, ResolveError> {
+        self.canonicalize_with_symlinks(path).map(|(cached_path, _)| cached_path)
+    }
+
+    /// [Cache::canonicalize_impl] with the [Symlinks] traversed.
+    fn canonicalize_with_symlinks(
+        &self,
+        path: &CachedPath,
+    ) -> Result<(CachedPath, Symlinks), ResolveError> {
         // Each canonicalization chain gets its own visited set for circular symlink detection
         let mut visited = StdHashSet::with_hasher(BuildHasherDefault::<IdentityHasher>::default());
 
@@ -377,9 +1174,9 @@ impl Cache {
         self.canonicalize_with_visited(path, &mut visited).or_else(|err| {
             // Fallback: if canonicalization fails and path's cache was cleared,
             // try direct FS canonicalize without caching the result
-            self.fs
+            self.fs()
                 .canonicalize(path.path())
-                .map(|canonical| self.value(&canonical))
+                .map(|canonical| (self.value(&canonical), None))
                 .map_err(|_| err)
         })
     }
@@ -389,9 +1186,9 @@ impl Cache {
         &self,
         path: &CachedPath,
         visited: &mut StdHashSet<u64, BuildHasherDefault<IdentityHasher>>,
-    ) -> Result<CachedPath, ResolveError> {
+    ) -> Result<(CachedPath, Symlinks), ResolveError> {
         // Check cache first - if this path was already canonicalized, return the cached result
-        if let Some((weak, path_box)) = path.canonicalized.get() {
+        if let Some((weak, path_box, symlinks)) = path.canonicalized.get() {
             return weak
                 .upgrade()
                 .map(CachedPath)
@@ -399,6 +1196,7 @@ impl Cache {
                     // Weak pointer upgrade failed - recreate from the stored canonical path
                     Some(self.value(path_box))
                 })
+                .map(|cached_path| (cached_path, symlinks.clone()))
                 .ok_or_else(|| {
                     io::Error::new(io::ErrorKind::NotFound, "Cached path no longer exists").into()
                 });
@@ -409,10 +1207,11 @@ impl Cache {
             return Err(io::Error::new(io::ErrorKind::NotFound, "Circular symlink").into());
         }
 
-        let res = path.parent(self).map_or_else(
-            || Ok(path.normalize_root(self)),
+        let (res, symlinks) = path.parent(self).map_or_else(
+            || Ok::<_, ResolveError>((path.normalize_root(self), None)),
             |parent| {
-                let parent_canonical = self.canonicalize_with_visited(&parent, visited)?;
+                let (parent_canonical, parent_symlinks) =
+                    self.canonicalize_with_visited(&parent, visited)?;
                 // When no ancestor is a symlink — the common case — the parent
                 // canonicalizes to itself, so `parent_canonical` is `parent`'s own interned
                 // Arc and the rebuild below would just re-derive `path`'s existing key.
@@ -441,16 +1240,29 @@ impl Cache {
                         .normalize_with(path.path().strip_prefix(parent.path()).unwrap(), self)
                 };
 
-                if path.link_metadata(self.fs()).is_some_and(|m| m.is_symlink) {
-                    let link = self.fs.read_link(normalized.path())?;
-                    if link.is_absolute() {
-                        return self
-                            .canonicalize_with_visited(&self.value(&link.normalize()), visited);
-                    } else if let Some(dir) = normalized.parent(self) {
+                if path.link_metadata(self).is_some_and(|m| m.is_symlink) {
+                    let link = self.fs().read_link(normalized.path())?;
+                    let target = if link.is_absolute() {
+                        Some(self.value(&link.normalize()))
+                    } else {
                         // Symlink is relative `../../foo.js`, use the path directory
                         // to resolve this symlink.
-                        return self
-                            .canonicalize_with_visited(&dir.normalize_with(&link, self), visited);
+                        normalized.parent(self).map(|dir| dir.normalize_with(&link, self))
+                    };
+                    if let Some(target) = target {
+                        let (res, target_symlinks) =
+                            self.canonicalize_with_visited(&target, visited)?;
+                        let symlinks = parent_symlinks
+                            .iter()
+                            .flat_map(|symlinks| symlinks.iter().cloned())
+                            .chain([normalized.0.path.clone()])
+                            .chain(
+                                target_symlinks
+                                    .iter()
+                                    .flat_map(|symlinks| symlinks.iter().cloned()),
+                            )
+                            .collect();
+                        return Ok((res, Some(symlinks)));
                     }
                     debug_assert!(
                         false,
@@ -459,16 +1271,26 @@ impl Cache {
                     );
                 }
 
-                Ok(normalized)
+                Ok((normalized, parent_symlinks))
             },
         )?;
 
         // Cache the result before removing from visited set
         // This ensures parent canonicalization results are cached and reused
-        let _ = path.canonicalized.set((Arc::downgrade(&res.0), res.0.path.clone()));
+        let _ =
+            path.canonicalized.set((Arc::downgrade(&res.0), res.0.path.clone(), symlinks.clone()));
 
         // Remove from visited set when unwinding the recursion
         visited.remove(&path.hash);
-        Ok(res)
+        Ok((res, symlinks))
+    }
+}
+
+/// The key of `name` in [DirectoryEntries], only allocated when `name` is not lower case ASCII.
+fn lowercase(name: &str) -> Cow<'_, str> {
+    if name.bytes().any(|b| !b.is_ascii() || b.is_ascii_uppercase()) {
+        Cow::Owned(name.to_lowercase())
+    } else {
+        Cow::Borrowed(name)
     }
 }
@@ -29,6 +29,8 @@ const EXISTS: u8 = 1 << 1;
 const IS_FILE: u8 = 1 << 2;
 const IS_DIR: u8 = 1 << 3;
 const IS_SYMLINK: u8 = 1 << 4;
+/// Set in the `link` slot alone when the `lstat` failed because the path is too long.
+const TOO_LONG: u8 = 1 << 5;
 
 /// Lazily-populated `lstat` (`link`) and `stat` (`followed`) metadata, one byte each.
 #[derive(Default)]
@@ -47,6 +49,29 @@ impl CachedMeta {
         get_or_init(&self.link, f)
     }
 
+    /// [CachedMeta::link_or_init] with `f` returning `Err(())` when the path is too long, see
+    /// [CachedMeta::is_too_long].
+    pub fn link_or_init_checked<F: FnOnce() -> Result<Option<FileMetadata>, ()>>(
+        &self,
+        f: F,
+    ) -> Option<FileMetadata> {
+        let bits = self.link.load(Ordering::Relaxed);
+        if (bits & INITIALIZED) != 0 {
+            return decode(bits);
+        }
+        let (meta, bits) = match f() {
+            Ok(meta) => (meta, encode(meta)),
+            Err(()) => (None, INITIALIZED | TOO_LONG),
+        };
+        self.link.store(bits, Ordering::Relaxed);
+        meta
+    }
+
+    /// Whether the cached `lstat` failed because the path exceeds the platform's limits.
+    pub fn is_too_long(&self) -> bool {
+        (self.link.load(Ordering::Relaxed) & TOO_LONG) != 0
+    }
+
     /// Return the cached `stat` (symlink-followed) view, or populate it from `f` and return that.
     pub fn followed_or_init<F: FnOnce() -> Option<FileMetadata>>(
         &self,
@@ -54,6 +79,21 @@ impl CachedMeta {
     ) -> Option<FileMetadata> {
         get_or_init(&self.followed, f)
     }
+
+    /// The raw `link` and `followed` slots, `0` when not probed yet. The encoding is stored as is
+    /// by [super::snapshot::CacheSnapshot].
+    pub fn bits(&self) -> (u8, u8) {
+        (self.link.load(Ordering::Relaxed), self.followed.load(Ordering::Relaxed))
+    }
+
+    /// Populate the slots which are not probed yet from [CachedMeta::bits].
+    pub fn seed(&self, link: u8, followed: u8) {
+        for (slot, bits) in [(&self.link, link), (&self.followed, followed)] {
+            if (bits & INITIALIZED) != 0 && (slot.load(Ordering::Relaxed) & INITIALIZED) == 0 {
+                slot.store(bits, Ordering::Relaxed);
+            }
+        }
+    }
 }
 
 fn get_or_init<F: FnOnce() -> Option<FileMetadata>>(slot: &AtomicU8, f: F) -> Option<FileMetadata> {
@@ -129,4 +169,16 @@ mod tests {
         assert_eq!(meta.link_or_init(|| panic!()).map(parts), Some((false, false, true)));
         assert_eq!(meta.followed_or_init(|| panic!()).map(parts), Some((false, true, false)));
     }
+
+    #[test]
+    fn too_long() {
+        let meta = CachedMeta::new();
+        assert_eq!(meta.link_or_init_checked(|| Err(())).map(parts), None);
+        assert!(meta.is_too_long());
+        assert_eq!(meta.link_or_init(|| panic!("must not be called")).map(parts), None);
+
+        let meta = CachedMeta::new();
+        meta.link_or_init_checked(|| Ok(None));
+        assert!(!meta.is_too_long());
+    }
 }
@@ -1,7 +1,9 @@
 use std::{
+    cfg_select,
     convert::AsRef,
     fmt,
     hash::{Hash, Hasher},
+    io,
     ops::Deref,
     path::{Component, Path, PathBuf},
     sync::{Arc, Weak},
@@ -11,10 +13,14 @@ use once_cell::sync::OnceCell as OnceLock;
 
 use super::{cache_impl::Cache, cached_meta::CachedMeta, thread_local::SCRATCH_PATH};
 use crate::{
-    FileMetadata, FileSystem, PackageJson, TsConfig, context::ResolveContext as Ctx,
+    FileMetadata, ModuleType, PackageJson, TsConfig, context::ResolveContext as Ctx,
     path::push_normalized_component,
 };
 
+/// The symlinks traversed by canonicalization, at their real locations, `None` when there are
+/// none. Shared with the parent directory unless the path is a symlink itself.
+pub type Symlinks = Option<Arc<[Box<Path>]>>;
+
 #[derive(Clone)]
 pub struct CachedPath(pub Arc<CachedPathImpl>);
 
@@ -28,14 +34,17 @@ pub struct CachedPathImpl {
     /// [`CachedMeta`] for the encoding and the rationale for skipping `OnceLock`.
     pub meta: CachedMeta,
     /// Stored as `Box<Path>` (not `PathBuf`) to save 8 bytes per cached path entry —
-    /// the canonical path is set once and never mutated.
-    pub canonicalized: OnceLock<(Weak<Self>, Box<Path>)>,
+    /// the canonical path is set once and never mutated. Kept with the [Symlinks] traversed to
+    /// reach it, which are dependencies of every resolution through this path.
+    pub canonicalized: OnceLock<(Weak<Self>, Box<Path>, Symlinks)>,
     pub node_modules: OnceLock<Option<Weak<Self>>>,
     pub package_json: OnceLock<Option<Arc<PackageJson>>>,
     /// `tsconfig.json` found at path.
     pub tsconfig: OnceLock<Option<Arc<TsConfig>>>,
     /// `tsconfig.json` after resolving `references`, `files`, `include` and `extend`.
     pub resolved_tsconfig: OnceLock<Option<Arc<TsConfig>>>,
+    /// Module type detected from the contents of the file, `None` when it cannot be read.
+    pub detected_module_type: OnceLock<Option<ModuleType>>,
 }
 
 impl CachedPathImpl {
@@ -58,6 +67,7 @@ impl CachedPathImpl {
             package_json: OnceLock::new(),
             tsconfig: OnceLock::new(),
             resolved_tsconfig: OnceLock::new(),
+            detected_module_type: OnceLock::new(),
         }
     }
 }
@@ -227,11 +237,43 @@ impl CachedPath {
     ///
     /// Used both to answer `is_file`/`is_dir` for non-symlinks and by canonicalization to decide
     /// whether to follow a symlink — so the two share a single `lstat` syscall per path.
-    pub(crate) fn link_metadata(&self, fs: &dyn FileSystem) -> Option<FileMetadata> {
-        self.meta.link_or_init(|| fs.symlink_metadata(&self.path).ok())
+    ///
+    /// A path exceeding the platform's length limits is remembered, see [CachedMeta::is_too_long].
+    /// On Windows it is retried in its `\\?\` form first.
+    ///
+    /// The size and modification time of a file, which the one byte [`CachedMeta`] drops, are
+    /// kept in [`Cache::file_metadata`] instead.
+    pub(crate) fn link_metadata(&self, cache: &Cache) -> Option<FileMetadata> {
+        let fs = cache.fs();
+        self.meta.link_or_init_checked(|| {
+            let meta = match fs.symlink_metadata(&self.path) {
+                Ok(meta) => Some(meta),
+                Err(error) if is_path_too_long(&error) => {
+                    let retried = cfg_select! {
+                        target_os = "windows" => crate::windows::to_verbatim_path(&self.path)
+                            .and_then(|path| fs.symlink_metadata(&path).ok()),
+                        _ => None,
+                    };
+                    Some(retried.ok_or(())?)
+                }
+                Err(_) => None,
+            };
+            if let Some(meta) = meta.filter(|meta| meta.is_file()) {
+                cache.file_metadata.insert(self.clone(), meta);
+            }
+            Ok(meta)
+        })
     }
 }
 
+/// Whether `error` reports a path exceeding the platform's limits, `ENAMETOOLONG` on Unix and
+/// `ERROR_FILENAME_EXCED_RANGE` on Windows.
+fn is_path_too_long(error: &io::Error) -> bool {
+    // Windows also reports invalid characters in a name as `InvalidFilename`.
+    error.kind() == io::ErrorKind::InvalidFilename
+        && (cfg!(not(target_os = "windows")) || error.raw_os_error().is_none_or(|code| code == 206))
+}
+
 impl Hash for CachedPath {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.hash.hash(state);
@@ -2,10 +2,16 @@ mod cache_impl;
 mod cached_meta;
 mod cached_path;
 mod hasher;
+mod snapshot;
+mod statistics;
 mod thread_local;
 
 pub use cache_impl::Cache;
+#[cfg(feature = "yarn_pnp")]
+pub use cache_impl::LOCKFILES;
+pub use cache_impl::ModuleDirectories;
 pub use cached_path::CachedPath;
+pub use statistics::CacheStatistics;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,349 @@
+//! A read-only artifact of cached metadata which many processes can share, see
+//! [crate::Cache::load_snapshot_mmap].
+//!
+//! The artifact is flat arrays and offsets without pointers, so a mapping of the file is used as
+//! is. All integers are little-endian:
+//!
+//! | Bytes     | Content                                                                    |
+//! |-----------|----------------------------------------------------------------------------|
+//! | 8         | [MAGIC], which names the format version                                    |
+//! | 4         | Token length `t`                                                           |
+//! | 4         | Entry count `n`                                                            |
+//! | `t`       | Token                                                                      |
+//! | `n` × 12  | Entries sorted by path: path offset and length (4 bytes each), the `link` and `followed` [super::cached_meta::CachedMeta::bits], 2 bytes of padding |
+//! | rest      | Paths, as [OsStr::as_encoded_bytes], at offsets from the start of this area |
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs, io,
+    ops::Deref,
+    path::Path,
+    sync::{PoisonError, RwLock},
+};
+
+const MAGIC: &[u8; 8] = b"OXCSNP01";
+const HEADER_LEN: usize = MAGIC.len() + 8;
+const ENTRY_LEN: usize = 12;
+
+/// Metadata of the paths of a [crate::Cache], looked up before the [crate::FileSystem] is asked.
+pub struct CacheSnapshot {
+    bytes: Bytes,
+    count: usize,
+    entries_start: usize,
+    paths_start: usize,
+    /// Paths forgotten by [crate::Cache::invalidate_path] since the load, with whether the paths
+    /// below them are forgotten too. The mapping itself is never written to.
+    invalidated: RwLock<BTreeMap<Box<Path>, bool>>,
+}
+
+impl CacheSnapshot {
+    /// Serialize `entries` of path and [super::cached_meta::CachedMeta::bits].
+    ///
+    /// # Errors
+    ///
+    /// * [io::ErrorKind::InvalidInput] when the artifact would exceed 4 GiB.
+    pub fn encode<'a, I: Iterator<Item = (&'a Path, u8, u8)>>(
+        entries: I,
+        token: &str,
+    ) -> io::Result<Vec<u8>> {
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "cache snapshot too large");
+        let to_u32 = |n: usize| u32::try_from(n).map_err(|_| too_large());
+        let mut entries = entries
+            .map(|(path, link, followed)| (path.as_os_str().as_encoded_bytes(), link, followed))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries.dedup_by(|a, b| a.0 == b.0);
+
+        let paths_len = entries.iter().map(|(path, _, _)| path.len()).sum::<usize>();
+        let mut bytes =
+            Vec::with_capacity(HEADER_LEN + token.len() + entries.len() * ENTRY_LEN + paths_len);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&to_u32(token.len())?.to_le_bytes());
+        bytes.extend_from_slice(&to_u32(entries.len())?.to_le_bytes());
+        bytes.extend_from_slice(token.as_bytes());
+        let mut offset = 0;
+        for (path, link, followed) in &entries {
+            bytes.extend_from_slice(&to_u32(offset)?.to_le_bytes());
+            bytes.extend_from_slice(&to_u32(path.len())?.to_le_bytes());
+            bytes.extend_from_slice(&[*link, *followed, 0, 0]);
+            offset += path.len();
+        }
+        to_u32(offset)?;
+        for (path, _, _) in entries {
+            bytes.extend_from_slice(path);
+        }
+        Ok(bytes)
+    }
+
+    /// Map the artifact at `path`, or read it where mapping is not supported.
+    ///
+    /// Returns `None` when the artifact was written with another `token`.
+    ///
+    /// # Errors
+    ///
+    /// * [io::ErrorKind::InvalidData] when `path` is not a valid artifact.
+    ///
+    /// # Safety
+    ///
+    /// The artifact must not be modified while the snapshot is alive.
+    pub unsafe fn open(path: &Path, token: &str) -> io::Result<Option<Self>> {
+        // SAFETY: Upheld by the caller.
+        Self::parse(unsafe { Bytes::open(path) }?, token)
+    }
+
+    fn parse(bytes: Bytes, token: &str) -> io::Result<Option<Self>> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid cache snapshot");
+        if bytes.len() < HEADER_LEN || !bytes.starts_with(MAGIC) {
+            return Err(invalid());
+        }
+        let token_len = read_u32(&bytes, MAGIC.len());
+        let count = read_u32(&bytes, MAGIC.len() + 4);
+        let entries_start = HEADER_LEN.checked_add(token_len).ok_or_else(invalid)?;
+        let paths_start = count
+            .checked_mul(ENTRY_LEN)
+            .and_then(|len| len.checked_add(entries_start))
+            .filter(|start| *start <= bytes.len())
+            .ok_or_else(invalid)?;
+        if &bytes[HEADER_LEN..entries_start] != token.as_bytes() {
+            return Ok(None);
+        }
+        let snapshot =
+            Self { bytes, count, entries_start, paths_start, invalidated: RwLock::default() };
+        // Validated once so that lookups can slice without checks.
+        let paths_len = snapshot.bytes.len() - paths_start;
+        if (0..count).any(|index| {
+            let entry = snapshot.entry_start(index);
+            let (offset, len) =
+                (read_u32(&snapshot.bytes, entry), read_u32(&snapshot.bytes, entry + 4));
+            offset.checked_add(len).is_none_or(|end| end > paths_len)
+        }) {
+            return Err(invalid());
+        }
+        Ok(Some(snapshot))
+    }
+
+    /// The `link` and `followed` bits of `path`, `None` when `path` is not in the artifact or
+    /// was invalidated.
+    pub fn get(&self, path: &Path) -> Option<(u8, u8)> {
+        let key = path.as_os_str().as_encoded_bytes();
+        let (mut low, mut high) = (0, self.count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.path_at(mid).cmp(key) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => {
+                    if self.is_invalidated(path) {
+                        return None;
+                    }
+                    let entry = self.entry_start(mid) + 8;
+                    return Some((self.bytes[entry], self.bytes[entry + 1]));
+                }
+            }
+        }
+        None
+    }
+
+    /// Stop answering for `path`, and for the paths below it when `recursive`.
+    pub fn invalidate(&self, path: &Path, recursive: bool) {
+        let mut invalidated = self.invalidated.write().unwrap_or_else(PoisonError::into_inner);
+        if path.ancestors().any(|ancestor| invalidated.get(ancestor) == Some(&true)) {
+            return;
+        }
+        if recursive {
+            invalidated.retain(|invalidated, _| !invalidated.starts_with(path));
+        }
+        invalidated.insert(path.into(), recursive);
+    }
+
+    /// The number of paths in the artifact.
+    pub const fn len(&self) -> usize {
+        self.count
+    }
+
+    fn is_invalidated(&self, path: &Path) -> bool {
+        let invalidated = self.invalidated.read().unwrap_or_else(PoisonError::into_inner);
+        if invalidated.is_empty() {
+            return false;
+        }
+        invalidated.contains_key(path)
+            || path.ancestors().skip(1).any(|ancestor| invalidated.get(ancestor) == Some(&true))
+    }
+
+    const fn entry_start(&self, index: usize) -> usize {
+        self.entries_start + index * ENTRY_LEN
+    }
+
+    fn path_at(&self, index: usize) -> &[u8] {
+        let entry = self.entry_start(index);
+        let offset = self.paths_start + read_u32(&self.bytes, entry);
+        &self.bytes[offset..offset + read_u32(&self.bytes, entry + 4)]
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> usize {
+    let mut buf = [0; 4];
+    buf.copy_from_slice(&bytes[at..at + 4]);
+    u32::from_le_bytes(buf) as usize
+}
+
+/// The contents of an artifact.
+enum Bytes {
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    Mapped(Mapping),
+    Owned(Box<[u8]>),
+}
+
+impl Bytes {
+    /// # Safety
+    ///
+    /// The file at `path` must not be modified while the bytes are alive.
+    unsafe fn open(path: &Path) -> io::Result<Self> {
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
+        {
+            let file = fs::File::open(path)?;
+            let len = usize::try_from(file.metadata()?.len())
+                .map_err(|_| io::Error::from(io::ErrorKind::OutOfMemory))?;
+            // An empty mapping is an error, and the header check rejects the empty file anyway.
+            if len > 0 {
+                // SAFETY: Upheld by the caller.
+                return unsafe { Mapping::new(&file, len) }.map(Self::Mapped);
+            }
+        }
+        fs::read(path).map(|bytes| Self::Owned(bytes.into_boxed_slice()))
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(any(target_os = "macos", target_os = "linux"))]
+            Self::Mapped(mapping) => mapping,
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A private read-only mapping of a whole file.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+struct Mapping {
+    ptr: std::ptr::NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: The mapping is read-only and owned by `Mapping` alone.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+unsafe impl Send for Mapping {}
+
+// SAFETY: The mapping is read-only, so shared access can not race.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+unsafe impl Sync for Mapping {}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+impl Mapping {
+    /// # Safety
+    ///
+    /// `file` must not be modified while the mapping is alive.
+    unsafe fn new(file: &fs::File, len: usize) -> io::Result<Self> {
+        use rustix::mm::{MapFlags, ProtFlags, mmap};
+        // SAFETY: A new mapping is requested, so no existing memory is replaced.
+        let ptr = unsafe {
+            mmap(std::ptr::null_mut(), len, ProtFlags::READ, MapFlags::PRIVATE, file, 0)
+        }?;
+        let ptr = std::ptr::NonNull::new(ptr.cast()).ok_or_else(io::Error::last_os_error)?;
+        Ok(Self { ptr, len })
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+impl Deref for Mapping {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` is a readable mapping of `len` bytes until `drop`, whose file is not
+        // modified, see `Mapping::new`.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `len` are the mapping made by `Mapping::new`, unmapped once.
+        _ = unsafe { rustix::mm::munmap(self.ptr.as_ptr().cast(), self.len) };
+    }
+}
+
+/// Write `bytes` to `path` through a temporary file, so that processes which mapped a previous
+/// artifact at `path` keep reading it unchanged.
+pub fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(OsStr::new(&format!(".{}.tmp", std::process::id())));
+    fs::write(&temp, bytes)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        _ = fs::remove_file(&temp);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{Bytes, CacheSnapshot};
+
+    fn snapshot(token: &str) -> Option<CacheSnapshot> {
+        let entries = [("/b", 3, 0), ("/a/c", 5, 7), ("/a", 9, 9)];
+        let bytes = CacheSnapshot::encode(
+            entries.iter().map(|(path, link, followed)| (Path::new(path), *link, *followed)),
+            "token",
+        )
+        .unwrap();
+        CacheSnapshot::parse(Bytes::Owned(bytes.into_boxed_slice()), token).unwrap()
+    }
+
+    #[test]
+    fn lookup() {
+        let snapshot = snapshot("token").unwrap();
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.get(Path::new("/a")), Some((9, 9)));
+        assert_eq!(snapshot.get(Path::new("/a/c")), Some((5, 7)));
+        assert_eq!(snapshot.get(Path::new("/b")), Some((3, 0)));
+        assert_eq!(snapshot.get(Path::new("/c")), None);
+        snapshot.invalidate(Path::new("/a"), true);
+        assert_eq!(snapshot.get(Path::new("/a/c")), None);
+        assert_eq!(snapshot.get(Path::new("/b")), Some((3, 0)));
+    }
+
+    #[test]
+    fn invalidate() {
+        let snapshot = snapshot("token").unwrap();
+        snapshot.invalidate(Path::new("/a/c"), false);
+        snapshot.invalidate(Path::new("/a/c"), false);
+        assert_eq!(snapshot.invalidated.read().unwrap().len(), 1);
+        assert_eq!(snapshot.get(Path::new("/a/c")), None);
+        assert_eq!(snapshot.get(Path::new("/a")), Some((9, 9)));
+        // A recursive invalidation covers the ones below it.
+        snapshot.invalidate(Path::new("/a"), true);
+        snapshot.invalidate(Path::new("/a/c"), false);
+        assert_eq!(snapshot.invalidated.read().unwrap().len(), 1);
+        assert_eq!(snapshot.get(Path::new("/a")), None);
+        assert_eq!(snapshot.get(Path::new("/b")), Some((3, 0)));
+    }
+
+    #[test]
+    fn token() {
+        assert!(snapshot("other").is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        for bytes in [&b""[..], b"OXCSNP01", b"OXCSNP01\0\0\0\0\x01\0\0\0", b"not a snapshot file"]
+        {
+            let bytes = Bytes::Owned(bytes.into());
+            assert!(CacheSnapshot::parse(bytes, "").is_err());
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Hit and miss counts of a [crate::Cache], see [crate::Cache::statistics].
+///
+/// A hit is a lookup answered from the cache, a miss one which went to the [crate::FileSystem].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStatistics {
+    /// Metadata lookups of files and directories answered from the cache.
+    pub file_hits: u64,
+    /// Metadata lookups of files and directories which went to the file system.
+    pub file_misses: u64,
+    /// package.json lookups of a directory answered from the cache. A directory without one
+    /// also looks up its parent.
+    pub package_json_hits: u64,
+    /// package.json lookups of a directory which went to the file system.
+    pub package_json_misses: u64,
+    /// tsconfig loads answered from the cache, including extended tsconfigs.
+    pub tsconfig_hits: u64,
+    /// tsconfig loads which read and parsed the file.
+    pub tsconfig_misses: u64,
+}
+
+/// The counters behind [CacheStatistics], readable from any thread without locking.
+///
+/// Nothing is counted until [CacheCounters::enable] is called, so that the lookups of a cache
+/// whose statistics are not read do not contend on the counters.
+#[derive(Debug, Default)]
+pub struct CacheCounters {
+    enabled: AtomicBool,
+    file_hits: AtomicU64,
+    file_misses: AtomicU64,
+    package_json_hits: AtomicU64,
+    package_json_misses: AtomicU64,
+    tsconfig_hits: AtomicU64,
+    tsconfig_misses: AtomicU64,
+}
+
+impl CacheCounters {
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn file(&self, hit: bool) {
+        self.count(hit, &self.file_hits, &self.file_misses);
+    }
+
+    pub fn package_json(&self, hit: bool) {
+        self.count(hit, &self.package_json_hits, &self.package_json_misses);
+    }
+
+    pub fn tsconfig(&self, hit: bool) {
+        self.count(hit, &self.tsconfig_hits, &self.tsconfig_misses);
+    }
+
+    fn count(&self, hit: bool, hits: &AtomicU64, misses: &AtomicU64) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        let counter = if hit { hits } else { misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> CacheStatistics {
+        CacheStatistics {
+            file_hits: self.file_hits.load(Ordering::Relaxed),
+            file_misses: self.file_misses.load(Ordering::Relaxed),
+            package_json_hits: self.package_json_hits.load(Ordering::Relaxed),
+            package_json_misses: self.package_json_misses.load(Ordering::Relaxed),
+            tsconfig_hits: self.tsconfig_hits.load(Ordering::Relaxed),
+            tsconfig_misses: self.tsconfig_misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&self) {
+        for counter in [
+            &self.file_hits,
+            &self.file_misses,
+            &self.package_json_hits,
+            &self.package_json_misses,
+            &self.tsconfig_hits,
+            &self.tsconfig_misses,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
@@ -1,8 +1,23 @@
-use std::path::{Path, PathBuf};
-
-use crate::error::ResolveError;
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    CachedPath, FileDirectoryAmbiguity, ModuleType, PackageEntryInfo,
+    data_url::DataUrl,
+    error::ResolveError,
+    explain::{ResolutionStep, StepOutcome},
+    timings::{Counter, Phase, PhaseTimer, ResolveStats, ResolveTimings},
+};
+
+/// The number of resolutions kept in [ResolveContext::trail].
+const TRAIL_LENGTH: usize = 8;
 
 #[derive(Debug, Default, Clone)]
+#[expect(clippy::struct_excessive_bools, reason = "independent flags of a single resolution")]
 pub struct ResolveContext {
     pub fully_specified: bool,
 
@@ -11,18 +26,84 @@ pub struct ResolveContext {
     pub fragment: Option<String>,
 
     /// Files that was found on file system.
-    pub file_dependencies: Option<Vec<PathBuf>>,
+    pub file_dependencies: Option<Vec<CachedPath>>,
 
     /// Dependencies that was not found on file system.
-    pub missing_dependencies: Option<Vec<PathBuf>>,
+    pub missing_dependencies: Option<Vec<CachedPath>>,
 
     /// The current resolving alias for bailing recursion alias.
     pub resolving_alias: Option<String>,
 
+    /// The specifier produced by the last alias which resolved, see
+    /// [crate::Resolution::resolved_specifier].
+    pub aliased_specifier: Option<String>,
+
+    /// The aliases applied as `(from, to)` specifiers, outermost first, see
+    /// [crate::ResolveContext::alias_chain]. Only collected after
+    /// [ResolveContext::init_file_dependencies].
+    pub alias_chain: Option<Vec<(String, String)>>,
+
+    /// The steps of the resolution, only collected by [crate::ResolverImpl::explain].
+    pub steps: Option<Vec<ResolutionStep>>,
+
     /// For avoiding infinite recursion, which will cause stack overflow.
-    pub depth: u8,
+    pub depth: usize,
+
+    /// The last resolutions once past half of [crate::ResolveOptions::max_resolve_depth],
+    /// reported by [ResolveError::RecursionLimitExceeded].
+    pub trail: VecDeque<String>,
 
     pub resolve_file: bool,
+
+    /// The module format of the importing file, see [crate::ResolverImpl::resolve_file].
+    pub importer_module_type: Option<ModuleType>,
+
+    /// The resolved path was found by appending an extension or by main file fallback.
+    pub probed: bool,
+
+    /// Loading the directory of a specifier with a trailing slash, which is exempt from
+    /// [crate::ResolveOptions::enforce_extension].
+    pub directory_request: bool,
+
+    /// The `node-addons` condition was taken when resolving `exports` or `imports`.
+    pub via_node_addons: bool,
+
+    /// The package.json files consulted, see [crate::ResolveOptions::collect_package_json_chain].
+    pub package_json_chain: Option<Vec<PathBuf>>,
+
+    /// The conditions taken to the `exports` or `imports` target being returned, innermost first,
+    /// when [crate::ResolveOptions::record_matched_entry] is enabled.
+    pub matched_conditions: Option<Vec<String>>,
+
+    /// The last matched `package.json` entry, see [crate::Resolution::package_entry].
+    pub package_entry: Option<PackageEntryInfo>,
+
+    /// See [crate::Resolution::file_directory_ambiguity].
+    pub file_directory_ambiguity: Option<FileDirectoryAmbiguity>,
+
+    /// The first candidate path which could not be checked because it is too long, reported by
+    /// [crate::ResolveError::PathTooLong] in place of [crate::ResolveError::NotFound].
+    pub path_too_long: Option<PathBuf>,
+
+    /// Set when the specifier resolved to a `data:` URL, which has no file to look up.
+    pub data_url: Option<DataUrl>,
+
+    /// The conditions of [crate::ResolveOptions::condition_names_provider] for the importer, or
+    /// [ResolveContext::condition_names_override].
+    pub condition_names: Option<Arc<[String]>>,
+
+    /// [crate::ResolveOverrides::condition_names], which take precedence over the conditions of
+    /// [crate::ResolveOptions::condition_names_provider].
+    pub condition_names_override: Option<Arc<[String]>>,
+
+    /// [crate::ResolveOverrides::main_fields].
+    pub main_fields: Option<Arc<[String]>>,
+
+    /// Phase durations, see [crate::ResolveContext::timings].
+    pub timings: Option<ResolveTimings>,
+
+    /// Counters, see [crate::ResolveContext::stats].
+    pub stats: Option<ResolveStats>,
 }
 
 impl ResolveContext {
@@ -42,17 +123,66 @@ impl ResolveContext {
     pub fn init_file_dependencies(&mut self) {
         self.file_dependencies.replace(vec![]);
         self.missing_dependencies.replace(vec![]);
+        self.alias_chain.replace(vec![]);
     }
 
-    pub fn add_file_dependency(&mut self, dep: &Path) {
+    pub fn add_file_dependency(&mut self, dep: &CachedPath) {
         if let Some(deps) = &mut self.file_dependencies {
-            deps.push(dep.to_path_buf());
+            deps.push(dep.clone());
         }
     }
 
-    pub fn add_missing_dependency(&mut self, dep: &Path) {
+    pub fn add_missing_dependency(&mut self, dep: &CachedPath) {
         if let Some(deps) = &mut self.missing_dependencies {
-            deps.push(dep.to_path_buf());
+            deps.push(dep.clone());
+        }
+    }
+
+    /// Record that the alias of `from` was applied, returning the length of the alias chain before,
+    /// for [ResolveContext::truncate_alias_chain] when the aliased specifier fails.
+    pub fn push_alias(&mut self, from: &str, to: &str) -> usize {
+        self.alias_chain.as_mut().map_or(0, |chain| {
+            chain.push((from.to_string(), to.to_string()));
+            chain.len() - 1
+        })
+    }
+
+    pub fn truncate_alias_chain(&mut self, len: usize) {
+        if let Some(chain) = &mut self.alias_chain {
+            chain.truncate(len);
+        }
+    }
+
+    pub fn add_package_json(&mut self, path: &Path) {
+        if let Some(chain) = &mut self.package_json_chain {
+            chain.push(path.to_path_buf());
+        }
+    }
+
+    /// Record a step for [crate::ResolverImpl::explain].
+    pub fn add_step(&mut self, operation: &'static str, path: &Path, outcome: StepOutcome) {
+        if let Some(steps) = &mut self.steps {
+            steps.push(ResolutionStep::new(operation, path, outcome));
+        }
+    }
+
+    /// Start timing `phase` when timings are collected, and count it when stats are.
+    pub fn start_phase(&mut self, phase: Phase) -> Option<PhaseTimer> {
+        if let Some(stats) = &mut self.stats {
+            stats.enter(phase);
+        }
+        self.timings.as_ref().map(|timings| PhaseTimer::start(phase, timings))
+    }
+
+    /// Stop the timer of [ResolveContext::start_phase], returning the elapsed time.
+    pub fn stop_phase(&mut self, timer: Option<PhaseTimer>) -> Option<Duration> {
+        timer.zip(self.timings.as_mut()).map(|(timer, timings)| timer.stop(timings))
+    }
+
+    /// Increment `counter` when stats are collected.
+    pub fn count(&mut self, counter: Counter) {
+        if let Some(stats) = &mut self.stats {
+            stats.count(counter);
         }
     }
 
@@ -64,12 +194,26 @@ impl ResolveContext {
     ///
     /// ### Errors
     ///
-    /// * [ResolveError::Recursion]
-    pub fn test_for_infinite_recursion(&mut self) -> Result<(), ResolveError> {
+    /// * [ResolveError::RecursionLimitExceeded]
+    pub fn test_for_infinite_recursion(
+        &mut self,
+        limit: usize,
+        specifier: &str,
+        path: &CachedPath,
+    ) -> Result<(), ResolveError> {
         self.depth += 1;
-        // 64 should be more than enough for detecting infinite recursion.
-        if self.depth > 64 {
-            return Err(ResolveError::Recursion);
+        // Only resolutions which are likely to recurse pay for the trail.
+        if self.depth > limit / 2 {
+            if self.trail.len() == TRAIL_LENGTH {
+                self.trail.pop_front();
+            }
+            self.trail.push_back(format!("{specifier} from {}", path.path().display()));
+        }
+        if self.depth > limit {
+            return Err(ResolveError::RecursionLimitExceeded {
+                limit,
+                trail: std::mem::take(&mut self.trail).into(),
+            });
         }
         Ok(())
     }
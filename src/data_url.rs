@@ -0,0 +1,157 @@
+//! `data:` URL specifiers, e.g. `import 'data:text/javascript,export default 42'`.
+//!
+//! <https://nodejs.org/api/esm.html#data-imports>
+
+use std::{
+    hash::{Hash, Hasher},
+    path::{MAIN_SEPARATOR, PathBuf},
+};
+
+use rustc_hash::FxHasher;
+
+use crate::{ModuleType, ResolveError};
+
+/// A `data:` URL the resolver resolved to, see [parse_data_url].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUrl {
+    /// The path standing in for the URL, which does not exist on the file system.
+    pub path: PathBuf,
+    /// The module type of the MIME type, as Node.js derives it.
+    pub module_type: Option<ModuleType>,
+}
+
+pub fn is_data_url(specifier: &str) -> bool {
+    specifier.get(..5).is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+}
+
+/// Split the trailing fragment off a `data:` URL.
+///
+/// The data may contain unescaped `?` and `#`, e.g. `data:text/javascript,export default 'a#1'`,
+/// so there is no query and only a last `#` followed by plain URL characters starts a fragment.
+pub fn split_fragment(specifier: &str) -> (&str, Option<&str>) {
+    let is_fragment = |fragment: &str| {
+        !fragment.is_empty()
+            && fragment
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"-._~!$&*+=:@/?%".contains(&b))
+    };
+    match specifier.rfind('#') {
+        Some(index) if specifier[..index].contains(',') && is_fragment(&specifier[index + 1..]) => {
+            (&specifier[..index], Some(&specifier[index..]))
+        }
+        _ => (specifier, None),
+    }
+}
+
+/// Parse `data:[<mediatype>][;base64],<data>`, without its fragment.
+///
+/// The [DataUrl::path] is derived from a hash of the URL, so the same URL always resolves to the
+/// same path, under a `__data_url__` directory of the root.
+///
+/// # Errors
+///
+/// * [ResolveError::DataUrl] when there is no `,` or the media type is not `type/subtype`.
+pub fn parse_data_url(specifier: &str) -> Result<DataUrl, ResolveError> {
+    let malformed = || ResolveError::DataUrl(specifier.to_string());
+    let (media_type, _data) = specifier[5..].split_once(',').ok_or_else(malformed)?;
+    let mut params = media_type.split(';');
+    let mime = params.next().unwrap_or_default().trim().to_ascii_lowercase();
+    if !mime.is_empty() && !is_mime_type(&mime) {
+        return Err(malformed());
+    }
+    if params.any(str::is_empty) {
+        return Err(malformed());
+    }
+
+    // https://github.com/nodejs/node/blob/main/lib/internal/modules/esm/formats.js
+    let (module_type, extension) = match mime.as_str() {
+        "text/javascript" => (Some(ModuleType::Module), ".mjs"),
+        "application/json" => (Some(ModuleType::Json), ".json"),
+        "application/wasm" => (Some(ModuleType::Wasm), ".wasm"),
+        _ => (None, ""),
+    };
+
+    let mut hasher = FxHasher::default();
+    specifier.hash(&mut hasher);
+    let path = PathBuf::from(format!(
+        "{MAIN_SEPARATOR}__data_url__{MAIN_SEPARATOR}{:016x}{extension}",
+        hasher.finish()
+    ));
+    Ok(DataUrl { path, module_type })
+}
+
+fn is_mime_type(mime: &str) -> bool {
+    let is_token = |s: &str| {
+        !s.is_empty()
+            && s.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+    };
+    mime.split_once('/').is_some_and(|(ty, subtype)| is_token(ty) && is_token(subtype))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_type() {
+        #[rustfmt::skip]
+        let data = [
+            ("data:text/javascript,export default 42", Some(ModuleType::Module)),
+            ("data:Text/JavaScript;charset=utf-8,export default 42", Some(ModuleType::Module)),
+            ("data:application/json,{}", Some(ModuleType::Json)),
+            ("data:application/wasm;base64,AGFzbQEAAAA=", Some(ModuleType::Wasm)),
+            ("data:text/plain,hello", None),
+            ("data:,hello", None),
+        ];
+        for (specifier, module_type) in data {
+            assert_eq!(parse_data_url(specifier).unwrap().module_type, module_type, "{specifier}");
+        }
+    }
+
+    #[test]
+    fn path() {
+        let a = parse_data_url("data:text/javascript,export default 1").unwrap();
+        let b = parse_data_url("data:text/javascript,export default 2").unwrap();
+        assert_eq!(a, parse_data_url("data:text/javascript,export default 1").unwrap());
+        assert_ne!(a.path, b.path);
+        assert!(a.path.is_absolute() || cfg!(windows));
+        assert_eq!(a.path.extension().unwrap(), "mjs");
+    }
+
+    #[test]
+    fn fragment() {
+        #[rustfmt::skip]
+        let data = [
+            ("data:text/javascript,export default 1#frag", ("data:text/javascript,export default 1", Some("#frag"))),
+            ("data:text/javascript,export default 'a#1'", ("data:text/javascript,export default 'a#1'", None)),
+            ("data:text/javascript,export default 'a?1'", ("data:text/javascript,export default 'a?1'", None)),
+            ("data:text/javascript,export default 'a?1'#frag", ("data:text/javascript,export default 'a?1'", Some("#frag"))),
+            ("data:text/javascript,export default 1#", ("data:text/javascript,export default 1#", None)),
+            ("data:text/javascript#frag", ("data:text/javascript#frag", None)),
+        ];
+        for (specifier, expected) in data {
+            assert_eq!(split_fragment(specifier), expected, "{specifier}");
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        for specifier in
+            ["data:text/javascript", "data:text,a", "data:/javascript,a", "data:a/b;;x,a"]
+        {
+            assert_eq!(
+                parse_data_url(specifier),
+                Err(ResolveError::DataUrl(specifier.to_string())),
+                "{specifier}"
+            );
+        }
+    }
+
+    #[test]
+    fn detect() {
+        assert!(is_data_url("data:,"));
+        assert!(is_data_url("DATA:,"));
+        assert!(!is_data_url("data"));
+        assert!(!is_data_url("./data:,"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Collecting the dependencies of resolutions, see [DependencyCollector] and
+//! [crate::ResolveOptions::dependency_sink].
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use rustc_hash::FxHashSet;
+
+use crate::{CachedPath, ResolveContext, ResolveStats, ResolveTimings};
+
+/// Whether a dependency of a resolution was found on the file system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// A file which was found, see [ResolveContext::file_dependencies].
+    File,
+    /// A path which was not found, see [ResolveContext::missing_dependencies].
+    Missing,
+}
+
+type SinkFn = dyn Fn(DependencyKind, &Path) + Send + Sync;
+
+/// Value for [crate::ResolveOptions::dependency_sink].
+#[derive(Clone)]
+pub struct DependencySink(pub Arc<SinkFn>);
+
+impl DependencySink {
+    pub fn new<F: Fn(DependencyKind, &Path) + Send + Sync + 'static>(f: F) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for DependencySink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DependencySink(<function>)")
+    }
+}
+
+/// [ResolveContext] recording the dependencies as the [CachedPath]s of the resolver's cache.
+///
+/// Every unique path is stored once by the cache, so unioning the dependencies of many
+/// resolutions costs a pointer per entry instead of a fresh [PathBuf]. The recorded paths stay
+/// alive, also in the cache, until they are dropped from this context.
+#[derive(Debug, Default, Clone)]
+pub struct ResolveContextInterned {
+    /// Files that was found on file system
+    pub file_dependencies: FxHashSet<CachedPath>,
+
+    /// Dependencies that was not found on file system
+    pub missing_dependencies: FxHashSet<CachedPath>,
+
+    /// See [ResolveContext::package_json_chain].
+    pub package_json_chain: Vec<PathBuf>,
+
+    /// See [ResolveContext::alias_chain].
+    pub alias_chain: Vec<(String, String)>,
+
+    /// See [ResolveContext::timings].
+    pub timings: Option<ResolveTimings>,
+
+    /// See [ResolveContext::stats].
+    pub stats: Option<ResolveStats>,
+}
+
+impl ResolveContextInterned {
+    pub fn iter_file_dependencies(&self) -> impl Iterator<Item = &Path> {
+        self.file_dependencies.iter().map(CachedPath::path)
+    }
+
+    pub fn iter_missing_dependencies(&self) -> impl Iterator<Item = &Path> {
+        self.missing_dependencies.iter().map(CachedPath::path)
+    }
+}
+
+impl From<ResolveContextInterned> for ResolveContext {
+    fn from(context: ResolveContextInterned) -> Self {
+        Self {
+            file_dependencies: context.iter_file_dependencies().map(Path::to_path_buf).collect(),
+            missing_dependencies: context
+                .iter_missing_dependencies()
+                .map(Path::to_path_buf)
+                .collect(),
+            package_json_chain: context.package_json_chain,
+            alias_chain: context.alias_chain,
+            timings: context.timings,
+            stats: context.stats,
+        }
+    }
+}
+
+impl ResolveContext {
+    pub fn iter_file_dependencies(&self) -> impl Iterator<Item = &Path> {
+        self.file_dependencies.iter().map(PathBuf::as_path)
+    }
+
+    pub fn iter_missing_dependencies(&self) -> impl Iterator<Item = &Path> {
+        self.missing_dependencies.iter().map(PathBuf::as_path)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Context of the `_with_context` methods of [crate::ResolverImpl], implemented by
+/// [ResolveContext] and [ResolveContextInterned].
+pub trait DependencyCollector: private::Sealed {
+    #[doc(hidden)]
+    fn add_dependency(&mut self, kind: DependencyKind, path: CachedPath);
+
+    #[doc(hidden)]
+    fn timings_mut(&mut self) -> &mut Option<ResolveTimings>;
+
+    #[doc(hidden)]
+    fn stats_mut(&mut self) -> &mut Option<ResolveStats>;
+
+    #[doc(hidden)]
+    fn set_package_json_chain(&mut self, chain: Vec<PathBuf>);
+
+    #[doc(hidden)]
+    fn set_alias_chain(&mut self, chain: Vec<(String, String)>);
+}
+
+impl private::Sealed for ResolveContext {}
+
+impl DependencyCollector for ResolveContext {
+    fn add_dependency(&mut self, kind: DependencyKind, path: CachedPath) {
+        let deps = match kind {
+            DependencyKind::File => &mut self.file_dependencies,
+            DependencyKind::Missing => &mut self.missing_dependencies,
+        };
+        if !deps.contains(path.path()) {
+            deps.insert(path.to_path_buf());
+        }
+    }
+
+    fn timings_mut(&mut self) -> &mut Option<ResolveTimings> {
+        &mut self.timings
+    }
+
+    fn stats_mut(&mut self) -> &mut Option<ResolveStats> {
+        &mut self.stats
+    }
+
+    fn set_package_json_chain(&mut self, chain: Vec<PathBuf>) {
+        self.package_json_chain = chain;
+    }
+
+    fn set_alias_chain(&mut self, chain: Vec<(String, String)>) {
+        self.alias_chain = chain;
+    }
+}
+
+impl private::Sealed for ResolveContextInterned {}
+
+impl DependencyCollector for ResolveContextInterned {
+    fn add_dependency(&mut self, kind: DependencyKind, path: CachedPath) {
+        match kind {
+            DependencyKind::File => self.file_dependencies.insert(path),
+            DependencyKind::Missing => self.missing_dependencies.insert(path),
+        };
+    }
+
+    fn timings_mut(&mut self) -> &mut Option<ResolveTimings> {
+        &mut self.timings
+    }
+
+    fn stats_mut(&mut self) -> &mut Option<ResolveStats> {
+        &mut self.stats
+    }
+
+    fn set_package_json_chain(&mut self, chain: Vec<PathBuf>) {
+        self.package_json_chain = chain;
+    }
+
+    fn set_alias_chain(&mut self, chain: Vec<(String, String)>) {
+        self.alias_chain = chain;
+    }
+}
+
+/// Context discarding everything, for streaming the dependencies of the context-less methods
+/// to [crate::ResolveOptions::dependency_sink].
+#[derive(Default)]
+pub struct SinkOnly {
+    timings: Option<ResolveTimings>,
+    stats: Option<ResolveStats>,
+}
+
+impl private::Sealed for SinkOnly {}
+
+impl DependencyCollector for SinkOnly {
+    fn add_dependency(&mut self, _kind: DependencyKind, _path: CachedPath) {}
+
+    fn timings_mut(&mut self) -> &mut Option<ResolveTimings> {
+        &mut self.timings
+    }
+
+    fn stats_mut(&mut self) -> &mut Option<ResolveStats> {
+        &mut self.stats
+    }
+
+    fn set_package_json_chain(&mut self, _chain: Vec<PathBuf>) {}
+
+    fn set_alias_chain(&mut self, _chain: Vec<(String, String)>) {}
+}
@@ -11,10 +11,10 @@
 //! - `typesVersions` package.json field support
 //! - When `exports` exists, `types`/`typings`/`main` are ignored
 
-use std::{borrow::Cow, path::Path};
+use std::{borrow::Cow, ffi::OsStr, path::Path};
 
 use crate::{
-    CachedPath, PackageJson, ResolveError, ResolverImpl,
+    CachedPath, PackageJson, ResolveError, ResolverImpl, TypesVersionsPaths,
     context::ResolveContext as Ctx,
     resolution::{ModuleType, Resolution},
     specifier::Specifier,
@@ -102,6 +102,7 @@ impl ResolverImpl {
         specifier: &str,
     ) -> Result<Resolution, ResolveError> {
         let mut ctx = Ctx::default();
+        let request = specifier;
 
         let containing_dir = containing_file.parent().unwrap_or(containing_file);
         let cached_dir = self.cache.value(containing_dir);
@@ -119,7 +120,7 @@ impl ResolverImpl {
             && !specifier.starts_with('/')
             && let Some(path) = self.dts_resolve_tsconfig_paths(specifier, &mut ctx)?
         {
-            return self.dts_finalize(&path, &mut ctx);
+            return self.dts_finalize(request, &path, &mut ctx);
         }
 
         // 2. Route by specifier type
@@ -154,24 +155,34 @@ impl ResolverImpl {
 
         result.map_or_else(
             || Err(ResolveError::NotFound(specifier.to_string())),
-            |path| self.dts_finalize(&path, &mut ctx),
+            |path| self.dts_finalize(request, &path, &mut ctx),
         )
     }
 
     fn dts_finalize(
         &self,
+        specifier: &str,
         cached_path: &CachedPath,
         ctx: &mut Ctx,
     ) -> Result<Resolution, ResolveError> {
-        let path = self.load_realpath(cached_path)?;
+        let path = self.load_realpath(cached_path, ctx)?;
+        self.check_forbidden_extensions(&path)?;
         let package_json = self.find_package_json_for_a_package(cached_path, ctx)?;
         let module_type = Self::dts_module_type(cached_path);
+        let file_metadata = self.cache.file_metadata(cached_path, self.options.symlinks);
         Ok(Resolution {
             path,
+            specifier: specifier.to_string(),
+            aliased_specifier: None,
             query: ctx.query.take(),
             fragment: ctx.fragment.take(),
             package_json,
             module_type,
+            probed: false,
+            via_node_addons: ctx.via_node_addons,
+            file_metadata,
+            package_entry: ctx.package_entry.take(),
+            file_directory_ambiguity: None,
         })
     }
 
@@ -211,14 +222,17 @@ impl ResolverImpl {
         candidate: &CachedPath,
         ctx: &mut Ctx,
     ) -> Option<CachedPath> {
-        let path_str = candidate.path().to_string_lossy();
+        // Work on the raw bytes so directories with non-UTF-8 names are kept intact.
+        let path_bytes = candidate.path().as_os_str().as_encoded_bytes();
 
         // Phase 1: Extension replacement (./foo.js -> ./foo.ts)
-        if let Some(original_ext) = Self::dts_get_known_extension(&path_str) {
+        if let Some(original_ext) = Self::dts_get_known_extension(path_bytes) {
             // Strip the extension to get the extensionless base
-            let base_len = path_str.len() - original_ext.len();
-            let base_str = &path_str[..base_len];
-            let base = self.cache.value(Path::new(base_str));
+            let base_bytes = &path_bytes[..path_bytes.len() - original_ext.len()];
+            // SAFETY: the extension starts with the ASCII `.`, so the split is on a boundary
+            // of the encoded bytes.
+            let base = unsafe { OsStr::from_encoded_bytes_unchecked(base_bytes) };
+            let base = self.cache.value(Path::new(base));
             if let Some(path) = self.dts_try_extensions(&base, extensions, original_ext, ctx) {
                 return Some(path);
             }
@@ -229,16 +243,16 @@ impl ResolverImpl {
     }
 
     /// Get the known extension from a path, including compound extensions like `.d.ts`.
-    fn dts_get_known_extension(path: &str) -> Option<&str> {
+    fn dts_get_known_extension(path: &[u8]) -> Option<&str> {
         // Check compound extensions first
-        for ext in &[".d.ts", ".d.mts", ".d.cts"] {
-            if path.ends_with(ext) {
+        for ext in [".d.ts", ".d.mts", ".d.cts"] {
+            if path.ends_with(ext.as_bytes()) {
                 return Some(ext);
             }
         }
         // Check single extensions
-        let dot_pos = path.rfind('.')?;
-        let ext = &path[dot_pos..];
+        let dot_pos = path.iter().rposition(|&b| b == b'.')?;
+        let ext = std::str::from_utf8(&path[dot_pos..]).ok()?;
         match ext {
             ".ts" | ".tsx" | ".mts" | ".cts" | ".js" | ".jsx" | ".mjs" | ".cjs" | ".json" => {
                 Some(ext)
@@ -361,7 +375,7 @@ impl ResolverImpl {
 
     fn dts_try_file(&self, base: &CachedPath, ext: &str, ctx: &mut Ctx) -> Option<CachedPath> {
         let candidate = base.add_extension(ext, &self.cache);
-        if self.is_file(&candidate, ctx) { Some(candidate) } else { None }
+        if self.is_file_impl(&candidate, ctx) { Some(candidate) } else { None }
     }
 
     /// Package entry for directory resolution: `typings`/`types`, falling back to `main`.
@@ -380,7 +394,7 @@ impl ResolverImpl {
                 Extensions::TYPESCRIPT.union(Extensions::JAVASCRIPT).union(Extensions::DECLARATION),
             )
         {
-            entry = pkg.main_fields(main_fields).next();
+            entry = pkg.main_fields(main_fields).next().map(|(_, main_field)| main_field);
         }
         entry
     }
@@ -392,7 +406,7 @@ impl ResolverImpl {
         candidate: &CachedPath,
         ctx: &mut Ctx,
     ) -> ResolveResult {
-        if !self.is_dir(candidate, ctx) {
+        if !self.is_dir_impl(candidate, ctx) {
             return Ok(None);
         }
 
@@ -401,7 +415,7 @@ impl ResolverImpl {
 
         // Try typesVersions paths
         if let Some(ref pkg) = pkg
-            && let Some(version_paths) = Self::dts_get_matching_version_paths(pkg)
+            && let Some(version_paths) = self.dts_get_matching_version_paths(pkg)
         {
             let entry = Self::dts_package_entry(pkg, extensions, &main_fields);
 
@@ -412,7 +426,7 @@ impl ResolverImpl {
                 extensions,
                 vp_specifier,
                 candidate,
-                &version_paths,
+                version_paths,
                 ctx,
             )? {
                 return Ok(Some(path));
@@ -434,7 +448,7 @@ impl ResolverImpl {
             if let Some(path) = self.dts_resolve_as_file(expanded, &entry_path, ctx) {
                 return Ok(Some(path));
             }
-            if self.is_dir(&entry_path, ctx) {
+            if self.is_dir_impl(&entry_path, ctx) {
                 let index = entry_path.push("index", &self.cache);
                 if let Some(path) = self.dts_resolve_as_file(expanded, &index, ctx) {
                     return Ok(Some(path));
@@ -468,7 +482,7 @@ impl ResolverImpl {
                 std::iter::successors(Some(directory.clone()), |cp| cp.parent(&self.cache))
             {
                 let nm = ancestor.push("node_modules", &self.cache);
-                if !self.is_dir(&nm, ctx) {
+                if !self.is_dir_impl(&nm, ctx) {
                     continue;
                 }
 
@@ -483,7 +497,7 @@ impl ResolverImpl {
                 if priority_exts.contains(Extensions::DECLARATION) {
                     let mangled = Self::dts_mangle_scoped_name(package_name);
                     let at_types_dir = nm.push("@types", &self.cache);
-                    if self.is_dir(&at_types_dir, ctx) {
+                    if self.is_dir_impl(&at_types_dir, ctx) {
                         let at_types_specifier = if rest.is_empty() {
                             mangled.clone()
                         } else {
@@ -508,7 +522,7 @@ impl ResolverImpl {
                 std::iter::successors(Some(directory.clone()), |cp| cp.parent(&self.cache))
             {
                 let nm = ancestor.push("node_modules", &self.cache);
-                if !self.is_dir(&nm, ctx) {
+                if !self.is_dir_impl(&nm, ctx) {
                     continue;
                 }
                 if let Some(path) =
@@ -532,7 +546,7 @@ impl ResolverImpl {
         let (package_name, rest) = Self::parse_package_specifier(specifier);
         let pkg_dir = nm_dir.normalize_with(package_name, &self.cache);
 
-        if !self.is_dir(&pkg_dir, ctx) {
+        if !self.is_dir_impl(&pkg_dir, ctx) {
             return Ok(None);
         }
 
@@ -546,7 +560,7 @@ impl ResolverImpl {
 
             for exports in pkg.exports_fields(&self.options.exports_fields) {
                 if let Ok(Some(path)) =
-                    self.package_exports_resolve(&pkg_dir, &subpath, &exports, None, ctx)
+                    self.package_exports_resolve(&pkg_dir, &subpath, pkg, &exports, None, ctx)
                 {
                     // Try to resolve the ESM match (file may need extension)
                     if let Some(resolved) = self.dts_resolve_esm_match(&path, ctx) {
@@ -562,14 +576,14 @@ impl ResolverImpl {
         // PRIORITY 2: typesVersions (for subpath imports: rest != "")
         if !rest.is_empty()
             && let Some(ref pkg) = pkg
-            && let Some(version_paths) = Self::dts_get_matching_version_paths(pkg)
+            && let Some(version_paths) = self.dts_get_matching_version_paths(pkg)
         {
             let rest_without_slash = rest.strip_prefix('/').unwrap_or(rest);
             if let Some(path) = self.dts_resolve_via_version_paths(
                 extensions,
                 rest_without_slash,
                 &pkg_dir,
-                &version_paths,
+                version_paths,
                 ctx,
             )? {
                 return Ok(Some(path));
@@ -582,7 +596,7 @@ impl ResolverImpl {
             if let Some(path) = self.dts_resolve_as_file(extensions, &candidate, ctx) {
                 return Ok(Some(path));
             }
-            if self.is_dir(&candidate, ctx) {
+            if self.is_dir_impl(&candidate, ctx) {
                 return self.dts_resolve_as_directory(extensions, &candidate, ctx);
             }
         }
@@ -597,7 +611,7 @@ impl ResolverImpl {
             return Some(path);
         }
         // Fall back to original file if it exists
-        if self.is_file(cached_path, ctx) {
+        if self.is_file_impl(cached_path, ctx) {
             return Some(cached_path.clone());
         }
         None
@@ -611,34 +625,15 @@ impl ResolverImpl {
 
     // -------- typesVersions --------
 
-    /// Get the first matching version path from typesVersions.
-    ///
-    /// TypeScript matches `*` (wildcard) as "any version", which is the common case.
-    /// For simplicity, we match all version ranges.
-    fn dts_get_matching_version_paths(pkg: &PackageJson) -> Option<Vec<(String, Vec<String>)>> {
-        let types_versions = pkg.types_versions()?;
-
-        // TypeScript iterates versions and picks the first matching one.
-        // The `*` key matches all versions, which is the overwhelmingly common case.
-        for (_version_range, paths_value) in types_versions.iter() {
-            if let Some(map) = paths_value.as_map() {
-                let mut result = Vec::new();
-                for (pattern, targets_entry) in map.iter() {
-                    let targets: Vec<String> = if let Some(arr) = targets_entry.as_array() {
-                        arr.iter().filter_map(|v| v.as_string().map(String::from)).collect()
-                    } else if let Some(s) = targets_entry.as_string() {
-                        vec![s.to_string()]
-                    } else {
-                        continue;
-                    };
-                    result.push((pattern.to_string(), targets));
-                }
-                if !result.is_empty() {
-                    return Some(result);
-                }
-            }
-        }
-        None
+    /// The path mappings of the typesVersions entry matching
+    /// [crate::ResolveOptions::typescript_version], or of the first entry without a version.
+    fn dts_get_matching_version_paths<'a>(
+        &self,
+        pkg: &'a PackageJson,
+    ) -> Option<&'a TypesVersionsPaths> {
+        pkg.types_versions()?
+            .paths_for(self.options.typescript_version.as_ref())
+            .filter(|paths| !paths.is_empty())
     }
 
     /// Resolve a specifier against typesVersions path mappings.
@@ -658,7 +653,7 @@ impl ResolverImpl {
                     if let Some(path) = self.dts_resolve_as_file(extensions, &candidate, ctx) {
                         return Ok(Some(path));
                     }
-                    if self.is_dir(&candidate, ctx)
+                    if self.is_dir_impl(&candidate, ctx)
                         && let Some(path) =
                             self.dts_resolve_as_directory(extensions, &candidate, ctx)?
                     {
@@ -671,7 +666,7 @@ impl ResolverImpl {
     }
 
     /// Match a specifier against a pattern with optional `*` wildcard.
-    fn dts_match_pattern<'a>(pattern: &str, specifier: &'a str) -> Option<Cow<'a, str>> {
+    pub(crate) fn dts_match_pattern<'a>(pattern: &str, specifier: &'a str) -> Option<Cow<'a, str>> {
         if let Some((prefix, suffix)) = pattern.split_once('*') {
             if specifier.starts_with(prefix)
                 && (suffix.is_empty() || specifier.ends_with(suffix))
@@ -701,9 +696,10 @@ impl ResolverImpl {
 
         // Resolve path aliases
         let paths = tsconfig.resolve_path_alias(specifier);
+        let skip_base_url = paths.is_some() && self.options.tsconfig_paths_strict_fallback;
         let extensions =
             Extensions::TYPESCRIPT.union(Extensions::DECLARATION).union(Extensions::JAVASCRIPT);
-        for path in paths {
+        for path in paths.into_iter().flatten() {
             let resolved_path = self.cache.value(&path);
             if let Some(result) = self.dts_resolve_relative(extensions, &resolved_path, ctx)? {
                 return Ok(Some(result));
@@ -711,7 +707,7 @@ impl ResolverImpl {
         }
 
         // Try baseUrl
-        if let Some(path) = tsconfig.resolve_base_url(specifier) {
+        if !skip_base_url && let Some(path) = tsconfig.resolve_base_url(specifier) {
             let resolved_path = self.cache.value(&path);
             if let Some(result) = self.dts_resolve_relative(extensions, &resolved_path, ctx)? {
                 return Ok(Some(result));
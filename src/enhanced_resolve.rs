@@ -0,0 +1,274 @@
+//! Translation of [enhanced-resolve](https://github.com/webpack/enhanced-resolve#resolver-options)
+//! resolver options, see [ResolveOptions::from_enhanced_resolve].
+
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+use crate::{AliasValue, EnforceExtension, JSONError, ResolveError, ResolveOptions, Restriction};
+
+/// An enhanced-resolve option which could not be mapped to [ResolveOptions].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedOption {
+    /// Option name as written in the configuration.
+    ///
+    /// Options nested in `byDependency` are prefixed with `byDependency.<dependency type>.`.
+    pub name: String,
+
+    /// Why the option was not mapped.
+    pub reason: String,
+}
+
+impl ResolveOptions {
+    /// Create options from a JSON serialized enhanced-resolve configuration, e.g. webpack's `resolve`.
+    ///
+    /// Options are mapped to the field with the same meaning, starting from [ResolveOptions::default].
+    /// Options that cannot be mapped, such as `plugins` or `unsafeCache`, and options with
+    /// values of an unexpected shape are returned alongside instead of being dropped.
+    ///
+    /// `byDependency` is validated here, its overlays are created with
+    /// [ResolveOptions::from_enhanced_resolve_by_dependency].
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::Json] when `json` is not a JSON object.
+    pub fn from_enhanced_resolve(
+        json: &str,
+    ) -> Result<(Self, Vec<UnsupportedOption>), ResolveError> {
+        let object = parse_object(json)?;
+        let mut unsupported = vec![];
+        let options = apply(Self::default(), &object, "", &mut unsupported);
+        Ok((options, unsupported))
+    }
+
+    /// Create the options for each dependency type of enhanced-resolve's `byDependency`, e.g.
+    /// `esm` or `commonjs`, in declaration order.
+    ///
+    /// Like enhanced-resolve, each overlay is merged into the options returned by
+    /// [ResolveOptions::from_enhanced_resolve]: a list replaces the base list unless it contains
+    /// `"..."`, which expands to the base values, and `alias`/`fallback` objects are merged by key.
+    /// The result can be passed to [crate::ResolverGeneric::clone_with_options].
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::Json] when `json` is not a JSON object.
+    pub fn from_enhanced_resolve_by_dependency(
+        json: &str,
+    ) -> Result<Vec<(String, Self)>, ResolveError> {
+        let object = parse_object(json)?;
+        let mut unsupported = vec![];
+        let base = apply(Self::default(), &object, "", &mut unsupported);
+        let Some(by_dependency) = object.get("byDependency").and_then(Value::as_object) else {
+            return Ok(vec![]);
+        };
+        Ok(by_dependency
+            .iter()
+            .filter_map(|(dependency_type, overlay)| {
+                let overlay = overlay.as_object()?;
+                let options = apply(base.clone(), overlay, "", &mut unsupported);
+                Some((dependency_type.clone(), options))
+            })
+            .collect())
+    }
+}
+
+fn parse_object(json: &str) -> Result<Map<String, Value>, ResolveError> {
+    let value = serde_json::from_str::<Value>(json)
+        .map_err(|error| ResolveError::from_serde_json_error(PathBuf::new(), &error))?;
+    match value {
+        Value::Object(object) => Ok(object),
+        _ => Err(ResolveError::Json(JSONError {
+            path: PathBuf::new(),
+            message: "Expected an object of resolver options".to_string(),
+            line: 0,
+            column: 0,
+        })),
+    }
+}
+
+/// Apply the enhanced-resolve `object` on top of `options`.
+fn apply(
+    mut options: ResolveOptions,
+    object: &Map<String, Value>,
+    prefix: &str,
+    unsupported: &mut Vec<UnsupportedOption>,
+) -> ResolveOptions {
+    const INVALID: &str = "invalid value";
+    for (key, value) in object {
+        let result = match key.as_str() {
+            "alias" => alias(value, &options.alias).map(|v| options.alias = v).ok_or(INVALID),
+            "aliasFields" => field_paths(value).map(|v| options.alias_fields = v).ok_or(INVALID),
+            "byDependency" if !prefix.is_empty() => Err("nested `byDependency` is not supported"),
+            "byDependency" => value
+                .as_object()
+                .map(|by_dependency| {
+                    for (dependency_type, overlay) in by_dependency {
+                        let prefix = format!("byDependency.{dependency_type}.");
+                        if let Some(overlay) = overlay.as_object() {
+                            apply(options.clone(), overlay, &prefix, unsupported);
+                        } else {
+                            unsupported.push(UnsupportedOption {
+                                name: format!("byDependency.{dependency_type}"),
+                                reason: INVALID.to_string(),
+                            });
+                        }
+                    }
+                })
+                .ok_or(INVALID),
+            "conditionNames" => strings(value, &options.condition_names)
+                .map(|v| options.condition_names = v)
+                .ok_or(INVALID),
+            "descriptionFiles" => strings(value, &options.description_files)
+                .map(|v| options.description_files = v)
+                .ok_or(INVALID),
+            "enforceExtension" => value
+                .as_bool()
+                .map(|enforce| {
+                    options.enforce_extension = if enforce {
+                        EnforceExtension::Enabled
+                    } else {
+                        EnforceExtension::Disabled
+                    };
+                })
+                .ok_or(INVALID),
+            "exportsFields" => {
+                field_paths(value).map(|v| options.exports_fields = v).ok_or(INVALID)
+            }
+            "extensionAlias" => {
+                extension_alias(value).map(|v| options.extension_alias = v).ok_or(INVALID)
+            }
+            "extensions" => {
+                strings(value, &options.extensions).map(|v| options.extensions = v).ok_or(INVALID)
+            }
+            "fallback" => {
+                alias(value, &options.fallback).map(|v| options.fallback = v).ok_or(INVALID)
+            }
+            "fullySpecified" => value.as_bool().map(|v| options.fully_specified = v).ok_or(INVALID),
+            "importsFields" => {
+                field_paths(value).map(|v| options.imports_fields = v).ok_or(INVALID)
+            }
+            "mainFields" => {
+                strings(value, &options.main_fields).map(|v| options.main_fields = v).ok_or(INVALID)
+            }
+            "mainFiles" => {
+                strings(value, &options.main_files).map(|v| options.main_files = v).ok_or(INVALID)
+            }
+            "modules" => {
+                strings(value, &options.modules).map(|v| options.modules = v).ok_or(INVALID)
+            }
+            "preferAbsolute" => value.as_bool().map(|v| options.prefer_absolute = v).ok_or(INVALID),
+            "preferRelative" => value.as_bool().map(|v| options.prefer_relative = v).ok_or(INVALID),
+            "resolveToContext" => {
+                value.as_bool().map(|v| options.resolve_to_context = v).ok_or(INVALID)
+            }
+            "restrictions" => strings(value, &[])
+                .map(|paths| {
+                    options.restrictions =
+                        paths.into_iter().map(|path| Restriction::Path(path.into())).collect();
+                })
+                .ok_or(INVALID),
+            "roots" => strings(value, &[])
+                .map(|paths| options.roots = paths.into_iter().map(PathBuf::from).collect())
+                .ok_or(INVALID),
+            "symlinks" => value.as_bool().map(|v| options.symlinks = v).ok_or(INVALID),
+            "cachePredicate" | "cacheWithContext" | "unsafeCache" => {
+                Err("file system lookups are always cached, see `ResolverGeneric::clear_cache`")
+            }
+            "fileSystem" => {
+                Err("use `ResolverGeneric::new_with_file_system` for a custom file system")
+            }
+            "plugins" => Err("plugins are not supported"),
+            "pnpApi" => Err("use the `yarn_pnp` feature and option"),
+            "useSyncFileSystemCalls" => Err("resolution is always synchronous"),
+            _ => Err("unknown option"),
+        };
+        if let Err(reason) = result {
+            unsupported.push(UnsupportedOption {
+                name: format!("{prefix}{key}"),
+                reason: reason.to_string(),
+            });
+        }
+    }
+    options
+}
+
+/// A string or a list of strings, `"..."` in the list expands to `base`.
+fn strings(value: &Value, base: &[String]) -> Option<Vec<String>> {
+    if let Some(s) = value.as_str() {
+        return Some(vec![s.to_string()]);
+    }
+    let mut list = vec![];
+    for item in value.as_array()? {
+        match item.as_str()? {
+            "..." => list.extend_from_slice(base),
+            s => list.push(s.to_string()),
+        }
+    }
+    Some(list)
+}
+
+/// A field name, or a list of field names or paths to a nested field.
+fn field_paths(value: &Value) -> Option<Vec<Vec<String>>> {
+    if let Some(s) = value.as_str() {
+        return Some(vec![vec![s.to_string()]]);
+    }
+    value.as_array()?.iter().map(|item| strings(item, &[])).collect()
+}
+
+/// `{ [name]: target }` or `[{ name, alias, onlyModule }]`, merged by key into `base`.
+fn alias(
+    value: &Value,
+    base: &[(String, Vec<AliasValue>)],
+) -> Option<Vec<(String, Vec<AliasValue>)>> {
+    fn targets(value: &Value) -> Option<Vec<AliasValue>> {
+        match value {
+            Value::Bool(false) => Some(vec![AliasValue::Ignore]),
+            Value::String(s) => Some(vec![AliasValue::from(s.as_str())]),
+            Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::Bool(false) => Some(AliasValue::Ignore),
+                    _ => item.as_str().map(AliasValue::from),
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+    let entries = if let Some(object) = value.as_object() {
+        object
+            .iter()
+            .map(|(name, target)| Some((name.clone(), targets(target)?)))
+            .collect::<Option<Vec<_>>>()?
+    } else {
+        value
+            .as_array()?
+            .iter()
+            .map(|item| {
+                let item = item.as_object()?;
+                let mut name = item.get("name")?.as_str()?.to_string();
+                if item.get("onlyModule").and_then(Value::as_bool) == Some(true) {
+                    name.push('$');
+                }
+                Some((name, targets(item.get("alias")?)?))
+            })
+            .collect::<Option<Vec<_>>>()?
+    };
+    let mut merged = base.to_vec();
+    for (name, targets) in entries {
+        if let Some(entry) = merged.iter_mut().find(|(key, _)| *key == name) {
+            entry.1 = targets;
+        } else {
+            merged.push((name, targets));
+        }
+    }
+    Some(merged)
+}
+
+/// `{ [extension]: extension | extension[] }`.
+fn extension_alias(value: &Value) -> Option<Vec<(String, Vec<String>)>> {
+    value
+        .as_object()?
+        .iter()
+        .map(|(extension, aliases)| Some((extension.clone(), strings(aliases, &[])?)))
+        .collect()
+}
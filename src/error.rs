@@ -31,6 +31,19 @@ pub enum ResolveError {
     #[error("Cannot find module '{0}'")]
     NotFound(/* specifier */ String),
 
+    /// Module not found in any of the directories passed to [crate::ResolverImpl::resolve_from_any]
+    #[error("Cannot find module '{specifier}' in any of {tried:?}")]
+    NotFoundInAny { specifier: String, tried: Vec<PathBuf> },
+
+    /// A bare specifier matched by [crate::ResolveOptions::external], which is left for the
+    /// runtime to provide.
+    ///
+    /// `specifier` is the request passed to the resolver and `resolved_name` the bare specifier
+    /// which matched, after [crate::ResolveOptions::alias] and tsconfig `paths`, e.g. `preact`
+    /// for `react` aliased to `preact`.
+    #[error("Module '{specifier}' is external as '{resolved_name}'")]
+    External { specifier: String, resolved_name: String },
+
     /// Matched alias value  not found
     #[error("Cannot find module '{0}' for matched aliased key '{1}'")]
     MatchedAliasNotFound(/* specifier */ String, /* alias key */ String),
@@ -62,6 +75,17 @@ pub enum ResolveError {
     #[error("Path {0:?} contains unsupported construct.")]
     PathNotSupported(PathBuf),
 
+    /// Occurs when a path which is not valid UTF-8 has to be used as a specifier,
+    /// e.g. when it matches an alias key.
+    #[error("Path {0:?} is not valid UTF-8")]
+    NonUtf8Path(PathBuf),
+
+    /// Occurs when a module is not found and a candidate path exceeded the platform's path
+    /// length limits, e.g. `MAX_PATH` on Windows without long path support, so it could not be
+    /// checked.
+    #[error("Path {0:?} is too long")]
+    PathTooLong(PathBuf),
+
     /// Node.js builtin module when `Options::builtin_modules` is enabled.
     ///
     /// `is_runtime_module` can be used to determine whether the request
@@ -71,6 +95,19 @@ pub enum ResolveError {
     #[error("Builtin module {resolved}")]
     Builtin { resolved: String, is_runtime_module: bool },
 
+    /// A `node:` specifier which is not a Node.js builtin module, when
+    /// `Options::builtin_modules` is enabled.
+    #[error("Unknown builtin module {0}")]
+    UnknownBuiltin(/* specifier */ String),
+
+    /// A URL specifier with a scheme other than `file:`, `node:` or `data:`, e.g. `https:`.
+    #[error("Cannot resolve '{specifier}', the URL scheme '{scheme}:' is not supported")]
+    UnsupportedUrlScheme { scheme: String, specifier: String },
+
+    /// A `data:` URL specifier without a `,` or with a malformed media type.
+    #[error("Malformed data: URL {0}")]
+    DataUrl(/* specifier */ String),
+
     /// All of the aliased extension are not found
     ///
     /// Displays `Cannot resolve 'index.mjs' with extension aliases 'index.mts' in ...`
@@ -103,6 +140,11 @@ pub enum ResolveError {
         conditions: ConditionNames,
     },
 
+    /// Occurs when the `exports` target of `subpath` is not a file as written,
+    /// see [crate::ResolveOptions::exports_require_exact_target].
+    #[error(r#""{subpath}" is exported as {target:?} which does not exist (see exports field in {package_json_path})"#)]
+    ExportsTargetNotFound { subpath: String, package_json_path: PathBuf, target: PathBuf },
+
     #[error(r#"Invalid package config "{0}", "exports" cannot contain some keys starting with '.' and some not. The exports object must either be an object of package subpath keys or an object of main entry condition name keys only."#)]
     InvalidPackageConfig(PathBuf),
 
@@ -115,13 +157,35 @@ pub enum ResolveError {
     #[error(r#"Package import specifier "{0}" is not defined in package {1}"#)]
     PackageImportNotDefined(String, PathBuf),
 
+    /// Occurs when the resolved path ends with one of [crate::ResolveOptions::forbidden_extensions].
+    #[error("Resolved path {path:?} has the forbidden extension '{extension}'")]
+    ForbiddenExtension { path: PathBuf, extension: String },
+
     #[error("{0} is unimplemented")]
     Unimplemented(&'static str),
 
+    /// Occurs when the entry of [crate::ResolveOptions::import_map] matching a specifier cannot
+    /// map it, i.e. a key ending with `/` whose address does not end with `/`, or a specifier
+    /// leaving the address with `..`.
+    #[error(r#"Import map entry "{key}" cannot map "{specifier}""#)]
+    ImportMapBlocked { specifier: String, key: String },
+
     /// Occurs when alias paths reference each other.
     #[error("Recursion in resolving")]
     Recursion,
 
+    /// Occurs when a resolution nests deeper than [crate::ResolveOptions::max_resolve_depth],
+    /// e.g. when aliases, browser field remaps or `exports` targets reference each other.
+    ///
+    /// `trail` holds the last resolutions, oldest first, as `"{specifier} from {directory}"`.
+    #[error("Recursion limit of {limit} exceeded in resolving, last resolving {trail:?}")]
+    RecursionLimitExceeded { limit: usize, trail: Vec<String> },
+
+    /// Occurs when the cancellation token passed to
+    /// [crate::ResolverImpl::resolve_tsconfig_with] is set.
+    #[error("Resolution cancelled")]
+    Cancelled,
+
     #[cfg(feature = "yarn_pnp")]
     #[error("Failed to find yarn pnp manifest in {0}.")]
     FailedToFindYarnPnpManifest(PathBuf),
@@ -137,6 +201,11 @@ impl ResolveError {
         matches!(self, Self::Ignored(_))
     }
 
+    #[must_use]
+    pub const fn is_external(&self) -> bool {
+        matches!(self, Self::External { .. })
+    }
+
     #[cold]
     #[must_use]
     pub fn from_serde_json_error(path: PathBuf, error: &serde_json::Error) -> Self {
@@ -0,0 +1,96 @@
+//! Helpers for [eslint-plugin-import](https://github.com/import-js/eslint-plugin-import) resolvers,
+//! see [ResolveOptions::default_for_eslint_import] and [into_eslint_result].
+
+use std::path::PathBuf;
+
+use crate::{Resolution, ResolveError, ResolveOptions};
+
+impl ResolveOptions {
+    /// Options matching the defaults of
+    /// [eslint-import-resolver-typescript](https://github.com/import-js/eslint-import-resolver-typescript):
+    ///
+    /// * TypeScript, JavaScript (`.jsx`, `.cjs`, `.mjs` through [ResolveOptions::extension_alias]),
+    ///   JSON and `.node` extensions.
+    /// * `types` and the ESM, CommonJS and Node.js conditions and main fields.
+    /// * [ResolveOptions::builtin_modules] so Node.js builtins are reported as
+    ///   [EslintResolveOutcome::Builtin].
+    ///
+    /// Resolve with the linted file as the importer through [crate::ResolverImpl::resolve_file],
+    /// and set [ResolveOptions::tsconfig] to apply `paths`.
+    #[must_use]
+    pub fn default_for_eslint_import() -> Self {
+        fn strings(values: &[&str]) -> Vec<String> {
+            values.iter().map(ToString::to_string).collect()
+        }
+        Self {
+            extensions: strings(&[".ts", ".tsx", ".d.ts", ".js", ".jsx", ".json", ".node"]),
+            extension_alias: vec![
+                (".js".into(), strings(&[".ts", ".tsx", ".d.ts", ".js"])),
+                (".jsx".into(), strings(&[".tsx", ".d.ts", ".jsx"])),
+                (".cjs".into(), strings(&[".cts", ".d.cts", ".cjs"])),
+                (".mjs".into(), strings(&[".mts", ".d.mts", ".mjs"])),
+            ],
+            condition_names: strings(&[
+                "types",
+                "import",
+                "esm2020",
+                "es2020",
+                "es2015",
+                "require",
+                "node",
+                "node-addons",
+                "browser",
+            ]),
+            main_fields: strings(&[
+                "types",
+                "typings",
+                "fesm2020",
+                "fesm2015",
+                "esm2020",
+                "es2020",
+                "module",
+                "jsnext:main",
+                "main",
+            ]),
+            builtin_modules: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Outcome of a resolution in the terms of an eslint-plugin-import resolver, see [into_eslint_result].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EslintResolveOutcome {
+    /// Resolved to a file, without query and fragment.
+    Found(PathBuf),
+
+    /// A Node.js builtin module, always prefixed with `node:`.
+    ///
+    /// Resolvers report these as found without a path.
+    Builtin(String),
+
+    /// Resolved to [crate::AliasValue::Ignore], a `false` browser field entry or an entry of
+    /// [crate::ResolveOptions::external].
+    ///
+    /// Resolvers report these as found without a path, like builtins.
+    Ignored,
+
+    /// Any other error, eslint only distinguishes found from not found.
+    NotFound,
+}
+
+/// Map the result of a resolution to an [EslintResolveOutcome].
+///
+/// Use [ResolveOptions::default_for_eslint_import] or enable [ResolveOptions::builtin_modules],
+/// otherwise builtins are not resolved and reported as [EslintResolveOutcome::NotFound].
+#[must_use]
+pub fn into_eslint_result(result: Result<Resolution, ResolveError>) -> EslintResolveOutcome {
+    match result {
+        Ok(resolution) => EslintResolveOutcome::Found(resolution.into_path_buf()),
+        Err(ResolveError::Builtin { resolved, .. }) => EslintResolveOutcome::Builtin(resolved),
+        Err(ResolveError::Ignored(_) | ResolveError::External { .. }) => {
+            EslintResolveOutcome::Ignored
+        }
+        Err(_) => EslintResolveOutcome::NotFound,
+    }
+}
@@ -0,0 +1,86 @@
+//! Traces of resolutions, see [crate::ResolverImpl::explain].
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{Resolution, ResolveError};
+
+/// A resolution and the steps taken to reach its result, returned by
+/// [crate::ResolverImpl::explain].
+///
+/// Its [Display](fmt::Display) lists the steps one per line.
+#[derive(Debug, Clone)]
+pub struct ResolutionExplanation {
+    pub directory: PathBuf,
+    pub specifier: String,
+    /// The steps in the order they were taken, steps of nested resolutions such as those of an
+    /// alias follow the step which started them.
+    pub steps: Vec<ResolutionStep>,
+    pub result: Result<Resolution, ResolveError>,
+}
+
+/// A step of a [ResolutionExplanation].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionStep {
+    /// What was done:
+    ///
+    /// * `"file"`: checking whether [ResolutionStep::path] is a file.
+    /// * `"alias"`: replacing the specifier in [StepOutcome::Matched] with [ResolutionStep::path],
+    ///   for [crate::ResolveOptions::alias] and [crate::ResolveOptions::fallback].
+    /// * `"tsconfig paths"`: trying [ResolutionStep::path] for the `compilerOptions.paths` of
+    ///   the specifier in [StepOutcome::Matched].
+    /// * `"exports condition"`: evaluating a condition of the `exports` or `imports` field of the
+    ///   package.json at [ResolutionStep::path].
+    /// * `"exports target"`: mapping the `exports` or `imports` key in [StepOutcome::Matched] to
+    ///   [ResolutionStep::path].
+    /// * `"resolved"`: the final path.
+    pub operation: &'static str,
+    pub path: PathBuf,
+    pub outcome: StepOutcome,
+}
+
+/// The outcome of a [ResolutionStep].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The file exists.
+    Found,
+    /// The file does not exist.
+    NotFound,
+    /// The key, specifier or condition was applied.
+    Matched(String),
+    /// The condition is not one of the conditions resolved with.
+    Skipped(String),
+}
+
+impl ResolutionStep {
+    pub(crate) fn new(operation: &'static str, path: &Path, outcome: StepOutcome) -> Self {
+        Self { operation, path: path.to_path_buf(), outcome }
+    }
+}
+
+impl fmt::Display for ResolutionStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.display();
+        match &self.outcome {
+            StepOutcome::Found => write!(f, "{}: {path} found", self.operation),
+            StepOutcome::NotFound => write!(f, "{}: {path} not found", self.operation),
+            StepOutcome::Matched(key) => write!(f, "{} {key}: {path}", self.operation),
+            StepOutcome::Skipped(key) => write!(f, "{} {key} skipped: {path}", self.operation),
+        }
+    }
+}
+
+impl fmt::Display for ResolutionExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "resolve {:?} in {}", self.specifier, self.directory.display())?;
+        for step in &self.steps {
+            writeln!(f, "  {step}")?;
+        }
+        match &self.result {
+            Ok(resolution) => write!(f, "=> {}", resolution.full_path().display()),
+            Err(error) => write!(f, "=> error: {error}"),
+        }
+    }
+}
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use std::{
     cfg_select, fs, io,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
 
 #[cfg(feature = "yarn_pnp")]
 use pnp::fs::{LruZipCache, VPath, VPathInfo, ZipCache};
@@ -76,20 +79,104 @@ pub trait FileSystem: Send + Sync {
     ///
     /// See [std::fs::canonicalize]
     fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Returns the paths of the entries of a directory, in no particular order.
+    ///
+    /// Only used by [crate::Cache::warm_from_directory]. The default implementation fails with
+    /// [io::ErrorKind::Unsupported].
+    ///
+    /// # Errors
+    ///
+    /// See [std::fs::read_dir]
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let _ = path;
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// The async interface of this file system, used by [crate::ResolverImpl::resolve_async].
+    ///
+    /// Without one, async resolutions run on tokio's blocking thread pool.
+    #[cfg(feature = "async")]
+    fn as_async(&self) -> Option<&dyn AsyncFileSystem> {
+        None
+    }
+}
+
+/// Future returned by the methods of [AsyncFileSystem].
+#[cfg(feature = "async")]
+pub type FileSystemFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Async counterpart of [FileSystem], see [FileSystem::as_async].
+///
+/// The resolution algorithm stays synchronous: [crate::ResolverImpl::resolve_async] keeps the
+/// results of these operations in the [crate::Cache] and resolves against them, awaiting the
+/// operations the resolution is missing before retrying.
+#[cfg(feature = "async")]
+pub trait AsyncFileSystem: Send + Sync {
+    /// See [FileSystem::read]
+    fn read_async<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, io::Result<Vec<u8>>>;
+
+    /// See [FileSystem::metadata]
+    fn metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>>;
+
+    /// See [FileSystem::symlink_metadata]
+    fn symlink_metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>>;
+
+    /// See [FileSystem::read_link]
+    fn read_link_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<PathBuf, ResolveError>>;
+
+    /// See [FileSystem::canonicalize]
+    fn canonicalize_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<PathBuf>>;
 }
 
 /// Metadata information about a file
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FileMetadata {
     pub(crate) is_file: bool,
     pub(crate) is_dir: bool,
     pub(crate) is_symlink: bool,
+    /// Size in bytes, `0` when the file system cannot provide it.
+    pub(crate) size: u64,
+    /// Last modification time, `None` when the file system cannot provide it.
+    pub(crate) modified: Option<SystemTime>,
 }
 
 impl FileMetadata {
     #[must_use]
     pub const fn new(is_file: bool, is_dir: bool, is_symlink: bool) -> Self {
-        Self { is_file, is_dir, is_symlink }
+        Self { is_file, is_dir, is_symlink, size: 0, modified: None }
+    }
+
+    /// Set the size in bytes and the last modification time.
+    #[must_use]
+    pub const fn with_size_and_modified(mut self, size: u64, modified: Option<SystemTime>) -> Self {
+        self.size = size;
+        self.modified = modified;
+        self
+    }
+
+    /// Size in bytes, `0` when the file system cannot provide it.
+    #[must_use]
+    pub const fn size(self) -> u64 {
+        self.size
+    }
+
+    /// Last modification time, `None` when the file system cannot provide it.
+    #[must_use]
+    pub const fn modified(self) -> Option<SystemTime> {
+        self.modified
     }
 
     #[must_use]
@@ -125,6 +212,30 @@ impl From<pnp::fs::FileType> for FileMetadata {
 impl From<fs::Metadata> for FileMetadata {
     fn from(metadata: fs::Metadata) -> Self {
         Self::new(metadata.is_file(), metadata.is_dir(), metadata.is_symlink())
+            .with_size_and_modified(metadata.len(), metadata.modified().ok())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<rustix::fs::Statx> for FileMetadata {
+    fn from(statx: rustix::fs::Statx) -> Self {
+        use rustix::fs::{FileType, StatxFlags};
+        let file_type = FileType::from_raw_mode(statx.stx_mode.into());
+        let modified = StatxFlags::from_bits_retain(statx.stx_mask)
+            .contains(StatxFlags::MTIME)
+            .then(|| {
+                let secs = statx.stx_mtime.tv_sec;
+                let duration =
+                    std::time::Duration::new(secs.unsigned_abs(), statx.stx_mtime.tv_nsec);
+                if secs >= 0 {
+                    SystemTime::UNIX_EPOCH.checked_add(duration)
+                } else {
+                    SystemTime::UNIX_EPOCH.checked_sub(duration)
+                }
+            })
+            .flatten();
+        Self::new(file_type.is_file(), file_type.is_dir(), file_type.is_symlink())
+            .with_size_and_modified(statx.stx_size, modified)
     }
 }
 
@@ -187,12 +298,10 @@ impl FileSystemOs {
                 Ok(result.into())
             }
             target_os = "linux" => {
-                use rustix::fs::{AtFlags, CWD, FileType, StatxFlags};
-                match rustix::fs::statx(CWD, path, AtFlags::STATX_DONT_SYNC, StatxFlags::TYPE) {
-                    Ok(statx) => {
-                        let file_type = FileType::from_raw_mode(statx.stx_mode.into());
-                        Ok(FileMetadata::new(file_type.is_file(), file_type.is_dir(), file_type.is_symlink()))
-                    }
+                use rustix::fs::{AtFlags, CWD, StatxFlags};
+                let mask = StatxFlags::TYPE | StatxFlags::SIZE | StatxFlags::MTIME;
+                match rustix::fs::statx(CWD, path, AtFlags::STATX_DONT_SYNC, mask) {
+                    Ok(statx) => Ok(statx.into()),
                     Err(rustix::io::Errno::NOSYS) => {
                         // statx is not available (kernel < 4.11), fall back to fs::metadata
                         fs::metadata(path).map(FileMetadata::from)
@@ -216,12 +325,10 @@ impl FileSystemOs {
                 Ok(crate::windows::symlink_metadata(path)?.into())
             }
             target_os = "linux" => {
-                use rustix::fs::{AtFlags, CWD, FileType, StatxFlags};
-                match rustix::fs::statx(CWD, path, AtFlags::SYMLINK_NOFOLLOW, StatxFlags::TYPE) {
-                    Ok(statx) => {
-                        let file_type = FileType::from_raw_mode(statx.stx_mode.into());
-                        Ok(FileMetadata::new(file_type.is_file(), file_type.is_dir(), file_type.is_symlink()))
-                    }
+                use rustix::fs::{AtFlags, CWD, StatxFlags};
+                let mask = StatxFlags::TYPE | StatxFlags::SIZE | StatxFlags::MTIME;
+                match rustix::fs::statx(CWD, path, AtFlags::SYMLINK_NOFOLLOW, mask) {
+                    Ok(statx) => Ok(statx.into()),
                     Err(rustix::io::Errno::NOSYS) => {
                         // statx is not available (kernel < 4.11), fall back to fs::symlink_metadata
                         fs::symlink_metadata(path).map(FileMetadata::from)
@@ -343,14 +450,163 @@ impl FileSystem for FileSystemOs {
         }
         Self::canonicalize(path)
     }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.yarn_pnp {
+            // The entries keep the virtual path of the directory.
+            let physical = match VPath::from(path)? {
+                VPath::Zip(_) => return Err(io::ErrorKind::Unsupported.into()),
+                VPath::Virtual(info) => info.physical_base_path(),
+                VPath::Native(path) => path,
+            };
+            return fs::read_dir(physical)?
+                .map(|entry| entry.map(|entry| path.join(entry.file_name())))
+                .collect();
+        }
+        fs::read_dir(path)?.map(|entry| entry.map(|entry| entry.path())).collect()
+    }
+
+    #[cfg(feature = "async")]
+    fn as_async(&self) -> Option<&dyn AsyncFileSystem> {
+        Some(self)
+    }
+}
+
+/// `tokio::fs`, or the blocking thread pool for the virtual paths of Yarn PnP.
+#[cfg(feature = "async")]
+impl AsyncFileSystem for FileSystemOs {
+    fn read_async<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, io::Result<Vec<u8>>> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.yarn_pnp {
+            return self.spawn_blocking(path, <Self as FileSystem>::read);
+        }
+        Box::pin(tokio::fs::read(path))
+    }
+
+    fn metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.yarn_pnp {
+            return self.spawn_blocking(path, <Self as FileSystem>::metadata);
+        }
+        Box::pin(async move { tokio::fs::metadata(path).await.map(FileMetadata::from) })
+    }
+
+    fn symlink_metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.yarn_pnp {
+            return self.spawn_blocking(path, <Self as FileSystem>::symlink_metadata);
+        }
+        Box::pin(async move { tokio::fs::symlink_metadata(path).await.map(FileMetadata::from) })
+    }
+
+    fn read_link_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<PathBuf, ResolveError>> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.yarn_pnp {
+            return Box::pin(async move {
+                self.spawn_blocking(path, |fs, path| Ok(FileSystem::read_link(fs, path))).await?
+            });
+        }
+        Box::pin(async move {
+            let path = tokio::fs::read_link(path).await?;
+            cfg_select! {
+                target_os = "windows" => crate::windows::strip_windows_prefix(path),
+                _ => Ok(path),
+            }
+        })
+    }
+
+    fn canonicalize_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<PathBuf>> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.yarn_pnp {
+            return self.spawn_blocking(path, <Self as FileSystem>::canonicalize);
+        }
+        Box::pin(tokio::fs::canonicalize(path))
+    }
+}
+
+#[cfg(all(feature = "async", feature = "yarn_pnp"))]
+impl FileSystemOs {
+    fn spawn_blocking<T: Send + 'static>(
+        &self,
+        path: &Path,
+        f: fn(&Self, &Path) -> io::Result<T>,
+    ) -> FileSystemFuture<'static, io::Result<T>> {
+        let fs = self.clone();
+        let path = path.to_path_buf();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || f(&fs, &path))
+                .await
+                .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))?
+        })
+    }
+}
+
+/// Share a file system with its owner, e.g. to add files to a [crate::MemoryFileSystem] in use.
+impl<Fs: FileSystem> FileSystem for std::sync::Arc<Fs> {
+    #[cfg(feature = "yarn_pnp")]
+    fn new(yarn_pnp: bool) -> Self {
+        Self::new(Fs::new(yarn_pnp))
+    }
+
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        Self::new(Fs::new())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        (**self).read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        (**self).metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        (**self).symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        (**self).read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        (**self).canonicalize(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        (**self).read_dir(path)
+    }
+
+    #[cfg(feature = "async")]
+    fn as_async(&self) -> Option<&dyn AsyncFileSystem> {
+        (**self).as_async()
+    }
 }
 
 #[test]
 fn metadata() {
-    let meta = FileMetadata { is_file: true, is_dir: true, is_symlink: true };
+    let meta =
+        FileMetadata { is_file: true, is_dir: true, is_symlink: true, size: 1, modified: None };
     assert_eq!(
         format!("{meta:?}"),
-        "FileMetadata { is_file: true, is_dir: true, is_symlink: true }"
+        "FileMetadata { is_file: true, is_dir: true, is_symlink: true, size: 1, modified: None }"
     );
 }
 
@@ -360,6 +616,13 @@ fn file_metadata_getters() {
     assert!(file_meta.is_file());
     assert!(!file_meta.is_dir());
     assert!(!file_meta.is_symlink());
+    assert_eq!(file_meta.size(), 0);
+    assert_eq!(file_meta.modified(), None);
+
+    let modified = std::time::SystemTime::UNIX_EPOCH;
+    let file_meta = file_meta.with_size_and_modified(1, Some(modified));
+    assert_eq!(file_meta.size(), 1);
+    assert_eq!(file_meta.modified(), Some(modified));
 
     let dir_meta = FileMetadata::new(false, true, false);
     assert!(!dir_meta.is_file());
@@ -0,0 +1,137 @@
+//! [Import maps](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps), see
+//! [crate::ResolveOptions::import_map].
+
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+
+use crate::{
+    CachedPath, ResolveError, ResolverImpl, TsConfig, context::ResolveContext as Ctx,
+    path::PathUtil,
+};
+
+/// Value for [crate::ResolveOptions::import_map].
+///
+/// Keys are specifiers, or specifier prefixes when ending with `/`. Addresses are paths, which
+/// must end with `/` for a prefix key, or specifiers resolved by the rest of the resolver.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ImportMap {
+    /// Mapping of specifiers to addresses for every importer.
+    pub imports: IndexMap<String, String>,
+
+    /// Mappings for the importers inside a directory, keyed by the directory ending with `/`.
+    ///
+    /// The mapping of the most specific scope containing the importer is tried first, then the
+    /// less specific ones, then [ImportMap::imports].
+    pub scopes: IndexMap<String, IndexMap<String, String>>,
+
+    /// Directory which relative keys, scopes and addresses are resolved against, usually the
+    /// directory of the import map file.
+    pub base: PathBuf,
+}
+
+/// Mapping of an [ImportMap] with path-like keys and addresses made absolute, sorted by
+/// descending key so that the longest matching key is found first.
+type SpecifierMap = Vec<(String, String)>;
+
+#[derive(Clone, Default)]
+pub struct CompiledImportMap {
+    imports: SpecifierMap,
+    /// Sorted by descending scope, so the most specific scope is tried first.
+    scopes: Vec<(String, SpecifierMap)>,
+}
+
+pub fn compile_import_map(import_map: &ImportMap) -> CompiledImportMap {
+    let base = import_map.base.as_path();
+    let compile = |imports: &IndexMap<String, String>| {
+        let mut map = imports
+            .iter()
+            .map(|(key, address)| {
+                (normalize_url_like(base, key), normalize_url_like(base, address))
+            })
+            .collect::<SpecifierMap>();
+        map.sort_by(|a, b| b.0.cmp(&a.0));
+        map
+    };
+    let mut scopes = import_map
+        .scopes
+        .iter()
+        .map(|(scope, imports)| (normalize_url_like(base, scope), compile(imports)))
+        .collect::<Vec<_>>();
+    scopes.sort_by(|a, b| b.0.cmp(&a.0));
+    CompiledImportMap { imports: compile(&import_map.imports), scopes }
+}
+
+/// Resolve `value` against `base` when it starts with `/`, `./` or `../`, keeping a trailing `/`.
+/// Other values are bare specifiers or URLs and are returned as is.
+fn normalize_url_like(base: &Path, value: &str) -> String {
+    if !(value.starts_with('/') || value.starts_with("./") || value.starts_with("../")) {
+        return value.to_string();
+    }
+    let mut normalized = base.normalize_with(value).normalize().to_string_lossy().into_owned();
+    if value.ends_with('/') && !normalized.ends_with('/') {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// The import map spec's "resolve an imports match": an exact key, or the longest prefix key.
+fn resolve_imports_match(
+    specifier: &str,
+    map: &SpecifierMap,
+) -> Option<Result<String, ResolveError>> {
+    let (key, address) = map.iter().find(|(key, _)| {
+        key == specifier || (key.ends_with('/') && specifier.starts_with(key.as_str()))
+    })?;
+    if key == specifier {
+        return Some(Ok(address.clone()));
+    }
+    let blocked =
+        || ResolveError::ImportMapBlocked { specifier: specifier.to_string(), key: key.clone() };
+    if !address.ends_with('/') {
+        return Some(Err(blocked()));
+    }
+    let after_prefix = &specifier[key.len()..];
+    let resolved = normalize_url_like(Path::new(address), &format!("./{after_prefix}"));
+    // The specifier must not back track above the address with `..`.
+    if Path::new(address).is_absolute() && !resolved.starts_with(address.as_str()) {
+        return Some(Err(blocked()));
+    }
+    Some(Ok(resolved))
+}
+
+impl ResolverImpl {
+    /// Map `specifier` with [crate::ResolveOptions::import_map] and resolve the address.
+    ///
+    /// Only the specifier passed to the resolver is mapped, not the ones produced by aliases,
+    /// browser fields or `exports` while resolving it.
+    pub(super) fn load_import_map(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> Result<Option<CachedPath>, ResolveError> {
+        let Some(import_map) = &self.import_map else { return Ok(None) };
+        if ctx.depth > 1 {
+            return Ok(None);
+        }
+        let specifier = normalize_url_like(cached_path.path(), specifier);
+        let mut referrer = cached_path.path().to_string_lossy().into_owned();
+        if !referrer.ends_with('/') {
+            referrer.push('/');
+        }
+        let address = import_map
+            .scopes
+            .iter()
+            .filter(|(scope, _)| scope.ends_with('/') && referrer.starts_with(scope.as_str()))
+            .find_map(|(_, map)| resolve_imports_match(&specifier, map))
+            .or_else(|| resolve_imports_match(&specifier, &import_map.imports));
+        let Some(address) = address.transpose()? else { return Ok(None) };
+        let path = self.require(cached_path, &address, tsconfig, ctx)?;
+        ctx.aliased_specifier.get_or_insert(address);
+        Ok(Some(path))
+    }
+}
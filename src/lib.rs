@@ -47,20 +47,38 @@
 mod alias;
 mod cache;
 mod context;
+mod data_url;
+mod dependencies;
 mod dts_resolver;
+#[cfg(feature = "serde")]
+mod enhanced_resolve;
 mod error;
+mod eslint;
+mod explain;
 mod file_system;
 #[cfg(not(target_arch = "wasm32"))]
 mod file_url;
+mod import_map;
+mod memory_file_system;
+mod module_syntax;
 mod node_path;
 mod options;
 mod package_json;
 mod path;
+mod phase;
+mod prefix_trie;
 mod resolution;
+#[cfg(feature = "async")]
+mod resolve_async;
+#[cfg(feature = "rayon")]
+mod resolve_many;
 mod specifier;
+mod target;
+mod timings;
 mod tsconfig;
 mod tsconfig_resolver;
-#[cfg(target_os = "windows")]
+mod validate;
+mod watch;
 mod windows;
 
 #[cfg(test)]
@@ -73,38 +91,74 @@ use std::{
     ffi::OsStr,
     fmt,
     path::{Component, Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
 };
 
+use arc_swap::ArcSwap;
 use rustc_hash::FxHashSet;
 
+#[cfg(feature = "serde")]
+pub use crate::enhanced_resolve::UnsupportedOption;
+#[cfg(feature = "async")]
+pub use crate::file_system::{AsyncFileSystem, FileSystemFuture};
 use crate::{
     alias::{CompiledAlias, compile_alias},
+    cache::ModuleDirectories,
     context::ResolveContext as Ctx,
+    data_url::DataUrl,
+    dependencies::SinkOnly,
+    import_map::{CompiledImportMap, compile_import_map},
     path::SLASH_START,
     specifier::Specifier,
+    timings::{Counter, Phase},
 };
 pub use crate::{
-    cache::{Cache, CachedPath},
+    cache::{Cache, CacheStatistics, CachedPath},
+    dependencies::{DependencyCollector, DependencyKind, DependencySink, ResolveContextInterned},
     error::{JSONError, ResolveError, SpecifierError},
+    eslint::{EslintResolveOutcome, into_eslint_result},
+    explain::{ResolutionExplanation, ResolutionStep, StepOutcome},
     file_system::{FileMetadata, FileSystem, FileSystemOs},
+    import_map::ImportMap,
+    memory_file_system::MemoryFileSystem,
     options::{
-        Alias, AliasValue, EnforceExtension, ResolveOptions, Restriction, TsconfigDiscovery,
-        TsconfigOptions, TsconfigReferences,
+        Alias, AliasValue, CaseSensitivity, ConditionNamesProvider, EnforceExtension,
+        MergeStrategy, ResolveOptions, ResolveOptionsBuilder, ResolveOverrides, Restriction,
+        TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
     },
     package_json::{
-        ImportsExportsArray, ImportsExportsEntry, ImportsExportsKind, ImportsExportsMap,
-        PackageJson, PackageType, SideEffects,
+        BinField, ImportsExportsArray, ImportsExportsEntry, ImportsExportsKind, ImportsExportsMap,
+        PackageJson, PackageType, SideEffects, TypesVersionsMap, TypesVersionsPaths,
     },
     path::PathUtil,
-    resolution::{ModuleType, Resolution},
+    phase::{ABSOLUTE_PHASES, AbsolutePhase, BARE_PHASES, BarePhase, REQUEST_PHASES, RequestPhase},
+    resolution::{
+        AmbiguityWinner, FileDirectoryAmbiguity, ModuleFieldConflict, ModuleType, PackageEntryInfo,
+        PackageEntryKind, PackageSubpathResolution, Resolution, ResolutionKey,
+    },
+    target::ResolveTarget,
+    timings::{ResolveStats, ResolveTimings},
     tsconfig::{
-        CompilerOptions, CompilerOptionsPathsMap, ExtendsField, ProjectReference, TsConfig,
+        CompilerOptions, CompilerOptionsPathsMap, ExtendsField, MissingProjectReference,
+        ProjectGraphReport, ProjectReference, TsConfig,
     },
+    validate::{DiagnosticSeverity, OptionsDiagnostic, ResolverBuilder},
+    watch::WatchTargets,
 };
 
 type ResolveResult = Result<Option<CachedPath>, ResolveError>;
 
+/// Position in the module directories of a directory and its ancestors,
+/// see [ResolverImpl::next_module_directory].
+enum ModuleDirectoryCursor {
+    /// The remaining memoized module directories.
+    Memoized(Option<Arc<ModuleDirectories>>),
+    /// The next ancestor to look for a module directory in, the memo is bypassed while
+    /// dependencies are collected so that every missing directory is reported.
+    Ancestor(Option<CachedPath>),
+}
+
 /// Context returned from the [ResolverImpl::resolve_with_context] API
 #[derive(Debug, Default, Clone)]
 pub struct ResolveContext {
@@ -113,6 +167,41 @@ pub struct ResolveContext {
 
     /// Dependencies that was not found on file system
     pub missing_dependencies: FxHashSet<PathBuf>,
+
+    /// The package.json files consulted by the latest resolution, closest to the resolved path
+    /// first, when [ResolveOptions::collect_package_json_chain] is enabled.
+    pub package_json_chain: Vec<PathBuf>,
+
+    /// The aliases applied by the latest resolution as `(from, to)` specifiers, outermost first,
+    /// e.g. `[("foo", "@internal/foo"), ("@internal/foo", "./packages/foo")]`. Covers
+    /// [ResolveOptions::alias] and [ResolveOptions::fallback].
+    pub alias_chain: Vec<(String, String)>,
+
+    /// Set to `Some` before resolving to collect the time spent in each phase of the
+    /// resolutions, see [ResolveTimings].
+    ///
+    /// Timing also records the `exports` resolution time of each package into
+    /// [ResolverImpl::package_exports_timings].
+    pub timings: Option<ResolveTimings>,
+
+    /// Set to `Some` before resolving to count the work done by the resolutions, see
+    /// [ResolveStats] and [ResolveContext::collect_stats].
+    pub stats: Option<ResolveStats>,
+}
+
+impl ResolveContext {
+    /// Collect [ResolveContext::timings] in the following resolutions, keeping the ones
+    /// already collected.
+    pub fn enable_timing(&mut self) {
+        self.timings.get_or_insert_with(ResolveTimings::default);
+    }
+
+    /// Collect [ResolveContext::stats] together with the [ResolveContext::timings] of the
+    /// phases in the following resolutions, keeping the ones already collected.
+    pub fn collect_stats(&mut self) {
+        self.stats.get_or_insert_with(ResolveStats::default);
+        self.enable_timing();
+    }
 }
 
 /// Resolver with the current operating system as the file system
@@ -124,16 +213,31 @@ pub type Resolver = ResolverGeneric<FileSystemOs>;
 /// algorithm compiles exactly once, regardless of the concrete `Fs` type used by
 /// [`ResolverGeneric`]. All public resolution methods live on this struct and are reachable
 /// through [`ResolverGeneric`]'s [`Deref`](std::ops::Deref) implementation.
+///
+/// Cloning shares the options, the cache and the compiled aliases, which the async resolutions
+/// rely on.
+#[derive(Clone)]
 pub struct ResolverImpl {
-    options: ResolveOptions,
+    options: Arc<ResolveOptions>,
     cache: Arc<Cache>,
-    alias: CompiledAlias,
-    fallback: CompiledAlias,
+    alias: Arc<CompiledAlias>,
+    fallback: Arc<CompiledAlias>,
+    external: Arc<CompiledAlias>,
+    import_map: Option<Arc<CompiledImportMap>>,
+    /// [ResolveOptions::extensions] reordered for ESM and CommonJS importers, see [ResolverImpl::extensions].
+    importer_extensions: [Option<Arc<[String]>>; 2],
+    /// See [ResolverImpl::module_field_conflicts].
+    module_field_conflicts: Arc<Mutex<Vec<ModuleFieldConflict>>>,
 }
 
 /// Generic implementation of the resolver, can be configured by the [Cache] trait
 pub struct ResolverGeneric<Fs> {
-    inner: ResolverImpl,
+    /// Replaced by [ResolverGeneric::update_options].
+    inner: ArcSwap<ResolverImpl>,
+    /// Every resolver stored in `inner`, kept until this is dropped because [`Deref`](std::ops::Deref)
+    /// lends them out. Held by [ResolverGeneric::update_options] so that concurrent updates apply
+    /// in turn.
+    resolvers: Mutex<Vec<Arc<ResolverImpl>>>,
     _marker: std::marker::PhantomData<Fs>,
 }
 
@@ -141,13 +245,16 @@ impl<Fs> std::ops::Deref for ResolverGeneric<Fs> {
     type Target = ResolverImpl;
 
     fn deref(&self) -> &ResolverImpl {
-        &self.inner
+        let current = self.inner.load();
+        // SAFETY: `resolvers` holds every resolver stored in `inner` until `self` is dropped, so
+        // the resolver outlives the borrow of `self` even when an update replaces it.
+        unsafe { &*Arc::as_ptr(&current) }
     }
 }
 
 impl<Fs> fmt::Debug for ResolverGeneric<Fs> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.options.fmt(f)
+        self.options().fmt(f)
     }
 }
 
@@ -160,52 +267,119 @@ impl<Fs: FileSystem + 'static> Default for ResolverGeneric<Fs> {
 impl<Fs: FileSystem + 'static> ResolverGeneric<Fs> {
     #[must_use]
     pub fn new(options: ResolveOptions) -> Self {
-        let options = options.sanitize();
-        let alias = compile_alias(&options.alias);
-        let fallback = compile_alias(&options.fallback);
         let fs = cfg_select! {
             feature = "yarn_pnp" => Fs::new(options.yarn_pnp),
             _ => Fs::new(),
         };
-        let cache = Arc::new(Cache::new(Arc::new(fs) as Arc<dyn FileSystem>));
-        let inner = ResolverImpl { options, cache, alias, fallback };
-        Self { inner, _marker: std::marker::PhantomData }
+        let cache = new_cache(Arc::new(fs), &options);
+        Self::from_impl(ResolverImpl::new(options, cache))
     }
 
     pub fn new_with_file_system(file_system: Fs, options: ResolveOptions) -> Self {
-        let options = options.sanitize();
-        let alias = compile_alias(&options.alias);
-        let fallback = compile_alias(&options.fallback);
-        let cache = Arc::new(Cache::new(Arc::new(file_system) as Arc<dyn FileSystem>));
-        let inner = ResolverImpl { options, cache, alias, fallback };
-        Self { inner, _marker: std::marker::PhantomData }
+        let cache = new_cache(Arc::new(file_system), &options);
+        Self::from_impl(ResolverImpl::new(options, cache))
+    }
+
+    fn from_impl(inner: ResolverImpl) -> Self {
+        let inner = Arc::new(inner);
+        Self {
+            inner: ArcSwap::new(Arc::clone(&inner)),
+            resolvers: Mutex::new(vec![inner]),
+            _marker: std::marker::PhantomData,
+        }
     }
 
     /// Clone the resolver using the same underlying cache.
     #[must_use]
     pub fn clone_with_options(&self, options: ResolveOptions) -> Self {
-        let options = options.sanitize();
-        let alias = compile_alias(&options.alias);
-        let fallback = compile_alias(&options.fallback);
+        Self::from_impl(Self::with_options(self, options))
+    }
+
+    /// Update the options of this resolver in place, keeping the underlying cache.
+    ///
+    /// `f` is called with a copy of the current options. Unlike [ResolverGeneric::clone_with_options],
+    /// every holder of this resolver observes the new options: resolutions started after this call
+    /// use them, while resolutions already in flight finish with the old options, so a single
+    /// resolution never sees a mix of both.
+    ///
+    /// Use [ResolverGeneric::current] to run several operations with the same options.
+    /// The resolvers with the replaced options are only freed when this resolver is dropped.
+    pub fn update_options<F: FnOnce(&mut ResolveOptions)>(&self, f: F) {
+        let mut resolvers = self.resolvers.lock().unwrap_or_else(PoisonError::into_inner);
+        let current = self.inner.load_full();
+        let mut options = current.options().clone();
+        f(&mut options);
+        let resolver = Arc::new(Self::with_options(&current, options));
+        resolvers.push(Arc::clone(&resolver));
+        self.inner.store(resolver);
+        drop(resolvers);
+    }
+
+    fn with_options(current: &ResolverImpl, options: ResolveOptions) -> ResolverImpl {
         let cache = cfg_select! {
             feature = "yarn_pnp" => {
-                if options.yarn_pnp == self.inner.options.yarn_pnp {
-                    Arc::clone(&self.inner.cache)
+                if options.yarn_pnp != current.options.yarn_pnp {
+                    new_cache(Arc::new(Fs::new(options.yarn_pnp)), &options)
+                } else if options.case_sensitivity != current.options.case_sensitivity
+                    || options.yarn_pnp_root != current.options.yarn_pnp_root
+                {
+                    new_cache(Arc::clone(&current.cache.fs), &options)
+                } else {
+                    Arc::clone(&current.cache)
+                }
+            }
+            _ => {
+                if options.case_sensitivity == current.options.case_sensitivity {
+                    Arc::clone(&current.cache)
                 } else {
-                    Arc::new(Cache::new(Arc::new(Fs::new(options.yarn_pnp)) as Arc<dyn FileSystem>))
+                    new_cache(Arc::clone(&current.cache.fs), &options)
                 }
             }
-            _ => Arc::clone(&self.inner.cache),
         };
-        let inner = ResolverImpl { options, cache, alias, fallback };
-        Self { inner, _marker: std::marker::PhantomData }
+        ResolverImpl::new(options, cache)
     }
 }
 
+impl<Fs> ResolverGeneric<Fs> {
+    /// The resolver with the current options, which keeps them across
+    /// [ResolverGeneric::update_options].
+    #[must_use]
+    pub fn current(&self) -> Arc<ResolverImpl> {
+        self.inner.load_full()
+    }
+}
+
+fn new_cache(fs: Arc<dyn FileSystem>, options: &ResolveOptions) -> Arc<Cache> {
+    let probe = options.cwd.clone().or_else(|| std::env::current_dir().ok()).unwrap_or_default();
+    Arc::new(Cache::new(fs).with_case_sensitivity(options.case_sensitivity, &probe))
+}
+
 impl ResolverImpl {
+    fn new(options: ResolveOptions, cache: Arc<Cache>) -> Self {
+        let options = options.sanitize();
+        let alias = Arc::new(compile_alias(&options.alias));
+        let fallback = Arc::new(compile_alias(&options.fallback));
+        let external = Arc::new(compile_alias(
+            &options.external.iter().map(|key| (key.clone(), vec![])).collect::<Vec<_>>(),
+        ));
+        let import_map = options.import_map.as_ref().map(|map| Arc::new(compile_import_map(map)));
+        let importer_extensions = [ModuleType::Module, ModuleType::CommonJs]
+            .map(|module_type| reorder_extensions(&options.extensions, module_type));
+        Self {
+            options: Arc::new(options),
+            cache,
+            alias,
+            fallback,
+            external,
+            import_map,
+            importer_extensions,
+            module_field_conflicts: Arc::default(),
+        }
+    }
+
     /// Returns the options.
     #[must_use]
-    pub const fn options(&self) -> &ResolveOptions {
+    pub fn options(&self) -> &ResolveOptions {
         &self.options
     }
 
@@ -216,6 +390,123 @@ impl ResolverImpl {
         self.cache.clear();
     }
 
+    /// Forget what the cache knows about the file or directory at `path`, see
+    /// [Cache::invalidate_path].
+    ///
+    /// Returns whether anything was forgotten.
+    ///
+    /// Warning: The caller must ensure that there're no ongoing resolution operations when calling this method. Otherwise, it may cause those operations to return an incorrect result.
+    pub fn invalidate_path<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.cache.invalidate_path(path.as_ref())
+    }
+
+    /// Forget what the cache knows about the directory at `path` and everything below it, see
+    /// [Cache::invalidate_dir_recursive].
+    ///
+    /// Returns whether anything was forgotten.
+    ///
+    /// Warning: The caller must ensure that there're no ongoing resolution operations when calling this method. Otherwise, it may cause those operations to return an incorrect result.
+    pub fn invalidate_dir_recursive<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.cache.invalidate_dir_recursive(path.as_ref())
+    }
+
+    /// Populate the cache from the files and directories below `root`, following symbolic links
+    /// and reading description files according to the options of this resolver, see
+    /// [Cache::warm_from_directory].
+    ///
+    /// Returns the number of entries populated.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when `root` can not be read.
+    pub fn warm_from_directory<P: AsRef<Path>>(&self, root: P) -> Result<usize, ResolveError> {
+        self.cache.warm_from_directory_with(root.as_ref(), &self.options)
+    }
+
+    /// Write what the cache knows about the file system to an artifact at `path`, see
+    /// [Cache::save_snapshot_mmap].
+    ///
+    /// Returns the number of paths written.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when the artifact can not be written.
+    pub fn save_cache_snapshot_mmap<P: AsRef<Path>>(
+        &self,
+        path: P,
+        token: &str,
+    ) -> Result<usize, ResolveError> {
+        self.cache.save_snapshot_mmap(path.as_ref(), token)
+    }
+
+    /// Map the artifact at `path` as a read-only layer beneath the cache, see
+    /// [Cache::load_snapshot_mmap].
+    ///
+    /// Returns whether the artifact is used, `false` when it was written with another `token`.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when `path` can not be read or is not an artifact.
+    ///
+    /// # Safety
+    ///
+    /// The artifact at `path` must not be truncated or modified in place for the lifetime of the
+    /// cache, see [Cache::load_snapshot_mmap].
+    pub unsafe fn load_cache_snapshot_mmap<P: AsRef<Path>>(
+        &self,
+        path: P,
+        token: &str,
+    ) -> Result<bool, ResolveError> {
+        // SAFETY: Upheld by the caller.
+        unsafe { self.cache.load_snapshot_mmap(path.as_ref(), token) }
+    }
+
+    /// The cumulative `exports` resolution time of each package, slowest first, keyed by the
+    /// path of its `package.json`.
+    ///
+    /// Only resolutions collecting [ResolveContext::timings] are measured. The times are shared
+    /// by the resolvers of the same cache and are not reset by [ResolverImpl::clear_cache].
+    #[must_use]
+    pub fn package_exports_timings(&self) -> Vec<(PathBuf, Duration)> {
+        let mut timings = self
+            .cache
+            .package_exports_timings
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect::<Vec<_>>();
+        timings.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        timings
+    }
+
+    /// The packages whose `module` field was preferred over an `exports` field resolving the
+    /// package to another file, in the order they were found, see
+    /// [ResolveOptions::prefer_module_field_over_exports].
+    ///
+    /// Each package is reported once per resolver.
+    pub fn module_field_conflicts(&self) -> Vec<ModuleFieldConflict> {
+        self.module_field_conflicts.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Start counting the hits and misses of [ResolverImpl::cache_statistics], see
+    /// [Cache::enable_statistics].
+    pub fn enable_cache_statistics(&self) {
+        self.cache.enable_statistics();
+    }
+
+    /// Hit and miss counts of the cache, see [Cache::statistics].
+    ///
+    /// The counts are shared by the resolvers of the same cache. Nothing is counted until
+    /// [ResolverImpl::enable_cache_statistics] is called.
+    #[must_use]
+    pub fn cache_statistics(&self) -> CacheStatistics {
+        self.cache.statistics()
+    }
+
+    /// Reset the counts of [ResolverImpl::cache_statistics], see [Cache::reset_statistics].
+    pub fn reset_cache_statistics(&self) {
+        self.cache.reset_statistics();
+    }
+
     /// Check if two resolvers share the same cache (for testing).
     #[cfg(all(test, feature = "yarn_pnp"))]
     pub(crate) fn shares_cache_with(&self, other: &Self) -> bool {
@@ -240,14 +531,133 @@ impl ResolverImpl {
         directory: P,
         specifier: &str,
     ) -> Result<Resolution, ResolveError> {
-        let mut ctx = Ctx::default();
         let path = directory.as_ref();
         let tsconfig = self.manual_tsconfig()?;
+        if self.options.dependency_sink.is_some() {
+            let mut sink_only = SinkOnly::default();
+            return self.resolve_with_context_impl(
+                path,
+                specifier,
+                tsconfig.as_deref(),
+                /* importer */ None,
+                &mut sink_only,
+            );
+        }
+        let mut ctx = Ctx::default();
         self.resolve_tracing(path, specifier, tsconfig.as_deref(), &mut ctx)
     }
 
+    /// [ResolverImpl::resolve] `specifier` in `directory` with some of the options replaced for
+    /// this call, e.g. to resolve the same specifier for several targets with one resolver.
+    ///
+    /// The cache is shared with the other calls, it only holds file system and `package.json`
+    /// data, which does not depend on the replaced options.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    pub fn resolve_with_options_override<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+        overrides: &ResolveOverrides,
+    ) -> Result<Resolution, ResolveError> {
+        let directory = directory.as_ref();
+        let tsconfig = self.manual_tsconfig()?;
+        let resolve = |ctx: &mut Ctx| {
+            ctx.condition_names_override = overrides.condition_names.as_deref().map(Arc::from);
+            ctx.main_fields = overrides.main_fields.as_deref().map(Arc::from);
+            self.resolve_tracing(directory, specifier, tsconfig.as_deref(), ctx)
+        };
+        if self.options.dependency_sink.is_some() {
+            return self.with_dependencies(&mut SinkOnly::default(), resolve);
+        }
+        resolve(&mut Ctx::default())
+    }
+
+    /// [ResolverImpl::resolve] `specifier` in `directory`, recording the files probed, the
+    /// aliases and tsconfig `paths` tried and the `exports` conditions evaluated on the way.
+    ///
+    /// Meant for debugging, the steps are recorded whether the files are cached or not.
+    pub fn explain<P: AsRef<Path>>(&self, directory: P, specifier: &str) -> ResolutionExplanation {
+        let directory = directory.as_ref();
+        let mut ctx = Ctx { steps: Some(vec![]), ..Ctx::default() };
+        let result = self.manual_tsconfig().and_then(|tsconfig| {
+            self.resolve_tracing(directory, specifier, tsconfig.as_deref(), &mut ctx)
+        });
+        let mut steps = ctx.steps.unwrap_or_default();
+        if let Ok(resolution) = &result {
+            steps.push(ResolutionStep::new("resolved", resolution.path(), StepOutcome::Found));
+        }
+        ResolutionExplanation {
+            directory: directory.to_path_buf(),
+            specifier: specifier.to_string(),
+            steps,
+            result,
+        }
+    }
+
+    /// Whether `specifier` resolves from `directory`, see [ResolverImpl::resolve].
+    ///
+    /// Cheaper than `resolve(..).is_ok()`: the package.json and module type of the resolved
+    /// path are not looked up.
+    pub fn can_resolve<P: AsRef<Path>>(&self, directory: P, specifier: &str) -> bool {
+        let Ok(tsconfig) = self.manual_tsconfig() else {
+            return false;
+        };
+        let mut ctx = Ctx::default();
+        let Ok(cached_path) =
+            self.require_from(directory.as_ref(), specifier, tsconfig.as_deref(), &mut ctx)
+        else {
+            return false;
+        };
+        ctx.data_url.is_some()
+            || self
+                .load_realpath(&cached_path, &mut ctx)
+                .is_ok_and(|path| self.check_forbidden_extensions(&path).is_ok())
+    }
+
+    /// Resolve `specifier` against each of `directories` in order, returning the index of the
+    /// first directory it resolves from alongside the resolution.
+    ///
+    /// Only [ResolveError::NotFound] moves on to the next directory, any other error is returned
+    /// immediately.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFoundInAny] listing `directories` when the specifier is not found in any of them.
+    /// * See [ResolveError]
+    pub fn resolve_from_any<P: AsRef<Path>>(
+        &self,
+        directories: &[P],
+        specifier: &str,
+    ) -> Result<(usize, Resolution), ResolveError> {
+        let tsconfig = self.manual_tsconfig()?;
+        for (index, directory) in directories.iter().enumerate() {
+            let mut ctx = Ctx::default();
+            match self.resolve_tracing(directory.as_ref(), specifier, tsconfig.as_deref(), &mut ctx)
+            {
+                Ok(resolution) => return Ok((index, resolution)),
+                Err(ResolveError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Err(ResolveError::NotFoundInAny {
+            specifier: specifier.to_string(),
+            tried: directories.iter().map(|d| d.as_ref().to_path_buf()).collect(),
+        })
+    }
+
     /// Resolve `specifier` for an absolute path to a file.
     ///
+    /// Specifiers are resolved from the parent directory of `file`, a trailing slash of `file` is
+    /// ignored.
+    ///
+    /// Like TypeScript, the extension of `file` biases file probing: from an `.mts` or `.mjs`
+    /// file, the `.mts` and `.mjs` [ResolveOptions::extensions] are tried before the `.cts` and
+    /// `.cjs` ones, and the other way around from a `.cts` or `.cjs` file. Only their relative
+    /// order changes, the other extensions are tried at their configured positions.
+    ///
     /// NOTE: [TsconfigDiscovery::Auto] only work for this API.
     ///
     /// # Errors
@@ -259,11 +669,55 @@ impl ResolverImpl {
         file: P,
         specifier: &str,
     ) -> Result<Resolution, ResolveError> {
+        if self.options.dependency_sink.is_some() {
+            return self.resolve_file_with_context(file, specifier, &mut SinkOnly::default());
+        }
         self.resolve_file_impl(file.as_ref(), specifier)
     }
 
+    /// [ResolverImpl::resolve_file] with [ResolveContext], recording `file` into the file
+    /// dependencies of `resolve_context` when it exists and into its missing dependencies
+    /// otherwise.
+    ///
+    /// `file` does not need to exist, e.g. for a virtual module, specifiers are resolved
+    /// from its parent directory regardless.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolverImpl::resolve_file]
+    pub fn resolve_file_with_context<P: AsRef<Path>, C: DependencyCollector>(
+        &self,
+        file: P,
+        specifier: &str,
+        resolve_context: &mut C,
+    ) -> Result<Resolution, ResolveError> {
+        let path = file.as_ref();
+        let Some(dir) = path.parent() else {
+            return Err(Self::invalid_resolve_file_path_error(path));
+        };
+        let cached_path = self.cache.value(path);
+        let kind = if self.cache.exists(&cached_path) {
+            DependencyKind::File
+        } else {
+            DependencyKind::Missing
+        };
+        self.add_dependency(resolve_context, kind, cached_path);
+        let tsconfig = self.find_tsconfig(path)?;
+        self.resolve_with_context_impl(
+            dir,
+            specifier,
+            tsconfig.as_deref(),
+            Some(path),
+            resolve_context,
+        )
+    }
+
     fn resolve_file_impl(&self, path: &Path, specifier: &str) -> Result<Resolution, ResolveError> {
-        let mut ctx = Ctx { resolve_file: true, ..Ctx::default() };
+        let mut ctx = Ctx {
+            resolve_file: true,
+            importer_module_type: importer_module_type(path),
+            ..Ctx::default()
+        };
         let Some(dir) = path.parent() else {
             return Err(Self::invalid_resolve_file_path_error(path));
         };
@@ -285,28 +739,381 @@ impl ResolverImpl {
 
     /// Resolve `specifier` at absolute `path` with [ResolveContext]
     ///
+    /// Use [ResolveContextInterned] to union the dependencies of many resolutions.
+    ///
     /// # Errors
     ///
     /// * See [ResolveError]
-    pub fn resolve_with_context<P: AsRef<Path>>(
+    pub fn resolve_with_context<P: AsRef<Path>, C: DependencyCollector>(
         &self,
         directory: P,
         specifier: &str,
         tsconfig: Option<&TsConfig>,
-        resolve_context: &mut ResolveContext,
+        resolve_context: &mut C,
     ) -> Result<Resolution, ResolveError> {
+        self.resolve_with_context_impl(
+            directory.as_ref(),
+            specifier,
+            tsconfig,
+            /* importer */ None,
+            resolve_context,
+        )
+    }
+
+    /// `importer` is the file passed to [ResolverImpl::resolve_file_with_context].
+    fn resolve_with_context_impl<C: DependencyCollector>(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        importer: Option<&Path>,
+        resolve_context: &mut C,
+    ) -> Result<Resolution, ResolveError> {
+        let (result, (chain, alias_chain)) = self.with_dependencies(resolve_context, |ctx| {
+            ctx.resolve_file = importer.is_some();
+            ctx.importer_module_type = importer.and_then(importer_module_type);
+            if self.options.collect_package_json_chain {
+                ctx.package_json_chain = Some(vec![]);
+            }
+            let start = ctx.timings.is_some().then(Instant::now);
+            let result = self.resolve_tracing(directory, specifier, tsconfig, ctx);
+            if let (Some(start), Some(timings)) = (start, &mut ctx.timings) {
+                timings.total += start.elapsed();
+            }
+            (result, (ctx.package_json_chain.take(), ctx.alias_chain.take()))
+        });
+        resolve_context.set_alias_chain(alias_chain.unwrap_or_default());
+        if let Some(mut chain) = chain {
+            // Deeper package.json files belong to closer scopes.
+            let mut seen = FxHashSet::default();
+            chain.retain(|path| seen.insert(path.clone()));
+            chain.sort_by_key(|path| std::cmp::Reverse(path.components().count()));
+            resolve_context.set_package_json_chain(chain);
+        }
+        result
+    }
+
+    /// Find the root directory of the installed package `package_name` (e.g. `lodash` or
+    /// `@scope/pkg`) as seen from `directory`, without resolving an entry point.
+    ///
+    /// Only the [ResolveOptions::modules] (or Yarn PnP) lookup is performed. The `exports`,
+    /// `main` and alias fields are not consulted, so packages which do not export their
+    /// `package.json` can be located to read their other files.
+    ///
+    /// The returned directory is canonicalized when [ResolveOptions::symlinks] is enabled.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when `package_name` is not a bare package name or the package
+    ///   is not installed.
+    /// * See [ResolveError] for invalid `package.json` and Yarn PnP errors.
+    pub fn resolve_package_root<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        package_name: &str,
+    ) -> Result<(PathBuf, Arc<PackageJson>), ResolveError> {
+        let mut ctx = Ctx::default();
+        let (package_root, package_json) =
+            self.find_package_root(directory.as_ref(), package_name, &mut ctx)?;
+        Ok((self.load_realpath(&package_root, &mut ctx)?, package_json))
+    }
+
+    /// Resolve the `subpaths` of the installed package `package_name` as seen from `directory`,
+    /// e.g. `"."` and `"./utils"`, against its `exports` or its files when it has none.
+    ///
+    /// The package is located once, like [ResolverImpl::resolve_package_root], instead of once per
+    /// subpath as `resolve(directory, "package_name/subpath")` would. `condition_names` replace
+    /// [ResolveOptions::condition_names] when given. Subpaths matched by an `exports` pattern
+    /// such as `"./features/*"` must be listed one by one, e.g. `"./features/a"`.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolverImpl::resolve_package_root] for locating the package. The errors of each
+    ///   subpath are reported in its [PackageSubpathResolution::resolution].
+    pub fn resolve_package_entries<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        package_name: &str,
+        subpaths: &[&str],
+        condition_names: Option<&[String]>,
+    ) -> Result<Vec<PackageSubpathResolution>, ResolveError> {
+        let directory = directory.as_ref();
+        let mut ctx = Ctx::default();
+        let (package_root, package_json) =
+            self.find_package_root(directory, package_name, &mut ctx)?;
+        let has_exports =
+            package_json.exports_fields(&self.options.exports_fields).next().is_some();
+        let condition_names = condition_names.map(Arc::from);
+        Ok(subpaths
+            .iter()
+            .map(|&subpath| {
+                let mut ctx = Ctx { condition_names: condition_names.clone(), ..Ctx::default() };
+                let resolution = self.load_package_entry(
+                    directory,
+                    &package_root,
+                    package_name,
+                    subpath,
+                    has_exports,
+                    &mut ctx,
+                );
+                PackageSubpathResolution { subpath: subpath.to_string(), resolution }
+            })
+            .collect())
+    }
+
+    /// Resolve the executable `bin_name` of the installed package `package_name` as seen from
+    /// `directory`, e.g. `typescript/bin/tsc` for `("typescript", Some("tsc"))`, from the
+    /// `"bin"` field of its package.json.
+    ///
+    /// Without `bin_name`, the executable named after the package is taken, or the only one. The
+    /// package is located like [ResolverImpl::resolve_package_root] and the returned path is
+    /// canonicalized when [ResolveOptions::symlinks] is enabled.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolverImpl::resolve_package_root] for locating the package.
+    /// * [ResolveError::NotFound] when the package has no such executable or its file does not
+    ///   exist.
+    pub fn resolve_bin<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        package_name: &str,
+        bin_name: Option<&str>,
+    ) -> Result<PathBuf, ResolveError> {
+        let mut ctx = Ctx::default();
+        let (package_root, package_json) =
+            self.find_package_root(directory.as_ref(), package_name, &mut ctx)?;
+        let not_found = || {
+            let bin = bin_name.map_or_else(String::new, |name| format!(" {name}"));
+            ResolveError::NotFound(format!("{package_name} bin{bin}"))
+        };
+        let path = package_json
+            .bin()
+            .and_then(|bin| bin.path(package_json.name().unwrap_or(package_name), bin_name))
+            .ok_or_else(not_found)?;
+        let cached_path = package_root.normalize_with(path, &self.cache);
+        if !self.is_file_impl(&cached_path, &mut ctx) {
+            return Err(not_found());
+        }
+        self.load_realpath(&cached_path, &mut ctx)
+    }
+
+    fn load_package_entry(
+        &self,
+        directory: &Path,
+        package_root: &CachedPath,
+        package_name: &str,
+        subpath: &str,
+        has_exports: bool,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution, ResolveError> {
+        let specifier = format!("{package_name}{}", subpath.strip_prefix('.').unwrap_or(subpath));
+        if subpath != "." && !subpath.starts_with("./") {
+            return Err(ResolveError::NotFound(specifier));
+        }
+        ctx.with_fully_specified(self.options.fully_specified);
+        let resolved = if has_exports {
+            self.load_package_exports(&specifier, &subpath[1..], package_root, None, ctx)?
+        } else {
+            // As in `load_module_directory`, packages without `exports` are probed.
+            if !self.options.fully_specified_applies_to_node_modules {
+                ctx.with_fully_specified(false);
+            }
+            match self.load_types_versions(package_root, &subpath[1..], None, ctx)? {
+                Some(path) => Some(path),
+                None => self.load_module_directory_file_or_directory(
+                    package_root,
+                    subpath,
+                    ".",
+                    None,
+                    ctx,
+                )?,
+            }
+        };
+        let Some(cached_path) = resolved else { return Err(ResolveError::NotFound(specifier)) };
+        self.resolution(directory, &specifier, &cached_path, ctx)
+    }
+
+    /// The unresolved root of `package_name`, see [ResolverImpl::resolve_package_root].
+    fn find_package_root(
+        &self,
+        directory: &Path,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> Result<(CachedPath, Arc<PackageJson>), ResolveError> {
+        let (name, subpath) = Self::parse_package_specifier(package_name);
+        if name.is_empty()
+            || !subpath.is_empty()
+            || name.starts_with(['.', '#'])
+            || Path::new(name).is_absolute()
+        {
+            return Err(ResolveError::NotFound(package_name.to_string()));
+        }
+        let cached_path = self.cache.value(directory);
+
+        #[cfg(feature = "yarn_pnp")]
+        if self.options.yarn_pnp
+            && let Some(package_root) = self.load_pnp_package_root(&cached_path, name, ctx)?
+        {
+            return Ok(package_root);
+        }
+
+        for module_name in &self.options.modules {
+            if Path::new(module_name).is_absolute() {
+                let module_directory = self.cache.value(Path::new(module_name));
+                if self.is_dir_impl(&module_directory, ctx)
+                    && let Some(package_root) =
+                        self.load_package_root(&module_directory, name, ctx)?
+                {
+                    return Ok(package_root);
+                }
+                continue;
+            }
+            let mut cursor = self.module_directory_cursor(&cached_path, module_name, ctx);
+            while let Some(module_directory) =
+                self.next_module_directory(&mut cursor, module_name, ctx)
+            {
+                if let Some(package_root) = self.load_package_root(&module_directory, name, ctx)? {
+                    return Ok(package_root);
+                }
+            }
+        }
+        Err(ResolveError::NotFound(package_name.to_string()))
+    }
+
+    /// Whether `path` is a file, as seen by the resolver.
+    ///
+    /// The answer comes from the shared cache and the configured [FileSystem], symlinks are
+    /// followed according to [ResolveOptions::symlinks].
+    pub fn is_file<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.is_file_with_context(path, &mut SinkOnly::default())
+    }
+
+    /// [ResolverImpl::is_file], recording `path` into the file dependencies of `resolve_context`
+    /// when it is a file and into its missing dependencies otherwise.
+    pub fn is_file_with_context<P: AsRef<Path>, C: DependencyCollector>(
+        &self,
+        path: P,
+        resolve_context: &mut C,
+    ) -> bool {
+        let cached_path = self.cache.value(path.as_ref());
+        self.with_dependencies(resolve_context, |ctx| self.is_file_impl(&cached_path, ctx))
+    }
+
+    /// Whether `path` is a directory, as seen by the resolver.
+    ///
+    /// The answer comes from the shared cache and the configured [FileSystem], symlinks are
+    /// followed according to [ResolveOptions::symlinks].
+    pub fn is_dir<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.is_dir_with_context(path, &mut SinkOnly::default())
+    }
+
+    /// [ResolverImpl::is_dir], recording `path` into the missing dependencies of
+    /// `resolve_context` when it does not exist.
+    ///
+    /// Like the directories probed during resolution, an existing path is not recorded.
+    pub fn is_dir_with_context<P: AsRef<Path>, C: DependencyCollector>(
+        &self,
+        path: P,
+        resolve_context: &mut C,
+    ) -> bool {
+        let cached_path = self.cache.value(path.as_ref());
+        self.with_dependencies(resolve_context, |ctx| self.is_dir_impl(&cached_path, ctx))
+    }
+
+    /// Resolve all symbolic links of `path` through the shared cache, regardless of
+    /// [ResolveOptions::symlinks].
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::IOError] when `path` does not exist or the configured [FileSystem] fails
+    ///   to read a link.
+    pub fn canonicalize<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf, ResolveError> {
+        self.canonicalize_with_context(path, &mut SinkOnly::default())
+    }
+
+    /// [ResolverImpl::canonicalize], recording `path` and the symlinks traversed into the file
+    /// dependencies of `resolve_context` when it exists and `path` into its missing dependencies
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolverImpl::canonicalize]
+    pub fn canonicalize_with_context<P: AsRef<Path>, C: DependencyCollector>(
+        &self,
+        path: P,
+        resolve_context: &mut C,
+    ) -> Result<PathBuf, ResolveError> {
+        let path = path.as_ref();
+        let cached_path = self.cache.value(path);
+        self.with_dependencies(resolve_context, |ctx| {
+            if !self.cache.exists(&cached_path) {
+                ctx.add_missing_dependency(&cached_path);
+                let message = format!("{} does not exist", path.display());
+                return Err(std::io::Error::new(std::io::ErrorKind::NotFound, message).into());
+            }
+            let result = self.cache.canonicalize(&cached_path, ctx);
+            ctx.add_file_dependency(&cached_path);
+            result
+        })
+    }
+
+    /// Run `f` with a dependency tracking [Ctx] and move the recorded dependencies and timings
+    /// into `resolve_context`.
+    fn with_dependencies<C: DependencyCollector, T>(
+        &self,
+        resolve_context: &mut C,
+        f: impl FnOnce(&mut Ctx) -> T,
+    ) -> T {
         let mut ctx = Ctx::default();
         ctx.init_file_dependencies();
-        let result = self.resolve_tracing(directory.as_ref(), specifier, tsconfig, &mut ctx);
-        if let Some(deps) = &mut ctx.file_dependencies {
-            resolve_context.file_dependencies.extend(deps.drain(..));
+        ctx.timings = resolve_context.timings_mut().take();
+        ctx.stats = resolve_context.stats_mut().take();
+        let result = f(&mut ctx);
+        *resolve_context.timings_mut() = ctx.timings.take();
+        *resolve_context.stats_mut() = ctx.stats.take();
+        let file_dependencies = ctx.file_dependencies.into_iter().flatten();
+        let missing_dependencies = ctx.missing_dependencies.into_iter().flatten();
+        for path in file_dependencies {
+            self.add_dependency(resolve_context, DependencyKind::File, path);
         }
-        if let Some(deps) = &mut ctx.missing_dependencies {
-            resolve_context.missing_dependencies.extend(deps.drain(..));
+        for path in missing_dependencies {
+            self.add_dependency(resolve_context, DependencyKind::Missing, path);
         }
         result
     }
 
+    /// Record `path` into `resolve_context` and stream it to [ResolveOptions::dependency_sink].
+    fn add_dependency<C: DependencyCollector>(
+        &self,
+        resolve_context: &mut C,
+        kind: DependencyKind,
+        path: CachedPath,
+    ) {
+        if let Some(sink) = &self.options.dependency_sink {
+            (sink.0)(kind, path.path());
+        }
+        resolve_context.add_dependency(kind, path);
+    }
+
+    /// The directory of `package_name` inside `module_directory`, if it has a `package.json`.
+    fn load_package_root(
+        &self,
+        module_directory: &CachedPath,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Option<(CachedPath, Arc<PackageJson>)>, ResolveError> {
+        let cached_path = module_directory.normalize_with(package_name, &self.cache);
+        if !self.is_dir_impl(&cached_path, ctx) {
+            return Ok(None);
+        }
+        let Some(package_json) = self.cache.get_package_json(&cached_path, &self.options, ctx)?
+        else {
+            return Ok(None);
+        };
+        Ok(Some((cached_path, package_json)))
+    }
+
     /// Wrap `resolve_impl` with `tracing` information
     fn resolve_tracing(
         &self,
@@ -331,29 +1138,108 @@ impl ResolverImpl {
 
     fn resolve_impl(
         &self,
-        path: &Path,
+        directory: &Path,
         specifier: &str,
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> Result<Resolution, ResolveError> {
+        let cached_path = self.require_from(directory, specifier, tsconfig, ctx)?;
+        if let Some(data_url) = ctx.data_url.take() {
+            return Ok(self.data_url_resolution(specifier, data_url, ctx));
+        }
+        self.resolution(directory, specifier, &cached_path, ctx)
+    }
+
+    /// [ResolverImpl::require] from `directory` with the options set up for a resolution: the
+    /// path before canonicalization, or [Ctx::data_url] for a `data:` URL.
+    fn require_from(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> Result<CachedPath, ResolveError> {
         ctx.with_fully_specified(self.options.fully_specified);
 
-        let cached_path = self.cache.value(path);
-        let cached_path = self.require(&cached_path, specifier, tsconfig, ctx)?;
-        let path = self.load_realpath(&cached_path)?;
+        let cached_path = self.cache.value(directory);
+        if let Some(condition_names) = &ctx.condition_names_override {
+            ctx.condition_names = Some(Arc::clone(condition_names));
+        } else if let Some(provider) = &self.options.condition_names_provider {
+            ctx.condition_names = self.cache.condition_names(&cached_path, provider);
+        }
+        self.require(&cached_path, specifier, tsconfig, ctx).map_err(|error| {
+            match (error, ctx.path_too_long.take()) {
+                (ResolveError::NotFound(_), Some(path)) => ResolveError::PathTooLong(path),
+                (ResolveError::External { resolved_name, .. }, _) => {
+                    ResolveError::External { specifier: specifier.to_string(), resolved_name }
+                }
+                (error, _) => error,
+            }
+        })
+    }
+
+    /// The [Resolution] of `specifier` resolved to `cached_path`.
+    fn resolution(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        cached_path: &CachedPath,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution, ResolveError> {
+        let timer = ctx.start_phase(Phase::Canonicalize);
+        let path = self.load_realpath(cached_path, ctx);
+        ctx.stop_phase(timer);
+        let path = path?;
+        self.check_forbidden_extensions(&path)?;
 
-        let package_json = self.find_package_json_for_a_package(&cached_path, ctx)?;
-        let module_type = self.esm_file_format(&cached_path, ctx)?;
+        let package_json = self.find_package_json_for_a_package(cached_path, ctx)?;
+        let module_type = self.esm_file_format(cached_path, ctx)?;
+        let file_metadata = self.cache.file_metadata(cached_path, self.options.symlinks);
+
+        let probed = self.options.extension_probe_report && ctx.probed;
+        if probed {
+            tracing::warn!(
+                importer = ?directory,
+                specifier = specifier,
+                resolved = ?path,
+                "resolved by extension probing or main file fallback"
+            );
+        }
 
         Ok(Resolution {
             path,
+            specifier: specifier.to_string(),
+            aliased_specifier: ctx.aliased_specifier.take(),
             query: ctx.query.take(),
             fragment: ctx.fragment.take(),
             package_json,
             module_type,
+            probed,
+            via_node_addons: ctx.via_node_addons,
+            file_metadata,
+            package_entry: ctx.package_entry.take(),
+            file_directory_ambiguity: ctx.file_directory_ambiguity.take(),
         })
     }
 
+    /// A [Resolution] of a `data:` URL, which skips the file system.
+    fn data_url_resolution(&self, specifier: &str, data_url: DataUrl, ctx: &mut Ctx) -> Resolution {
+        Resolution {
+            path: data_url.path,
+            specifier: specifier.to_string(),
+            aliased_specifier: ctx.aliased_specifier.take(),
+            query: ctx.query.take(),
+            fragment: ctx.fragment.take(),
+            package_json: None,
+            module_type: data_url.module_type.filter(|_| self.options.module_type),
+            probed: false,
+            via_node_addons: false,
+            file_metadata: None,
+            package_entry: None,
+            file_directory_ambiguity: None,
+        }
+    }
+
     fn find_package_json_for_a_package(
         &self,
         cached_path: &CachedPath,
@@ -367,7 +1253,7 @@ impl ResolverImpl {
             let mut last = None;
             // Go up directories when the querying path is not a directory
             let mut cp = cached_path.clone();
-            if !self.is_dir(&cp, ctx)
+            if !self.is_dir_impl(&cp, ctx)
                 && let Some(cv) = cp.parent(&self.cache)
             {
                 cp = cv;
@@ -398,12 +1284,12 @@ impl ResolverImpl {
     }
 
     /// [`Cache::is_file`] using this resolver's [`ResolveOptions::symlinks`] policy.
-    fn is_file(&self, path: &CachedPath, ctx: &mut Ctx) -> bool {
+    fn is_file_impl(&self, path: &CachedPath, ctx: &mut Ctx) -> bool {
         self.cache.is_file(path, self.options.symlinks, ctx)
     }
 
     /// [`Cache::is_dir`] using this resolver's [`ResolveOptions::symlinks`] policy.
-    fn is_dir(&self, path: &CachedPath, ctx: &mut Ctx) -> bool {
+    fn is_dir_impl(&self, path: &CachedPath, ctx: &mut Ctx) -> bool {
         self.cache.is_dir(path, self.options.symlinks, ctx)
     }
 
@@ -420,7 +1306,7 @@ impl ResolverImpl {
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> Result<CachedPath, ResolveError> {
-        ctx.test_for_infinite_recursion()?;
+        ctx.test_for_infinite_recursion(self.options.max_resolve_depth, specifier, cached_path)?;
 
         // enhanced-resolve: parse
         let (parsed, try_fragment_as_path) =
@@ -439,28 +1325,133 @@ impl ResolverImpl {
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> Result<CachedPath, ResolveError> {
-        // tsconfig-paths
-        if let Some(path) =
-            self.resolve_tsconfig_compiler_options(cached_path, specifier, tsconfig, ctx)?
-        {
-            return Ok(path);
+        let mut error = None;
+        #[cfg_attr(
+            target_arch = "wasm32",
+            expect(
+                unused_mut,
+                reason = "file:// specifiers are only converted on non-wasm targets"
+            )
+        )]
+        let mut request = Cow::Borrowed(specifier);
+        for phase in REQUEST_PHASES {
+            match phase {
+                RequestPhase::ImportMap => {
+                    if let Some(path) =
+                        self.load_import_map(cached_path, specifier, tsconfig, ctx)?
+                    {
+                        return Ok(path);
+                    }
+                }
+                // tsconfig-paths
+                RequestPhase::TsconfigPaths => {
+                    let timer = ctx.start_phase(Phase::TsconfigPaths);
+                    let resolved = self.resolve_tsconfig_compiler_options(
+                        cached_path,
+                        specifier,
+                        tsconfig,
+                        ctx,
+                    );
+                    ctx.stop_phase(timer);
+                    if let Some(path) = resolved? {
+                        return Ok(path);
+                    }
+                }
+                // enhanced-resolve: try alias
+                RequestPhase::Alias => {
+                    let timer = ctx.start_phase(Phase::Alias);
+                    let resolved =
+                        self.load_alias(cached_path, specifier, &self.alias, tsconfig, ctx);
+                    ctx.stop_phase(timer);
+                    if let Some(path) = resolved? {
+                        return Ok(path);
+                    }
+                }
+                RequestPhase::Specifier => {
+                    if data_url::is_data_url(specifier) {
+                        match data_url::parse_data_url(specifier) {
+                            Ok(data_url) => {
+                                let path = self.cache.value(&data_url.path);
+                                ctx.data_url = Some(data_url);
+                                return Ok(path);
+                            }
+                            Err(err) => {
+                                error = Some(err);
+                                continue;
+                            }
+                        }
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        request = file_url::resolve_file_protocol(specifier)?;
+                    }
+                    match self.require_specifier(cached_path, &request, tsconfig, ctx) {
+                        Ok(path) => return Ok(path),
+                        Err(err) if err.is_ignore() || err.is_external() => return Err(err),
+                        Err(err) => error = Some(err),
+                    }
+                }
+                // enhanced-resolve: try fallback
+                RequestPhase::Fallback => {
+                    if let Some(err) = error.take() {
+                        let timer = ctx.start_phase(Phase::Alias);
+                        let resolved =
+                            self.load_alias(cached_path, &request, &self.fallback, tsconfig, ctx);
+                        ctx.stop_phase(timer);
+                        return resolved?.ok_or_else(|| {
+                            self.ignore_missing_optional_peer(cached_path, &request, err, ctx)
+                        });
+                    }
+                }
+            }
         }
+        Err(error.unwrap_or_else(|| ResolveError::NotFound(specifier.to_string())))
+    }
 
-        // enhanced-resolve: try alias
-        if let Some(path) = self.load_alias(cached_path, specifier, &self.alias, tsconfig, ctx)? {
-            return Ok(path);
+    /// `error` as [ResolveError::Ignored] when the bare `specifier` was not found and names an
+    /// optional dependency of the package scope of `cached_path`, see
+    /// [ResolveOptions::ignore_missing_optional_peers].
+    fn ignore_missing_optional_peer(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        error: ResolveError,
+        ctx: &mut Ctx,
+    ) -> ResolveError {
+        let is_bare = specifier.as_bytes().first() != Some(&b'#')
+            && matches!(Path::new(specifier).components().next(), Some(Component::Normal(_)));
+        if !self.options.ignore_missing_optional_peers
+            || !is_bare
+            || !matches!(error, ResolveError::NotFound(_))
+        {
+            return error;
         }
+        let (package_name, _) = Self::parse_package_specifier(specifier);
+        match self.cache.find_package_json(cached_path, &self.options, ctx) {
+            Ok(Some(package_json)) if package_json.is_optional_dependency(package_name) => {
+                ResolveError::Ignored(cached_path.to_path_buf())
+            }
+            _ => error,
+        }
+    }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        let specifier = file_url::resolve_file_protocol(specifier)?;
-        #[cfg(not(target_arch = "wasm32"))]
-        let specifier = specifier.as_ref();
-
-        let result = match Path::new(&specifier).components().next() {
+    fn require_specifier(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> Result<CachedPath, ResolveError> {
+        match Path::new(specifier).components().next() {
             // 2. If X begins with '/'
-            Some(Component::RootDir | Component::Prefix(_)) => {
+            Some(Component::RootDir) => {
                 self.require_absolute(cached_path, specifier, tsconfig, ctx)
             }
+            // Windows drive letters, UNC and DOS device paths.
+            Some(Component::Prefix(_)) => {
+                let specifier = crate::windows::normalize_specifier(specifier)?;
+                self.require_absolute(cached_path, &specifier, tsconfig, ctx)
+            }
             // 3. If X is '.' or begins with './' or '/' or '../'
             Some(Component::CurDir | Component::ParentDir) => {
                 self.require_relative(cached_path, specifier, tsconfig, ctx)
@@ -470,6 +1461,15 @@ impl ResolverImpl {
                 self.require_hash(cached_path, specifier, tsconfig, ctx)
             }
             _ => {
+                // `node:` is left to `require_core`.
+                if let Some(scheme) = crate::specifier::url_scheme(specifier)
+                    && scheme != "node"
+                {
+                    return Err(ResolveError::UnsupportedUrlScheme {
+                        scheme: scheme.to_string(),
+                        specifier: specifier.to_string(),
+                    });
+                }
                 // 1. If X is a core module,
                 //   a. return the core module
                 //   b. STOP
@@ -480,15 +1480,7 @@ impl ResolverImpl {
                 // Set resolved the result of PACKAGE_RESOLVE(specifier, parentURL).
                 self.require_bare(cached_path, specifier, tsconfig, ctx)
             }
-        };
-
-        result.or_else(|err| {
-            if err.is_ignore() {
-                return Err(err);
-            }
-            // enhanced-resolve: try fallback
-            self.load_alias(cached_path, specifier, &self.fallback, tsconfig, ctx)?.ok_or(err)
-        })
+        }
     }
 
     // PACKAGE_RESOLVE(packageSpecifier, parentURL)
@@ -497,14 +1489,15 @@ impl ResolverImpl {
     fn require_core(&self, specifier: &str) -> Result<(), ResolveError> {
         if self.options.builtin_modules {
             let is_runtime_module = specifier.starts_with("node:");
-            if is_runtime_module
-                || nodejs_built_in_modules::BUILTINS.binary_search(&specifier).is_ok()
-            {
-                let resolved = if is_runtime_module {
-                    specifier.to_string()
-                } else {
-                    format!("node:{specifier}")
-                };
+            if is_runtime_module {
+                if !nodejs_built_in_modules::is_nodejs_builtin_module(specifier) {
+                    return Err(ResolveError::UnknownBuiltin(specifier.to_string()));
+                }
+                let resolved = specifier.to_string();
+                return Err(ResolveError::Builtin { resolved, is_runtime_module });
+            }
+            if nodejs_built_in_modules::BUILTINS.binary_search(&specifier).is_ok() {
+                let resolved = format!("node:{specifier}");
                 return Err(ResolveError::Builtin { resolved, is_runtime_module });
             }
         }
@@ -525,21 +1518,37 @@ impl ResolverImpl {
                 .next()
                 .is_some_and(|c| matches!(c, Component::RootDir | Component::Prefix(_)))
         );
-        if !self.options.prefer_relative
-            && self.options.prefer_absolute
-            && let Ok(path) =
-                self.load_package_self_or_node_modules(cached_path, specifier, tsconfig, ctx)
-        {
-            return Ok(path);
-        }
-        if let Some(path) = self.load_roots(cached_path, specifier, tsconfig, ctx) {
-            return Ok(path);
-        }
-        // 2. If X begins with '/'
-        //   a. set Y to be the file system root
-        let path = self.cache.value(Path::new(specifier.trim_end_matches('/')));
-        if let Some(path) = self.load_as_file_or_directory(&path, specifier, tsconfig, ctx)? {
-            return Ok(path);
+        for phase in ABSOLUTE_PHASES {
+            match phase {
+                AbsolutePhase::PreferAbsolute => {
+                    if !self.options.prefer_relative
+                        && self.options.prefer_absolute
+                        && let Ok(path) = self.load_package_self_or_node_modules(
+                            cached_path,
+                            specifier,
+                            tsconfig,
+                            ctx,
+                        )
+                    {
+                        return Ok(path);
+                    }
+                }
+                AbsolutePhase::Roots => {
+                    if let Some(path) = self.load_roots(cached_path, specifier, tsconfig, ctx) {
+                        return Ok(path);
+                    }
+                }
+                // 2. If X begins with '/'
+                //   a. set Y to be the file system root
+                AbsolutePhase::FileSystem => {
+                    let path = self.cache.value(Path::new(specifier.trim_end_matches('/')));
+                    if let Some(path) =
+                        self.load_as_file_or_directory(&path, specifier, tsconfig, ctx)?
+                    {
+                        return Ok(path);
+                    }
+                }
+            }
         }
         Err(ResolveError::NotFound(specifier.to_string()))
     }
@@ -599,10 +1608,11 @@ impl ResolverImpl {
                 .next()
                 .is_some_and(|c| matches!(c, Component::Normal(_)))
         );
-        if self.options.prefer_relative
-            && let Ok(path) = self.require_relative(cached_path, specifier, tsconfig, ctx)
-        {
-            return Ok(path);
+        if self.external.any_entry_matches(specifier) {
+            return Err(ResolveError::External {
+                specifier: specifier.to_string(),
+                resolved_name: specifier.to_string(),
+            });
         }
         self.load_package_self_or_node_modules(cached_path, specifier, tsconfig, ctx)
     }
@@ -622,7 +1632,16 @@ impl ResolverImpl {
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> Result<(Specifier<'s>, Option<CachedPath>), ResolveError> {
-        let parsed = Specifier::parse(specifier).map_err(ResolveError::Specifier)?;
+        // A `data:` URL keeps its `?` and `#` in the data, and has no path to try.
+        if data_url::is_data_url(specifier) {
+            let parsed = Specifier::parse_data_url(specifier);
+            ctx.with_query_fragment(None, parsed.fragment);
+            return Ok((parsed, None));
+        }
+        let timer = ctx.start_phase(Phase::Parse);
+        let parsed = Specifier::parse(specifier).map_err(ResolveError::Specifier);
+        ctx.stop_phase(timer);
+        let parsed = parsed?;
         ctx.with_query_fragment(parsed.query, parsed.fragment);
 
         // There is an edge-case where a request with # can be a path or a fragment -> try both
@@ -646,20 +1665,66 @@ impl ResolverImpl {
         ctx: &mut Ctx,
     ) -> Result<CachedPath, ResolveError> {
         let (package_name, subpath) = Self::parse_package_specifier(specifier);
-        if subpath.is_empty() {
-            ctx.with_fully_specified(false);
-        }
-        // 5. LOAD_PACKAGE_SELF(X, dirname(Y))
-        if let Some(path) = self.load_package_self(cached_path, specifier, tsconfig, ctx)? {
-            return Ok(path);
-        }
-        // 6. LOAD_NODE_MODULES(X, dirname(Y))
-        if let Some(path) =
-            self.load_node_modules(cached_path, specifier, package_name, subpath, tsconfig, ctx)?
-        {
-            return Ok(path);
+        for phase in BARE_PHASES {
+            match phase {
+                // Not reachable from `require_absolute`, which only gets here without `prefer_relative`.
+                BarePhase::PreferRelative => {
+                    if self.options.prefer_relative
+                        && let Ok(path) =
+                            self.require_relative(cached_path, specifier, tsconfig, ctx)
+                    {
+                        return Ok(path);
+                    }
+                }
+                // 5. LOAD_PACKAGE_SELF(X, dirname(Y))
+                BarePhase::PackageSelf => {
+                    if subpath.is_empty() {
+                        ctx.with_fully_specified(false);
+                    }
+                    if let Some(path) =
+                        self.load_package_self(cached_path, specifier, tsconfig, ctx)?
+                    {
+                        return Ok(path);
+                    }
+                }
+                // 6. LOAD_NODE_MODULES(X, dirname(Y))
+                BarePhase::NodeModules => {
+                    if subpath.is_empty() {
+                        ctx.with_fully_specified(false);
+                    }
+                    if let Some(path) = self.load_node_modules(
+                        cached_path,
+                        specifier,
+                        package_name,
+                        subpath,
+                        tsconfig,
+                        ctx,
+                    )? {
+                        return Ok(path);
+                    }
+                    if let Some(path) = self.load_node_modules_relative_legacy(
+                        cached_path,
+                        specifier,
+                        tsconfig,
+                        ctx,
+                    )? {
+                        return Ok(path);
+                    }
+                }
+            }
         }
 
+        // 7. THROW "not found"
+        Err(ResolveError::NotFound(specifier.to_string()))
+    }
+
+    fn load_node_modules_relative_legacy(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
         // TODO: add a new option for this legacy behavior?
         // abnormal relative specifier like `jest-runner-../../..`
         // which only works with `require` not ESM
@@ -685,12 +1750,10 @@ impl ResolverImpl {
                     ctx,
                 )?
             {
-                return Ok(path);
+                return Ok(Some(path));
             }
         }
-
-        // 7. THROW "not found"
-        Err(ResolveError::NotFound(specifier.to_string()))
+        Ok(None)
     }
 
     /// LOAD_PACKAGE_IMPORTS(X, DIR)
@@ -703,17 +1766,65 @@ impl ResolverImpl {
     ) -> ResolveResult {
         // 1. Find the closest package scope SCOPE to DIR.
         // 2. If no scope was found, return.
-        let Some(package_json) = self.cache.find_package_json(cached_path, &self.options, ctx)?
-        else {
-            return Ok(None);
-        };
-        // 3. If the SCOPE/package.json "imports" is null or undefined, return.
-        // 4. let MATCH = PACKAGE_IMPORTS_RESOLVE(X, pathToFileURL(SCOPE), ["node", "require"]) defined in the ESM resolver.
-        if let Some(path) = self.package_imports_resolve(specifier, &package_json, tsconfig, ctx)? {
-            // 5. RESOLVE_ESM_MATCH(MATCH).
-            return self.resolve_esm_match(specifier, &path, tsconfig, ctx);
+        let package_json = self.find_package_scope(cached_path, ctx)?;
+        let mut not_defined = None;
+        if let Some(package_json) = &package_json {
+            // 3. If the SCOPE/package.json "imports" is null or undefined, return.
+            // 4. let MATCH = PACKAGE_IMPORTS_RESOLVE(X, pathToFileURL(SCOPE), ["node", "require"]) defined in the ESM resolver.
+            match self.package_imports_resolve(specifier, package_json, tsconfig, ctx) {
+                // 5. RESOLVE_ESM_MATCH(MATCH).
+                Ok(Some(path)) => return self.resolve_esm_match(specifier, &path, tsconfig, ctx),
+                Ok(None) => {}
+                Err(err @ ResolveError::PackageImportNotDefined(..))
+                    if !self.options.imports_fallback_scopes.is_empty() =>
+                {
+                    not_defined = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
         }
-        Ok(None)
+        self.load_package_imports_fallback(specifier, package_json.as_deref(), tsconfig, ctx)?
+            .map_or_else(
+                || not_defined.map_or(Ok(None), Err),
+                |path| self.resolve_esm_match(specifier, &path, tsconfig, ctx),
+            )
+    }
+
+    /// Tries the `imports` of [ResolveOptions::imports_fallback_scopes] in order for a specifier
+    /// which the closest package.json does not map.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::PackageImportNotDefined] naming the closest package.json when a fallback
+    ///   scope defines `imports` but none of them maps the specifier.
+    fn load_package_imports_fallback(
+        &self,
+        specifier: &str,
+        closest: Option<&PackageJson>,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> Result<Option<CachedPath>, ResolveError> {
+        let mut not_defined = None;
+        for scope in &self.options.imports_fallback_scopes {
+            let scope = self.cache.value(scope);
+            let Some(package_json) = self.cache.get_package_json(&scope, &self.options, ctx)?
+            else {
+                continue;
+            };
+            if closest.is_some_and(|closest| closest.path() == package_json.path()) {
+                continue;
+            }
+            match self.package_imports_resolve(specifier, &package_json, tsconfig, ctx) {
+                Ok(Some(path)) => return Ok(Some(path)),
+                Ok(None) => {}
+                Err(ResolveError::PackageImportNotDefined(specifier, path)) => {
+                    let path = closest.map_or(path, |closest| closest.path().to_path_buf());
+                    not_defined = Some(ResolveError::PackageImportNotDefined(specifier, path));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        not_defined.map_or(Ok(None), Err)
     }
 
     fn load_as_file(
@@ -726,7 +1837,7 @@ impl ResolverImpl {
         if let Some(path) = self.load_extension_alias(cached_path, tsconfig, ctx)? {
             return Ok(Some(path));
         }
-        if self.options.enforce_extension.is_disabled() {
+        if self.options.enforce_extension.is_disabled() || ctx.directory_request {
             // 1. If X is a file, load X as its file extension format. STOP
             if let Some(path) = self.load_alias_or_file(cached_path, tsconfig, ctx)? {
                 return Ok(Some(path));
@@ -736,9 +1847,27 @@ impl ResolverImpl {
         // 3. If X.json is a file, parse X.json to a JavaScript Object. STOP
         // 4. If X.node is a file, load X.node as binary addon. STOP
         if !ctx.fully_specified {
-            for extension in &self.options.extensions {
+            let extensions = self.extensions(ctx);
+            let directory = self
+                .options
+                .adaptive_extension_probing
+                .then(|| cached_path.parent(&self.cache))
+                .flatten();
+            let listed = directory
+                .as_ref()
+                .map_or(0, |directory| self.listed_extensions(cached_path, directory, extensions));
+            for (index, extension) in extensions.iter().enumerate() {
                 let cached_path = cached_path.add_extension(extension, &self.cache);
-                if let Some(path) = self.load_alias_or_file(&cached_path, tsconfig, ctx)? {
+                let path = if index < listed {
+                    self.load_alias_or_listed_file(&cached_path, tsconfig, ctx)?
+                } else {
+                    self.load_alias_or_file(&cached_path, tsconfig, ctx)?
+                };
+                if let Some(path) = path {
+                    if let Some(directory) = &directory {
+                        self.cache.set_extension_hint(directory, extension, index == 0);
+                    }
+                    ctx.probed = true;
                     return Ok(Some(path));
                 }
             }
@@ -746,42 +1875,62 @@ impl ResolverImpl {
         Ok(None)
     }
 
+    /// The number of [ResolveOptions::extensions] to look up in the listing of `directory`
+    /// instead of stat'ing them, see [ResolveOptions::adaptive_extension_probing].
+    ///
+    /// Those are the extensions before the one last found in `directory`, when `cached_path`
+    /// with that extension is a file. Tried first, it is likely found again, and the listing
+    /// then only has to rule out the extensions which the configured order prefers.
+    fn listed_extensions(
+        &self,
+        cached_path: &CachedPath,
+        directory: &CachedPath,
+        extensions: &[String],
+    ) -> usize {
+        let Some(hint) = self.cache.extension_hint(directory, extensions) else { return 0 };
+        let hinted = cached_path.add_extension(&extensions[hint], &self.cache);
+        // The dependencies are recorded when the configured order reaches the hinted file.
+        if self.cache.is_file(&hinted, self.options.symlinks, &mut Ctx::default()) {
+            hint
+        } else {
+            0
+        }
+    }
+
+    /// [ResolveOptions::extensions] with those of the importer's module format moved ahead of
+    /// those of the other format, see [ResolverImpl::resolve_file].
+    fn extensions(&self, ctx: &Ctx) -> &[String] {
+        let reordered = match ctx.importer_module_type {
+            Some(ModuleType::Module) => self.importer_extensions[0].as_deref(),
+            Some(ModuleType::CommonJs) => self.importer_extensions[1].as_deref(),
+            _ => None,
+        };
+        reordered.unwrap_or(&self.options.extensions)
+    }
+
     fn load_as_directory(
         &self,
         cached_path: &CachedPath,
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> ResolveResult {
+        let mut directories_lib = None;
         // 1. If X/package.json is a file,
         // a. Parse X/package.json, and look for "main" field.
         if let Some(package_json) = self.cache.get_package_json(cached_path, &self.options, ctx)? {
             // b. If "main" is a falsy value, GOTO 2.
-            for main_field in package_json.main_fields(&self.options.main_fields) {
-                // ref https://github.com/webpack/enhanced-resolve/blob/main/lib/MainFieldPlugin.js#L66-L67
-                // `normalize_with` treats a leading `./` (`Component::CurDir`) as a no-op, so a
-                // non-empty relative main field can be borrowed directly, skipping the `format!`
-                // allocation for the common `"main": "index.js"` / `"dist/index.js"` shapes. Only
-                // an empty field (which must become "./" to resolve the directory itself) or an
-                // absolute path (whose `RootDir`/`Prefix` head must stay behind a "./" so it is
-                // joined rather than replacing the package directory) needs the owned form.
-                let main_field = if main_field.is_empty()
-                    || matches!(
-                        Path::new(main_field).components().next(),
-                        Some(Component::RootDir | Component::Prefix(_))
-                    ) {
-                    Cow::Owned(format!("./{main_field}"))
-                } else {
-                    Cow::Borrowed(main_field)
-                };
-
-                // c. let M = X + (json main field)
-                let cached_path = cached_path.normalize_with(main_field.as_ref(), &self.cache);
-                // d. LOAD_AS_FILE(M)
-                if let Some(path) = self.load_as_file(&cached_path, tsconfig, ctx)? {
-                    return Ok(Some(path));
-                }
-                // e. LOAD_INDEX(M)
-                if let Some(path) = self.load_index(&cached_path, tsconfig, ctx)? {
+            let main_fields = ctx.main_fields.clone();
+            let main_fields = main_fields.as_deref().unwrap_or(&self.options.main_fields);
+            for (main_field_name, main_field) in package_json.main_fields(main_fields) {
+                if let Some(path) = self.load_main_field(cached_path, main_field, tsconfig, ctx)? {
+                    // A browser field replacement of the main file is the more specific entry.
+                    if self.options.record_matched_entry && ctx.package_entry.is_none() {
+                        ctx.package_entry = Some(PackageEntryInfo {
+                            kind: PackageEntryKind::Main,
+                            key: main_field_name.to_string(),
+                            conditions: vec![],
+                        });
+                    }
                     return Ok(Some(path));
                 }
             }
@@ -794,20 +1943,99 @@ impl ResolverImpl {
             // * <https://github.com/vitejs/vite/pull/20252>
             // * <https://github.com/nodejs/node/issues/58827>
             if self.options.allow_package_exports_in_directory_resolve {
-                for exports in package_json.exports_fields(&self.options.exports_fields) {
-                    if let Some(path) =
-                        self.package_exports_resolve(cached_path, ".", &exports, tsconfig, ctx)?
-                    {
+                for exports in package_json.exports_fields(&self.options.exports_fields) {
+                    if let Some(path) = self.package_exports_resolve(
+                        cached_path,
+                        ".",
+                        &package_json,
+                        &exports,
+                        tsconfig,
+                        ctx,
+                    )? {
                         return Ok(Some(path));
                     }
                 }
             }
+
+            // Legacy `"directories": { "lib": "lib" }`, opt-in via `use_directories_lib`.
+            // `<lib>/<main_file>` candidates are appended after `LOAD_INDEX(X)`.
+            if self.options.use_directories_lib
+                && let Some(lib) = package_json.directories_lib()
+            {
+                directories_lib = Some(
+                    cached_path.normalize_with(lib.trim_start_matches(SLASH_START), &self.cache),
+                );
+            }
         }
 
         // 2. LOAD_INDEX(X)
-        self.load_index(cached_path, tsconfig, ctx)
+        if let Some(path) = self.load_index(cached_path, tsconfig, ctx)? {
+            return Ok(Some(path));
+        }
+        if let Some(lib) = directories_lib {
+            return self.load_index(&lib, tsconfig, ctx);
+        }
+        Ok(None)
+    }
+
+    /// Load the main field `main_field` of the package at `cached_path`.
+    ///
+    /// A main field pointing to a nested directory (`"browser": "./lib"`) resolves the directory
+    /// as a whole, so `lib/package.json` takes priority over `lib/index.js` like in
+    /// enhanced-resolve.
+    fn load_main_field(
+        &self,
+        cached_path: &CachedPath,
+        main_field: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        // ref https://github.com/webpack/enhanced-resolve/blob/main/lib/MainFieldPlugin.js#L66-L67
+        // `normalize_with` treats a leading `./` (`Component::CurDir`) as a no-op, so a
+        // non-empty relative main field can be borrowed directly, skipping the `format!`
+        // allocation for the common `"main": "index.js"` / `"dist/index.js"` shapes. Only
+        // an empty field (which must become "./" to resolve the directory itself) or an
+        // absolute path (whose `RootDir`/`Prefix` head must stay behind a "./" so it is
+        // joined rather than replacing the package directory) needs the owned form.
+        let main_field = if main_field.is_empty()
+            || matches!(
+                Path::new(main_field).components().next(),
+                Some(Component::RootDir | Component::Prefix(_))
+            ) {
+            Cow::Owned(format!("./{main_field}"))
+        } else {
+            Cow::Borrowed(main_field)
+        };
+
+        // c. let M = X + (json main field)
+        let main_path = cached_path.normalize_with(main_field.as_ref(), &self.cache);
+        // d. LOAD_AS_FILE(M)
+        if let Some(path) = self.load_as_file(&main_path, tsconfig, ctx)? {
+            return Ok(Some(path));
+        }
+        // e. LOAD_INDEX(M)
+        // Only directories below the package are loaded with their own `package.json`, which
+        // keeps `"main": "./"` and `"main": "../pkg"` from recursing.
+        if main_path.path() != cached_path.path()
+            && main_path.path().starts_with(cached_path.path())
+        {
+            return self.load_as_directory(&main_path, tsconfig, ctx);
+        }
+        self.load_index(&main_path, tsconfig, ctx)
     }
 
+    /// Load a relative or absolute `specifier`, which is already joined into `cached_path`.
+    ///
+    /// A trailing slash (`./dir/`) makes the specifier an explicit directory request. Duplicate
+    /// slashes (`.//dir//`) are removed when joining and the query and fragment are stripped
+    /// beforehand, so `./dir/?query` behaves like `./dir/`.
+    ///
+    /// | option                 | `./dir`                   | `./dir/`               |
+    /// | ---------------------- | ------------------------- | ---------------------- |
+    /// | default                | file, then directory      | directory only         |
+    /// | `enforce_extension`    | applies to file and main  | does not apply         |
+    /// | `fully_specified`      | file only                 | not found              |
+    /// | `resolve_to_context`   | the directory itself      | the directory itself   |
     fn load_as_file_or_directory(
         &self,
         cached_path: &CachedPath,
@@ -816,24 +2044,100 @@ impl ResolverImpl {
         ctx: &mut Ctx,
     ) -> ResolveResult {
         if self.options.resolve_to_context {
-            return Ok(self.is_dir(cached_path, ctx).then(|| cached_path.clone()));
+            return Ok(self.is_dir_impl(cached_path, ctx).then(|| cached_path.clone()));
         }
-        if !specifier.ends_with('/')
-            && let Some(path) = self.load_as_file(cached_path, tsconfig, ctx)?
-        {
+        let directory_request = specifier.ends_with('/');
+        if !directory_request && let Some(path) = self.load_as_file(cached_path, tsconfig, ctx)? {
+            if self.options.report_file_directory_ambiguity
+                && self.matches_directory(cached_path, tsconfig, ctx)
+            {
+                Self::report_file_directory_ambiguity(
+                    path.to_path_buf(),
+                    cached_path,
+                    AmbiguityWinner::File,
+                    ctx,
+                );
+            }
             return Ok(Some(path));
         }
-        if self.is_dir(cached_path, ctx)
-            && let Some(path) = self.load_as_directory(cached_path, tsconfig, ctx)?
-        {
-            return Ok(Some(path));
+        // enhanced-resolve: a fully specified request is never resolved as a directory.
+        if !ctx.fully_specified && self.is_dir_impl(cached_path, ctx) {
+            // The main field and main files of an explicit directory request are loaded as is.
+            ctx.directory_request = directory_request;
+            let result = self.load_as_directory(cached_path, tsconfig, ctx);
+            ctx.directory_request = false;
+            if let Some(path) = result? {
+                if self.options.report_file_directory_ambiguity
+                    && !directory_request
+                    && let Some(file) = self.excluded_file(cached_path, ctx)
+                {
+                    Self::report_file_directory_ambiguity(
+                        file,
+                        cached_path,
+                        AmbiguityWinner::Directory,
+                        ctx,
+                    );
+                }
+                return Ok(Some(path));
+            }
         }
         Ok(None)
     }
 
-    fn load_realpath(&self, cached_path: &CachedPath) -> Result<PathBuf, ResolveError> {
+    /// Whether `cached_path` also loads as a directory, checked without recording dependencies,
+    /// see [ResolveOptions::report_file_directory_ambiguity].
+    fn matches_directory(
+        &self,
+        cached_path: &CachedPath,
+        tsconfig: Option<&TsConfig>,
+        ctx: &Ctx,
+    ) -> bool {
+        let mut scratch = Ctx { depth: ctx.depth, ..Ctx::default() };
+        !ctx.fully_specified
+            && self.is_dir_impl(cached_path, &mut scratch)
+            && matches!(self.load_as_directory(cached_path, tsconfig, &mut scratch), Ok(Some(_)))
+    }
+
+    /// The first file candidate of `cached_path` which exists although
+    /// [ResolverImpl::load_as_file] did not load it, see
+    /// [ResolveOptions::report_file_directory_ambiguity].
+    fn excluded_file(&self, cached_path: &CachedPath, ctx: &Ctx) -> Option<PathBuf> {
+        let mut scratch = Ctx::default();
+        std::iter::once(cached_path.clone())
+            .chain(
+                self.extensions(ctx)
+                    .iter()
+                    .map(|extension| cached_path.add_extension(extension, &self.cache)),
+            )
+            .find(|candidate| self.is_file_impl(candidate, &mut scratch))
+            .map(|candidate| candidate.to_path_buf())
+    }
+
+    fn report_file_directory_ambiguity(
+        file: PathBuf,
+        directory: &CachedPath,
+        winner: AmbiguityWinner,
+        ctx: &mut Ctx,
+    ) {
+        let ambiguity = FileDirectoryAmbiguity { file, directory: directory.to_path_buf(), winner };
+        tracing::warn!(
+            file = ?ambiguity.file,
+            directory = ?ambiguity.directory,
+            winner = ?ambiguity.winner,
+            "both a file and a directory match the specifier"
+        );
+        ctx.file_directory_ambiguity = Some(ambiguity);
+    }
+
+    /// The real path of `cached_path` when [ResolveOptions::symlinks] is enabled, recording the
+    /// symlinks traversed into the file dependencies of `ctx`.
+    fn load_realpath(
+        &self,
+        cached_path: &CachedPath,
+        ctx: &mut Ctx,
+    ) -> Result<PathBuf, ResolveError> {
         if self.options.symlinks {
-            self.cache.canonicalize(cached_path)
+            self.cache.canonicalize(cached_path, ctx)
         } else {
             // On Windows, collect from components to normalize forward slashes to backslashes.
             #[cfg(target_os = "windows")]
@@ -872,6 +2176,22 @@ impl ResolverImpl {
         true
     }
 
+    /// [ResolveOptions::forbidden_extensions] for the final resolved path.
+    fn check_forbidden_extensions(&self, path: &Path) -> Result<(), ResolveError> {
+        let Some(file_name) = path.file_name() else { return Ok(()) };
+        let file_name = file_name.as_encoded_bytes();
+        self.options
+            .forbidden_extensions
+            .iter()
+            .find(|extension| file_name.ends_with(extension.as_bytes()))
+            .map_or(Ok(()), |extension| {
+                Err(ResolveError::ForbiddenExtension {
+                    path: path.to_path_buf(),
+                    extension: extension.clone(),
+                })
+            })
+    }
+
     fn load_index(
         &self,
         cached_path: &CachedPath,
@@ -879,14 +2199,18 @@ impl ResolverImpl {
         ctx: &mut Ctx,
     ) -> ResolveResult {
         if !ctx.fully_specified {
+            // Same nesting as enhanced-resolve's `MainFilePlugin`: every extension is tried for a
+            // main file before moving on to the next main file, i.e. `index.ts` wins over `main.js`.
+            let extensions = self.extensions(ctx);
             for main_file in &self.options.main_files {
                 // 1. If X/index.js is a file, load X/index.js as JavaScript text. STOP
                 // 2. If X/index.json is a file, parse X/index.json to a JavaScript object. STOP
                 // 3. If X/index.node is a file, load X/index.node as binary addon. STOP
-                for extension in &self.options.extensions {
+                for extension in extensions {
                     let cached_path =
                         cached_path.add_name_and_extension(main_file, extension, &self.cache);
                     if let Some(path) = self.load_alias_or_file(&cached_path, tsconfig, ctx)? {
+                        ctx.probed = true;
                         return Ok(Some(path));
                     }
                 }
@@ -910,15 +2234,19 @@ impl ResolverImpl {
         }
         // enhanced-resolve: try file as alias.
         // Gate on the raw `OsStr` bytes first so `to_str`'s UTF-8 validation is skipped on this
-        // hot path when no alias key matches; a non-UTF-8 path can't match a string alias anyway.
+        // hot path when no alias key matches.
         if !self.options.alias.is_empty() {
             let path_bytes = cached_path.path().as_os_str().as_encoded_bytes();
-            if self.alias.any_key_matches(path_bytes)
-                && let Some(alias_specifier) = cached_path.path().to_str()
-                && let Some(path) =
+            if self.alias.any_key_matches(path_bytes) {
+                // The matched path becomes the specifier, which must not be lossy converted.
+                let Some(alias_specifier) = cached_path.path().to_str() else {
+                    return Err(ResolveError::NonUtf8Path(cached_path.to_path_buf()));
+                };
+                if let Some(path) =
                     self.load_alias(cached_path, alias_specifier, &self.alias, tsconfig, ctx)?
-            {
-                return Ok(Some(path));
+                {
+                    return Ok(Some(path));
+                }
             }
         }
         Ok(None)
@@ -933,10 +2261,38 @@ impl ResolverImpl {
         if let Some(path) = self.load_browser_field_or_alias(cached_path, tsconfig, ctx)? {
             return Ok(Some(path));
         }
-        if self.is_file(cached_path, ctx) && self.check_restrictions(cached_path.path()) {
-            return Ok(Some(cached_path.clone()));
+        Ok(self.load_file(cached_path, ctx))
+    }
+
+    /// [ResolverImpl::load_alias_or_file] which skips the stat of a file missing from the
+    /// listing of its directory, see [ResolveOptions::adaptive_extension_probing].
+    fn load_alias_or_listed_file(
+        &self,
+        cached_path: &CachedPath,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        if let Some(path) = self.load_browser_field_or_alias(cached_path, tsconfig, ctx)? {
+            return Ok(Some(path));
+        }
+        if !self.cache.may_exist(cached_path) {
+            ctx.add_missing_dependency(cached_path);
+            ctx.add_step("file", cached_path.path(), StepOutcome::NotFound);
+            return Ok(None);
+        }
+        Ok(self.load_file(cached_path, ctx))
+    }
+
+    fn load_file(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> Option<CachedPath> {
+        let timer = ctx.start_phase(Phase::FileProbes);
+        let is_file = self.is_file_impl(cached_path, ctx);
+        ctx.stop_phase(timer);
+        let outcome = if is_file { StepOutcome::Found } else { StepOutcome::NotFound };
+        ctx.add_step("file", cached_path.path(), outcome);
+        if is_file && self.check_restrictions(cached_path.path()) {
+            return Some(cached_path.clone());
         }
-        Ok(None)
+        None
     }
 
     fn load_node_modules(
@@ -947,6 +2303,28 @@ impl ResolverImpl {
         subpath: &str,
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let timer = ctx.start_phase(Phase::NodeModulesWalk);
+        let resolved = self.load_node_modules_impl(
+            cached_path,
+            specifier,
+            package_name,
+            subpath,
+            tsconfig,
+            ctx,
+        );
+        ctx.stop_phase(timer);
+        resolved
+    }
+
+    fn load_node_modules_impl(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        package_name: &str,
+        subpath: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
     ) -> ResolveResult {
         #[cfg(feature = "yarn_pnp")]
         if self.options.yarn_pnp
@@ -958,87 +2336,215 @@ impl ResolverImpl {
         // 1. let DIRS = NODE_MODULES_PATHS(START)
         // 2. for each DIR in DIRS:
         for module_name in &self.options.modules {
-            for cached_path in
-                std::iter::successors(Some(cached_path.clone()), |cp| cp.parent(&self.cache))
-            {
-                // Skip if /path/to/node_modules does not exist
-                if !self.is_dir(&cached_path, ctx) {
-                    continue;
-                }
-
-                let Some(cached_path) = self.get_module_directory(&cached_path, module_name, ctx)
-                else {
-                    continue;
-                };
-                // Optimize node_modules lookup by inspecting whether the package exists
-                // From LOAD_PACKAGE_EXPORTS(X, DIR)
-                // 1. Try to interpret X as a combination of NAME and SUBPATH where the name
-                //    may have a @scope/ prefix and the subpath begins with a slash (`/`).
-                if !package_name.is_empty() {
-                    let cached_path = cached_path.normalize_with(package_name, &self.cache);
-                    // Try foo/node_modules/package_name
-                    if self.is_dir(&cached_path, ctx) {
-                        // a. LOAD_PACKAGE_EXPORTS(X, DIR)
-                        if let Some(path) = self.load_package_exports(
-                            specifier,
-                            subpath,
-                            &cached_path,
-                            tsconfig,
-                            ctx,
-                        )? {
-                            return Ok(Some(path));
-                        }
-                    } else {
-                        // foo/node_modules/package_name is not a directory, so useless to check inside it
-                        if !subpath.is_empty() {
-                            continue;
-                        }
-                        // Skip if the directory lead to the scope package does not exist
-                        // i.e. `foo/node_modules/@scope` is not a directory for `foo/node_modules/@scope/package`
-                        if package_name.starts_with('@')
-                            && let Some(path) = cached_path.parent(&self.cache).as_ref()
-                            && !self.is_dir(path, ctx)
-                        {
-                            continue;
-                        }
-                    }
+            // An absolute module directory is the same for every ancestor, look it up only once.
+            if Path::new(module_name).is_absolute() {
+                let module_directory = self.cache.value(Path::new(module_name));
+                if self.is_dir_impl(&module_directory, ctx)
+                    && let Some(path) = self.load_module_directory(
+                        &module_directory,
+                        specifier,
+                        package_name,
+                        subpath,
+                        tsconfig,
+                        ctx,
+                    )?
+                {
+                    return Ok(Some(path));
                 }
-
-                // Try as file or directory for all other cases
-                // b. LOAD_AS_FILE(DIR/X)
-                // c. LOAD_AS_DIRECTORY(DIR/X)
-
-                let cached_path = cached_path.normalize_with(specifier, &self.cache);
-
-                if self.options.resolve_to_context {
-                    return Ok(self.is_dir(&cached_path, ctx).then(|| cached_path.clone()));
+                continue;
+            }
+            let mut cursor = self.module_directory_cursor(cached_path, module_name, ctx);
+            while let Some(cached_path) = self.next_module_directory(&mut cursor, module_name, ctx)
+            {
+                if let Some(path) = self.load_module_directory(
+                    &cached_path,
+                    specifier,
+                    package_name,
+                    subpath,
+                    tsconfig,
+                    ctx,
+                )? {
+                    return Ok(Some(path));
                 }
+            }
+        }
+        Ok(None)
+    }
 
-                // Only load the file if it is targeting a `X/sub/dir`.
-                if specifier != package_name
-                    && !specifier.ends_with('/')
-                    && let Some(path) = self.load_as_file(&cached_path, tsconfig, ctx)?
+    /// Load `specifier` from a single module directory (`node_modules` or any other name in
+    /// [ResolveOptions::modules]), taking the same steps regardless of the directory name.
+    fn load_module_directory(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        package_name: &str,
+        subpath: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        ctx.count(Counter::NodeModulesDirectory);
+        // Optimize node_modules lookup by inspecting whether the package exists
+        // From LOAD_PACKAGE_EXPORTS(X, DIR)
+        // 1. Try to interpret X as a combination of NAME and SUBPATH where the name
+        //    may have a @scope/ prefix and the subpath begins with a slash (`/`).
+        let mut relax_fully_specified = false;
+        if !package_name.is_empty() {
+            let cached_path = cached_path.normalize_with(package_name, &self.cache);
+            // Try foo/node_modules/package_name
+            if self.is_dir_impl(&cached_path, ctx) {
+                // a. LOAD_PACKAGE_EXPORTS(X, DIR)
+                if let Some(path) =
+                    self.load_package_exports(specifier, subpath, &cached_path, tsconfig, ctx)?
                 {
                     return Ok(Some(path));
                 }
-                // Otherwise just load the directory.
-                // No modern package manager creates `node_modules/X.js`.
-                if self.is_dir(&cached_path, ctx) {
-                    if let Some(path) =
-                        self.load_browser_field_or_alias(&cached_path, tsconfig, ctx)?
-                    {
-                        return Ok(Some(path));
-                    }
-                    if let Some(path) = self.load_as_directory(&cached_path, tsconfig, ctx)? {
-                        return Ok(Some(path));
-                    }
-                // Still need to try to load the file in case there are path aliases.
-                } else if let Some(path) = self.load_as_file(&cached_path, tsconfig, ctx)? {
+                if let Some(path) =
+                    self.load_types_versions(&cached_path, subpath, tsconfig, ctx)?
+                {
                     return Ok(Some(path));
                 }
+                relax_fully_specified = ctx.fully_specified
+                    && !self.options.fully_specified_applies_to_node_modules
+                    && self.cache.get_package_json(&cached_path, &self.options, ctx)?.is_none_or(
+                        |package_json| {
+                            package_json
+                                .exports_fields(&self.options.exports_fields)
+                                .next()
+                                .is_none()
+                        },
+                    );
+            } else {
+                // foo/node_modules/package_name is not a directory, so useless to check inside it
+                if !subpath.is_empty() {
+                    return Ok(None);
+                }
+                // Skip if the directory lead to the scope package does not exist
+                // i.e. `foo/node_modules/@scope` is not a directory for `foo/node_modules/@scope/package`
+                if package_name.starts_with('@')
+                    && let Some(path) = cached_path.parent(&self.cache).as_ref()
+                    && !self.is_dir_impl(path, ctx)
+                {
+                    return Ok(None);
+                }
             }
         }
-        Ok(None)
+
+        // Try as file or directory for all other cases
+        // b. LOAD_AS_FILE(DIR/X)
+        // c. LOAD_AS_DIRECTORY(DIR/X)
+
+        // Extensions and main files are probed inside packages without `exports`, like webpack
+        // only applying `fullySpecified` to the user's own requests.
+        if relax_fully_specified {
+            ctx.with_fully_specified(false);
+        }
+        let resolved = self.load_module_directory_file_or_directory(
+            cached_path,
+            specifier,
+            package_name,
+            tsconfig,
+            ctx,
+        );
+        if relax_fully_specified {
+            ctx.with_fully_specified(true);
+        }
+        resolved
+    }
+
+    /// Load `specifier` as a file or directory inside the module directory `cached_path`.
+    fn load_module_directory_file_or_directory(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        package_name: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let cached_path = cached_path.normalize_with(specifier, &self.cache);
+
+        if self.options.resolve_to_context {
+            return Ok(self.is_dir_impl(&cached_path, ctx).then(|| cached_path.clone()));
+        }
+
+        // Only load the file if it is targeting a `X/sub/dir`.
+        if specifier != package_name
+            && !specifier.ends_with('/')
+            && let Some(path) = self.load_as_file(&cached_path, tsconfig, ctx)?
+        {
+            return Ok(Some(path));
+        }
+        // Otherwise just load the directory.
+        // No modern package manager creates `node_modules/X.js`.
+        if self.is_dir_impl(&cached_path, ctx) {
+            if let Some(path) = self.load_browser_field_or_alias(&cached_path, tsconfig, ctx)? {
+                return Ok(Some(path));
+            }
+            return self.load_as_directory(&cached_path, tsconfig, ctx);
+        }
+        // Still need to try to load the file in case there are path aliases.
+        self.load_as_file(&cached_path, tsconfig, ctx)
+    }
+
+    /// The Yarn PnP manifest for resolving from `cached_path`, inside
+    /// [ResolveOptions::yarn_pnp_root].
+    ///
+    /// This is the closest of [ResolveOptions::pnp_manifest_paths] covering `cached_path`, or the
+    /// manifest found from [ResolveOptions::cwd] when there are none.
+    #[cfg(feature = "yarn_pnp")]
+    fn yarn_pnp_manifest(
+        &self,
+        cached_path: &CachedPath,
+    ) -> Result<Option<Arc<pnp::Manifest>>, ResolveError> {
+        let root = self.options.yarn_pnp_root.as_deref();
+        if self.options.pnp_manifest_paths.is_empty() {
+            return self.cache.get_yarn_pnp_manifest(self.options.cwd.as_deref(), root);
+        }
+        let mut closest: Option<Arc<pnp::Manifest>> = None;
+        for path in &self.options.pnp_manifest_paths {
+            let manifest = self.cache.get_yarn_pnp_manifest_at(path)?;
+            if cached_path.path().starts_with(&manifest.manifest_dir)
+                && root.is_none_or(|root| manifest.manifest_dir.starts_with(root))
+                && closest
+                    .as_ref()
+                    .is_none_or(|closest| manifest.manifest_dir.starts_with(&closest.manifest_dir))
+            {
+                closest = Some(manifest);
+            }
+        }
+        Ok(closest)
+    }
+
+    /// The package directory of `package_name` registered in the Yarn PnP manifest.
+    #[cfg(feature = "yarn_pnp")]
+    fn load_pnp_package_root(
+        &self,
+        cached_path: &CachedPath,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Option<(CachedPath, Arc<PackageJson>)>, ResolveError> {
+        let Some(pnp_manifest) = self.yarn_pnp_manifest(cached_path)? else { return Ok(None) };
+
+        // `resolve_to_unqualified` requires a trailing slash
+        let path = cached_path.path().join("");
+
+        if !self.yarn_pnp_manifest_applies(&pnp_manifest, cached_path, &path, ctx) {
+            return Ok(None);
+        }
+
+        match pnp::resolve_to_unqualified_via_manifest(&pnp_manifest, package_name, &path) {
+            Ok(pnp::Resolution::Resolved(path, _)) => {
+                // Drop the trailing slash of the package location.
+                let cached_path = self.cache.value(&path.normalize());
+                let Some(package_json) =
+                    self.cache.get_package_json(&cached_path, &self.options, ctx)?
+                else {
+                    return Err(ResolveError::NotFound(package_name.to_string()));
+                };
+                Ok(Some((cached_path, package_json)))
+            }
+            Ok(pnp::Resolution::Skipped) => Ok(None),
+            Err(err) => Err(ResolveError::YarnPnpError(err)),
+        }
     }
 
     #[cfg(feature = "yarn_pnp")]
@@ -1049,22 +2555,33 @@ impl ResolverImpl {
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> Result<Option<CachedPath>, ResolveError> {
-        let pnp_manifest = self.cache.get_yarn_pnp_manifest(self.options.cwd.as_deref())?;
+        let pnp_manifest = self.yarn_pnp_manifest(cached_path)?;
 
         // "pnpapi" in a P'n'P builtin module
         if specifier == "pnpapi" {
+            let Some(pnp_manifest) = pnp_manifest else {
+                let cwd = self.options.cwd.as_deref();
+                let cwd = cwd.map_or_else(std::env::current_dir, |cwd| Ok(cwd.to_path_buf()))?;
+                return Err(ResolveError::FailedToFindYarnPnpManifest(cwd));
+            };
             return Ok(Some(self.cache.value(pnp_manifest.manifest_path.as_path())));
         }
 
+        // Not a PnP project, fall back to `node_modules`.
+        let Some(pnp_manifest) = pnp_manifest else { return Ok(None) };
+
         // `resolve_to_unqualified` requires a trailing slash
         let path = cached_path.path().join("");
 
-        let resolution = pnp::resolve_to_unqualified_via_manifest(pnp_manifest, specifier, &path);
+        if !self.yarn_pnp_manifest_applies(&pnp_manifest, cached_path, &path, ctx) {
+            return Ok(None);
+        }
+
+        let resolution = pnp::resolve_to_unqualified_via_manifest(&pnp_manifest, specifier, &path);
 
         match resolution {
             Ok(pnp::Resolution::Resolved(path, subpath)) => {
                 let cached_path = self.cache.value(&path);
-                let cached_path_string = cached_path.path().to_string_lossy();
 
                 let export_resolution =
                     self.load_package_self(&cached_path, specifier, tsconfig, ctx)?;
@@ -1074,11 +2591,15 @@ impl ResolverImpl {
                 }
 
                 // symbol linked package doesn't have node_modules structure
-                let pkg_name = cached_path_string.rsplit_once("node_modules/").map_or(
-                    "",
-                    // remove trailing slash
-                    |(_, last)| last.strip_suffix('/').unwrap_or(last),
-                );
+                let path_bytes = cached_path.path().as_os_str().as_encoded_bytes();
+                let pkg_name = path_bytes
+                    .windows(b"node_modules/".len())
+                    .rposition(|window| window == b"node_modules/")
+                    .map_or(&[][..], |index| {
+                        let last = &path_bytes[index + b"node_modules/".len()..];
+                        // remove trailing slash
+                        last.strip_suffix(b"/").unwrap_or(last)
+                    });
 
                 let inner_request = if pkg_name.is_empty() {
                     subpath.map_or_else(
@@ -1103,29 +2624,167 @@ impl ResolverImpl {
                         + inner_specifier.strip_prefix("/").unwrap_or(inner_specifier)
                 };
 
-                // it could be a directory with `package.json` that redirects to another file,
-                // take `@atlaskit/pragmatic-drag-and-drop` for example, as described at import-js/eslint-import-resolver-typescript#409
-                if let Ok(Some(result)) = self.load_as_directory(
-                    &self.cache.value(&path.join(&*inner_request).normalize()),
-                    tsconfig,
-                    ctx,
-                ) {
-                    return Ok(Some(result));
-                }
+                // it could be a directory with `package.json` that redirects to another file,
+                // take `@atlaskit/pragmatic-drag-and-drop` for example, as described at import-js/eslint-import-resolver-typescript#409
+                if let Ok(Some(result)) = self.load_as_directory(
+                    &self.cache.value(&path.join(&*inner_request).normalize()),
+                    tsconfig,
+                    ctx,
+                ) {
+                    return Ok(Some(result));
+                }
+
+                // try as file or directory `path` in the pnp folder
+                let cached_path = self.cache.value(&path);
+                let Ok(inner_resolution) = self.require(&cached_path, &inner_request, None, ctx)
+                else {
+                    return Err(ResolveError::NotFound(specifier.to_string()));
+                };
+
+                Ok(Some(self.cache.value(inner_resolution.path())))
+            }
+
+            Ok(pnp::Resolution::Skipped) => Ok(None),
+            Err(err) => Err(ResolveError::YarnPnpError(err)),
+        }
+    }
+
+    /// Whether the PnP manifest applies to the importer at `cached_path`.
+    ///
+    /// The importer's project root is the closest directory holding a lockfile.
+    /// When it lies below the manifest's directory, e.g. a plain npm project inside a home
+    /// directory with a stray PnP install, the manifest only applies if it registers a package
+    /// located within that project root. Otherwise the manifest is for a different project and
+    /// `node_modules` is used instead.
+    #[cfg(feature = "yarn_pnp")]
+    fn yarn_pnp_manifest_applies(
+        &self,
+        manifest: &pnp::Manifest,
+        cached_path: &CachedPath,
+        path: &Path,
+        ctx: &mut Ctx,
+    ) -> bool {
+        let below_manifest = |directory: &CachedPath| {
+            directory.path() != manifest.manifest_dir
+                && directory.path().starts_with(&manifest.manifest_dir)
+        };
+        let project_root = if ctx.missing_dependencies.is_some() {
+            // The memo is bypassed while dependencies are collected so that every missing
+            // lockfile is reported.
+            std::iter::successors(Some(cached_path.clone()), |cp| cp.parent(&self.cache))
+                .take_while(below_manifest)
+                .find(|cp| self.has_lockfile(cp, ctx))
+        } else {
+            self.lockfile_directory(cached_path).filter(below_manifest)
+        };
+        project_root.is_none_or(|project_root| {
+            pnp::find_locator(manifest, path)
+                .and_then(|locator| pnp::get_package(manifest, locator).ok())
+                .is_some_and(|package| package.package_location.starts_with(project_root.path()))
+        })
+    }
+
+    /// The closest directory of `cached_path` and its ancestors holding a lockfile, memoized
+    /// per directory.
+    #[cfg(feature = "yarn_pnp")]
+    fn lockfile_directory(&self, cached_path: &CachedPath) -> Option<CachedPath> {
+        if let Some(directory) = self.cache.lockfile_directories.get(cached_path) {
+            return directory.clone();
+        }
+        // Dependencies are not collected when the memo is used.
+        let directory = if self.has_lockfile(cached_path, &mut Ctx::default()) {
+            Some(cached_path.clone())
+        } else {
+            // The shard lock is released before recursing, the parent may live in the same shard.
+            cached_path.parent(&self.cache).and_then(|parent| self.lockfile_directory(&parent))
+        };
+        self.cache.lockfile_directories.entry(cached_path.clone()).or_insert(directory).clone()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn has_lockfile(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> bool {
+        cache::LOCKFILES
+            .iter()
+            .any(|lockfile| self.is_file_impl(&cached_path.push(lockfile, &self.cache), ctx))
+    }
 
-                // try as file or directory `path` in the pnp folder
-                let cached_path = self.cache.value(&path);
-                let Ok(inner_resolution) = self.require(&cached_path, &inner_request, None, ctx)
-                else {
-                    return Err(ResolveError::NotFound(specifier.to_string()));
-                };
+    /// Start iterating over the module directories named `module_name` of `cached_path` and
+    /// its ancestors with [Self::next_module_directory].
+    fn module_directory_cursor(
+        &self,
+        cached_path: &CachedPath,
+        module_name: &str,
+        ctx: &Ctx,
+    ) -> ModuleDirectoryCursor {
+        if ctx.missing_dependencies.is_some() {
+            ModuleDirectoryCursor::Ancestor(Some(cached_path.clone()))
+        } else {
+            ModuleDirectoryCursor::Memoized(self.module_directories(cached_path, module_name))
+        }
+    }
 
-                Ok(Some(self.cache.value(inner_resolution.path())))
+    fn next_module_directory(
+        &self,
+        cursor: &mut ModuleDirectoryCursor,
+        module_name: &str,
+        ctx: &mut Ctx,
+    ) -> Option<CachedPath> {
+        match cursor {
+            ModuleDirectoryCursor::Memoized(next) => {
+                let module_directories = next.take()?;
+                next.clone_from(&module_directories.parent);
+                Some(module_directories.directory.clone())
             }
+            ModuleDirectoryCursor::Ancestor(next) => loop {
+                let cached_path = next.take()?;
+                *next = cached_path.parent(&self.cache);
+                // Skip if /path/to/node_modules does not exist
+                if !self.is_dir_impl(&cached_path, ctx) {
+                    continue;
+                }
+                if let Some(module_directory) =
+                    self.get_module_directory(&cached_path, module_name, ctx)
+                {
+                    return Some(module_directory);
+                }
+            },
+        }
+    }
 
-            Ok(pnp::Resolution::Skipped) => Ok(None),
-            Err(err) => Err(ResolveError::YarnPnpError(err)),
+    /// Memoized module directories named `module_name` of `cached_path` and its ancestors.
+    ///
+    /// Resolving from deeply nested directories would otherwise walk up to the root for every
+    /// bare specifier, the memo is computed once per directory and shared with its children.
+    fn module_directories(
+        &self,
+        cached_path: &CachedPath,
+        module_name: &str,
+    ) -> Option<Arc<ModuleDirectories>> {
+        let module_name = if module_name == "node_modules" {
+            Cow::Borrowed("node_modules")
+        } else {
+            Cow::Owned(module_name.to_string())
+        };
+        let key = (cached_path.clone(), module_name);
+        if let Some(module_directories) = self.cache.module_directories.get(&key) {
+            return module_directories.clone();
         }
+        // The shard lock is released before recursing, the parent may live in the same shard.
+        let parent = cached_path
+            .parent(&self.cache)
+            .and_then(|parent| self.module_directories(&parent, &key.1));
+        // Dependencies are not collected when the memo is used.
+        let mut ctx = Ctx::default();
+        let directory = if self.is_dir_impl(cached_path, &mut ctx) {
+            self.get_module_directory(cached_path, &key.1, &mut ctx)
+        } else {
+            None
+        };
+        let module_directories = match directory {
+            Some(directory) => Some(Arc::new(ModuleDirectories { directory, parent })),
+            None => parent,
+        };
+        self.cache.module_directories.entry(key).or_insert(module_directories).clone()
     }
 
     fn get_module_directory(
@@ -1159,6 +2818,12 @@ impl ResolverImpl {
         else {
             return Ok(None);
         };
+        if subpath.is_empty()
+            && let Some(path) =
+                self.load_module_field_over_exports(cached_path, &package_json, tsconfig, ctx)?
+        {
+            return Ok(Some(path));
+        }
         // 3. Parse DIR/NAME/package.json, and look for "exports" field.
         // 4. If "exports" is null or undefined, return.
         // 5. let MATCH = PACKAGE_EXPORTS_RESOLVE(pathToFileURL(DIR/NAME), "." + SUBPATH,
@@ -1169,17 +2834,159 @@ impl ResolverImpl {
             if let Some(path) = self.package_exports_resolve(
                 cached_path,
                 dot_subpath.as_ref(),
+                &package_json,
                 &exports,
                 tsconfig,
                 ctx,
             )? {
                 // 6. RESOLVE_ESM_MATCH(MATCH)
-                return self.resolve_esm_match(specifier, &path, tsconfig, ctx);
+                return self.resolve_exports_match(
+                    specifier,
+                    cached_path,
+                    dot_subpath.as_ref(),
+                    &path,
+                    tsconfig,
+                    ctx,
+                );
             }
         }
         Ok(None)
     }
 
+    /// The `module` field of the package at `cached_path` when it also has an `exports` field and
+    /// [ResolveOptions::prefer_module_field_over_exports] is enabled.
+    fn load_module_field_over_exports(
+        &self,
+        cached_path: &CachedPath,
+        package_json: &PackageJson,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        if !self.options.prefer_module_field_over_exports {
+            return Ok(None);
+        }
+        let Some(module) = package_json.module() else { return Ok(None) };
+        if package_json.exports_fields(&self.options.exports_fields).next().is_none() {
+            return Ok(None);
+        }
+        let Some(path) = self.load_main_field(cached_path, module, tsconfig, ctx)? else {
+            return Ok(None);
+        };
+        let mut scratch = Ctx { depth: ctx.depth, ..Ctx::default() };
+        let exports =
+            package_json.exports_fields(&self.options.exports_fields).find_map(|exports| {
+                self.package_exports_resolve(
+                    cached_path,
+                    ".",
+                    package_json,
+                    &exports,
+                    tsconfig,
+                    &mut scratch,
+                )
+                .ok()
+                .flatten()
+            });
+        if let Some(exports) = exports
+            && exports.path() != path.path()
+        {
+            self.report_module_field_conflict(package_json, &path, &exports);
+        }
+        Ok(Some(path))
+    }
+
+    fn report_module_field_conflict(
+        &self,
+        package_json: &PackageJson,
+        module: &CachedPath,
+        exports: &CachedPath,
+    ) {
+        let mut conflicts =
+            self.module_field_conflicts.lock().unwrap_or_else(PoisonError::into_inner);
+        if conflicts.iter().any(|conflict| conflict.package_json == package_json.path) {
+            return;
+        }
+        let conflict = ModuleFieldConflict {
+            package_json: package_json.path.clone(),
+            module: module.to_path_buf(),
+            exports: exports.to_path_buf(),
+        };
+        tracing::warn!(
+            package_json = ?conflict.package_json,
+            module = ?conflict.module,
+            exports = ?conflict.exports,
+            "the `module` field and `exports` resolve the package to different files"
+        );
+        conflicts.push(conflict);
+    }
+
+    /// Redirect `subpath` of the package at `cached_path` with the `typesVersions` entry matching
+    /// [ResolveOptions::typescript_version].
+    fn load_types_versions(
+        &self,
+        cached_path: &CachedPath,
+        subpath: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let Some(typescript_version) = &self.options.typescript_version else { return Ok(None) };
+        let Some(package_json) = self.cache.get_package_json(cached_path, &self.options, ctx)?
+        else {
+            return Ok(None);
+        };
+        let Some(paths) = package_json
+            .types_versions()
+            .and_then(|types_versions| types_versions.paths_for(Some(typescript_version)))
+        else {
+            return Ok(None);
+        };
+        // Like TypeScript, the package itself is matched by its entry point, without `./`.
+        let main_fields = ctx.main_fields.clone();
+        let main_fields = main_fields.as_deref().unwrap_or(&self.options.main_fields);
+        let request = if subpath.is_empty() {
+            package_json
+                .types()
+                .or_else(|| package_json.typings())
+                .or_else(|| package_json.main_fields(main_fields).next().map(|f| f.1))
+                .map_or("index", |entry| entry.strip_prefix("./").unwrap_or(entry))
+        } else {
+            subpath.trim_start_matches('/')
+        };
+        // The targets are extensionless by convention.
+        let fully_specified = ctx.fully_specified;
+        ctx.with_fully_specified(false);
+        let resolved = paths.iter().find_map(|(pattern, targets)| {
+            let matched = Self::dts_match_pattern(pattern, request)?;
+            targets.iter().find_map(|target| {
+                let target = target.replace('*', &matched);
+                let path = cached_path.normalize_with(&target, &self.cache);
+                self.load_as_file_or_directory(&path, &target, tsconfig, ctx).transpose()
+            })
+        });
+        ctx.with_fully_specified(fully_specified);
+        resolved.transpose()
+    }
+
+    /// The closest package scope of `cached_path`, which does not reach past the `node_modules`
+    /// directory `cached_path` is in, like `GET_PACKAGE_SCOPE` of Node.js.
+    ///
+    /// A bundled dependency without a `package.json` is not in the scope of the package bundling
+    /// it.
+    fn find_package_scope(
+        &self,
+        cached_path: &CachedPath,
+        ctx: &mut Ctx,
+    ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        let Some(package_json) = self.cache.find_package_json(cached_path, &self.options, ctx)?
+        else {
+            return Ok(None);
+        };
+        let scope = package_json.path.parent().unwrap_or(&package_json.path);
+        let crosses_node_modules = cached_path.path().strip_prefix(scope).is_ok_and(|relative| {
+            relative.components().any(|component| component.as_os_str() == "node_modules")
+        });
+        Ok((!crosses_node_modules).then_some(package_json))
+    }
+
     fn load_package_self(
         &self,
         cached_path: &CachedPath,
@@ -1189,8 +2996,7 @@ impl ResolverImpl {
     ) -> ResolveResult {
         // 1. Find the closest package scope SCOPE to DIR.
         // 2. If no scope was found, return.
-        let Some(package_json) = self.cache.find_package_json(cached_path, &self.options, ctx)?
-        else {
+        let Some(package_json) = self.find_package_scope(cached_path, ctx)? else {
             return Ok(None);
         };
         // 3. If the SCOPE/package.json "exports" is null or undefined, return.
@@ -1210,12 +3016,20 @@ impl ResolverImpl {
                 if let Some(cached_path) = self.package_exports_resolve(
                     &package_url,
                     dot_subpath.as_ref(),
+                    &package_json,
                     &exports,
                     tsconfig,
                     ctx,
                 )? {
                     // 6. RESOLVE_ESM_MATCH(MATCH)
-                    return self.resolve_esm_match(specifier, &cached_path, tsconfig, ctx);
+                    return self.resolve_exports_match(
+                        specifier,
+                        &package_url,
+                        dot_subpath.as_ref(),
+                        &cached_path,
+                        tsconfig,
+                        ctx,
+                    );
                 }
             }
         }
@@ -1242,6 +3056,31 @@ impl ResolverImpl {
         Err(ResolveError::NotFound(specifier.to_string()))
     }
 
+    /// RESOLVE_ESM_MATCH(MATCH) for a match of the `exports` field of the package at `package_url`.
+    ///
+    /// With [ResolveOptions::exports_require_exact_target], the match must be a file as written.
+    fn resolve_exports_match(
+        &self,
+        specifier: &str,
+        package_url: &CachedPath,
+        subpath: &str,
+        cached_path: &CachedPath,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        if !self.options.exports_require_exact_target {
+            return self.resolve_esm_match(specifier, cached_path, tsconfig, ctx);
+        }
+        if let Some(path) = self.load_alias_or_file(cached_path, tsconfig, ctx)? {
+            return Ok(Some(path));
+        }
+        Err(ResolveError::ExportsTargetNotFound {
+            subpath: subpath.to_string(),
+            package_json_path: self.package_json_path(package_url),
+            target: cached_path.to_path_buf(),
+        })
+    }
+
     /// enhanced-resolve: AliasFieldPlugin for [crate::ResolveOptions::alias_fields]
     fn load_browser_field(
         &self,
@@ -1270,7 +3109,7 @@ impl ResolverImpl {
                 .as_ref()
                 .is_some_and(|s| path.ends_with(Path::new(s)))
             {
-                return if self.is_file(cached_path, ctx) {
+                return if self.is_file_impl(cached_path, ctx) {
                     if self.check_restrictions(cached_path.path()) {
                         Ok(Some(cached_path.clone()))
                     } else {
@@ -1285,7 +3124,19 @@ impl ResolverImpl {
         ctx.with_resolving_alias(new_specifier.to_string());
         ctx.with_fully_specified(false);
         let package_url = self.cache.value(package_json.path().parent().unwrap());
-        self.require(&package_url, new_specifier, None, ctx).map(Some)
+        let resolved = self.require(&package_url, new_specifier, None, ctx)?;
+        if self.options.record_matched_entry {
+            let key = module_specifier.map_or_else(
+                || {
+                    let relative = path.strip_prefix(package_url.path()).unwrap_or(path);
+                    format!("./{}", relative.to_string_lossy())
+                },
+                ToString::to_string,
+            );
+            ctx.package_entry =
+                Some(PackageEntryInfo { kind: PackageEntryKind::Browser, key, conditions: vec![] });
+        }
+        Ok(Some(resolved))
     }
 
     /// Given an extension alias map `{".js": [".ts", ".js"]}`,
@@ -1327,7 +3178,7 @@ impl ResolverImpl {
             }
         }
         // Bail if path is module directory such as `ipaddr.js`
-        if !self.is_file(cached_path, ctx) {
+        if !self.is_file_impl(cached_path, ctx) {
             ctx.with_fully_specified(false);
             return Ok(None);
         } else if !self.check_restrictions(cached_path.path()) {
@@ -1361,26 +3212,58 @@ impl ResolverImpl {
         if self.options.roots.is_empty() {
             return None;
         }
-        if let Some(specifier) = specifier.strip_prefix(SLASH_START) {
-            if specifier.is_empty() {
-                if self.options.roots.iter().any(|root| root.as_path() == cached_path.path())
-                    && let Ok(path) = self.require_relative(cached_path, "./", tsconfig, ctx)
-                {
-                    return Some(path);
+        if let Some(relative) = specifier.strip_prefix(SLASH_START) {
+            if relative.is_empty() {
+                if self.options.roots.iter().any(|root| root.as_path() == cached_path.path()) {
+                    return self
+                        .load_root_package_exports(cached_path, specifier, tsconfig, ctx)
+                        .or_else(|| self.require_relative(cached_path, "./", tsconfig, ctx).ok());
                 }
             } else {
                 for root in &self.options.roots {
                     let cached_path = self.cache.value(root);
-                    if let Ok(path) = self.require_relative(&cached_path, specifier, tsconfig, ctx)
+                    if let Some(path) =
+                        self.load_root_package_exports(&cached_path, specifier, tsconfig, ctx)
                     {
                         return Some(path);
                     }
+                    if let Ok(path) = self.require_relative(&cached_path, relative, tsconfig, ctx) {
+                        return Some(path);
+                    }
                 }
             }
         }
         None
     }
 
+    /// The `exports` of the package.json in `root` for the server-relative `specifier`, when
+    /// [ResolveOptions::allow_package_exports_in_directory_resolve] is enabled, so that a root
+    /// pointing at a built package resolves like the package does.
+    ///
+    /// A subpath not exported by the package falls back to the files of the root.
+    fn load_root_package_exports(
+        &self,
+        root: &CachedPath,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> Option<CachedPath> {
+        if !self.options.allow_package_exports_in_directory_resolve {
+            return None;
+        }
+        let package_json = self.cache.get_package_json(root, &self.options, ctx).ok()??;
+        if package_json.directory() != root.path() {
+            return None;
+        }
+        let dot_subpath = Self::dot_subpath(specifier.trim_end_matches('/'));
+        package_json.exports_fields(&self.options.exports_fields).find_map(|exports| {
+            let path = self
+                .package_exports_resolve(root, &dot_subpath, &package_json, &exports, tsconfig, ctx)
+                .ok()??;
+            self.resolve_exports_match(specifier, root, &dot_subpath, &path, tsconfig, ctx).ok()?
+        })
+    }
+
     /// PACKAGE_RESOLVE(packageSpecifier, parentURL)
     fn package_resolve(
         &self,
@@ -1398,29 +3281,36 @@ impl ResolverImpl {
 
         // 11. While parentURL is not the file system root,
         for module_name in &self.options.modules {
-            for cached_path in
-                std::iter::successors(Some(cached_path.clone()), |cp| cp.parent(&self.cache))
+            let mut cursor = self.module_directory_cursor(cached_path, module_name, ctx);
+            // 1. Let packageURL be the URL resolution of "node_modules/" concatenated with packageSpecifier, relative to parentURL.
+            while let Some(cached_path) = self.next_module_directory(&mut cursor, module_name, ctx)
             {
-                // 1. Let packageURL be the URL resolution of "node_modules/" concatenated with packageSpecifier, relative to parentURL.
-                let Some(cached_path) = self.get_module_directory(&cached_path, module_name, ctx)
-                else {
-                    continue;
-                };
                 // 2. Set parentURL to the parent folder URL of parentURL.
                 let cached_path = cached_path.normalize_with(package_name, &self.cache);
                 // 3. If the folder at packageURL does not exist, then
                 //   1. Continue the next loop iteration.
-                if self.is_dir(&cached_path, ctx) {
+                if self.is_dir_impl(&cached_path, ctx) {
                     // 4. Let pjson be the result of READ_PACKAGE_JSON(packageURL).
                     if let Some(package_json) =
                         self.cache.get_package_json(&cached_path, &self.options, ctx)?
                     {
+                        if subpath == "."
+                            && let Some(path) = self.load_module_field_over_exports(
+                                &cached_path,
+                                &package_json,
+                                tsconfig,
+                                ctx,
+                            )?
+                        {
+                            return Ok(Some(path));
+                        }
                         // 5. If pjson is not null and pjson.exports is not null or undefined, then
                         // 1. Return the result of PACKAGE_EXPORTS_RESOLVE(packageURL, packageSubpath, pjson.exports, defaultConditions).
                         for exports in package_json.exports_fields(&self.options.exports_fields) {
                             if let Some(path) = self.package_exports_resolve(
                                 &cached_path,
                                 dot_subpath.as_ref(),
+                                &package_json,
                                 &exports,
                                 tsconfig,
                                 ctx,
@@ -1431,11 +3321,14 @@ impl ResolverImpl {
                         // 6. Otherwise, if packageSubpath is equal to ".", then
                         if subpath == "." {
                             // 1. If pjson.main is a string, then
-                            for main_field in package_json.main_fields(&self.options.main_fields) {
+                            let main_fields = ctx.main_fields.clone();
+                            let main_fields =
+                                main_fields.as_deref().unwrap_or(&self.options.main_fields);
+                            for (_, main_field) in package_json.main_fields(main_fields) {
                                 // 1. Return the URL resolution of main in packageURL.
                                 let cached_path =
                                     cached_path.normalize_with(main_field, &self.cache);
-                                if self.is_file(&cached_path, ctx)
+                                if self.is_file_impl(&cached_path, ctx)
                                     && self.check_restrictions(cached_path.path())
                                 {
                                     return Ok(Some(cached_path));
@@ -1459,25 +3352,40 @@ impl ResolverImpl {
         &self,
         package_url: &CachedPath,
         subpath: &str,
+        package_json: &PackageJson,
+        exports: &ImportsExportsEntry<'_>,
+        tsconfig: Option<&TsConfig>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let timer = ctx.start_phase(Phase::ExportsResolution);
+        let resolved = self.package_exports_resolve_impl(
+            package_url,
+            subpath,
+            package_json,
+            exports,
+            tsconfig,
+            ctx,
+        );
+        if let Some(elapsed) = ctx.stop_phase(timer) {
+            self.cache.add_package_exports_timing(package_json.path(), elapsed);
+        }
+        resolved
+    }
+
+    fn package_exports_resolve_impl(
+        &self,
+        package_url: &CachedPath,
+        subpath: &str,
+        package_json: &PackageJson,
         exports: &ImportsExportsEntry<'_>,
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> ResolveResult {
-        let conditions = &self.options.condition_names;
+        let provided = ctx.condition_names.clone();
+        let conditions = provided.as_deref().unwrap_or(&self.options.condition_names);
         // 1. If exports is an Object with both a key starting with "." and a key not starting with ".", throw an Invalid Package Configuration error.
-        if let Some(map) = exports.as_map() {
-            let mut has_dot = false;
-            let mut without_dot = false;
-            for key in map.keys() {
-                let starts_with_dot_or_hash = key.starts_with(['.', '#']);
-                has_dot = has_dot || starts_with_dot_or_hash;
-                without_dot = without_dot || !starts_with_dot_or_hash;
-                if has_dot && without_dot {
-                    return Err(ResolveError::InvalidPackageConfig(
-                        package_url.path().join("package.json"),
-                    ));
-                }
-            }
+        if package_json.exports_has_mixed_keys(exports) {
+            return Err(ResolveError::InvalidPackageConfig(self.package_json_path(package_url)));
         }
         // 2. If subpath is equal to ".", then
         // Note: subpath is not prepended with a dot when passed in.
@@ -1515,7 +3423,9 @@ impl ResolverImpl {
                     conditions,
                     tsconfig,
                     ctx,
-                )?;
+                );
+                self.record_package_entry(PackageEntryKind::Exports, ".", &resolved, ctx);
+                let resolved = resolved?;
                 // 2. If resolved is not null or undefined, return resolved.
                 if let Some(path) = resolved {
                     return Ok(Some(path));
@@ -1545,11 +3455,48 @@ impl ResolverImpl {
         Err(ResolveError::PackagePathNotExported {
             subpath: subpath.to_string(),
             package_path: package_url.path().to_path_buf(),
-            package_json_path: package_url.path().join("package.json"),
-            conditions: self.options.condition_names.clone().into(),
+            package_json_path: self.package_json_path(package_url),
+            conditions: conditions.to_vec().into(),
         })
     }
 
+    /// The description file of the package at `package_url` for errors and steps, one of
+    /// [ResolveOptions::description_files] rather than always `package.json`.
+    fn package_json_path(&self, package_url: &CachedPath) -> PathBuf {
+        self.cache
+            .get_package_json(package_url, &self.options, &mut Ctx::default())
+            .ok()
+            .flatten()
+            .filter(|package_json| package_json.path().parent() == Some(package_url.path()))
+            .map_or_else(
+                || {
+                    let file_name = self
+                        .options
+                        .description_files
+                        .first()
+                        .map_or("package.json", String::as_str);
+                    package_url.path().join(file_name)
+                },
+                |package_json| package_json.path().to_path_buf(),
+            )
+    }
+
+    /// Record the `key` which `resolved` was matched by, with the conditions taken in
+    /// [ResolverImpl::package_target_resolve], see [ResolveOptions::record_matched_entry].
+    fn record_package_entry(
+        &self,
+        kind: PackageEntryKind,
+        key: &str,
+        resolved: &ResolveResult,
+        ctx: &mut Ctx,
+    ) {
+        if self.options.record_matched_entry && matches!(resolved, Ok(Some(_))) {
+            let mut conditions = ctx.matched_conditions.take().unwrap_or_default();
+            conditions.reverse();
+            ctx.package_entry = Some(PackageEntryInfo { kind, key: key.to_string(), conditions });
+        }
+    }
+
     /// PACKAGE_IMPORTS_RESOLVE(specifier, parentURL, conditions)
     fn package_imports_resolve(
         &self,
@@ -1580,12 +3527,13 @@ impl ResolverImpl {
                     ));
                 }
             }
+            let provided = ctx.condition_names.clone();
             if let Some(path) = self.package_imports_exports_resolve(
                 specifier,
                 &imports,
                 &self.cache.value(package_json.directory()),
                 /* is_imports */ true,
-                &self.options.condition_names,
+                provided.as_deref().unwrap_or(&self.options.condition_names),
                 tsconfig,
                 ctx,
             )? {
@@ -1626,7 +3574,7 @@ impl ResolverImpl {
             // 1. Let target be the value of matchObj[matchKey].
             if let Some(target) = match_obj.get(match_key) {
                 // 2. Return the result of PACKAGE_TARGET_RESOLVE(packageURL, target, null, isImports, conditions).
-                return self.package_target_resolve(
+                let resolved = self.package_target_resolve(
                     package_url,
                     match_key,
                     &target,
@@ -1636,6 +3584,10 @@ impl ResolverImpl {
                     tsconfig,
                     ctx,
                 );
+                let kind =
+                    if is_imports { PackageEntryKind::Imports } else { PackageEntryKind::Exports };
+                self.record_package_entry(kind, match_key, &resolved, ctx);
+                return resolved;
             }
         }
 
@@ -1644,6 +3596,11 @@ impl ResolverImpl {
         let mut best_key = "";
         // 2. Let expansionKeys be the list of keys of matchObj containing only a single "*", sorted by the sorting function PATTERN_KEY_COMPARE which orders in descending order of specificity.
         // 3. For each key expansionKey in expansionKeys, do
+        //
+        // Instead of sorting, the most specific key is kept. Two keys which both match
+        // `match_key` only compare equal when they are the same key: the same base length and
+        // key length leave no room for different trailers. So the winner does not depend on the
+        // order of the keys in the object.
         for (expansion_key, target) in match_obj.iter() {
             if expansion_key.starts_with("./") || expansion_key.starts_with('#') {
                 // 1. Let patternBase be the substring of expansionKey up to but excluding the first "*" character.
@@ -1678,7 +3635,7 @@ impl ResolverImpl {
         }
         if let Some(best_target) = best_target {
             // 3. Return the result of PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions).
-            return self.package_target_resolve(
+            let resolved = self.package_target_resolve(
                 package_url,
                 best_key,
                 &best_target,
@@ -1688,6 +3645,10 @@ impl ResolverImpl {
                 tsconfig,
                 ctx,
             );
+            let kind =
+                if is_imports { PackageEntryKind::Imports } else { PackageEntryKind::Exports };
+            self.record_package_entry(kind, best_key, &resolved, ctx);
+            return resolved;
         }
         // 4. Return null.
         Ok(None)
@@ -1706,12 +3667,12 @@ impl ResolverImpl {
         tsconfig: Option<&TsConfig>,
         ctx: &mut Ctx,
     ) -> ResolveResult {
+        /// `None` for a folder mapping whose key or target does not end with `/`.
         fn normalize_string_target<'a>(
             target_key: &'a str,
             target: &'a str,
             pattern_match: Option<&'a str>,
-            package_url: &CachedPath,
-        ) -> Result<Cow<'a, str>, ResolveError> {
+        ) -> Option<Cow<'a, str>> {
             let target = if let Some(pattern_match) = pattern_match {
                 if !target_key.contains('*') && !target.contains('*') {
                     // enhanced-resolve behaviour
@@ -1719,9 +3680,7 @@ impl ResolverImpl {
                     if target_key.ends_with('/') && target.ends_with('/') {
                         Cow::Owned(format!("{target}{pattern_match}"))
                     } else {
-                        return Err(ResolveError::InvalidPackageConfigDirectory(
-                            package_url.path().join("package.json"),
-                        ));
+                        return None;
                     }
                 } else {
                     Cow::Owned(target.replace('*', pattern_match))
@@ -1729,13 +3688,15 @@ impl ResolverImpl {
             } else {
                 Cow::Borrowed(target)
             };
-            Ok(target)
+            Some(target)
         }
 
         // 1. If target is a String, then
         if let Some(target) = target.as_string() {
             // Target string con contain queries or fragments:
             // `"exports": { ".": { "default": "./foo.js?query#fragment" }`
+            // They are split off before `*` is substituted, so that a `#` in the pattern match
+            // (`pkg/a#b` is tried as a path) stays part of the path.
             let parsed = Specifier::parse(target).map_err(ResolveError::Specifier)?;
             ctx.with_query_fragment(parsed.query, parsed.fragment);
             let target = parsed.path();
@@ -1748,32 +3709,68 @@ impl ResolverImpl {
                     return Err(ResolveError::InvalidPackageTarget(
                         (*target).to_string(),
                         target_key.to_string(),
-                        package_url.path().join("package.json"),
+                        self.package_json_path(package_url),
                     ));
                 }
                 // 2. If patternMatch is a String, then
                 //   1. Return PACKAGE_RESOLVE(target with every instance of "*" replaced by patternMatch, packageURL + "/").
-                let target =
-                    normalize_string_target(target_key, target, pattern_match, package_url)?;
+                let target = normalize_string_target(target_key, target, pattern_match)
+                    .ok_or_else(|| {
+                        ResolveError::InvalidPackageConfigDirectory(
+                            self.package_json_path(package_url),
+                        )
+                    })?;
                 // // 3. Return PACKAGE_RESOLVE(target, packageURL + "/").
-                return self.package_resolve(package_url, &target, tsconfig, ctx);
+                let resolved = self.package_resolve(package_url, &target, tsconfig, ctx);
+                if self.options.record_matched_entry && matches!(resolved, Ok(Some(_))) {
+                    ctx.matched_conditions = Some(vec![]);
+                }
+                return resolved;
             }
 
+            // 6. If patternMatch split on "/" or "\" contains any "", ".", "..", or "node_modules" segments, case insensitive and including percent encoded variants, throw an Invalid Module Specifier error.
+            // Checked before the target is joined and normalized, so that `pkg/lib/../feature`
+            // can not reach the targets of `./feature` through `./lib/*`.
+            // Packages without `exports` keep resolving the normalized path.
+            if let Some(pattern_match) = pattern_match
+                && Self::has_invalid_subpath_segment(pattern_match)
+            {
+                let subpath = if target_key.contains('*') {
+                    target_key.replacen('*', pattern_match, 1)
+                } else {
+                    format!("{target_key}{pattern_match}")
+                };
+                return Err(ResolveError::InvalidModuleSpecifier(
+                    subpath,
+                    self.package_json_path(package_url),
+                ));
+            }
             // 2. If target split on "/" or "\" contains any "", ".", "..", or "node_modules" segments after the first "." segment, case insensitive and including percent encoded variants, throw an Invalid Package Target error.
             // 3. Let resolvedTarget be the URL resolution of the concatenation of packageURL and target.
             // 4. Assert: resolvedTarget is contained in packageURL.
             // 5. If patternMatch is null, then
-            let target = normalize_string_target(target_key, target, pattern_match, package_url)?;
+            let target =
+                normalize_string_target(target_key, target, pattern_match).ok_or_else(|| {
+                    ResolveError::InvalidPackageConfigDirectory(self.package_json_path(package_url))
+                })?;
             if Path::new(target.as_ref()).is_invalid_exports_target() {
                 return Err(ResolveError::InvalidPackageTarget(
                     target.to_string(),
                     target_key.to_string(),
-                    package_url.path().join("package.json"),
+                    self.package_json_path(package_url),
                 ));
             }
-            // 6. If patternMatch split on "/" or "\" contains any "", ".", "..", or "node_modules" segments, case insensitive and including percent encoded variants, throw an Invalid Module Specifier error.
+            if self.options.record_matched_entry {
+                ctx.matched_conditions = Some(vec![]);
+            }
             // 7. Return the URL resolution of resolvedTarget with every instance of "*" replaced with patternMatch.
-            return Ok(Some(package_url.normalize_with(target.as_ref(), &self.cache)));
+            let resolved = package_url.normalize_with(target.as_ref(), &self.cache);
+            ctx.add_step(
+                "exports target",
+                resolved.path(),
+                StepOutcome::Matched(target_key.to_string()),
+            );
+            return Ok(Some(resolved));
         }
         // 2. Otherwise, if target is a non-null Object, then
         else if let Some(target) = target.as_map() {
@@ -1781,9 +3778,25 @@ impl ResolverImpl {
             // 2. For each property p of target, in object insertion order as,
             for (key, target_value) in target.iter() {
                 // 1. If p equals "default" or conditions contains an entry for p, then
-                if key == "default" || conditions.iter().any(|condition| condition == key) {
+                let matched =
+                    key == "default" || conditions.iter().any(|condition| condition == key);
+                if ctx.steps.is_some() {
+                    ctx.add_step(
+                        "exports condition",
+                        &self.package_json_path(package_url),
+                        if matched {
+                            StepOutcome::Matched(key.to_string())
+                        } else {
+                            StepOutcome::Skipped(key.to_string())
+                        },
+                    );
+                }
+                if matched {
                     // 1. Let targetValue be the value of the p property in target.
                     // 2. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions).
+                    // Keep passing the matched `target_key` rather than the condition name `key`:
+                    // for a folder mapping (`"./helpers/": { "node": "./helpers/node/" }`) its
+                    // trailing slash is what allows joining `patternMatch` onto the folder target.
                     let resolved = self.package_target_resolve(
                         package_url,
                         target_key,
@@ -1796,6 +3809,12 @@ impl ResolverImpl {
                     );
                     // 3. If resolved is equal to undefined, continue the loop.
                     if let Some(path) = resolved? {
+                        if key == "node-addons" {
+                            ctx.via_node_addons = true;
+                        }
+                        if let Some(conditions) = &mut ctx.matched_conditions {
+                            conditions.push(key.to_string());
+                        }
                         // 4. Return resolved.
                         return Ok(Some(path));
                     }
@@ -1812,14 +3831,23 @@ impl ResolverImpl {
                 return Err(ResolveError::PackagePathNotExported {
                     subpath: pattern_match.unwrap_or(".").to_string(),
                     package_path: package_url.path().to_path_buf(),
-                    package_json_path: package_url.path().join("package.json"),
-                    conditions: self.options.condition_names.clone().into(),
+                    package_json_path: self.package_json_path(package_url),
+                    conditions: conditions.to_vec().into(),
                 });
             }
+            // The first `imports` match which does not exist, with its conditions, query and
+            // fragment, see below.
+            let mut missing = None;
+            // The last Invalid Package Target error, thrown when no entry resolves.
+            let mut last_error = None;
             // 2. For each item targetValue in target, do
-            for (i, target_value) in targets.iter().enumerate() {
+            for target_value in targets.iter() {
+                // An entry which is skipped must not leave its query and fragment behind.
+                let query = ctx.query.clone();
+                let fragment = ctx.fragment.clone();
+                let probed = ctx.probed;
                 // 1. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions), continuing the loop on any Invalid Package Target error.
-                let resolved = self.package_target_resolve(
+                let resolved = match self.package_target_resolve(
                     package_url,
                     target_key,
                     &target_value,
@@ -1828,26 +3856,55 @@ impl ResolverImpl {
                     conditions,
                     tsconfig,
                     ctx,
-                );
-
-                // `NotFound` is exempt: it comes from re-resolving a bare `imports` target
-                // through the module system, which enhanced-resolve lets fall through to the
-                // next array entry / a soft failure rather than a hard error.
-                if i + 1 == targets.len()
-                    && let Err(err) = &resolved
-                    && !matches!(err, ResolveError::NotFound(_))
-                {
-                    return resolved;
-                }
+                ) {
+                    Ok(resolved) => resolved,
+                    Err(err @ ResolveError::InvalidPackageTarget(..)) => {
+                        (ctx.query, ctx.fragment) = (query, fragment);
+                        last_error = Some(err);
+                        continue;
+                    }
+                    // `NotFound` comes from re-resolving a bare `imports` target through the
+                    // module system, which enhanced-resolve lets fall through to the next entry.
+                    Err(ResolveError::NotFound(_)) => {
+                        (ctx.query, ctx.fragment) = (query, fragment);
+                        continue;
+                    }
+                    Err(err) => return Err(err),
+                };
 
                 // 2. If resolved is undefined, continue the loop.
-                if let Ok(Some(path)) = resolved {
+                if let Some(path) = resolved {
+                    // An `imports` fallback array moves on to the next entry when the file does
+                    // not exist or fails to load, the first match is kept when none loads.
+                    // `exports` matches are returned as is.
+                    if is_imports {
+                        if let Ok(Some(loaded)) =
+                            self.load_as_file_or_directory(&path, "", tsconfig, ctx)
+                        {
+                            return Ok(Some(loaded));
+                        }
+                        let query = std::mem::replace(&mut ctx.query, query);
+                        let fragment = std::mem::replace(&mut ctx.fragment, fragment);
+                        ctx.probed = probed;
+                        if missing.is_none() {
+                            let conditions = ctx.matched_conditions.take();
+                            missing = Some((path, conditions, query, fragment));
+                        }
+                        continue;
+                    }
                     // 3. Return resolved.
                     return Ok(Some(path));
                 }
             }
+            if let Some((path, conditions, query, fragment)) = missing {
+                ctx.matched_conditions = conditions;
+                (ctx.query, ctx.fragment) = (query, fragment);
+                return Ok(Some(path));
+            }
             // 3. Return or throw the last fallback resolution null return or error.
-            // Note: see `i + 1 == targets.len()`
+            if let Some(err) = last_error {
+                return Err(err);
+            }
         }
         // 4. Otherwise, if target is null, return null.
         Ok(None)
@@ -1888,6 +3945,16 @@ impl ResolverImpl {
         Cow::Owned(dot_subpath)
     }
 
+    /// Whether `pattern_match` split on "/" or "\" contains any "", ".", "..", or "node_modules"
+    /// segments, case insensitive and including percent encoded variants.
+    fn has_invalid_subpath_segment(pattern_match: &str) -> bool {
+        pattern_match.split(['/', '\\']).any(|segment| {
+            let segment = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+            matches!(segment.as_ref(), "" | "." | "..")
+                || segment.eq_ignore_ascii_case("node_modules")
+        })
+    }
+
     /// PATTERN_KEY_COMPARE(keyA, keyB)
     fn pattern_key_compare(key_a: &str, key_b: &str) -> Ordering {
         if key_a.is_empty() {
@@ -1970,26 +4037,51 @@ impl ResolverImpl {
             //   1. If packageType is not null, then
             //     1. Return packageType.
             Some("js" | "ts") => {
-                // 7. Let packageURL be the result of LOOKUP_PACKAGE_SCOPE(url).
-                // 8. Let pjson be the result of READ_PACKAGE_JSON(packageURL).
-                let package_json = self.cache.find_package_json(cached_path, &self.options, ctx)?;
-                // 9. Let packageType be null.
-                if let Some(package_json) = package_json {
-                    // 10. If pjson?.type is "module" or "commonjs", then
-                    //   1. Set packageType to pjson.type.
-                    if let Some(ty) = package_json.r#type() {
-                        return Ok(Some(match ty {
-                            PackageType::Module => ModuleType::Module,
-                            PackageType::CommonJs => ModuleType::CommonJs,
-                        }));
-                    }
+                if let Some(module_type) = self.package_module_type(cached_path, ctx)? {
+                    return Ok(Some(module_type));
+                }
+                //   2. If the result of DETECT_MODULE_SYNTAX(source) is true, then
+                //     1. Return "module".
+                //   3. Return "commonjs".
+                if ext == Some("js") && self.options.module_type_detection {
+                    return Ok(self.cache.detected_module_type(cached_path));
                 }
                 Ok(None)
             }
-            // Step 11.2 .. 12 omitted, which involves detecting file content.
+            // 12. If url does not have any extension, then
+            //   2. If packageType is not null, then
+            //     1. Return packageType.
+            //   3. If the result of DETECT_MODULE_SYNTAX(source) is true, then
+            //     1. Return "module".
+            //   4. Return "commonjs".
+            None if self.options.module_type_detection => {
+                if let Some(module_type) = self.package_module_type(cached_path, ctx)? {
+                    return Ok(Some(module_type));
+                }
+                Ok(self.cache.detected_module_type(cached_path))
+            }
+            // Step 12 is omitted without `module_type_detection`.
             _ => Ok(None),
         }
     }
+
+    /// The `"type"` of the package scope of `cached_path`.
+    fn package_module_type(
+        &self,
+        cached_path: &CachedPath,
+        ctx: &mut Ctx,
+    ) -> Result<Option<ModuleType>, ResolveError> {
+        // 7. Let packageURL be the result of LOOKUP_PACKAGE_SCOPE(url).
+        // 8. Let pjson be the result of READ_PACKAGE_JSON(packageURL).
+        let package_json = self.cache.find_package_json(cached_path, &self.options, ctx)?;
+        // 9. Let packageType be null.
+        // 10. If pjson?.type is "module" or "commonjs", then
+        //   1. Set packageType to pjson.type.
+        Ok(package_json.and_then(|package_json| package_json.r#type()).map(|ty| match ty {
+            PackageType::Module => ModuleType::Module,
+            PackageType::CommonJs => ModuleType::CommonJs,
+        }))
+    }
 }
 
 /// Strip BOM in place by replacing with spaces (no reallocation)
@@ -1999,3 +4091,46 @@ pub(crate) fn replace_bom_with_whitespace(s: &mut [u8]) {
         s[..3].fill(b' ');
     }
 }
+
+/// The module format of an importing file, from its `.mts`, `.mjs`, `.cts` or `.cjs` extension.
+fn importer_module_type(path: &Path) -> Option<ModuleType> {
+    path.extension()?.to_str().and_then(module_type_of_extension)
+}
+
+/// The module format of an extension in [ResolveOptions::extensions], e.g. `.mts` or `.d.cts`.
+/// `extensions` with those of `module_type` moved ahead of those of the other module format,
+/// or `None` when the order does not change.
+fn reorder_extensions(extensions: &[String], module_type: ModuleType) -> Option<Arc<[String]>> {
+    let (same, other): (Vec<_>, Vec<_>) = extensions
+        .iter()
+        .filter(|extension| extension_module_type(extension).is_some())
+        .partition(|extension| extension_module_type(extension) == Some(module_type));
+    if same.is_empty() || other.is_empty() {
+        return None;
+    }
+    let mut reordered = same.into_iter().chain(other);
+    Some(
+        extensions
+            .iter()
+            .map(|extension| {
+                if extension_module_type(extension).is_some() {
+                    reordered.next().unwrap_or(extension).clone()
+                } else {
+                    extension.clone()
+                }
+            })
+            .collect(),
+    )
+}
+
+fn extension_module_type(extension: &str) -> Option<ModuleType> {
+    extension.rsplit('.').next().and_then(module_type_of_extension)
+}
+
+fn module_type_of_extension(extension: &str) -> Option<ModuleType> {
+    match extension {
+        "mts" | "mjs" => Some(ModuleType::Module),
+        "cts" | "cjs" => Some(ModuleType::CommonJs),
+        _ => None,
+    }
+}
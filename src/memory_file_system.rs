@@ -0,0 +1,254 @@
+//! A [FileSystem] of virtual files, see [MemoryFileSystem].
+
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+    sync::{PoisonError, RwLock},
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+#[cfg(feature = "async")]
+use crate::{AsyncFileSystem, FileSystemFuture};
+use crate::{FileMetadata, FileSystem, PathUtil, ResolveError};
+
+/// Maximum number of symlinks followed for a single path, the limit of Linux.
+const MAX_SYMLINKS: usize = 40;
+
+/// In-memory file system of files and symlinks at absolute paths.
+///
+/// The parent directories of each entry exist implicitly.
+///
+/// Entries can be added while a resolver uses the file system, the resolver caches the results
+/// of its queries so call [crate::ResolverImpl::clear_cache] afterwards.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    entries: RwLock<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    files: FxHashMap<PathBuf, Vec<u8>>,
+    directories: FxHashSet<PathBuf>,
+    /// Symlinks to their normalized absolute target.
+    symlinks: FxHashMap<PathBuf, PathBuf>,
+}
+
+impl Entries {
+    fn add_parents(&mut self, path: &Path) {
+        for parent in path.ancestors().skip(1) {
+            if !self.directories.insert(parent.to_path_buf()) {
+                break;
+            }
+        }
+    }
+
+    /// The path of `path` with the symlinks of its parent directories followed.
+    fn resolve_parent(&self, path: &Path) -> io::Result<PathBuf> {
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(file_name)) => Ok(self.resolve(parent)?.join(file_name)),
+            _ => Ok(path.to_path_buf()),
+        }
+    }
+
+    /// The path of `path` with all symlinks followed.
+    fn resolve(&self, path: &Path) -> io::Result<PathBuf> {
+        let mut resolved = PathBuf::new();
+        let mut components = path.components().collect::<Vec<_>>();
+        components.reverse();
+        let mut followed = 0;
+        while let Some(component) = components.pop() {
+            match component {
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::CurDir => {}
+                component => resolved.push(component),
+            }
+            if let Some(target) = self.symlinks.get(&resolved) {
+                followed += 1;
+                if followed > MAX_SYMLINKS {
+                    return Err(io::Error::other(format!(
+                        "Too many levels of symbolic links: {}",
+                        path.display()
+                    )));
+                }
+                components.extend(target.components().rev());
+                resolved = PathBuf::new();
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn metadata(&self, path: &Path) -> Option<FileMetadata> {
+        self.files.get(path).map_or_else(
+            || self.directories.contains(path).then(|| FileMetadata::new(false, true, false)),
+            |content| {
+                let meta = FileMetadata::new(true, false, false);
+                Some(meta.with_size_and_modified(content.len() as u64, None))
+            },
+        )
+    }
+}
+
+impl MemoryFileSystem {
+    /// A file system of the `(path, content)` pairs of `files`.
+    #[must_use]
+    pub fn new<I, P, C>(files: I) -> Self
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: AsRef<Path>,
+        C: AsRef<[u8]>,
+    {
+        let fs = Self::default();
+        for (path, content) in files {
+            fs.add_file(path, content);
+        }
+        fs
+    }
+
+    /// Add or replace the file at the absolute `path`.
+    pub fn add_file<P: AsRef<Path>, C: AsRef<[u8]>>(&self, path: P, content: C) {
+        let path = path.as_ref().normalize();
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+        entries.symlinks.remove(&path);
+        entries.add_parents(&path);
+        entries.files.insert(path, content.as_ref().to_vec());
+    }
+
+    /// Add or replace a symlink at the absolute `path`.
+    ///
+    /// A relative `target` is relative to the directory of `path`, like `ln -s`.
+    pub fn add_symlink<P: AsRef<Path>, T: AsRef<Path>>(&self, path: P, target: T) {
+        let path = path.as_ref().normalize();
+        let target = path.parent().map_or_else(
+            || target.as_ref().normalize(),
+            |parent| parent.normalize_with(target.as_ref()),
+        );
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+        entries.files.remove(&path);
+        entries.add_parents(&path);
+        entries.symlinks.insert(path, target);
+    }
+
+    fn read_entries<T>(&self, f: impl FnOnce(&Entries) -> io::Result<T>) -> io::Result<T> {
+        f(&self.entries.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, format!("{} does not exist", path.display()))
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read_entries(|entries| {
+            entries.files.get(&entries.resolve(path)?).cloned().ok_or_else(|| Self::not_found(path))
+        })
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.read_entries(|entries| {
+            entries.metadata(&entries.resolve(path)?).ok_or_else(|| Self::not_found(path))
+        })
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.read_entries(|entries| {
+            let path = entries.resolve_parent(path)?;
+            if entries.symlinks.contains_key(&path) {
+                return Ok(FileMetadata::new(false, false, true));
+            }
+            entries.metadata(&path).ok_or_else(|| Self::not_found(&path))
+        })
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        let target = self.read_entries(|entries| {
+            entries
+                .symlinks
+                .get(&entries.resolve_parent(path)?)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a symlink"))
+        })?;
+        Ok(target)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.read_entries(|entries| {
+            let resolved = entries.resolve(path)?;
+            entries.metadata(&resolved).map(|_| resolved).ok_or_else(|| Self::not_found(path))
+        })
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.read_entries(|entries| {
+            let resolved = entries.resolve(path)?;
+            if !entries.directories.contains(&resolved) {
+                return Err(Self::not_found(path));
+            }
+            let children =
+                entries.files.keys().chain(&entries.directories).chain(entries.symlinks.keys());
+            Ok(children
+                .filter(|child| child.parent() == Some(resolved.as_path()))
+                .filter_map(|child| child.file_name().map(|name| path.join(name)))
+                .collect())
+        })
+    }
+
+    #[cfg(feature = "async")]
+    fn as_async(&self) -> Option<&dyn AsyncFileSystem> {
+        Some(self)
+    }
+}
+
+/// Completes immediately with the result of the [FileSystem] method.
+#[cfg(feature = "async")]
+impl AsyncFileSystem for MemoryFileSystem {
+    fn read_async<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, io::Result<Vec<u8>>> {
+        Box::pin(std::future::ready(FileSystem::read(self, path)))
+    }
+
+    fn metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        Box::pin(std::future::ready(FileSystem::metadata(self, path)))
+    }
+
+    fn symlink_metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        Box::pin(std::future::ready(FileSystem::symlink_metadata(self, path)))
+    }
+
+    fn read_link_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<PathBuf, ResolveError>> {
+        Box::pin(std::future::ready(FileSystem::read_link(self, path)))
+    }
+
+    fn canonicalize_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<PathBuf>> {
+        Box::pin(std::future::ready(FileSystem::canonicalize(self, path)))
+    }
+}
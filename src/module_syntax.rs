@@ -0,0 +1,118 @@
+//! Module syntax detection of JavaScript sources, see [ResolveOptions::module_type_detection].
+//!
+//! [ResolveOptions::module_type_detection]: crate::ResolveOptions::module_type_detection
+
+use crate::ModuleType;
+
+/// [ModuleType::Module] when `source` has a top-level `import` or `export` statement or uses
+/// `import.meta`, [ModuleType::CommonJs] otherwise.
+///
+/// Comments, string literals and template literals are skipped, dynamic `import()` is
+/// allowed in CommonJS and does not count. Regular expression literals are not recognized.
+pub fn detect_module_type(source: &[u8]) -> ModuleType {
+    if has_module_syntax(source) { ModuleType::Module } else { ModuleType::CommonJs }
+}
+
+fn has_module_syntax(source: &[u8]) -> bool {
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < source.len() {
+        match source[i] {
+            b'/' if source.get(i + 1) == Some(&b'/') => {
+                i = memchr::memchr(b'\n', &source[i..]).map_or(source.len(), |end| i + end);
+            }
+            b'/' if source.get(i + 1) == Some(&b'*') => {
+                i = memchr::memmem::find(&source[i + 2..], b"*/")
+                    .map_or(source.len(), |end| i + 2 + end + 2);
+            }
+            quote @ (b'"' | b'\'' | b'`') => i = skip_string(source, i + 1, quote),
+            b'{' | b'(' | b'[' => {
+                depth += 1;
+                i += 1;
+            }
+            b'}' | b')' | b']' => {
+                depth = depth.saturating_sub(1);
+                i += 1;
+            }
+            b if is_identifier_byte(b) => {
+                let start = i;
+                while i < source.len() && is_identifier_byte(source[i]) {
+                    i += 1;
+                }
+                let word = &source[start..i];
+                // `foo.import` and `obj.export` are property accesses.
+                let is_property = source[..start].trim_ascii_end().ends_with(b".");
+                if is_property {
+                    continue;
+                }
+                let next = source[i..].trim_ascii_start().first().copied();
+                match word {
+                    b"export" if depth == 0 => return true,
+                    b"import" => match next {
+                        // `import.meta`
+                        Some(b'.') => return true,
+                        // `import()` is allowed in CommonJS.
+                        Some(b'(') | None => {}
+                        Some(_) if depth == 0 => return true,
+                        Some(_) => {}
+                    },
+                    _ => {}
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    false
+}
+
+/// The index after the closing `quote` of the string starting before `i`.
+fn skip_string(source: &[u8], mut i: usize, quote: u8) -> usize {
+    while i < source.len() {
+        match source[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            // Unterminated string.
+            b'\n' if quote != b'`' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    source.len()
+}
+
+const fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$') || !b.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect() {
+        #[rustfmt::skip]
+        let data = [
+            ("import foo from 'foo';", ModuleType::Module),
+            ("import 'side-effect';", ModuleType::Module),
+            ("import{a}from'a'", ModuleType::Module),
+            ("export default 42;", ModuleType::Module),
+            ("export const a = 1;", ModuleType::Module),
+            ("console.log(import.meta.url);", ModuleType::Module),
+            ("function f() { return import.meta; }", ModuleType::Module),
+            ("const a = require('a');\nmodule.exports = a;", ModuleType::CommonJs),
+            ("import('foo').then(() => {});", ModuleType::CommonJs),
+            ("const s = 'import foo from \"foo\"';", ModuleType::CommonJs),
+            ("const s = \"export default 1\";", ModuleType::CommonJs),
+            ("const s = `\nimport a from 'a'\n`;", ModuleType::CommonJs),
+            ("// import foo from 'foo'\nrequire('a')", ModuleType::CommonJs),
+            ("/* export default 1 */", ModuleType::CommonJs),
+            ("obj.import = 1; obj.export = 2;", ModuleType::CommonJs),
+            ("const important = 1; const exported = 2;", ModuleType::CommonJs),
+            ("function f() { export_(); }", ModuleType::CommonJs),
+            ("'unterminated\nexport default 1", ModuleType::Module),
+            ("", ModuleType::CommonJs),
+        ];
+        for (source, expected) in data {
+            assert_eq!(detect_module_type(source.as_bytes()), expected, "{source}");
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     sync::Arc,
 };
 
-use crate::node_path::NodePath;
+use crate::{DependencySink, ImportMap, node_path::NodePath};
 
 /// Module Resolution Options
 ///
@@ -13,6 +13,8 @@ use crate::node_path::NodePath;
 /// See [webpack resolve](https://webpack.js.org/configuration/resolve/) for information and examples
 #[expect(clippy::struct_excessive_bools, reason = "options are ported 1:1 from enhanced-resolve")]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ResolveOptions {
     /// Current working directory, used for testing purposes.
     pub cwd: Option<PathBuf>,
@@ -47,6 +49,24 @@ pub struct ResolveOptions {
     /// Default `[]`
     pub condition_names: Vec<String>,
 
+    /// Called with the directory of the importer to select the condition names for `exports` and
+    /// `imports` in place of [ResolveOptions::condition_names], e.g. from the browserslist
+    /// environment of each app in a monorepo.
+    ///
+    /// [ResolveOptions::condition_names] are used when it returns `None`.
+    /// The result is cached per directory, see [crate::Cache::invalidate_path].
+    ///
+    /// Default: `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub condition_names_provider: Option<ConditionNamesProvider>,
+
+    /// The JSON files to use as description files, tried in order in each directory.
+    ///
+    /// All of them are read as `package.json`.
+    ///
+    /// Default `["package.json"]`
+    pub description_files: Vec<String>,
+
     /// Set to [EnforceExtension::Enabled] for [ESM Mandatory file extensions](https://nodejs.org/api/esm.html#mandatory-file-extensions).
     ///
     /// If `enforce_extension` is set to [EnforceExtension::Enabled], resolution will not allow extension-less files.
@@ -59,6 +79,8 @@ pub struct ResolveOptions {
     ///
     /// Explicitly set the value to [EnforceExtension::Disabled] to disable this automatic behavior.
     ///
+    /// Directories requested with a trailing slash (`./dir/`) load their main field as is.
+    ///
     /// For reference, this behavior is aligned with `enhanced-resolve`. See <https://github.com/webpack/enhanced-resolve/pull/285>.
     pub enforce_extension: EnforceExtension,
 
@@ -97,6 +119,16 @@ pub struct ResolveOptions {
     /// Default `[]`
     pub fallback: Alias,
 
+    /// [Import map](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps) applied
+    /// to the specifier passed to the resolver, before [ResolveOptions::tsconfig] paths and
+    /// [ResolveOptions::alias].
+    ///
+    /// The address of the exact or longest prefix key matching the specifier is resolved in its
+    /// place, as in browsers and Deno.
+    ///
+    /// Default: `None`
+    pub import_map: Option<ImportMap>,
+
     /// Request passed to resolve is already fully specified and extensions or main files are not resolved for it (they are still resolved for internal requests).
     ///
     /// See also webpack configuration [resolve.fullySpecified](https://webpack.js.org/configuration/module/#resolvefullyspecified)
@@ -104,6 +136,16 @@ pub struct ResolveOptions {
     /// Default `false`
     pub fully_specified: bool,
 
+    /// Whether [ResolveOptions::fully_specified] also applies to requests into packages in
+    /// [ResolveOptions::modules] which have no `exports` field.
+    ///
+    /// When disabled, extensions and main files are probed inside such packages, so `lodash/get`
+    /// resolves to `lodash/get.js`, while the user's own relative requests stay fully specified.
+    /// This matches webpack, which only applies `fullySpecified` to the user's ESM requests.
+    ///
+    /// Default `true`
+    pub fully_specified_applies_to_node_modules: bool,
+
     /// A list of main fields in description files
     ///
     /// Default `["main"]`.
@@ -140,8 +182,18 @@ pub struct ResolveOptions {
     /// A list of resolve restrictions to restrict the paths that a request can be resolved on.
     ///
     /// Default `[]`
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_restrictions"))]
     pub restrictions: Vec<Restriction>,
 
+    /// A list of file name suffixes that a resolved path must not end with, e.g. `[".env", ".pem"]`.
+    ///
+    /// Matched against the full file name, so multi-dot suffixes such as `.env.local` are supported.
+    /// Applies to every resolution, including ones from `exports`, aliases and main files,
+    /// and fails with [crate::ResolveError::ForbiddenExtension].
+    ///
+    /// Default `[]`
+    pub forbidden_extensions: Vec<String>,
+
     /// A list of directories where requests of server-relative URLs (starting with '/') are resolved.
     /// On non-Windows systems these requests are resolved as an absolute path first.
     ///
@@ -199,9 +251,195 @@ pub struct ResolveOptions {
     /// * <https://github.com/vitejs/vite/pull/20252>
     /// * <https://github.com/nodejs/node/issues/58827>
     ///
+    /// Also applies to server-relative requests of [ResolveOptions::roots], resolving them through
+    /// the `exports` of a package.json in the root before its files.
+    ///
     /// Default: `false`
     pub allow_package_exports_in_directory_resolve: bool,
 
+    /// Probe the package.json `directories.lib` field when resolving a directory.
+    ///
+    /// When enabled and neither the `main_fields` nor the main files of the directory itself
+    /// resolved, `<lib>/<main_file>` candidates are tried last.
+    /// This is a legacy npm convention and is not part of `enhanced-resolve`.
+    ///
+    /// Default: `false`
+    pub use_directories_lib: bool,
+
+    /// Report resolutions that only succeeded because of extension probing or main file fallback,
+    /// i.e. resolutions that would fail with [ResolveOptions::fully_specified].
+    ///
+    /// When enabled, [crate::Resolution::probed] is set for such resolutions and a `tracing`
+    /// warning naming the importer, the specifier and the chosen file is emitted, so strict CI
+    /// runs can collect them without failing the build. Exact `exports` matches are never reported.
+    ///
+    /// Default: `false`
+    pub extension_probe_report: bool,
+
+    /// Require `exports` targets to point at an existing file exactly as written, like Node.js does.
+    ///
+    /// By default a target such as `"./lib/index"` is resolved with extension probing and main
+    /// files, which hides packaging bugs that fail at runtime in Node.js.
+    /// When enabled, such a target fails with [crate::ResolveError::ExportsTargetNotFound].
+    ///
+    /// Default: `false`
+    pub exports_require_exact_target: bool,
+
+    /// Only fall back to tsconfig `baseUrl` when no `compilerOptions.paths` pattern matched the
+    /// specifier, like tsc does.
+    ///
+    /// By default the `baseUrl` candidate is also tried after the candidates of a matched pattern,
+    /// which hides typos in `paths` targets.
+    ///
+    /// Default: `false`
+    pub tsconfig_paths_strict_fallback: bool,
+
+    /// Only apply tsconfig `compilerOptions.paths`, `baseUrl` and `rootDirs` to specifiers
+    /// imported from directories covered by the `files`, `include` and `exclude` of the tsconfig,
+    /// like `tsc` which does not resolve imports of files outside of the project with them.
+    ///
+    /// Other importers, e.g. under a `scripts` directory which is not included, fall through to
+    /// the other steps of the resolution. A directory is covered when an `include` pattern
+    /// matches files in it and no `exclude` pattern excludes it as a whole, or when it contains
+    /// a file listed in `files`. The coverage is cached per directory.
+    ///
+    /// Default: `false`
+    pub tsconfig_respect_include: bool,
+
+    /// Collect every package.json consulted by a resolution into
+    /// [crate::ResolveContext::package_json_chain].
+    ///
+    /// Only [crate::ResolverImpl::resolve_with_context] reports the chain, tsconfig lookups are
+    /// not included.
+    ///
+    /// Default: `false`
+    pub collect_package_json_chain: bool,
+
+    /// Record the `package.json` entry which a resolution was matched by into
+    /// [crate::Resolution::package_entry], for diagnostics.
+    ///
+    /// Default: `false`
+    pub record_matched_entry: bool,
+
+    /// Record into [crate::Resolution::file_directory_ambiguity] when both a file and a directory
+    /// match a relative or module path, e.g. `./thing.js` and `./thing/index.js` for `./thing`.
+    ///
+    /// The winner is not changed: the file wins like in Node.js, unless it is excluded, e.g. by
+    /// [ResolveOptions::restrictions], in which case the directory wins.
+    ///
+    /// Default: `false`
+    pub report_file_directory_ambiguity: bool,
+
+    /// The number of nested resolutions, e.g. through aliases, browser field remaps and `exports`
+    /// targets, after which a resolution fails with
+    /// [crate::ResolveError::RecursionLimitExceeded].
+    ///
+    /// Default: `64`
+    pub max_resolve_depth: usize,
+
+    /// Try the extension which was last found in a directory first when probing
+    /// [ResolveOptions::extensions] there.
+    ///
+    /// A directory importing many extensionless specifiers otherwise pays a failed stat for each
+    /// extension tried before the one found, e.g. `.js` before `.ts` in a TypeScript project.
+    /// The configured order still wins: a file found with the remembered extension is only
+    /// taken when the listing of the directory has none of the extensions before it, and the
+    /// directory is only listed once such a file was found. Directories which the file system
+    /// cannot list, see [crate::FileSystem::read_dir], are probed in the configured order.
+    /// Listings are cached like metadata, see [crate::Cache::invalidate_path].
+    ///
+    /// Default: `false`
+    pub adaptive_extension_probing: bool,
+
+    /// The TypeScript version selecting the `typesVersions` entry of a package.json, see
+    /// [crate::PackageJson::types_versions].
+    ///
+    /// When set, the paths of the package are redirected by the entry whose range matches,
+    /// after `exports` and before the main fields. It is also used by
+    /// [crate::ResolverImpl::resolve_dts], which takes the first entry when it is not set.
+    ///
+    /// Default: `None`
+    pub typescript_version: Option<semver::Version>,
+
+    /// Fail a bare specifier which is not found with [crate::ResolveError::Ignored] instead of
+    /// [crate::ResolveError::NotFound] when the package scope importing it declares the package
+    /// an optional dependency, in `peerDependenciesMeta` with `"optional": true` or in
+    /// `optionalDependencies`.
+    ///
+    /// [ResolveOptions::fallback] is tried before the specifier is ignored.
+    ///
+    /// Default: `false`
+    pub ignore_missing_optional_peers: bool,
+
+    /// Detect the [crate::Resolution::module_type] of `.js` and extensionless files outside of
+    /// packages with a `"type"` field from their contents, like Node.js' `--experimental-detect-module`.
+    ///
+    /// A file is a [crate::ModuleType::Module] when it has a top-level `import` or `export`
+    /// statement, or uses `import.meta`, and a [crate::ModuleType::CommonJs] otherwise.
+    /// The contents are scanned for these tokens outside of comments and strings rather than
+    /// parsed, so e.g. `import` in a regular expression literal may be mistaken for a statement.
+    ///
+    /// Requires [ResolveOptions::module_type].
+    ///
+    /// Default: `false`
+    pub module_type_detection: bool,
+
+    /// Resolve the root of a package with an `exports` field from its `module` field first, like
+    /// webpack 4 and Rollup configurations which list `module` in their main fields.
+    ///
+    /// Subpaths such as `pkg/utils` are still resolved by `exports`. When the package root
+    /// resolves to a different file with `exports`, a `tracing` warning naming both files is
+    /// emitted once per package, see [crate::ResolverImpl::module_field_conflicts], so that
+    /// packages can be fixed before this option is turned off.
+    ///
+    /// Default: `false`
+    pub prefer_module_field_over_exports: bool,
+
+    /// Whether paths are looked up ignoring case, e.g. so that `import 'React'` or `./Foo`
+    /// resolve on Linux as they do on the case-insensitive filesystems of macOS and Windows.
+    ///
+    /// With [CaseSensitivity::Insensitive], a path which is not found is looked up in a listing
+    /// of its directory ignoring case, and the spelling on disk is used from there on, so that
+    /// the resolved path matches the file name on disk. Directories with several entries which
+    /// only differ in case keep their exact matches.
+    ///
+    /// Resolvers with a different value do not share their cache, see
+    /// [crate::ResolverGeneric::clone_with_options].
+    ///
+    /// Default: [CaseSensitivity::Sensitive]
+    pub case_sensitivity: CaseSensitivity,
+
+    /// Directories whose package.json `imports` are tried in order for `#` specifiers which the
+    /// closest package.json does not map, e.g. the root of a workspace for its members, like
+    /// Vite's workspace root fallback.
+    ///
+    /// Node.js only consults the closest package.json. The
+    /// [crate::ResolveError::PackageImportNotDefined] of the closest package.json is returned when
+    /// none of the directories maps the specifier either.
+    ///
+    /// Default: `[]`
+    pub imports_fallback_scopes: Vec<PathBuf>,
+
+    /// Bare specifiers which are left unresolved because the runtime provides them, failing with
+    /// [crate::ResolveError::External] instead.
+    ///
+    /// Entries are matched like the keys of [ResolveOptions::alias]: `lodash` matches `lodash`
+    /// and `lodash/fp`, `lodash$` only `lodash`, and `@scope/*` any package of the scope.
+    /// They are matched after [ResolveOptions::alias] and tsconfig `paths`.
+    ///
+    /// Default: `[]`
+    pub external: Vec<String>,
+
+    /// Called with every file and missing dependency of every resolution, including those
+    /// made without a [crate::ResolveContext], so that dependencies can be streamed without
+    /// collecting them into a set per call.
+    ///
+    /// A path may be reported more than once.
+    ///
+    /// Default: `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub dependency_sink: Option<DependencySink>,
+
     /// Enable Yarn Plug'n'Play?.
     ///
     /// Pass in `!!process.versions.pnp` if called from node.js.
@@ -209,9 +447,32 @@ pub struct ResolveOptions {
     /// Default: when env var `OXC_RESOLVER_YARN_PNP` is set.
     #[cfg(feature = "yarn_pnp")]
     pub yarn_pnp: bool,
+
+    /// Only use a Yarn PnP manifest located in this directory or below it.
+    ///
+    /// The search for a manifest upwards from [ResolveOptions::cwd] stops at this directory, and
+    /// a manifest of [ResolveOptions::pnp_manifest_paths] above it is ignored. `node_modules` are
+    /// used when there is no manifest.
+    ///
+    /// Default: `None`
+    #[cfg(feature = "yarn_pnp")]
+    pub yarn_pnp_root: Option<PathBuf>,
+
+    /// Yarn PnP manifests (`.pnp.cjs` files) to use instead of the one found from
+    /// [ResolveOptions::cwd], like enhanced-resolve's `pnpApi` option.
+    ///
+    /// Each resolution uses the manifest whose directory is the closest ancestor of the directory
+    /// being resolved from, so nested workspaces with their own install are resolved by their own
+    /// manifest. When no manifest covers the directory, `node_modules` are used instead.
+    ///
+    /// Default: `[]`
+    #[cfg(feature = "yarn_pnp")]
+    pub pnp_manifest_paths: Vec<PathBuf>,
 }
 
 impl ResolveOptions {
+    /// Same as [ResolveOptionsBuilder::condition_names] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -221,11 +482,12 @@ impl ResolveOptions {
     /// assert_eq!(options.condition_names, vec!["bar".to_string()])
     /// ```
     #[must_use]
-    pub fn with_condition_names(mut self, names: &[&str]) -> Self {
-        self.condition_names = names.iter().map(ToString::to_string).collect::<Vec<String>>();
-        self
+    pub fn with_condition_names(self, names: &[&str]) -> Self {
+        self.into_builder().condition_names(names.iter().map(ToString::to_string).collect()).options
     }
 
+    /// Same as [ResolveOptionsBuilder::node_path] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -235,11 +497,12 @@ impl ResolveOptions {
     /// assert_eq!(options.node_path, false)
     /// ```
     #[must_use]
-    pub const fn with_node_path(mut self, flag: bool) -> Self {
-        self.node_path = flag;
-        self
+    pub fn with_node_path(self, flag: bool) -> Self {
+        self.into_builder().node_path(flag).options
     }
 
+    /// Same as [ResolveOptionsBuilder::builtin_modules] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -249,13 +512,14 @@ impl ResolveOptions {
     /// assert_eq!(options.builtin_modules, false)
     /// ```
     #[must_use]
-    pub const fn with_builtin_modules(mut self, flag: bool) -> Self {
-        self.builtin_modules = flag;
-        self
+    pub fn with_builtin_modules(self, flag: bool) -> Self {
+        self.into_builder().builtin_modules(flag).options
     }
 
     /// Adds a single root to the options
     ///
+    /// Same as [ResolveOptionsBuilder::root] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -267,13 +531,14 @@ impl ResolveOptions {
     /// assert_eq!(options.roots, vec![PathBuf::from("foo")])
     /// ```
     #[must_use]
-    pub fn with_root<P: AsRef<Path>>(mut self, root: P) -> Self {
-        self.roots.push(root.as_ref().to_path_buf());
-        self
+    pub fn with_root<P: AsRef<Path>>(self, root: P) -> Self {
+        self.into_builder().root(root).options
     }
 
     /// Adds a single extension to the list of extensions. Extension must start with a `.`
     ///
+    /// Same as [ResolveOptionsBuilder::extension] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -285,13 +550,14 @@ impl ResolveOptions {
     /// assert!(options.extensions.contains(&".jsonc".to_string()));
     /// ```
     #[must_use]
-    pub fn with_extension<S: Into<String>>(mut self, extension: S) -> Self {
-        self.extensions.push(extension.into());
-        self
+    pub fn with_extension<S: Into<String>>(self, extension: S) -> Self {
+        self.into_builder().extension(extension).options
     }
 
     /// Adds a single main field to the list of fields
     ///
+    /// Same as [ResolveOptionsBuilder::main_field] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -303,13 +569,14 @@ impl ResolveOptions {
     /// assert!(options.main_fields.contains(&"something".to_string()));
     /// ```
     #[must_use]
-    pub fn with_main_field<S: Into<String>>(mut self, field: S) -> Self {
-        self.main_fields.push(field.into());
-        self
+    pub fn with_main_field<S: Into<String>>(self, field: S) -> Self {
+        self.into_builder().main_field(field).options
     }
 
     /// Changes how the extension should be treated
     ///
+    /// Same as [ResolveOptionsBuilder::enforce_extension] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -321,13 +588,14 @@ impl ResolveOptions {
     /// assert_eq!(options.enforce_extension, EnforceExtension::Enabled);
     /// ```
     #[must_use]
-    pub const fn with_force_extension(mut self, enforce_extension: EnforceExtension) -> Self {
-        self.enforce_extension = enforce_extension;
-        self
+    pub fn with_force_extension(self, enforce_extension: EnforceExtension) -> Self {
+        self.into_builder().enforce_extension(enforce_extension).options
     }
 
     /// Sets the value for [ResolveOptions::fully_specified]
     ///
+    /// Same as [ResolveOptionsBuilder::fully_specified] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -339,13 +607,14 @@ impl ResolveOptions {
     /// assert_eq!(options.fully_specified, true);
     /// ```
     #[must_use]
-    pub const fn with_fully_specified(mut self, fully_specified: bool) -> Self {
-        self.fully_specified = fully_specified;
-        self
+    pub fn with_fully_specified(self, fully_specified: bool) -> Self {
+        self.into_builder().fully_specified(fully_specified).options
     }
 
     /// Sets the value for [ResolveOptions::prefer_relative]
     ///
+    /// Same as [ResolveOptionsBuilder::prefer_relative] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -357,13 +626,14 @@ impl ResolveOptions {
     /// assert_eq!(options.prefer_relative, true);
     /// ```
     #[must_use]
-    pub const fn with_prefer_relative(mut self, flag: bool) -> Self {
-        self.prefer_relative = flag;
-        self
+    pub fn with_prefer_relative(self, flag: bool) -> Self {
+        self.into_builder().prefer_relative(flag).options
     }
 
     /// Sets the value for [ResolveOptions::prefer_absolute]
     ///
+    /// Same as [ResolveOptionsBuilder::prefer_absolute] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -375,13 +645,14 @@ impl ResolveOptions {
     /// assert_eq!(options.prefer_absolute, true);
     /// ```
     #[must_use]
-    pub const fn with_prefer_absolute(mut self, flag: bool) -> Self {
-        self.prefer_absolute = flag;
-        self
+    pub fn with_prefer_absolute(self, flag: bool) -> Self {
+        self.into_builder().prefer_absolute(flag).options
     }
 
     /// Changes the value of [ResolveOptions::symlinks]
     ///
+    /// Same as [ResolveOptionsBuilder::symlinks] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -391,13 +662,14 @@ impl ResolveOptions {
     /// assert_eq!(options.symlinks, false);
     /// ```
     #[must_use]
-    pub const fn with_symbolic_link(mut self, flag: bool) -> Self {
-        self.symlinks = flag;
-        self
+    pub fn with_symbolic_link(self, flag: bool) -> Self {
+        self.into_builder().symlinks(flag).options
     }
 
     /// Adds a module to [ResolveOptions::modules]
     ///
+    /// Same as [ResolveOptionsBuilder::module] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -407,13 +679,14 @@ impl ResolveOptions {
     /// assert!(options.modules.contains(&"module".to_string()));
     /// ```
     #[must_use]
-    pub fn with_module<M: Into<String>>(mut self, module: M) -> Self {
-        self.modules.push(module.into());
-        self
+    pub fn with_module<M: Into<String>>(self, module: M) -> Self {
+        self.into_builder().module(module).options
     }
 
     /// Adds a main file to [ResolveOptions::main_files]
     ///
+    /// Same as [ResolveOptionsBuilder::main_file] without [ResolveOptionsBuilder::build].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -423,9 +696,8 @@ impl ResolveOptions {
     /// assert!(options.main_files.contains(&"foo".to_string()));
     /// ```
     #[must_use]
-    pub fn with_main_file<M: Into<String>>(mut self, module: M) -> Self {
-        self.main_files.push(module.into());
-        self
+    pub fn with_main_file<M: Into<String>>(self, module: M) -> Self {
+        self.into_builder().main_file(module).options
     }
 
     pub(crate) fn sanitize(mut self) -> Self {
@@ -444,15 +716,548 @@ impl ResolveOptions {
         }
 
         if self.node_path {
-            self.modules.extend_from_slice(NodePath::build());
+            // Skip the paths added by [ResolveOptionsBuilder::build] already.
+            for module in NodePath::build() {
+                if !self.modules.contains(module) {
+                    self.modules.push(module.clone());
+                }
+            }
         }
 
         self
     }
+
+    /// Returns a [ResolveOptionsBuilder] starting from the default options.
+    #[must_use]
+    pub fn builder() -> ResolveOptionsBuilder {
+        ResolveOptionsBuilder::default()
+    }
+
+    const fn into_builder(self) -> ResolveOptionsBuilder {
+        ResolveOptionsBuilder { options: self }
+    }
+}
+
+/// Builder of [ResolveOptions], starting from [ResolveOptions::default].
+///
+/// ## Examples
+///
+/// ```
+/// use oxc_resolver::{EnforceExtension, ResolveOptions};
+///
+/// let options = ResolveOptions::builder()
+///     .extension(".ts")
+///     .extension("")
+///     .alias("@", vec!["./src".into()])
+///     .condition_names(vec!["import".into()])
+///     .symlinks(false)
+///     .build();
+/// assert!(options.extensions.contains(&".ts".to_string()));
+/// assert_eq!(options.enforce_extension, EnforceExtension::Enabled);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptionsBuilder {
+    options: ResolveOptions,
+}
+
+impl ResolveOptionsBuilder {
+    /// Adds a single extension to [ResolveOptions::extensions]. Extension must start with a `.`,
+    /// or be empty to allow the specifier as written.
+    #[must_use]
+    pub fn extension<S: Into<String>>(mut self, extension: S) -> Self {
+        self.options.extensions.push(extension.into());
+        self
+    }
+
+    /// Sets [ResolveOptions::extensions].
+    #[must_use]
+    pub fn extensions(mut self, extensions: Vec<String>) -> Self {
+        self.options.extensions = extensions;
+        self
+    }
+
+    /// Adds an entry to [ResolveOptions::alias].
+    #[must_use]
+    pub fn alias<S: Into<String>>(mut self, key: S, values: Vec<AliasValue>) -> Self {
+        self.options.alias.push((key.into(), values));
+        self
+    }
+
+    /// Adds an entry to [ResolveOptions::fallback].
+    #[must_use]
+    pub fn fallback<S: Into<String>>(mut self, key: S, values: Vec<AliasValue>) -> Self {
+        self.options.fallback.push((key.into(), values));
+        self
+    }
+
+    /// Sets [ResolveOptions::condition_names].
+    #[must_use]
+    pub fn condition_names(mut self, names: Vec<String>) -> Self {
+        self.options.condition_names = names;
+        self
+    }
+
+    /// Uses the tsconfig of `tsconfig` instead of discovering it, see [ResolveOptions::tsconfig].
+    #[must_use]
+    pub fn tsconfig(mut self, tsconfig: TsconfigOptions) -> Self {
+        self.options.tsconfig = Some(TsconfigDiscovery::Manual(tsconfig));
+        self
+    }
+
+    /// Sets [ResolveOptions::symlinks].
+    #[must_use]
+    pub const fn symlinks(mut self, flag: bool) -> Self {
+        self.options.symlinks = flag;
+        self
+    }
+
+    /// Adds a single main field to [ResolveOptions::main_fields].
+    #[must_use]
+    pub fn main_field<S: Into<String>>(mut self, field: S) -> Self {
+        self.options.main_fields.push(field.into());
+        self
+    }
+
+    /// Adds a main file to [ResolveOptions::main_files].
+    #[must_use]
+    pub fn main_file<S: Into<String>>(mut self, file: S) -> Self {
+        self.options.main_files.push(file.into());
+        self
+    }
+
+    /// Adds a module to [ResolveOptions::modules].
+    #[must_use]
+    pub fn module<S: Into<String>>(mut self, module: S) -> Self {
+        self.options.modules.push(module.into());
+        self
+    }
+
+    /// Adds a single root to [ResolveOptions::roots].
+    #[must_use]
+    pub fn root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.options.roots.push(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets [ResolveOptions::enforce_extension], which is otherwise computed by [Self::build].
+    #[must_use]
+    pub const fn enforce_extension(mut self, enforce_extension: EnforceExtension) -> Self {
+        self.options.enforce_extension = enforce_extension;
+        self
+    }
+
+    /// Sets [ResolveOptions::fully_specified].
+    #[must_use]
+    pub const fn fully_specified(mut self, flag: bool) -> Self {
+        self.options.fully_specified = flag;
+        self
+    }
+
+    /// Sets [ResolveOptions::prefer_relative].
+    #[must_use]
+    pub const fn prefer_relative(mut self, flag: bool) -> Self {
+        self.options.prefer_relative = flag;
+        self
+    }
+
+    /// Sets [ResolveOptions::prefer_absolute].
+    #[must_use]
+    pub const fn prefer_absolute(mut self, flag: bool) -> Self {
+        self.options.prefer_absolute = flag;
+        self
+    }
+
+    /// Sets [ResolveOptions::builtin_modules].
+    #[must_use]
+    pub const fn builtin_modules(mut self, flag: bool) -> Self {
+        self.options.builtin_modules = flag;
+        self
+    }
+
+    /// Sets [ResolveOptions::node_path].
+    #[must_use]
+    pub const fn node_path(mut self, flag: bool) -> Self {
+        self.options.node_path = flag;
+        self
+    }
+
+    /// Returns the options the resolver would use: [ResolveOptions::enforce_extension] is
+    /// computed from the presence of an empty extension when left at [EnforceExtension::Auto],
+    /// and `NODE_PATH` is added to the modules with [ResolveOptions::node_path].
+    #[must_use]
+    pub fn build(self) -> ResolveOptions {
+        self.options.sanitize()
+    }
+}
+
+impl ResolveOptions {
+    /// Layers `other` on top of these options with [MergeStrategy::Concat].
+    ///
+    /// * Fields of `other` left at their default value do not override these options.
+    /// * List fields such as [ResolveOptions::extensions] and [ResolveOptions::modules] are
+    ///   concatenated and deduplicated.
+    /// * [ResolveOptions::alias], [ResolveOptions::fallback] and
+    ///   [ResolveOptions::extension_alias] are merged by key, with `other` taking priority.
+    /// * Scalars such as [ResolveOptions::symlinks] and [ResolveOptions::tsconfig] prefer `other`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let base = ResolveOptions { extensions: vec![".ts".into()], ..ResolveOptions::default() };
+    /// let overrides = ResolveOptions {
+    ///     extensions: vec![".tsx".into(), ".ts".into()],
+    ///     symlinks: false,
+    ///     ..ResolveOptions::default()
+    /// };
+    /// let options = base.merge(&overrides);
+    /// assert_eq!(options.extensions, vec![".ts".to_string(), ".tsx".to_string()]);
+    /// assert!(!options.symlinks);
+    /// ```
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        self.merge_with(other, MergeStrategy::Concat)
+    }
+
+    /// Layers `other` on top of these options, combining list and map fields with `strategy`.
+    ///
+    /// See [ResolveOptions::merge].
+    #[must_use]
+    #[expect(clippy::too_many_lines, reason = "field-by-field merge of every option")]
+    pub fn merge_with(&self, other: &Self, strategy: MergeStrategy) -> Self {
+        let default = Self::default();
+        // Destructured so that a new field is not forgotten here.
+        let Self {
+            cwd,
+            tsconfig,
+            alias,
+            alias_fields,
+            condition_names,
+            condition_names_provider,
+            description_files,
+            enforce_extension,
+            exports_fields,
+            imports_fields,
+            extension_alias,
+            extensions,
+            fallback,
+            import_map,
+            fully_specified,
+            fully_specified_applies_to_node_modules,
+            main_fields,
+            main_files,
+            modules,
+            resolve_to_context,
+            prefer_relative,
+            prefer_absolute,
+            restrictions,
+            forbidden_extensions,
+            roots,
+            symlinks,
+            node_path,
+            builtin_modules,
+            module_type,
+            allow_package_exports_in_directory_resolve,
+            use_directories_lib,
+            extension_probe_report,
+            exports_require_exact_target,
+            tsconfig_paths_strict_fallback,
+            tsconfig_respect_include,
+            collect_package_json_chain,
+            record_matched_entry,
+            report_file_directory_ambiguity,
+            max_resolve_depth,
+            adaptive_extension_probing,
+            typescript_version,
+            ignore_missing_optional_peers,
+            module_type_detection,
+            prefer_module_field_over_exports,
+            case_sensitivity,
+            imports_fallback_scopes,
+            external,
+            dependency_sink,
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp,
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp_root,
+            #[cfg(feature = "yarn_pnp")]
+            pnp_manifest_paths,
+        } = other;
+        Self {
+            cwd: cwd.clone().or_else(|| self.cwd.clone()),
+            tsconfig: tsconfig.clone().or_else(|| self.tsconfig.clone()),
+            alias: strategy.merge_map(&self.alias, alias),
+            alias_fields: strategy.merge_list(
+                &self.alias_fields,
+                alias_fields,
+                &default.alias_fields,
+            ),
+            condition_names: strategy.merge_list(
+                &self.condition_names,
+                condition_names,
+                &default.condition_names,
+            ),
+            condition_names_provider: condition_names_provider
+                .clone()
+                .or_else(|| self.condition_names_provider.clone()),
+            description_files: strategy.merge_list(
+                &self.description_files,
+                description_files,
+                &default.description_files,
+            ),
+            enforce_extension: if enforce_extension.is_auto() {
+                self.enforce_extension
+            } else {
+                *enforce_extension
+            },
+            exports_fields: strategy.merge_list(
+                &self.exports_fields,
+                exports_fields,
+                &default.exports_fields,
+            ),
+            imports_fields: strategy.merge_list(
+                &self.imports_fields,
+                imports_fields,
+                &default.imports_fields,
+            ),
+            extension_alias: strategy.merge_map(&self.extension_alias, extension_alias),
+            extensions: strategy.merge_list(&self.extensions, extensions, &default.extensions),
+            fallback: strategy.merge_map(&self.fallback, fallback),
+            import_map: import_map.clone().or_else(|| self.import_map.clone()),
+            fully_specified: merge_scalar(
+                self.fully_specified,
+                *fully_specified,
+                default.fully_specified,
+            ),
+            fully_specified_applies_to_node_modules: merge_scalar(
+                self.fully_specified_applies_to_node_modules,
+                *fully_specified_applies_to_node_modules,
+                default.fully_specified_applies_to_node_modules,
+            ),
+            main_fields: strategy.merge_list(&self.main_fields, main_fields, &default.main_fields),
+            main_files: strategy.merge_list(&self.main_files, main_files, &default.main_files),
+            modules: strategy.merge_list(&self.modules, modules, &default.modules),
+            resolve_to_context: merge_scalar(
+                self.resolve_to_context,
+                *resolve_to_context,
+                default.resolve_to_context,
+            ),
+            prefer_relative: merge_scalar(
+                self.prefer_relative,
+                *prefer_relative,
+                default.prefer_relative,
+            ),
+            prefer_absolute: merge_scalar(
+                self.prefer_absolute,
+                *prefer_absolute,
+                default.prefer_absolute,
+            ),
+            restrictions: strategy.merge_restrictions(&self.restrictions, restrictions),
+            forbidden_extensions: strategy.merge_list(
+                &self.forbidden_extensions,
+                forbidden_extensions,
+                &default.forbidden_extensions,
+            ),
+            roots: strategy.merge_list(&self.roots, roots, &default.roots),
+            symlinks: merge_scalar(self.symlinks, *symlinks, default.symlinks),
+            node_path: merge_scalar(self.node_path, *node_path, default.node_path),
+            builtin_modules: merge_scalar(
+                self.builtin_modules,
+                *builtin_modules,
+                default.builtin_modules,
+            ),
+            module_type: merge_scalar(self.module_type, *module_type, default.module_type),
+            allow_package_exports_in_directory_resolve: merge_scalar(
+                self.allow_package_exports_in_directory_resolve,
+                *allow_package_exports_in_directory_resolve,
+                default.allow_package_exports_in_directory_resolve,
+            ),
+            use_directories_lib: merge_scalar(
+                self.use_directories_lib,
+                *use_directories_lib,
+                default.use_directories_lib,
+            ),
+            extension_probe_report: merge_scalar(
+                self.extension_probe_report,
+                *extension_probe_report,
+                default.extension_probe_report,
+            ),
+            exports_require_exact_target: merge_scalar(
+                self.exports_require_exact_target,
+                *exports_require_exact_target,
+                default.exports_require_exact_target,
+            ),
+            tsconfig_paths_strict_fallback: merge_scalar(
+                self.tsconfig_paths_strict_fallback,
+                *tsconfig_paths_strict_fallback,
+                default.tsconfig_paths_strict_fallback,
+            ),
+            tsconfig_respect_include: merge_scalar(
+                self.tsconfig_respect_include,
+                *tsconfig_respect_include,
+                default.tsconfig_respect_include,
+            ),
+            collect_package_json_chain: merge_scalar(
+                self.collect_package_json_chain,
+                *collect_package_json_chain,
+                default.collect_package_json_chain,
+            ),
+            record_matched_entry: merge_scalar(
+                self.record_matched_entry,
+                *record_matched_entry,
+                default.record_matched_entry,
+            ),
+            report_file_directory_ambiguity: merge_scalar(
+                self.report_file_directory_ambiguity,
+                *report_file_directory_ambiguity,
+                default.report_file_directory_ambiguity,
+            ),
+            max_resolve_depth: merge_scalar(
+                self.max_resolve_depth,
+                *max_resolve_depth,
+                default.max_resolve_depth,
+            ),
+            adaptive_extension_probing: merge_scalar(
+                self.adaptive_extension_probing,
+                *adaptive_extension_probing,
+                default.adaptive_extension_probing,
+            ),
+            typescript_version: typescript_version
+                .clone()
+                .or_else(|| self.typescript_version.clone()),
+            ignore_missing_optional_peers: merge_scalar(
+                self.ignore_missing_optional_peers,
+                *ignore_missing_optional_peers,
+                default.ignore_missing_optional_peers,
+            ),
+            module_type_detection: merge_scalar(
+                self.module_type_detection,
+                *module_type_detection,
+                default.module_type_detection,
+            ),
+            prefer_module_field_over_exports: merge_scalar(
+                self.prefer_module_field_over_exports,
+                *prefer_module_field_over_exports,
+                default.prefer_module_field_over_exports,
+            ),
+            case_sensitivity: merge_scalar(
+                self.case_sensitivity,
+                *case_sensitivity,
+                default.case_sensitivity,
+            ),
+            imports_fallback_scopes: strategy.merge_list(
+                &self.imports_fallback_scopes,
+                imports_fallback_scopes,
+                &default.imports_fallback_scopes,
+            ),
+            external: strategy.merge_list(&self.external, external, &default.external),
+            dependency_sink: dependency_sink.clone().or_else(|| self.dependency_sink.clone()),
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp: merge_scalar(self.yarn_pnp, *yarn_pnp, default.yarn_pnp),
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp_root: yarn_pnp_root.clone().or_else(|| self.yarn_pnp_root.clone()),
+            #[cfg(feature = "yarn_pnp")]
+            pnp_manifest_paths: strategy.merge_list(
+                &self.pnp_manifest_paths,
+                pnp_manifest_paths,
+                &default.pnp_manifest_paths,
+            ),
+        }
+    }
+}
+
+/// `other` when it is not the default value.
+fn merge_scalar<T: Copy + PartialEq>(base: T, other: T, default: T) -> T {
+    if other == default { base } else { other }
+}
+
+/// How [ResolveOptions::merge_with] combines the list and map fields which are set in both
+/// option sets.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MergeStrategy {
+    /// Lists are concatenated and deduplicated, maps are merged by key.
+    #[default]
+    Concat,
+    /// Lists and maps of the other options replace the base ones.
+    Replace,
+}
+
+impl MergeStrategy {
+    fn merge_list<T: Clone + PartialEq>(self, base: &[T], other: &[T], default: &[T]) -> Vec<T> {
+        if other == default {
+            return base.to_vec();
+        }
+        match self {
+            Self::Concat => {
+                let mut merged = base.to_vec();
+                for value in other {
+                    if !merged.contains(value) {
+                        merged.push(value.clone());
+                    }
+                }
+                merged
+            }
+            Self::Replace => other.to_vec(),
+        }
+    }
+
+    fn merge_map<V: Clone>(self, base: &[(String, V)], other: &[(String, V)]) -> Vec<(String, V)> {
+        if other.is_empty() {
+            return base.to_vec();
+        }
+        match self {
+            Self::Concat => {
+                let mut merged = base.to_vec();
+                for (key, value) in other {
+                    if let Some(entry) = merged.iter_mut().find(|(k, _)| k == key) {
+                        entry.1 = value.clone();
+                    } else {
+                        merged.push((key.clone(), value.clone()));
+                    }
+                }
+                merged
+            }
+            Self::Replace => other.to_vec(),
+        }
+    }
+
+    fn merge_restrictions(self, base: &[Restriction], other: &[Restriction]) -> Vec<Restriction> {
+        if other.is_empty() {
+            return base.to_vec();
+        }
+        match self {
+            Self::Concat => {
+                let mut merged = base.to_vec();
+                for restriction in other {
+                    let duplicate = merged.iter().any(|r| match (r, restriction) {
+                        (Restriction::Path(a), Restriction::Path(b)) => a == b,
+                        (Restriction::Fn(a), Restriction::Fn(b)) => Arc::ptr_eq(a, b),
+                        _ => false,
+                    });
+                    if !duplicate {
+                        merged.push(restriction.clone());
+                    }
+                }
+                merged
+            }
+            Self::Replace => other.to_vec(),
+        }
+    }
+}
+
+/// Serialize [ResolveOptions::restrictions] without [Restriction::Fn], which cannot be serialized.
+#[cfg(feature = "serde")]
+fn serialize_restrictions<S: serde::Serializer>(
+    restrictions: &[Restriction],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(restrictions.iter().filter(|r| matches!(r, Restriction::Path(_))))
 }
 
 /// Value for [ResolveOptions::enforce_extension]
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnforceExtension {
     Auto,
     Enabled,
@@ -476,11 +1281,38 @@ impl EnforceExtension {
     }
 }
 
+/// Options replaced for a single call of [crate::ResolverImpl::resolve_with_options_override].
+#[derive(Debug, Default, Clone)]
+pub struct ResolveOverrides {
+    /// Replaces [ResolveOptions::condition_names] and the result of
+    /// [ResolveOptions::condition_names_provider].
+    pub condition_names: Option<Vec<String>>,
+
+    /// Replaces [ResolveOptions::main_fields].
+    pub main_fields: Option<Vec<String>>,
+}
+
+/// Value for [ResolveOptions::case_sensitivity]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CaseSensitivity {
+    /// Paths must match the case of the file names on disk, unless the filesystem itself
+    /// ignores case.
+    #[default]
+    Sensitive,
+    /// Paths which are not found are looked up ignoring case.
+    Insensitive,
+    /// [CaseSensitivity::Insensitive] when the filesystem ignores case, which is checked by
+    /// looking up [ResolveOptions::cwd], or the current directory, with its case flipped.
+    Auto,
+}
+
 /// Alias for [ResolveOptions::alias] and [ResolveOptions::fallback]
 pub type Alias = Vec<(String, Vec<AliasValue>)>;
 
 /// Alias Value for [ResolveOptions::alias] and [ResolveOptions::fallback]
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AliasValue {
     /// The path value
     Path(String),
@@ -499,9 +1331,14 @@ where
 }
 
 /// Value for [ResolveOptions::restrictions]
+///
+/// With the `serde` feature, [Restriction::Fn] is omitted when serializing
+/// [ResolveOptions::restrictions], and fails to serialize on its own.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Restriction {
     Path(PathBuf),
+    #[cfg_attr(feature = "serde", serde(skip))]
     Fn(Arc<dyn Fn(&Path) -> bool + Sync + Send>),
 }
 
@@ -514,7 +1351,26 @@ impl std::fmt::Debug for Restriction {
     }
 }
 
+type ConditionNamesFn = dyn Fn(&Path) -> Option<Vec<String>> + Send + Sync;
+
+/// Value for [ResolveOptions::condition_names_provider].
+#[derive(Clone)]
+pub struct ConditionNamesProvider(pub Arc<ConditionNamesFn>);
+
+impl ConditionNamesProvider {
+    pub fn new<F: Fn(&Path) -> Option<Vec<String>> + Send + Sync + 'static>(f: F) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for ConditionNamesProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConditionNamesProvider(<function>)")
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TsconfigDiscovery {
     Auto,
     Manual(TsconfigOptions),
@@ -524,11 +1380,15 @@ pub enum TsconfigDiscovery {
 ///
 /// Derived from [tsconfig-paths-webpack-plugin](https://github.com/dividab/tsconfig-paths-webpack-plugin#options)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TsconfigOptions {
     /// Allows you to specify where to find the TypeScript configuration file.
     /// You may provide
     /// * a relative path to the configuration file. It will be resolved relative to cwd.
     /// * an absolute path to the configuration file.
+    /// * a directory containing `tsconfig.json`, or `jsconfig.json` when there is no `tsconfig.json`.
+    ///
+    /// A `jsconfig.json` is parsed like a `tsconfig.json`, with `allowJs` defaulting to `true`.
     pub config_file: PathBuf,
 
     /// Support for Typescript Project References.
@@ -537,6 +1397,7 @@ pub struct TsconfigOptions {
 
 /// Configuration for [TsconfigOptions::references]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TsconfigReferences {
     Disabled,
     /// Use the `references` field from tsconfig of `config_file`.
@@ -551,13 +1412,17 @@ impl Default for ResolveOptions {
             alias: vec![],
             alias_fields: vec![],
             condition_names: vec![],
+            condition_names_provider: None,
+            description_files: vec!["package.json".into()],
             enforce_extension: EnforceExtension::Auto,
             extension_alias: vec![],
             exports_fields: vec![vec!["exports".into()]],
             imports_fields: vec![vec!["imports".into()]],
             extensions: vec![".js".into(), ".json".into(), ".node".into()],
             fallback: vec![],
+            import_map: None,
             fully_specified: false,
+            fully_specified_applies_to_node_modules: true,
             main_fields: vec!["main".into()],
             main_files: vec!["index".into()],
             modules: vec!["node_modules".into()],
@@ -565,20 +1430,44 @@ impl Default for ResolveOptions {
             prefer_relative: false,
             prefer_absolute: false,
             restrictions: vec![],
+            forbidden_extensions: vec![],
             roots: vec![],
             symlinks: true,
             node_path: true,
             builtin_modules: false,
             module_type: false,
             allow_package_exports_in_directory_resolve: false,
+            use_directories_lib: false,
+            extension_probe_report: false,
+            exports_require_exact_target: false,
+            tsconfig_paths_strict_fallback: false,
+            tsconfig_respect_include: false,
+            collect_package_json_chain: false,
+            record_matched_entry: false,
+            report_file_directory_ambiguity: false,
+            max_resolve_depth: 64,
+            adaptive_extension_probing: false,
+            typescript_version: None,
+            ignore_missing_optional_peers: false,
+            module_type_detection: false,
+            prefer_module_field_over_exports: false,
+            case_sensitivity: CaseSensitivity::Sensitive,
+            imports_fallback_scopes: vec![],
+            external: vec![],
+            dependency_sink: None,
             #[cfg(feature = "yarn_pnp")]
             yarn_pnp: std::env::var("OXC_RESOLVER_YARN_PNP").is_ok(),
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp_root: None,
+            #[cfg(feature = "yarn_pnp")]
+            pnp_manifest_paths: vec![],
         }
     }
 }
 
 // For tracing
 impl fmt::Display for ResolveOptions {
+    #[expect(clippy::too_many_lines, reason = "one line per option")]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(tsconfig) = &self.tsconfig {
             write!(f, "tsconfig:{tsconfig:?},")?;
@@ -592,6 +1481,12 @@ impl fmt::Display for ResolveOptions {
         if !self.condition_names.is_empty() {
             write!(f, "condition_names:{:?},", self.condition_names)?;
         }
+        if let Some(condition_names_provider) = &self.condition_names_provider {
+            write!(f, "condition_names_provider:{condition_names_provider:?},")?;
+        }
+        if !self.description_files.is_empty() {
+            write!(f, "description_files:{:?},", self.description_files)?;
+        }
         if self.enforce_extension.is_enabled() {
             write!(f, "enforce_extension:{:?},", self.enforce_extension)?;
         }
@@ -610,9 +1505,19 @@ impl fmt::Display for ResolveOptions {
         if !self.fallback.is_empty() {
             write!(f, "fallback:{:?},", self.fallback)?;
         }
+        if let Some(import_map) = &self.import_map {
+            write!(f, "import_map:{import_map:?},")?;
+        }
         if self.fully_specified {
             write!(f, "fully_specified:{:?},", self.fully_specified)?;
         }
+        if self.fully_specified_applies_to_node_modules {
+            write!(
+                f,
+                "fully_specified_applies_to_node_modules:{:?},",
+                self.fully_specified_applies_to_node_modules
+            )?;
+        }
         if !self.main_fields.is_empty() {
             write!(f, "main_fields:{:?},", self.main_fields)?;
         }
@@ -634,6 +1539,9 @@ impl fmt::Display for ResolveOptions {
         if !self.restrictions.is_empty() {
             write!(f, "restrictions:{:?},", self.restrictions)?;
         }
+        if !self.forbidden_extensions.is_empty() {
+            write!(f, "forbidden_extensions:{:?},", self.forbidden_extensions)?;
+        }
         if !self.roots.is_empty() {
             write!(f, "roots:{:?},", self.roots)?;
         }
@@ -653,6 +1561,68 @@ impl fmt::Display for ResolveOptions {
                 self.allow_package_exports_in_directory_resolve
             )?;
         }
+        if self.use_directories_lib {
+            write!(f, "use_directories_lib:{:?},", self.use_directories_lib)?;
+        }
+        if self.extension_probe_report {
+            write!(f, "extension_probe_report:{:?},", self.extension_probe_report)?;
+        }
+        if self.exports_require_exact_target {
+            write!(f, "exports_require_exact_target:{:?},", self.exports_require_exact_target)?;
+        }
+        if self.tsconfig_paths_strict_fallback {
+            write!(f, "tsconfig_paths_strict_fallback:{:?},", self.tsconfig_paths_strict_fallback)?;
+        }
+        if self.tsconfig_respect_include {
+            write!(f, "tsconfig_respect_include:{:?},", self.tsconfig_respect_include)?;
+        }
+        if self.collect_package_json_chain {
+            write!(f, "collect_package_json_chain:{:?},", self.collect_package_json_chain)?;
+        }
+        if self.record_matched_entry {
+            write!(f, "record_matched_entry:{:?},", self.record_matched_entry)?;
+        }
+        if self.report_file_directory_ambiguity {
+            write!(
+                f,
+                "report_file_directory_ambiguity:{:?},",
+                self.report_file_directory_ambiguity
+            )?;
+        }
+        if self.max_resolve_depth != Self::default().max_resolve_depth {
+            write!(f, "max_resolve_depth:{:?},", self.max_resolve_depth)?;
+        }
+        if self.adaptive_extension_probing {
+            write!(f, "adaptive_extension_probing:{:?},", self.adaptive_extension_probing)?;
+        }
+        if let Some(typescript_version) = &self.typescript_version {
+            write!(f, "typescript_version:{typescript_version},")?;
+        }
+        if self.ignore_missing_optional_peers {
+            write!(f, "ignore_missing_optional_peers:{:?},", self.ignore_missing_optional_peers)?;
+        }
+        if self.module_type_detection {
+            write!(f, "module_type_detection:{:?},", self.module_type_detection)?;
+        }
+        if self.prefer_module_field_over_exports {
+            write!(
+                f,
+                "prefer_module_field_over_exports:{:?},",
+                self.prefer_module_field_over_exports
+            )?;
+        }
+        if self.case_sensitivity != CaseSensitivity::Sensitive {
+            write!(f, "case_sensitivity:{:?},", self.case_sensitivity)?;
+        }
+        if !self.imports_fallback_scopes.is_empty() {
+            write!(f, "imports_fallback_scopes:{:?},", self.imports_fallback_scopes)?;
+        }
+        if !self.external.is_empty() {
+            write!(f, "external:{:?},", self.external)?;
+        }
+        if let Some(dependency_sink) = &self.dependency_sink {
+            write!(f, "dependency_sink:{dependency_sink:?},")?;
+        }
         Ok(())
     }
 }
@@ -662,8 +1632,9 @@ mod test {
     use std::path::PathBuf;
 
     use super::{
-        AliasValue, EnforceExtension, ResolveOptions, Restriction, TsconfigDiscovery,
-        TsconfigOptions, TsconfigReferences,
+        AliasValue, CaseSensitivity, ConditionNamesProvider, DependencySink, EnforceExtension,
+        ImportMap, MergeStrategy, ResolveOptions, ResolveOptionsBuilder, Restriction,
+        TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
     };
 
     #[test]
@@ -682,6 +1653,7 @@ mod test {
     }
 
     #[test]
+    #[expect(clippy::too_many_lines, reason = "exhaustive literal of every option")]
     fn display() {
         let options = ResolveOptions {
             tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
@@ -691,23 +1663,45 @@ mod test {
             alias: vec![("a".into(), vec![AliasValue::Ignore])],
             alias_fields: vec![vec!["browser".into()]],
             condition_names: vec!["require".into()],
+            condition_names_provider: Some(ConditionNamesProvider::new(|_| None)),
             enforce_extension: EnforceExtension::Enabled,
             extension_alias: vec![(".js".into(), vec![".ts".into()])],
             exports_fields: vec![vec!["exports".into()]],
             imports_fields: vec![vec!["imports".into()]],
             fallback: vec![("fallback".into(), vec![AliasValue::Ignore])],
+            import_map: Some(ImportMap::default()),
             fully_specified: true,
+            fully_specified_applies_to_node_modules: true,
             resolve_to_context: true,
             prefer_relative: true,
             prefer_absolute: true,
             restrictions: vec![Restriction::Path(PathBuf::from("restrictions"))],
+            forbidden_extensions: vec![".env".into()],
             roots: vec![PathBuf::from("roots")],
             builtin_modules: true,
             allow_package_exports_in_directory_resolve: true,
+            use_directories_lib: true,
+            extension_probe_report: true,
+            exports_require_exact_target: true,
+            tsconfig_paths_strict_fallback: true,
+            tsconfig_respect_include: true,
+            collect_package_json_chain: true,
+            record_matched_entry: true,
+            report_file_directory_ambiguity: true,
+            max_resolve_depth: 8,
+            adaptive_extension_probing: true,
+            typescript_version: Some(semver::Version::new(5, 4, 0)),
+            ignore_missing_optional_peers: true,
+            module_type_detection: true,
+            prefer_module_field_over_exports: true,
+            case_sensitivity: CaseSensitivity::Insensitive,
+            imports_fallback_scopes: vec![PathBuf::from("workspace")],
+            external: vec!["react$".into()],
+            dependency_sink: Some(DependencySink::new(|_, _| {})),
             ..ResolveOptions::default()
         };
 
-        let expected = r#"tsconfig:Manual(TsconfigOptions { config_file: "tsconfig.json", references: Auto }),alias:[("a", [Ignore])],alias_fields:[["browser"]],condition_names:["require"],enforce_extension:Enabled,exports_fields:[["exports"]],imports_fields:[["imports"]],extension_alias:[(".js", [".ts"])],extensions:[".js", ".json", ".node"],fallback:[("fallback", [Ignore])],fully_specified:true,main_fields:["main"],main_files:["index"],modules:["node_modules"],resolve_to_context:true,prefer_relative:true,prefer_absolute:true,restrictions:[Path("restrictions")],roots:["roots"],symlinks:true,builtin_modules:true,allow_package_exports_in_directory_resolve:true,"#;
+        let expected = r#"tsconfig:Manual(TsconfigOptions { config_file: "tsconfig.json", references: Auto }),alias:[("a", [Ignore])],alias_fields:[["browser"]],condition_names:["require"],condition_names_provider:ConditionNamesProvider(<function>),description_files:["package.json"],enforce_extension:Enabled,exports_fields:[["exports"]],imports_fields:[["imports"]],extension_alias:[(".js", [".ts"])],extensions:[".js", ".json", ".node"],fallback:[("fallback", [Ignore])],import_map:ImportMap { imports: {}, scopes: {}, base: "" },fully_specified:true,fully_specified_applies_to_node_modules:true,main_fields:["main"],main_files:["index"],modules:["node_modules"],resolve_to_context:true,prefer_relative:true,prefer_absolute:true,restrictions:[Path("restrictions")],forbidden_extensions:[".env"],roots:["roots"],symlinks:true,builtin_modules:true,allow_package_exports_in_directory_resolve:true,use_directories_lib:true,extension_probe_report:true,exports_require_exact_target:true,tsconfig_paths_strict_fallback:true,tsconfig_respect_include:true,collect_package_json_chain:true,record_matched_entry:true,report_file_directory_ambiguity:true,max_resolve_depth:8,adaptive_extension_probing:true,typescript_version:5.4.0,ignore_missing_optional_peers:true,module_type_detection:true,prefer_module_field_over_exports:true,case_sensitivity:Insensitive,imports_fallback_scopes:["workspace"],external:["react$"],dependency_sink:DependencySink(<function>),"#;
         assert_eq!(format!("{options}"), expected);
 
         let options = ResolveOptions {
@@ -717,29 +1711,167 @@ mod test {
             node_path: true,
             builtin_modules: false,
             condition_names: vec![],
+            condition_names_provider: None,
+            description_files: vec![],
             enforce_extension: EnforceExtension::Disabled,
             exports_fields: vec![],
             extension_alias: vec![],
             extensions: vec![],
             fallback: vec![],
+            import_map: None,
             fully_specified: false,
+            fully_specified_applies_to_node_modules: false,
             imports_fields: vec![],
             main_fields: vec![],
             main_files: vec![],
             modules: vec![],
             #[cfg(feature = "yarn_pnp")]
             yarn_pnp: false,
+            #[cfg(feature = "yarn_pnp")]
+            yarn_pnp_root: None,
+            #[cfg(feature = "yarn_pnp")]
+            pnp_manifest_paths: vec![],
             prefer_absolute: false,
             prefer_relative: false,
             resolve_to_context: false,
             restrictions: vec![],
+            forbidden_extensions: vec![],
             roots: vec![],
             symlinks: false,
             tsconfig: None,
             module_type: false,
             allow_package_exports_in_directory_resolve: false,
+            use_directories_lib: false,
+            extension_probe_report: false,
+            exports_require_exact_target: false,
+            tsconfig_paths_strict_fallback: false,
+            tsconfig_respect_include: false,
+            collect_package_json_chain: false,
+            record_matched_entry: false,
+            report_file_directory_ambiguity: false,
+            max_resolve_depth: 64,
+            adaptive_extension_probing: false,
+            typescript_version: None,
+            ignore_missing_optional_peers: false,
+            module_type_detection: false,
+            prefer_module_field_over_exports: false,
+            case_sensitivity: CaseSensitivity::Sensitive,
+            imports_fallback_scopes: vec![],
+            external: vec![],
+            dependency_sink: None,
         };
 
         assert_eq!(format!("{options}"), "");
     }
+
+    #[test]
+    fn builder() {
+        let options = ResolveOptionsBuilder::default()
+            .extensions(vec![".js".into()])
+            .alias("foo", vec![AliasValue::Ignore])
+            .tsconfig(TsconfigOptions {
+                config_file: PathBuf::from("tsconfig.json"),
+                references: TsconfigReferences::Auto,
+            })
+            .symlinks(false)
+            .build();
+        assert_eq!(options.extensions, vec![".js".to_string()]);
+        assert_eq!(options.alias, vec![("foo".into(), vec![AliasValue::Ignore])]);
+        assert!(matches!(options.tsconfig, Some(TsconfigDiscovery::Manual(_))));
+        assert!(!options.symlinks);
+        assert_eq!(options.enforce_extension, EnforceExtension::Disabled);
+
+        let options = ResolveOptions::builder().extension("").build();
+        assert_eq!(options.enforce_extension, EnforceExtension::Enabled);
+        let options = ResolveOptions::builder()
+            .extension("")
+            .enforce_extension(EnforceExtension::Disabled)
+            .build();
+        assert_eq!(options.enforce_extension, EnforceExtension::Disabled);
+
+        // Building is idempotent with the sanitizing of the resolver.
+        let options = ResolveOptions::builder().node_path(true).build();
+        assert_eq!(options.clone().sanitize().modules, options.modules);
+    }
+
+    #[test]
+    fn merge() {
+        let base = ResolveOptions {
+            alias: vec![("a".into(), vec!["./a".into()]), ("b".into(), vec!["./b".into()])],
+            extensions: vec![".ts".into(), ".js".into()],
+            modules: vec!["node_modules".into(), "src".into()],
+            fully_specified: true,
+            tsconfig: Some(TsconfigDiscovery::Auto),
+            ..ResolveOptions::default()
+        };
+        let other = ResolveOptions {
+            alias: vec![("b".into(), vec![AliasValue::Ignore]), ("c".into(), vec!["./c".into()])],
+            extensions: vec![".tsx".into(), ".ts".into()],
+            modules: vec!["node_modules".into(), "lib".into()],
+            symlinks: false,
+            tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+                config_file: PathBuf::from("tsconfig.json"),
+                references: TsconfigReferences::Disabled,
+            })),
+            ..ResolveOptions::default()
+        };
+
+        let merged = base.merge(&other);
+        let alias = vec![
+            ("a".into(), vec!["./a".into()]),
+            ("b".into(), vec![AliasValue::Ignore]),
+            ("c".into(), vec!["./c".into()]),
+        ];
+        assert_eq!(merged.alias, alias);
+        assert_eq!(merged.extensions, vec![".ts", ".js", ".tsx"]);
+        assert_eq!(merged.modules, vec!["node_modules", "src", "lib"]);
+        // Defaults of `other` do not override.
+        assert!(merged.fully_specified);
+        assert_eq!(merged.main_fields, vec!["main"]);
+        assert!(!merged.symlinks);
+        assert!(matches!(merged.tsconfig, Some(TsconfigDiscovery::Manual(_))));
+
+        let merged = base.merge_with(&other, MergeStrategy::Replace);
+        assert_eq!(merged.alias, other.alias);
+        assert_eq!(merged.extensions, vec![".tsx", ".ts"]);
+        assert_eq!(merged.modules, vec!["node_modules", "lib"]);
+        assert!(merged.fully_specified);
+
+        let merged = base.merge(&ResolveOptions::default());
+        assert_eq!(format!("{merged}"), format!("{base}"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let options = ResolveOptions {
+            tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+                config_file: PathBuf::from("tsconfig.json"),
+                references: TsconfigReferences::Auto,
+            })),
+            alias: vec![("a".into(), vec![AliasValue::Ignore, "./b".into()])],
+            enforce_extension: EnforceExtension::Enabled,
+            restrictions: vec![
+                Restriction::Path(PathBuf::from("restrictions")),
+                Restriction::Fn(std::sync::Arc::new(|_| true)),
+            ],
+            symlinks: false,
+            ..ResolveOptions::default()
+        };
+        let json = serde_json::to_string(&options).unwrap();
+        let deserialized: ResolveOptions = serde_json::from_str(&json).unwrap();
+        // `Restriction::Fn` is omitted.
+        let expected =
+            ResolveOptions { restrictions: vec![options.restrictions[0].clone()], ..options };
+        assert_eq!(format!("{deserialized}"), format!("{expected}"));
+
+        serde_json::to_string(&Restriction::Fn(std::sync::Arc::new(|_| true))).unwrap_err();
+
+        // Missing fields are defaults.
+        let options: ResolveOptions =
+            serde_json::from_str(r#"{ "extensions": [".ts"], "tsconfig": "Auto" }"#).unwrap();
+        assert_eq!(options.extensions, vec![".ts"]);
+        assert!(matches!(options.tsconfig, Some(TsconfigDiscovery::Auto)));
+        assert_eq!(options.main_fields, ResolveOptions::default().main_fields);
+    }
 }
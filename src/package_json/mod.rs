@@ -9,16 +9,19 @@
 mod serde;
 #[cfg(target_endian = "little")]
 mod simd;
+mod types_versions;
 
 use std::{
     fmt,
     path::{Path, PathBuf},
+    sync::OnceLock,
 };
 
 #[cfg(target_endian = "big")]
 pub use serde::*;
 #[cfg(target_endian = "little")]
 pub use simd::*;
+pub use types_versions::{TypesVersionsMap, TypesVersionsPaths};
 
 use crate::{JSONError, ResolveError, path::PathUtil};
 
@@ -69,13 +72,51 @@ pub enum ImportsExportsKind {
     Invalid,
 }
 
+/// The "sideEffects" field, see [PackageJson::side_effects].
+///
+/// Borrowed from the parsed package.json, so reading it does not parse the file again.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SideEffects<'a> {
+    /// `false` when no module of the package has side effects, `true` when all may have them.
     Bool(bool),
+    /// A single glob of the modules with side effects.
     String(&'a str),
+    /// The globs of the modules with side effects.
     Array(Vec<&'a str>),
 }
 
+/// The "bin" field, see [PackageJson::bin].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BinField<'a> {
+    /// The path of the single executable, named after the package.
+    Single(&'a str),
+    /// The executable names and their paths, in field order.
+    Map(Vec<(&'a str, &'a str)>),
+}
+
+impl<'a> BinField<'a> {
+    /// The path of the executable `name`.
+    ///
+    /// Without a name, the executable named after `package_name` is taken, or the only one.
+    #[must_use]
+    pub fn path(&self, package_name: &str, name: Option<&str>) -> Option<&'a str> {
+        // `@scope/pkg` installs the executable `pkg`.
+        let default_name = package_name.rsplit('/').next().unwrap_or(package_name);
+        match self {
+            Self::Single(path) => name.is_none_or(|name| name == default_name).then_some(*path),
+            Self::Map(entries) => {
+                let name = name.unwrap_or(default_name);
+                entries.iter().find(|(key, _)| *key == name).map(|(_, path)| *path).or_else(|| {
+                    match entries.as_slice() {
+                        [(_, path)] if name == default_name => Some(*path),
+                        _ => None,
+                    }
+                })
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // JSON backend abstraction
 // ---------------------------------------------------------------------------
@@ -151,6 +192,19 @@ pub struct PackageJsonGeneric<S> {
     pub realpath: PathBuf,
 
     pub(crate) store: S,
+
+    /// Memoized [ImportsExportsMapGeneric::has_mixed_keys] of the `"exports"` field.
+    pub(crate) exports_mixed_keys: OnceLock<bool>,
+
+    /// Number of key scans by [Self::exports_has_mixed_keys].
+    #[cfg(test)]
+    pub(crate) exports_mixed_keys_scans: std::sync::atomic::AtomicUsize,
+
+    /// Memoized [Self::types_versions].
+    pub(crate) types_versions: OnceLock<Option<TypesVersionsMap>>,
+
+    /// Memoized [Self::workspaces].
+    pub(crate) workspaces: OnceLock<Option<Box<[String]>>>,
 }
 
 impl<S: PackageJsonBackend> fmt::Debug for PackageJsonGeneric<S> {
@@ -194,7 +248,8 @@ impl<S: PackageJsonBackend> PackageJsonGeneric<S> {
     /// * When the `package.json` path is misconfigured.
     #[must_use]
     pub fn directory(&self) -> &Path {
-        debug_assert!(self.realpath.file_name().is_some_and(|x| x == "package.json"));
+        // Any of `ResolveOptions::description_files`, not only `package.json`.
+        debug_assert!(self.realpath.file_name().is_some());
         self.realpath.parent().unwrap()
     }
 
@@ -246,6 +301,62 @@ impl<S: PackageJsonBackend> PackageJsonGeneric<S> {
             .map(|arr| SideEffects::Array(arr.iter().filter_map(JsonValue::as_str).collect()))
     }
 
+    /// The "bin" field, the executables of the package.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#bin>
+    #[must_use]
+    pub fn bin(&self) -> Option<BinField<'_>> {
+        let value = self.field("bin")?;
+        if let Some(path) = value.as_str() {
+            return Some(BinField::Single(path));
+        }
+        let entries = value
+            .as_object()?
+            .iter()
+            .filter_map(|(name, path)| Some((name, path.as_str()?)))
+            .collect();
+        Some(BinField::Map(entries))
+    }
+
+    /// The "peerDependenciesMeta" field, the names of the peer dependencies it describes and
+    /// whether each is `"optional"`, in field order.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#peerdependenciesmeta>
+    #[must_use]
+    pub fn peer_dependencies_meta(&self) -> Option<Vec<(&str, bool)>> {
+        let entries = self
+            .field("peerDependenciesMeta")?
+            .as_object()?
+            .iter()
+            .map(|(name, meta)| {
+                let optional = meta
+                    .as_object()
+                    .and_then(|meta| meta.get("optional")?.as_bool())
+                    .unwrap_or_default();
+                (name, optional)
+            })
+            .collect();
+        Some(entries)
+    }
+
+    /// The names in the "optionalDependencies" field, in field order.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#optionaldependencies>
+    #[must_use]
+    pub fn optional_dependencies(&self) -> Option<Vec<&str>> {
+        Some(
+            self.field("optionalDependencies")?.as_object()?.iter().map(|(name, _)| name).collect(),
+        )
+    }
+
+    /// Whether `package_name` is an optional peer dependency or an optional dependency, see
+    /// [crate::ResolveOptions::ignore_missing_optional_peers].
+    pub(crate) fn is_optional_dependency(&self, package_name: &str) -> bool {
+        self.peer_dependencies_meta().is_some_and(|peers| {
+            peers.iter().any(|(name, optional)| *optional && *name == package_name)
+        }) || self.optional_dependencies().is_some_and(|names| names.contains(&package_name))
+    }
+
     /// The "exports" field allows defining the entry points of a package.
     ///
     /// <https://nodejs.org/api/packages.html#exports>
@@ -270,16 +381,53 @@ impl<S: PackageJsonBackend> PackageJsonGeneric<S> {
         self.field("typings")?.as_str()
     }
 
-    /// The "typesVersions" field in package.json.
+    /// The "module" field in package.json.
+    ///
+    /// The ES module entry point of bundlers predating the "exports" field, see
+    /// [crate::ResolveOptions::prefer_module_field_over_exports].
+    #[must_use]
+    pub fn module(&self) -> Option<&str> {
+        self.field("module")?.as_str()
+    }
+
+    /// The "directories.lib" field in package.json.
     ///
-    /// Returns the raw JSON value for the "typesVersions" field, which maps
-    /// TypeScript version ranges to path redirect maps.
+    /// A legacy npm convention pointing to the directory holding the library code.
+    /// Only consulted when [crate::ResolveOptions::use_directories_lib] is enabled.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#directorieslib>
+    #[must_use]
+    pub fn directories_lib(&self) -> Option<&str> {
+        self.field("directories")?.as_object()?.get("lib")?.as_str()
+    }
+
+    /// The "typesVersions" field in package.json, which maps TypeScript version ranges to path
+    /// redirect maps. Parsed once.
     ///
     /// <https://www.typescriptlang.org/docs/handbook/declaration-files/publishing.html#version-selection-with-typesversions>
-    pub(crate) fn types_versions(
-        &self,
-    ) -> Option<ImportsExportsMapGeneric<'_, <S::Value<'_> as JsonValue>::Object>> {
-        Some(ImportsExportsMapGeneric(self.field("typesVersions")?.as_object()?))
+    #[must_use]
+    pub fn types_versions(&self) -> Option<&TypesVersionsMap> {
+        self.types_versions
+            .get_or_init(|| TypesVersionsMap::from_json(self.field("typesVersions")?))
+            .as_ref()
+    }
+
+    /// The "workspaces" field of a monorepo root, the glob patterns of the directories of its
+    /// local packages, e.g. `["packages/*"]`. Parsed once.
+    ///
+    /// The object form of Yarn, `{ "packages": ["packages/*"] }`, is read as its `packages`.
+    ///
+    /// <https://docs.npmjs.com/cli/using-npm/workspaces>
+    #[must_use]
+    pub fn workspaces(&self) -> Option<&[String]> {
+        self.workspaces
+            .get_or_init(|| {
+                let value = self.field("workspaces")?;
+                let patterns =
+                    value.as_slice().or_else(|| value.as_object()?.get("packages")?.as_slice())?;
+                Some(patterns.iter().filter_map(JsonValue::as_str).map(String::from).collect())
+            })
+            .as_deref()
     }
 
     /// The "main" field defines the entry point of a package when imported by
@@ -288,18 +436,19 @@ impl<S: PackageJsonBackend> PackageJsonGeneric<S> {
     /// When a package has an "exports" field, this will take precedence over
     /// the "main" field when importing the package by name.
     ///
-    /// Values are dynamically retrieved from [crate::ResolveOptions::main_fields].
+    /// Values are dynamically retrieved from [crate::ResolveOptions::main_fields], each with the
+    /// name of its field.
     ///
     /// <https://nodejs.org/api/packages.html#main>
     pub(crate) fn main_fields<'a>(
         &'a self,
         main_fields: &'a [String],
-    ) -> impl Iterator<Item = &'a str> + 'a {
+    ) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
         let object = self.store.root().as_object();
-        main_fields
-            .iter()
-            .filter_map(move |main_field| object?.get(main_field.as_str()))
-            .filter_map(JsonValue::as_str)
+        main_fields.iter().filter_map(move |main_field| {
+            let value = object?.get(main_field.as_str())?.as_str()?;
+            Some((main_field.as_str(), value))
+        })
     }
 
     /// The "exports" field allows defining the entry points of a package when
@@ -318,6 +467,28 @@ impl<S: PackageJsonBackend> PackageJsonGeneric<S> {
             .map(ImportsExportsEntryGeneric)
     }
 
+    /// Whether `exports` is an object mixing keys starting with `.` and keys not starting with `.`,
+    /// which is an Invalid Package Configuration.
+    ///
+    /// The outcome never changes for a parsed `package.json`, so it is computed once for the
+    /// `"exports"` field instead of on every resolution into the package.
+    /// Other [crate::ResolveOptions::exports_fields] are checked every time.
+    pub(crate) fn exports_has_mixed_keys<'a>(
+        &'a self,
+        exports: &ImportsExportsEntryGeneric<'a, S::Value<'a>>,
+    ) -> bool {
+        let Some(map) = exports.as_map() else { return false };
+        let scan = || {
+            #[cfg(test)]
+            self.exports_mixed_keys_scans.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            map.has_mixed_keys()
+        };
+        if self.field("exports").is_some_and(|value| std::ptr::eq(value, exports.0)) {
+            return *self.exports_mixed_keys.get_or_init(scan);
+        }
+        scan()
+    }
+
     /// In addition to the "exports" field, there is a package "imports" field
     /// to create private mappings that only apply to import specifiers from
     /// within the package itself.
@@ -457,6 +628,21 @@ impl<'a, O: JsonObject> ImportsExportsMapGeneric<'a, O> {
         self.0.iter().map(|(key, _)| key)
     }
 
+    /// Whether some keys start with `.` or `#` and some do not.
+    pub(crate) fn has_mixed_keys(&self) -> bool {
+        let mut has_dot = false;
+        let mut without_dot = false;
+        for key in self.keys() {
+            let starts_with_dot_or_hash = key.starts_with(['.', '#']);
+            has_dot = has_dot || starts_with_dot_or_hash;
+            without_dot = without_dot || !starts_with_dot_or_hash;
+            if has_dot && without_dot {
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn iter(
         &self,
     ) -> impl Iterator<Item = (&'a str, ImportsExportsEntryGeneric<'a, O::Value>)> {
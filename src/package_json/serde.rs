@@ -3,7 +3,7 @@
 //! The accessor logic lives in [`super`]; this module only provides the storage
 //! (an owned [`Value`]), the [`JsonValue`]/[`JsonObject`] implementations, and `parse`.
 
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::OnceLock};
 
 use serde_json::{Map, Value};
 
@@ -103,6 +103,15 @@ impl PackageJson {
             line: error.line(),
             column: error.column(),
         })?;
-        Ok(Self { path, realpath, store: value })
+        Ok(Self {
+            path,
+            realpath,
+            store: value,
+            exports_mixed_keys: OnceLock::new(),
+            #[cfg(test)]
+            exports_mixed_keys_scans: std::sync::atomic::AtomicUsize::new(0),
+            types_versions: OnceLock::new(),
+            workspaces: OnceLock::new(),
+        })
     }
 }
@@ -8,7 +8,7 @@
     reason = "`self_cell!` generates `pub` constructors with `impl FnOnce` parameters"
 )]
 
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::OnceLock};
 
 use self_cell::MutBorrow;
 use simd_json::BorrowedValue;
@@ -165,6 +165,15 @@ impl PackageJson {
             }
         })?;
 
-        Ok(Self { path, realpath, store: cell })
+        Ok(Self {
+            path,
+            realpath,
+            store: cell,
+            exports_mixed_keys: OnceLock::new(),
+            #[cfg(test)]
+            exports_mixed_keys_scans: std::sync::atomic::AtomicUsize::new(0),
+            types_versions: OnceLock::new(),
+            workspaces: OnceLock::new(),
+        })
     }
 }
@@ -0,0 +1,129 @@
+//! The `typesVersions` field, see [TypesVersionsMap].
+
+use semver::{Prerelease, Version, VersionReq};
+
+use super::{JsonObject, JsonValue};
+
+/// Path mappings of a `typesVersions` entry, e.g. `{ "*": ["ts3.1/*"] }`, in field order.
+///
+/// A pattern maps to several targets when its value is an array.
+pub type TypesVersionsPaths = Vec<(String, Vec<String>)>;
+
+/// The parsed `"typesVersions"` field of a package.json, a list of TypeScript version ranges
+/// and their path mappings, in field order.
+///
+/// <https://www.typescriptlang.org/docs/handbook/declaration-files/publishing.html#version-selection-with-typesversions>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypesVersionsMap(Vec<(String, TypesVersionsPaths)>);
+
+impl TypesVersionsMap {
+    pub(super) fn from_json<V: JsonValue>(value: &V) -> Option<Self> {
+        let entries = value
+            .as_object()?
+            .iter()
+            .filter_map(|(range, paths)| {
+                let paths = paths
+                    .as_object()?
+                    .iter()
+                    .filter_map(|(pattern, targets)| {
+                        let targets = if let Some(target) = targets.as_str() {
+                            vec![target.to_string()]
+                        } else {
+                            targets
+                                .as_slice()?
+                                .iter()
+                                .filter_map(JsonValue::as_str)
+                                .map(String::from)
+                                .collect()
+                        };
+                        Some((pattern.to_string(), targets))
+                    })
+                    .collect();
+                Some((range.to_string(), paths))
+            })
+            .collect();
+        Some(Self(entries))
+    }
+
+    /// The version ranges and their path mappings.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TypesVersionsPaths)> {
+        self.0.iter().map(|(range, paths)| (range.as_str(), paths))
+    }
+
+    /// The path mappings of the first entry whose range matches `typescript_version`, like
+    /// TypeScript picks them.
+    ///
+    /// Without a version, the first entry with mappings is taken, which is the `*` entry of
+    /// most packages.
+    #[must_use]
+    pub fn paths_for(&self, typescript_version: Option<&Version>) -> Option<&TypesVersionsPaths> {
+        let Some(version) = typescript_version else {
+            return self.0.iter().find(|(_, paths)| !paths.is_empty()).map(|(_, paths)| paths);
+        };
+        // TypeScript's prereleases satisfy the ranges of their release.
+        let version = Version { pre: Prerelease::EMPTY, ..version.clone() };
+        self.0
+            .iter()
+            .find(|(range, _)| version_range_matches(range, &version))
+            .map(|(_, paths)| paths)
+    }
+}
+
+/// Whether `version` is in a `typesVersions` range such as `*`, `>=3.1`, `>=4.2 <5` or
+/// `<3.9 || >=4.1`.
+///
+/// Bare versions match like `=` does, e.g. `3.1` matches `3.1.x`. An unparsable range matches
+/// nothing.
+fn version_range_matches(range: &str, version: &Version) -> bool {
+    range.split("||").any(|range| {
+        let comparators = range.split_whitespace().collect::<Vec<_>>();
+        let comparators = match comparators.as_slice() {
+            [] | ["*"] => return true,
+            // Hyphen range, `3.1 - 4.0`
+            [from, "-", to] => format!(">={from}, <={to}"),
+            comparators => comparators
+                .iter()
+                .map(|comparator| {
+                    if comparator.starts_with(['<', '>', '=', '~', '^']) {
+                        (*comparator).to_string()
+                    } else {
+                        format!("={comparator}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        VersionReq::parse(&comparators).is_ok_and(|req| req.matches(version))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use super::version_range_matches;
+
+    #[test]
+    fn version_range() {
+        #[rustfmt::skip]
+        let data = [
+            ("*", "3.0.0", true),
+            ("", "3.0.0", true),
+            (">=3.1", "3.1.0", true),
+            (">=3.1", "3.0.9", false),
+            (">=4.2 <5", "4.9.5", true),
+            (">=4.2 <5", "5.0.0", false),
+            ("<3.9 || >=4.1", "4.0.0", false),
+            ("<3.9 || >=4.1", "4.1.2", true),
+            ("3.1", "3.1.5", true),
+            ("3.1", "3.2.0", false),
+            ("3.1 - 4.0", "3.5.0", true),
+            ("3.1 - 4.0", "4.1.0", false),
+            ("not a range", "4.0.0", false),
+        ];
+        for (range, version, expected) in data {
+            let version = Version::parse(version).unwrap();
+            assert_eq!(version_range_matches(range, &version), expected, "{range} {version}");
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Resolution phase priority tables.
+//!
+//! The dispatch functions iterate over these tables, so the order in which the resolution
+//! mechanisms are tried is defined here and only here.
+//! The first phase that produces a path wins.
+
+/// Phases of resolving a request, see [REQUEST_PHASES].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RequestPhase {
+    /// [crate::ResolveOptions::import_map], only for the specifier passed to the resolver.
+    ImportMap,
+    /// `compilerOptions.paths`, `compilerOptions.rootDirs` and `compilerOptions.baseUrl` from tsconfig.
+    TsconfigPaths,
+    /// [crate::ResolveOptions::alias].
+    Alias,
+    /// Absolute ([ABSOLUTE_PHASES]), relative, `#` imports or bare ([BARE_PHASES]) specifier.
+    Specifier,
+    /// [crate::ResolveOptions::fallback], only tried when a previous phase returned an error.
+    Fallback,
+}
+
+/// Priority of the phases for resolving a request.
+pub const REQUEST_PHASES: &[RequestPhase] = &[
+    RequestPhase::ImportMap,
+    RequestPhase::TsconfigPaths,
+    RequestPhase::Alias,
+    RequestPhase::Specifier,
+    RequestPhase::Fallback,
+];
+
+/// Phases of resolving an absolute specifier, see [ABSOLUTE_PHASES].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AbsolutePhase {
+    /// Resolve as a module when [crate::ResolveOptions::prefer_absolute] is set.
+    PreferAbsolute,
+    /// [crate::ResolveOptions::roots].
+    Roots,
+    /// The path on the file system.
+    FileSystem,
+}
+
+/// Priority of the phases for resolving an absolute specifier.
+pub const ABSOLUTE_PHASES: &[AbsolutePhase] =
+    &[AbsolutePhase::PreferAbsolute, AbsolutePhase::Roots, AbsolutePhase::FileSystem];
+
+/// Phases of resolving a bare specifier, see [BARE_PHASES].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BarePhase {
+    /// Resolve as a relative path when [crate::ResolveOptions::prefer_relative] is set.
+    PreferRelative,
+    /// LOAD_PACKAGE_SELF: the `exports` field, then the browser field of the closest package.json.
+    PackageSelf,
+    /// LOAD_NODE_MODULES: [crate::ResolveOptions::modules].
+    NodeModules,
+}
+
+/// Priority of the phases for resolving a bare specifier.
+pub const BARE_PHASES: &[BarePhase] =
+    &[BarePhase::PreferRelative, BarePhase::PackageSelf, BarePhase::NodeModules];
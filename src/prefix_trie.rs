@@ -0,0 +1,87 @@
+//! Byte-wise prefix trie over rule keys.
+//!
+//! Used by [crate::alias::CompiledAlias] and the compiled tsconfig `paths` so a specifier is
+//! matched against the few rules whose static prefix it starts with, found in a single descent,
+//! instead of scanning every rule.
+
+/// Maps key prefixes to the indices of the rules declaring them.
+#[derive(Clone, Debug)]
+pub struct PrefixTrie {
+    /// `nodes[0]` is the root, holding the rules with an empty prefix.
+    nodes: Vec<PrefixTrieNode>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct PrefixTrieNode {
+    /// Sorted by byte.
+    children: Vec<(u8, u32)>,
+    /// Rule indices in insertion order.
+    values: Vec<u32>,
+}
+
+impl Default for PrefixTrie {
+    fn default() -> Self {
+        Self { nodes: vec![PrefixTrieNode::default()] }
+    }
+}
+
+impl PrefixTrie {
+    pub fn insert(&mut self, key: &[u8], value: u32) {
+        let mut node = 0;
+        for &byte in key {
+            node = match self.nodes[node].children.binary_search_by_key(&byte, |&(b, _)| b) {
+                Ok(i) => self.nodes[node].children[i].1 as usize,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(PrefixTrieNode::default());
+                    #[expect(clippy::cast_possible_truncation, reason = "rule keys are short")]
+                    self.nodes[node].children.insert(i, (byte, child as u32));
+                    child
+                }
+            };
+        }
+        self.nodes[node].values.push(value);
+    }
+
+    /// Values of every inserted key that is a prefix of `input`, shortest key first.
+    /// Values inserted under the same key keep their insertion order.
+    pub fn prefixes_of<'a>(&'a self, input: &'a [u8]) -> impl Iterator<Item = &'a [u32]> {
+        let mut node = Some(0usize);
+        let mut bytes = input.iter();
+        std::iter::from_fn(move || {
+            let current = node?;
+            node = bytes.next().and_then(|byte| {
+                let children = &self.nodes[current].children;
+                children
+                    .binary_search_by_key(byte, |&(b, _)| b)
+                    .ok()
+                    .map(|i| children[i].1 as usize)
+            });
+            Some(self.nodes[current].values.as_slice())
+        })
+        .filter(|values| !values.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PrefixTrie;
+
+    #[test]
+    fn prefixes_of() {
+        let mut trie = PrefixTrie::default();
+        trie.insert(b"@gen/a", 0);
+        trie.insert(b"", 1);
+        trie.insert(b"@gen/", 2);
+        trie.insert(b"@gen/a", 3);
+        trie.insert(b"@other", 4);
+
+        let values =
+            |input: &str| trie.prefixes_of(input.as_bytes()).flatten().copied().collect::<Vec<_>>();
+        assert_eq!(values("@gen/a/b"), vec![1, 2, 0, 3]);
+        assert_eq!(values("@gen/b"), vec![1, 2]);
+        assert_eq!(values("@gen"), vec![1]);
+        assert_eq!(values("react"), vec![1]);
+        assert_eq!(values(""), vec![1]);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
+    ffi::OsString,
     fmt,
+    hash::{Hash, Hasher},
     path::{Path, PathBuf},
     sync::Arc,
 };
 
-use crate::PackageJson;
+use crate::{FileMetadata, PackageJson};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ModuleType {
@@ -16,10 +18,22 @@ pub enum ModuleType {
 }
 
 /// The final path resolution with optional `?query` and `#fragment`
+///
+/// Two resolutions are equal, and hash the same, when their path, query and fragment are equal,
+/// i.e. when their [Resolution::full_path] is equal. The `package.json`, module type and the other
+/// details are derived from the path and do not take part in the comparison.
+/// Use [Resolution::key] to keep the identity without the details.
 #[derive(Clone)]
 pub struct Resolution {
     pub(crate) path: PathBuf,
 
+    /// The specifier passed to the resolver.
+    pub(crate) specifier: String,
+
+    /// The specifier produced by [crate::ResolveOptions::import_map], [crate::ResolveOptions::alias]
+    /// or [crate::ResolveOptions::fallback], if one was applied.
+    pub(crate) aliased_specifier: Option<String>,
+
     /// Path query `?query`, contains `?`.
     pub(crate) query: Option<String>,
 
@@ -37,15 +51,120 @@ pub struct Resolution {
     ///
     ///  The algorithm uses the file extension or finds the closest `package.json` with the `type` field.
     pub(crate) module_type: Option<ModuleType>,
+
+    /// Resolution relied on extension probing or main file fallback.
+    ///
+    /// Enable with [crate::ResolveOptions::extension_probe_report].
+    pub(crate) probed: bool,
+
+    /// The `node-addons` condition was taken in `exports` or `imports`.
+    pub(crate) via_node_addons: bool,
+
+    /// Metadata of the resolved file, taken from the resolver's cache.
+    pub(crate) file_metadata: Option<FileMetadata>,
+
+    /// The `package.json` entry which was matched.
+    ///
+    /// Enable with [crate::ResolveOptions::record_matched_entry].
+    pub(crate) package_entry: Option<PackageEntryInfo>,
+
+    /// Both a file and a directory matched the specifier.
+    ///
+    /// Enable with [crate::ResolveOptions::report_file_directory_ambiguity].
+    pub(crate) file_directory_ambiguity: Option<FileDirectoryAmbiguity>,
+}
+
+/// A file and a directory which both matched a specifier, see
+/// [Resolution::file_directory_ambiguity].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDirectoryAmbiguity {
+    /// The matching file, e.g. `/app/thing.js` for `./thing`.
+    pub file: PathBuf,
+
+    /// The matching directory, e.g. `/app/thing` for `./thing`.
+    pub directory: PathBuf,
+
+    /// Which of the two the specifier was resolved to.
+    pub winner: AmbiguityWinner,
+}
+
+/// The winner of a [FileDirectoryAmbiguity].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbiguityWinner {
+    /// The file, like in Node.js.
+    File,
+    /// The directory, because the file was excluded, e.g. by [crate::ResolveOptions::restrictions].
+    Directory,
+}
+
+/// A package whose `module` field was preferred over an `exports` field resolving the package to
+/// another file, see [crate::ResolverImpl::module_field_conflicts].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleFieldConflict {
+    /// The `package.json` of the package.
+    pub package_json: PathBuf,
+
+    /// The file the `module` field resolved to, which was returned.
+    pub module: PathBuf,
+
+    /// The file `exports` resolves the package root to.
+    pub exports: PathBuf,
+}
+
+/// A subpath of a package resolved by [crate::ResolverImpl::resolve_package_entries].
+#[derive(Debug, Clone)]
+pub struct PackageSubpathResolution {
+    /// The subpath as requested, e.g. `.` or `./utils`.
+    pub subpath: String,
+
+    /// The resolution of the subpath, or why it cannot be resolved.
+    pub resolution: Result<Resolution, crate::ResolveError>,
+}
+
+/// The `package.json` entry a [Resolution] was matched by, see [Resolution::package_entry].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageEntryInfo {
+    /// The field the entry was found in.
+    pub kind: PackageEntryKind,
+
+    /// The matched key: the subpath or pattern of `exports` and `imports` (e.g. `./feature` or
+    /// `./lib/*`), the name of the main field (e.g. `module`), or the specifier or `./` relative
+    /// path replaced by the browser field.
+    pub key: String,
+
+    /// The conditions taken to the target, outermost first, e.g. `["node", "require"]`.
+    ///
+    /// Empty for the main and browser fields.
+    pub conditions: Vec<String>,
+}
+
+/// The field of a [PackageEntryInfo].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageEntryKind {
+    /// [crate::ResolveOptions::exports_fields]
+    Exports,
+    /// [crate::ResolveOptions::imports_fields]
+    Imports,
+    /// [crate::ResolveOptions::main_fields]
+    Main,
+    /// [crate::ResolveOptions::alias_fields]
+    Browser,
 }
 
 impl fmt::Debug for Resolution {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Resolution")
             .field("path", &self.path)
+            .field("specifier", &self.specifier)
+            .field("aliased_specifier", &self.aliased_specifier)
             .field("query", &self.query)
             .field("fragment", &self.fragment)
             .field("module_type", &self.module_type)
+            .field("probed", &self.probed)
+            .field("via_node_addons", &self.via_node_addons)
+            .field("file_metadata", &self.file_metadata)
+            .field("package_entry", &self.package_entry)
+            .field("file_directory_ambiguity", &self.file_directory_ambiguity)
             .field("package_json", &self.package_json.as_ref().map(|p| p.path()))
             .finish()
     }
@@ -58,6 +177,62 @@ impl PartialEq for Resolution {
 }
 impl Eq for Resolution {}
 
+impl Hash for Resolution {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.query.hash(state);
+        self.fragment.hash(state);
+    }
+}
+
+/// The identity of a [Resolution]: its path, query and fragment.
+///
+/// Returned by [Resolution::key], for use as a key of module graphs and caches.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ResolutionKey {
+    path: PathBuf,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl ResolutionKey {
+    /// Returns the path without query and fragment
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path query `?query`, contains the leading `?`
+    #[must_use]
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Returns the path fragment `#fragment`, contains the leading `#`
+    #[must_use]
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    /// Returns the full path with query and fragment
+    #[must_use]
+    pub fn full_path(&self) -> PathBuf {
+        full_path(&self.path, self.query.as_deref(), self.fragment.as_deref())
+    }
+}
+
+fn full_path(path: &Path, query: Option<&str>, fragment: Option<&str>) -> PathBuf {
+    let mut path = OsString::from(path);
+    if let Some(query) = query {
+        path.push(query);
+    }
+    if let Some(fragment) = fragment {
+        path.push(fragment);
+    }
+    PathBuf::from(path)
+}
+
 impl Resolution {
     /// Returns the path without query and fragment
     #[must_use]
@@ -71,6 +246,21 @@ impl Resolution {
         self.path
     }
 
+    /// Returns the specifier this path was resolved from, as passed to the resolver.
+    #[must_use]
+    pub fn specifier(&self) -> &str {
+        &self.specifier
+    }
+
+    /// Returns the specifier after [crate::ResolveOptions::import_map], [crate::ResolveOptions::alias]
+    /// or [crate::ResolveOptions::fallback] were applied, the [Resolution::specifier] when none was.
+    ///
+    /// With chained aliases, the specifier produced by the last one is returned.
+    #[must_use]
+    pub fn resolved_specifier(&self) -> &str {
+        self.aliased_specifier.as_deref().unwrap_or(&self.specifier)
+    }
+
     /// Returns the path query `?query`, contains the leading `?`
     #[must_use]
     pub fn query(&self) -> Option<&str> {
@@ -90,16 +280,52 @@ impl Resolution {
     }
 
     /// Returns the full path with query and fragment
+    ///
+    /// Equal for equal resolutions, see [Resolution::key].
     #[must_use]
     pub fn full_path(&self) -> PathBuf {
-        let mut path = self.path.clone().into_os_string();
-        if let Some(query) = &self.query {
-            path.push(query);
-        }
-        if let Some(fragment) = &self.fragment {
-            path.push(fragment);
+        full_path(&self.path, self.query.as_deref(), self.fragment.as_deref())
+    }
+
+    /// Returns the full path with query and fragment as a string, like `result.path` of
+    /// enhanced-resolve.
+    ///
+    /// A path which is not valid UTF-8 is converted lossily.
+    #[must_use]
+    pub fn full_path_string(&self) -> String {
+        let mut path = self.path.to_string_lossy().into_owned();
+        path.extend(self.query.as_deref());
+        path.extend(self.fragment.as_deref());
+        path
+    }
+
+    /// Returns the path, query and fragment, which identify this resolution.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::{collections::HashMap, path::Path};
+    ///
+    /// use oxc_resolver::Resolver;
+    ///
+    /// let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/enhanced-resolve/test/fixtures");
+    /// let resolver = Resolver::default();
+    ///
+    /// // Importers of each module.
+    /// let mut importers = HashMap::new();
+    /// for (importer, request) in [(dir.clone(), "./a.js"), (dir.join("lib"), "../a")] {
+    ///     let resolution = resolver.resolve(&importer, request).unwrap();
+    ///     importers.entry(resolution.key()).or_insert_with(Vec::new).push(importer);
+    /// }
+    /// assert_eq!(importers.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn key(&self) -> ResolutionKey {
+        ResolutionKey {
+            path: self.path.clone(),
+            query: self.query.clone(),
+            fragment: self.fragment.clone(),
         }
-        PathBuf::from(path)
     }
 
     /// Returns the module type of this path.
@@ -107,4 +333,52 @@ impl Resolution {
     pub fn module_type(&self) -> Option<ModuleType> {
         self.module_type
     }
+
+    /// Returns whether this path was only found by appending an extension or by main file
+    /// fallback, i.e. it would not resolve with [crate::ResolveOptions::fully_specified].
+    ///
+    /// Always `false` unless [crate::ResolveOptions::extension_probe_report] is enabled.
+    #[must_use]
+    pub const fn probed(&self) -> bool {
+        self.probed
+    }
+
+    /// Returns whether the `node-addons` condition was taken when resolving `exports` or `imports`,
+    /// i.e. the resolution is a native addon entry point which bundlers should keep external.
+    ///
+    /// The condition is only matched when it is listed in [crate::ResolveOptions::condition_names].
+    #[must_use]
+    pub const fn via_node_addons(&self) -> bool {
+        self.via_node_addons
+    }
+
+    /// Returns the metadata of the resolved file, including its size and modification time,
+    /// so callers can build cache keys without another `stat`.
+    ///
+    /// The metadata is of the symlink target when [crate::ResolveOptions::symlinks] is enabled.
+    /// `None` when the resolution is not a file, e.g. with [crate::ResolveOptions::resolve_to_context].
+    #[must_use]
+    pub const fn file_metadata(&self) -> Option<FileMetadata> {
+        self.file_metadata
+    }
+
+    /// Returns the `package.json` entry this path was matched by, e.g. which `exports` key and
+    /// conditions were taken.
+    ///
+    /// When several entries were applied, e.g. a browser field replacement of a main field,
+    /// the last one is returned.
+    ///
+    /// Always `None` unless [crate::ResolveOptions::record_matched_entry] is enabled.
+    #[must_use]
+    pub const fn package_entry(&self) -> Option<&PackageEntryInfo> {
+        self.package_entry.as_ref()
+    }
+
+    /// Returns the file and the directory which both matched the specifier, and which one won.
+    ///
+    /// Always `None` unless [crate::ResolveOptions::report_file_directory_ambiguity] is enabled.
+    #[must_use]
+    pub const fn file_directory_ambiguity(&self) -> Option<&FileDirectoryAmbiguity> {
+        self.file_directory_ambiguity.as_ref()
+    }
 }
@@ -0,0 +1,436 @@
+//! Async resolution, see [ResolverImpl::resolve_async].
+
+use std::{
+    future::Future,
+    hash::BuildHasherDefault,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError, atomic::AtomicBool},
+};
+
+use dashmap::DashMap;
+use rustc_hash::FxHasher;
+
+use crate::{
+    AsyncFileSystem, FileMetadata, FileSystem, Resolution, ResolveContext, ResolveError,
+    ResolverImpl, TsConfig,
+};
+
+impl ResolverImpl {
+    /// [ResolverImpl::resolve] without blocking the async runtime on the I/O of the
+    /// [crate::FileSystem].
+    ///
+    /// With an [AsyncFileSystem] (see [FileSystem::as_async]), the resolution runs in the
+    /// calling task against the results of the file system operations awaited by earlier async
+    /// resolutions, and is retried after awaiting the ones it missed. Its result is then memoized
+    /// in the cache like for [ResolverImpl::resolve]. A resolution whose operations were all
+    /// awaited before completes on the first poll. Dropping the future between two operations
+    /// leaves the cache intact.
+    ///
+    /// Otherwise the resolution runs on tokio's blocking thread pool.
+    ///
+    /// The returned future does not borrow the resolver and can be spawned. It must be polled
+    /// within a tokio runtime when the file system needs one.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    ///
+    /// # Panics
+    ///
+    /// * Resumes a panic of the resolution.
+    #[expect(clippy::needless_pass_by_value, reason = "the same arguments as `resolve`")]
+    pub fn resolve_async<P: AsRef<Path> + Send>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> impl Future<Output = Result<Resolution, ResolveError>> + Send + 'static {
+        let directory = directory.as_ref().to_path_buf();
+        Self::resolve_owned_async(Arc::new(self.clone()), directory, specifier.to_string())
+    }
+
+    /// [ResolverImpl::resolve_async] with a resolver which the returned future owns.
+    async fn resolve_owned_async(
+        resolver: Arc<Self>,
+        directory: PathBuf,
+        specifier: String,
+    ) -> Result<Resolution, ResolveError> {
+        if let Some(fs) = resolver.cache.fs.as_async() {
+            let mut ctx = ResolveContext::default();
+            return resolver.resolve_prefetched(fs, &directory, &specifier, None, &mut ctx).await;
+        }
+        spawn_blocking(move || resolver.resolve(directory, &specifier)).await?
+    }
+
+    /// [ResolverImpl::resolve_with_context] on tokio's blocking thread pool, see
+    /// [ResolverImpl::resolve_async].
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    ///
+    /// # Panics
+    ///
+    /// * Resumes a panic of the resolution.
+    pub async fn resolve_with_context_async<P: AsRef<Path> + Send>(
+        &self,
+        directory: P,
+        specifier: &str,
+        tsconfig: Option<Arc<TsConfig>>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<Resolution, ResolveError> {
+        Self::resolve_with_context_owned_async(
+            Arc::new(self.clone()),
+            directory.as_ref().to_path_buf(),
+            specifier.to_string(),
+            tsconfig,
+            resolve_context,
+        )
+        .await
+    }
+
+    /// [ResolverImpl::resolve_with_context_async] with a resolver which is moved to the blocking
+    /// thread pool.
+    async fn resolve_with_context_owned_async(
+        resolver: Arc<Self>,
+        directory: PathBuf,
+        specifier: String,
+        tsconfig: Option<Arc<TsConfig>>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<Resolution, ResolveError> {
+        let collect_package_json_chain = resolver.options.collect_package_json_chain;
+        let timings = resolve_context.timings;
+        let stats = resolve_context.stats;
+        let (result, context) = if let Some(fs) = resolver.cache.fs.as_async() {
+            let mut context = ResolveContext { timings, stats, ..ResolveContext::default() };
+            let result = resolver
+                .resolve_prefetched(fs, &directory, &specifier, tsconfig.as_deref(), &mut context)
+                .await;
+            (result, context)
+        } else {
+            spawn_blocking(move || {
+                let mut context = ResolveContext { timings, stats, ..ResolveContext::default() };
+                let result = resolver.resolve_with_context(
+                    directory,
+                    &specifier,
+                    tsconfig.as_deref(),
+                    &mut context,
+                );
+                (result, context)
+            })
+            .await?
+        };
+        resolve_context.file_dependencies.extend(context.file_dependencies);
+        resolve_context.missing_dependencies.extend(context.missing_dependencies);
+        if collect_package_json_chain {
+            resolve_context.package_json_chain = context.package_json_chain;
+        }
+        resolve_context.alias_chain = context.alias_chain;
+        resolve_context.timings = context.timings;
+        resolve_context.stats = context.stats;
+        result
+    }
+
+    /// Resolve against the [FileSystemResults] of the cache, awaiting the operations the
+    /// resolution misses on the [AsyncFileSystem] until it misses none, see
+    /// [ResolverImpl::prefetch].
+    async fn resolve_prefetched(
+        &self,
+        async_fs: &dyn AsyncFileSystem,
+        directory: &Path,
+        specifier: &str,
+        tsconfig: Option<&TsConfig>,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<Resolution, ResolveError> {
+        self.prefetch(async_fs, |scratch| {
+            let mut ctx = ResolveContext::default();
+            let _ = scratch.resolve_with_context(directory, specifier, tsconfig, &mut ctx);
+        })
+        .await;
+        self.resolve_with_context(directory, specifier, tsconfig, resolve_context)
+    }
+
+    /// Run `attempt` against the [FileSystemResults] of the cache, awaiting the operations it
+    /// misses on the [AsyncFileSystem] until it misses none.
+    ///
+    /// The memos of an attempt which missed an operation are wrong, so the attempts run on a
+    /// [crate::Cache::scratch] cache which forgets what depends on the missed paths before the next
+    /// attempt. The caller then runs the operation which misses nothing on the cache of the
+    /// resolver, where an operation whose result was invalidated in the meantime runs on the
+    /// [FileSystem].
+    async fn prefetch(&self, async_fs: &dyn AsyncFileSystem, attempt: impl Fn(&Self)) {
+        let results = &self.cache.file_system_results;
+        let fs = Arc::new(PrefetchedFileSystem::new(Arc::clone(results), None));
+        let scratch =
+            Self { cache: Arc::new(self.cache.scratch(Arc::clone(&fs) as _)), ..self.clone() };
+        loop {
+            attempt(&scratch);
+            let misses =
+                std::mem::take(&mut *fs.misses.lock().unwrap_or_else(PoisonError::into_inner));
+            if misses.is_empty() {
+                break;
+            }
+            for (operation, path) in misses {
+                if !results.contains_key(&(operation, path.clone())) {
+                    let result = operation.run(&self.cache.fs, async_fs, &path).await;
+                    results.insert((operation, path.clone()), result);
+                }
+                scratch.cache.forget(&path, true);
+            }
+        }
+    }
+
+    /// [ResolverImpl::resolve_tsconfig_with] without blocking the async runtime on the I/O of
+    /// the [crate::FileSystem], like [ResolverImpl::resolve_async].
+    ///
+    /// `cancel` is also checked while the operations of the load are awaited.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::Cancelled] when `cancel` is set before the load completes.
+    /// * See [ResolveError]
+    ///
+    /// # Panics
+    ///
+    /// * Resumes a panic of the load.
+    #[expect(clippy::needless_pass_by_value, reason = "the same arguments as `resolve_tsconfig`")]
+    pub fn resolve_tsconfig_async<P: AsRef<Path> + Send>(
+        &self,
+        path: P,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> impl Future<Output = Result<Arc<TsConfig>, ResolveError>> + Send + 'static {
+        let path = path.as_ref().to_path_buf();
+        Self::resolve_tsconfig_owned_async(Arc::new(self.clone()), path, cancel)
+    }
+
+    /// [ResolverImpl::resolve_tsconfig_async] with a resolver which the returned future owns.
+    async fn resolve_tsconfig_owned_async(
+        resolver: Arc<Self>,
+        path: PathBuf,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Arc<TsConfig>, ResolveError> {
+        if let Some(fs) = resolver.cache.fs.as_async() {
+            // Once `cancel` is set, an attempt returns before any operation, which ends the loop.
+            resolver
+                .prefetch(fs, |scratch| {
+                    let _ = scratch.resolve_tsconfig_with(&path, cancel.clone());
+                })
+                .await;
+            return resolver.resolve_tsconfig_with(&path, cancel);
+        }
+        spawn_blocking(move || resolver.resolve_tsconfig_with(path, cancel)).await?
+    }
+}
+
+async fn spawn_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, ResolveError> {
+    tokio::task::spawn_blocking(f).await.map_err(|err| {
+        if err.is_panic() {
+            std::panic::resume_unwind(err.into_panic());
+        }
+        // The runtime is shutting down.
+        std::io::Error::new(std::io::ErrorKind::Interrupted, err).into()
+    })
+}
+
+/// Results of the [AsyncFileSystem] operations awaited by [ResolverImpl::resolve_async].
+pub type FileSystemResults =
+    DashMap<(Operation, PathBuf), OperationResult, BuildHasherDefault<FxHasher>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Read,
+    Metadata,
+    SymlinkMetadata,
+    ReadLink,
+    Canonicalize,
+    ReadDir,
+}
+
+pub enum OperationResult {
+    Read(Result<Arc<[u8]>, Arc<io::Error>>),
+    Metadata(Result<FileMetadata, Arc<io::Error>>),
+    ReadLink(Result<PathBuf, ResolveError>),
+    Canonicalize(Result<PathBuf, Arc<io::Error>>),
+    ReadDir(Result<Arc<[PathBuf]>, Arc<io::Error>>),
+}
+
+impl Operation {
+    async fn run(
+        self,
+        sync_fs: &Arc<dyn FileSystem>,
+        fs: &dyn AsyncFileSystem,
+        path: &Path,
+    ) -> OperationResult {
+        match self {
+            Self::Read => {
+                OperationResult::Read(fs.read_async(path).await.map(Arc::from).map_err(Arc::new))
+            }
+            Self::Metadata => {
+                OperationResult::Metadata(fs.metadata_async(path).await.map_err(Arc::new))
+            }
+            Self::SymlinkMetadata => {
+                OperationResult::Metadata(fs.symlink_metadata_async(path).await.map_err(Arc::new))
+            }
+            Self::ReadLink => OperationResult::ReadLink(fs.read_link_async(path).await),
+            Self::Canonicalize => {
+                OperationResult::Canonicalize(fs.canonicalize_async(path).await.map_err(Arc::new))
+            }
+            // [AsyncFileSystem] has no directory listing.
+            Self::ReadDir => {
+                let sync_fs = Arc::clone(sync_fs);
+                let path = path.to_path_buf();
+                let entries = tokio::task::spawn_blocking(move || sync_fs.read_dir(&path))
+                    .await
+                    .unwrap_or_else(|err| Err(io::Error::new(io::ErrorKind::Interrupted, err)));
+                OperationResult::ReadDir(entries.map(Arc::from).map_err(Arc::new))
+            }
+        }
+    }
+}
+
+/// Answers from [FileSystemResults], and from `fs` for the operations without a result, or
+/// records them as misses when there is no `fs`.
+pub struct PrefetchedFileSystem {
+    results: Arc<FileSystemResults>,
+    fs: Option<Arc<dyn FileSystem>>,
+    misses: Mutex<Vec<(Operation, PathBuf)>>,
+}
+
+impl PrefetchedFileSystem {
+    pub fn new(results: Arc<FileSystemResults>, fs: Option<Arc<dyn FileSystem>>) -> Self {
+        Self { results, fs, misses: Mutex::default() }
+    }
+
+    fn get<T>(
+        &self,
+        operation: Operation,
+        path: &Path,
+        f: impl FnOnce(&OperationResult) -> Option<T>,
+        run: impl FnOnce(&dyn FileSystem) -> T,
+    ) -> Option<T> {
+        if let Some(result) = self.results.get(&(operation, path.to_path_buf())).and_then(|r| f(&r))
+        {
+            return Some(result);
+        }
+        if let Some(fs) = &self.fs {
+            return Some(run(&**fs));
+        }
+        self.misses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((operation, path.to_path_buf()));
+        None
+    }
+
+    fn get_io<T>(
+        &self,
+        operation: Operation,
+        path: &Path,
+        f: impl FnOnce(&OperationResult) -> Option<Result<T, &Arc<io::Error>>>,
+        run: impl FnOnce(&dyn FileSystem) -> io::Result<T>,
+    ) -> io::Result<T> {
+        self.get(operation, path, |result| Some(f(result)?.map_err(|err| copy_io_error(err))), run)
+            .unwrap_or_else(|| Err(io::ErrorKind::WouldBlock.into()))
+    }
+}
+
+/// `io::Error` is not `Clone`, keep what the resolver looks at.
+fn copy_io_error(error: &io::Error) -> io::Error {
+    error.raw_os_error().map_or_else(
+        || io::Error::new(error.kind(), error.to_string()),
+        io::Error::from_raw_os_error,
+    )
+}
+
+impl FileSystem for PrefetchedFileSystem {
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!("constructed by `PrefetchedFileSystem::new`")
+    }
+
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!("constructed by `PrefetchedFileSystem::new`")
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.get_io(
+            Operation::Read,
+            path,
+            |result| match result {
+                OperationResult::Read(result) => Some(result.as_ref().map(|bytes| bytes.to_vec())),
+                _ => None,
+            },
+            |fs| fs.read(path),
+        )
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        crate::FileSystemOs::validate_string(self.read(path)?)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.get_io(
+            Operation::Metadata,
+            path,
+            |result| match result {
+                OperationResult::Metadata(result) => Some(result.as_ref().copied()),
+                _ => None,
+            },
+            |fs| fs.metadata(path),
+        )
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.get_io(
+            Operation::SymlinkMetadata,
+            path,
+            |result| match result {
+                OperationResult::Metadata(result) => Some(result.as_ref().copied()),
+                _ => None,
+            },
+            |fs| fs.symlink_metadata(path),
+        )
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.get(
+            Operation::ReadLink,
+            path,
+            |result| match result {
+                OperationResult::ReadLink(result) => Some(result.clone()),
+                _ => None,
+            },
+            |fs| fs.read_link(path),
+        )
+        .unwrap_or_else(|| Err(io::Error::from(io::ErrorKind::WouldBlock).into()))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.get_io(
+            Operation::Canonicalize,
+            path,
+            |result| match result {
+                OperationResult::Canonicalize(result) => Some(result.as_ref().cloned()),
+                _ => None,
+            },
+            |fs| fs.canonicalize(path),
+        )
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.get_io(
+            Operation::ReadDir,
+            path,
+            |result| match result {
+                OperationResult::ReadDir(result) => {
+                    Some(result.as_ref().map(|entries| entries.to_vec()))
+                }
+                _ => None,
+            },
+            |fs| fs.read_dir(path),
+        )
+    }
+}
@@ -0,0 +1,23 @@
+//! Parallel resolution of a batch of specifiers, see [ResolverImpl::resolve_many].
+
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::{Resolution, ResolveError, ResolverImpl};
+
+impl ResolverImpl {
+    /// [ResolverImpl::resolve] each of `specifiers` against `directory` on rayon's thread pool,
+    /// e.g. all imports of a module.
+    ///
+    /// The resolutions share the cache of this resolver. The results are in the order of
+    /// `specifiers`.
+    pub fn resolve_many<P: AsRef<Path> + Sync>(
+        &self,
+        directory: P,
+        specifiers: &[&str],
+    ) -> Vec<Result<Resolution, ResolveError>> {
+        let directory = directory.as_ref();
+        specifiers.par_iter().map(|specifier| self.resolve(directory, specifier)).collect()
+    }
+}
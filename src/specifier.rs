@@ -33,6 +33,13 @@ impl<'a> Specifier<'a> {
         Ok(Self { path, query, fragment })
     }
 
+    /// A `data:` URL, which has no query and at most a trailing fragment, see
+    /// [crate::data_url::split_fragment].
+    pub fn parse_data_url(specifier: &'a str) -> Self {
+        let (path, fragment) = crate::data_url::split_fragment(specifier);
+        Self { path: Cow::Borrowed(path), query: None, fragment }
+    }
+
     fn parse_query_fragment(
         specifier: &'a str,
         skip: usize,
@@ -89,9 +96,21 @@ impl<'a> Specifier<'a> {
     }
 }
 
+/// The scheme of a URL specifier such as `npm:` or `https://`.
+///
+/// Single letter schemes are not reported, they are Windows drive letters.
+pub fn url_scheme(specifier: &str) -> Option<&str> {
+    let (scheme, _) = specifier.split_once(':')?;
+    let mut bytes = scheme.bytes();
+    (scheme.len() > 1
+        && bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.')))
+    .then_some(scheme)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Specifier, SpecifierError};
+    use super::{Specifier, SpecifierError, url_scheme};
 
     #[test]
     fn debug() {
@@ -238,4 +257,18 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn scheme() {
+        assert_eq!(url_scheme("data:text/javascript,export default 1"), Some("data"));
+        assert_eq!(url_scheme("https://example.com/a.js"), Some("https"));
+        assert_eq!(url_scheme("git+ssh://host/repo"), Some("git+ssh"));
+        assert_eq!(url_scheme("node:fs"), Some("node"));
+        assert_eq!(url_scheme("C:/a.js"), None);
+        assert_eq!(url_scheme("C:\\a.js"), None);
+        assert_eq!(url_scheme("./a:b"), None);
+        assert_eq!(url_scheme("pkg/a:b"), None);
+        assert_eq!(url_scheme("1a:b"), None);
+        assert_eq!(url_scheme("lodash"), None);
+    }
 }
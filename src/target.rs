@@ -0,0 +1,84 @@
+//! Options for the JavaScript runtimes packages publish conditional exports for,
+//! see [ResolveOptions::default_for_target].
+
+use crate::ResolveOptions;
+
+/// A JavaScript runtime to resolve ES modules for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolveTarget {
+    /// Node.js.
+    Node,
+
+    /// Browsers, through a bundler.
+    Browser,
+
+    /// Cloudflare Workers.
+    Workerd,
+
+    /// Vercel Edge Runtime.
+    EdgeLight,
+
+    /// Bun.
+    Bun,
+
+    /// Deno.
+    Deno,
+}
+
+impl ResolveTarget {
+    /// All targets, in declaration order.
+    pub const ALL: [Self; 6] =
+        [Self::Node, Self::Browser, Self::Workerd, Self::EdgeLight, Self::Bun, Self::Deno];
+
+    /// The [ResolveOptions::condition_names] of the target.
+    ///
+    /// The runtime key comes first, followed by the keys of the platforms the runtime is
+    /// compatible with, as the runtimes and their bundlers match them:
+    ///
+    /// * `workerd` and `edge-light` are worker environments with browser APIs.
+    /// * `bun` and `deno` implement the Node.js APIs.
+    ///
+    /// The order documents precedence, a package's `exports` decide which of the keys wins.
+    #[must_use]
+    pub const fn condition_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Node => &["node", "import"],
+            Self::Browser => &["browser", "import", "module"],
+            Self::Workerd => &["workerd", "worker", "browser", "import", "module"],
+            Self::EdgeLight => &["edge-light", "worker", "browser", "import", "module"],
+            Self::Bun => &["bun", "node", "import"],
+            Self::Deno => &["deno", "node", "import"],
+        }
+    }
+
+    /// Whether the target reads the `browser` field of package.json.
+    #[must_use]
+    pub const fn is_browser_like(self) -> bool {
+        matches!(self, Self::Browser | Self::Workerd | Self::EdgeLight)
+    }
+}
+
+impl ResolveOptions {
+    /// Options for resolving ES modules in `target`:
+    ///
+    /// * [ResolveOptions::condition_names] from [ResolveTarget::condition_names].
+    /// * For browser-like targets, the `browser` and `module` main fields and the `browser`
+    ///   alias field.
+    ///
+    /// The other options are the defaults, add conditions such as `types` or `development`
+    /// to [ResolveOptions::condition_names] as needed.
+    #[must_use]
+    pub fn default_for_target(target: ResolveTarget) -> Self {
+        let condition_names = target.condition_names().iter().map(ToString::to_string).collect();
+        if target.is_browser_like() {
+            Self {
+                condition_names,
+                main_fields: vec!["browser".into(), "module".into(), "main".into()],
+                alias_fields: vec![vec!["browser".into()]],
+                ..Self::default()
+            }
+        } else {
+            Self { condition_names, ..Self::default() }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::{
+    FileMetadata, FileSystem, FileSystemOs, MemoryFileSystem, ResolveContext, ResolveError,
+    ResolveOptions, Resolver, ResolverGeneric,
+};
+
+/// Counts the metadata queries.
+struct CountingFS {
+    fs: FileSystemOs,
+    queries: AtomicUsize,
+}
+
+impl FileSystem for CountingFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        Self { fs: FileSystemOs::new(), queries: AtomicUsize::new(0) }
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(yarn_pnp: bool) -> Self {
+        Self { fs: FileSystemOs::new(yarn_pnp), queries: AtomicUsize::new(0) }
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.fs.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        self.fs.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        self.fs.symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.fs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.fs.read_dir(path)
+    }
+}
+
+const FILES: usize = 20;
+
+#[test]
+#[cfg_attr(target_os = "wasi", ignore)]
+fn fewer_metadata_queries() {
+    let f = super::fixture_root().join("adaptive-extension-probing/src");
+    let resolve_all = |adaptive_extension_probing| {
+        #[cfg(not(feature = "yarn_pnp"))]
+        let fs = Arc::new(CountingFS::new());
+        #[cfg(feature = "yarn_pnp")]
+        let fs = Arc::new(CountingFS::new(false));
+        let options = ResolveOptions {
+            extensions: vec![".js".into(), ".jsx".into(), ".mjs".into(), ".ts".into()],
+            adaptive_extension_probing,
+            ..ResolveOptions::default()
+        };
+        let resolver = ResolverGeneric::new_with_file_system(Arc::clone(&fs), options);
+        let resolutions = (0..FILES)
+            .map(|i| resolver.resolve(&f, &format!("./file{i}")).map(|r| r.full_path()))
+            .collect::<Vec<_>>();
+        (resolutions, fs.queries.load(Ordering::Relaxed))
+    };
+
+    let (probed, probed_queries) = resolve_all(false);
+    let (adaptive, adaptive_queries) = resolve_all(true);
+    assert_eq!(probed, adaptive);
+    assert_eq!(adaptive[0], Ok(f.join("file0.ts")));
+    // Every extension before `.ts` is a failed stat of its own without the hint.
+    assert!(adaptive_queries * 2 < probed_queries, "{adaptive_queries} vs {probed_queries}");
+}
+
+#[test]
+fn configured_order_wins() {
+    let f = super::fixture_root().join("adaptive-extension-probing/mixed");
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".js".into(), ".ts".into()],
+        adaptive_extension_probing: true,
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let data = [
+        ("remembers `.ts`", "./a", "a.ts"),
+        ("`.js` is listed", "./both", "both.js"),
+        ("`b.ts` is missing", "./b", "b.js"),
+        ("found again after the hint was dropped", "./a", "a.ts"),
+    ];
+    for (comment, request, expected) in data {
+        let path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(path, Ok(f.join(expected)), "{comment}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn invalidate_path() {
+    let fs = Arc::new(MemoryFileSystem::new([("/app/src/a.ts", ""), ("/app/src/b.ts", "")]));
+    let options = ResolveOptions {
+        extensions: vec![".js".into(), ".ts".into()],
+        adaptive_extension_probing: true,
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(Arc::clone(&fs), options);
+    for request in ["./a", "./b"] {
+        resolver.resolve("/app/src", request).unwrap();
+    }
+
+    // A new file is only seen after the listing of its directory is invalidated.
+    fs.add_file(Path::new("/app/src/b.js"), "");
+    assert!(resolver.invalidate_path("/app/src/b.js"));
+    assert_eq!(
+        resolver.resolve("/app/src", "./b").map(|r| r.full_path()),
+        Ok(PathBuf::from("/app/src/b.js"))
+    );
+}
+
+#[test]
+fn same_resolutions() {
+    let f = super::fixture();
+    let mixed = super::fixture_root().join("adaptive-extension-probing/mixed");
+
+    #[rustfmt::skip]
+    let data = [
+        (f.clone(), "./a"),
+        (f.clone(), "./no"),
+        (f.clone(), "./dirOrFile"),
+        (f.clone(), "./dirOrFile/"),
+        (f.clone(), "./directory-default"),
+        (f.clone(), "./main-field-self"),
+        (f.clone(), "m1/a"),
+        (f.clone(), "m2/b"),
+        (f.clone(), "complex"),
+        (f.join("extensions"), "./foo"),
+        (f.join("extensions"), "./dir"),
+        (f.join("extensions"), "."),
+        (f.join("extensions"), "module/"),
+        (f.join("extensions"), "./missing"),
+        (f.join("browser-module"), "./lib/replaced"),
+        (f.join("browser-module"), "./lib/ignore"),
+        (f.join("multiple-modules"), "m1/a"),
+        (mixed.clone(), "./a"),
+        (mixed.clone(), "./both"),
+        (mixed.clone(), "./b"),
+        (mixed, "./missing"),
+    ];
+
+    let options = ResolveOptions {
+        extensions: vec![".js".into(), ".json".into(), ".ts".into()],
+        alias_fields: vec![vec!["browser".into()]],
+        ..ResolveOptions::default()
+    };
+    let probed = Resolver::new(options.clone());
+    let adaptive = Resolver::new(ResolveOptions { adaptive_extension_probing: true, ..options });
+
+    // The second round resolves with the extensions remembered by the first.
+    for (path, request) in data.iter().chain(&data) {
+        let mut probed_ctx = ResolveContext::default();
+        let mut adaptive_ctx = ResolveContext::default();
+        let expected = probed.resolve_with_context(path, request, None, &mut probed_ctx);
+        let resolved = adaptive.resolve_with_context(path, request, None, &mut adaptive_ctx);
+        assert_eq!(resolved, expected, "{path:?} {request}");
+        assert_eq!(adaptive_ctx.file_dependencies, probed_ctx.file_dependencies, "{request}");
+        assert_eq!(adaptive_ctx.missing_dependencies, probed_ctx.missing_dependencies, "{request}");
+    }
+}
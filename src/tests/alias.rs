@@ -149,7 +149,10 @@ fn infinite_recursion() {
         ..ResolveOptions::default()
     });
     let resolution = resolver.resolve(f, "./a");
-    assert_eq!(resolution, Err(ResolveError::Recursion));
+    assert!(
+        matches!(resolution, Err(ResolveError::RecursionLimitExceeded { limit: 64, ref trail }) if trail.len() == 8),
+        "{resolution:?}"
+    );
 }
 
 fn check_slash(path: &Path) {
@@ -315,3 +318,111 @@ fn alias_with_multiple_fallbacks() {
     let resolution = resolver.resolve(&f, "multi/index.js").map(|r| r.full_path());
     assert_eq!(resolution, Ok(f.join("foo/index.js")));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn alias_value_errors() {
+    use std::sync::Arc;
+
+    use crate::{MemoryFileSystem, ResolverGeneric};
+
+    let file_system = Arc::new(MemoryFileSystem::new([
+        ("/node_modules/broken/package.json", "{"),
+        ("/node_modules/broken/index.js", ""),
+        ("/node_modules/working/index.js", ""),
+    ]));
+    let resolver = |values: &[&str]| {
+        ResolverGeneric::new_with_file_system(
+            Arc::clone(&file_system),
+            ResolveOptions {
+                alias: vec![(
+                    "pkg".to_string(),
+                    values.iter().map(|value| AliasValue::from(*value)).collect(),
+                )],
+                ..ResolveOptions::default()
+            },
+        )
+    };
+
+    // The invalid package.json of the first value does not stop the second.
+    let resolution = resolver(&["broken", "working"]).resolve("/", "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(Path::new("/node_modules/working/index.js").to_path_buf()));
+
+    // The error is reported rather than `MatchedAliasNotFound` when all values fail.
+    let resolution = resolver(&["broken", "missing"]).resolve("/", "pkg");
+    assert!(matches!(resolution, Err(ResolveError::Json(_))), "{resolution:?}");
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn alias_chain() {
+    use crate::{MemoryFileSystem, ResolverGeneric};
+
+    let file_system = MemoryFileSystem::new([("/packages/foo/index.js", "")]);
+    let resolver = ResolverGeneric::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            alias: vec![
+                ("foo".to_string(), vec![AliasValue::from("@internal/foo")]),
+                ("@internal/foo".to_string(), vec![AliasValue::from("/packages/foo")]),
+                (
+                    "multi".to_string(),
+                    vec![AliasValue::from("/missing"), AliasValue::from("/packages/foo")],
+                ),
+            ],
+            fallback: vec![("bar".to_string(), vec![AliasValue::from("foo")])],
+            ..ResolveOptions::default()
+        },
+    );
+    let chain = |pairs: &[(&str, &str)]| {
+        pairs.iter().map(|(from, to)| ((*from).to_string(), (*to).to_string())).collect::<Vec<_>>()
+    };
+
+    #[rustfmt::skip]
+    let data = [
+        ("nested", "foo", chain(&[("foo", "@internal/foo"), ("@internal/foo", "/packages/foo")])),
+        ("failed values are left out", "multi", chain(&[("multi", "/packages/foo")])),
+        ("fallback", "bar", chain(&[("bar", "foo"), ("foo", "@internal/foo"), ("@internal/foo", "/packages/foo")])),
+        ("no alias", "/packages/foo", vec![]),
+    ];
+
+    let mut ctx = ResolveContext::default();
+    for (comment, request, expected) in data {
+        let resolution = resolver.resolve_with_context("/", request, None, &mut ctx);
+        assert_eq!(
+            resolution.map(|r| r.full_path()),
+            Ok(Path::new("/packages/foo/index.js").to_path_buf()),
+            "{comment}"
+        );
+        assert_eq!(ctx.alias_chain, expected, "{comment}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn alias_path_then_ignore() {
+    use std::path::PathBuf;
+
+    use crate::{MemoryFileSystem, ResolverGeneric};
+
+    let shim_then_ignore = || vec![AliasValue::from("/shim-fs.js"), AliasValue::Ignore];
+    let ignore_then_shim = || vec![AliasValue::Ignore, AliasValue::from("/shim-fs.js")];
+    let ignored = Err(ResolveError::Ignored(PathBuf::from("/app/fs")));
+
+    #[rustfmt::skip]
+    let data = [
+        ("shim exists", shim_then_ignore(), &["/shim-fs.js"][..], Ok(PathBuf::from("/shim-fs.js"))),
+        ("shim is missing", shim_then_ignore(), &[][..], ignored.clone()),
+        ("ignore first", ignore_then_shim(), &["/shim-fs.js"][..], ignored),
+    ];
+
+    for (comment, values, files, expected) in data {
+        let file_system = MemoryFileSystem::new(files.iter().map(|path| (*path, "")));
+        let resolver = ResolverGeneric::new_with_file_system(
+            file_system,
+            ResolveOptions { alias: vec![("fs".to_string(), values)], ..ResolveOptions::default() },
+        );
+        let resolution = resolver.resolve("/app", "fs").map(|r| r.full_path());
+        assert_eq!(resolution, expected, "{comment}");
+    }
+}
@@ -165,13 +165,25 @@ fn recursive() {
     let data = [
         ("should handle recursive file 1", f.clone(), "recursive-file/a"),
         ("should handle recursive file 2", f.clone(), "recursive-file/b"),
+    ];
+
+    for (comment, path, request) in data {
+        let resolved_path = resolver.resolve(&path, request);
+        assert_eq!(resolved_path, Err(ResolveError::Recursion), "{comment} {path:?} {request}");
+    }
+
+    // `c.js` and `d.js` remap to each other.
+    let data = [
         ("should handle recursive file 3", f.clone(), "recursive-file/c"),
         ("should handle recursive file 4", f, "recursive-file/d"),
     ];
 
     for (comment, path, request) in data {
         let resolved_path = resolver.resolve(&path, request);
-        assert_eq!(resolved_path, Err(ResolveError::Recursion), "{comment} {path:?} {request}");
+        assert!(
+            matches!(resolved_path, Err(ResolveError::RecursionLimitExceeded { limit: 64, .. })),
+            "{comment} {path:?} {request} {resolved_path:?}"
+        );
     }
 }
 
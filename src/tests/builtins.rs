@@ -68,3 +68,17 @@ fn imports() {
         assert_eq!(resolved_path, Err(err));
     }
 }
+
+#[test]
+fn unknown() {
+    let f = Path::new("/");
+    let resolver = Resolver::new(ResolveOptions::default().with_builtin_modules(true));
+    for request in ["node:does-not-exist", "node:fs/does-not-exist", "node:"] {
+        let resolved_path = resolver.resolve(f, request);
+        assert_eq!(resolved_path, Err(ResolveError::UnknownBuiltin(request.to_string())));
+    }
+
+    // Without `builtin_modules`, `node:` specifiers are looked up like any other.
+    let resolved_path = Resolver::default().resolve(f, "node:does-not-exist");
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("node:does-not-exist".into())));
+}
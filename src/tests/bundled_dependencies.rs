@@ -0,0 +1,52 @@
+//! Not part of enhanced_resolve's test suite
+//!
+//! A package shipping `bundleDependencies` has a `node_modules` of its own inside its directory.
+//! `app/node_modules/aliased` is installed under an alias of `outer`.
+
+use crate::Resolver;
+
+#[test]
+fn bundled_dependencies() {
+    let f = super::fixture_root().join("bundled-dependencies");
+    let outer = f.join("app/node_modules/outer");
+    let dep = outer.join("node_modules/dep");
+
+    #[rustfmt::skip]
+    let data = [
+        ("bundled dependency importing the package bundling it", dep.clone(), "outer", outer.join("index.js"), outer.clone()),
+        ("bundled dependency importing a subpath of the package bundling it", dep.clone(), "outer/util", outer.join("lib/util.js"), outer.clone()),
+        ("bundled dependency importing itself", dep.clone(), "dep/feature", dep.join("feature.js"), dep.clone()),
+        ("bundled dependency importing itself", dep.clone(), "dep", dep.join("index.js"), dep.clone()),
+        ("package importing its bundled dependency", outer.clone(), "dep", dep.join("index.js"), dep.clone()),
+        ("package importing itself", outer.join("lib"), "outer", outer.join("index.js"), outer.clone()),
+        ("package importing itself from its bundled dependency directory", outer.join("node_modules"), "outer/util", outer.join("lib/util.js"), outer.clone()),
+        ("copy higher in the tree", f.clone(), "outer", f.join("node_modules/outer/index.js"), f.join("node_modules/outer")),
+    ];
+
+    let resolver = Resolver::default();
+    for (comment, directory, request, expected_path, expected_package) in data {
+        let resolution = resolver.resolve(&directory, request).unwrap_or_else(|err| {
+            panic!("{comment} {request}: {err}");
+        });
+        assert_eq!(resolution.full_path(), expected_path, "{comment} {request}");
+        let package_json = resolution.package_json().unwrap();
+        assert_eq!(package_json.directory(), expected_package, "{comment} {request}");
+    }
+}
+
+/// A bundled dependency without a `package.json` has no package scope, the scope of the package
+/// bundling it ends at its `node_modules`.
+#[test]
+fn bundled_dependency_without_package_json() {
+    let f = super::fixture_root().join("bundled-dependencies");
+    let legacy = f.join("app/node_modules/aliased/node_modules/legacy");
+
+    let resolver = Resolver::default();
+    let resolution = resolver.resolve(&legacy, "outer").unwrap();
+    assert_eq!(resolution.full_path(), f.join("app/node_modules/outer/index.js"));
+    let package_json = resolution.package_json().unwrap();
+    assert_eq!(package_json.directory(), f.join("app/node_modules/outer"));
+
+    let resolution = resolver.resolve(&legacy, "./index.js").unwrap();
+    assert!(resolution.package_json().is_none());
+}
@@ -0,0 +1,136 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::{
+    FileMetadata, FileSystem, FileSystemOs, ResolveError, ResolveOptions, Resolver, ResolverGeneric,
+};
+
+/// Counts the metadata queries.
+struct CountingFS {
+    fs: FileSystemOs,
+    queries: AtomicUsize,
+}
+
+impl FileSystem for CountingFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        Self { fs: FileSystemOs::new(), queries: AtomicUsize::new(0) }
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(yarn_pnp: bool) -> Self {
+        Self { fs: FileSystemOs::new(yarn_pnp), queries: AtomicUsize::new(0) }
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.fs.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        self.fs.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        self.fs.symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.fs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+}
+
+fn counting_resolver() -> (Arc<CountingFS>, ResolverGeneric<Arc<CountingFS>>) {
+    #[cfg(not(feature = "yarn_pnp"))]
+    let fs = Arc::new(CountingFS::new());
+    #[cfg(feature = "yarn_pnp")]
+    let fs = Arc::new(CountingFS::new(false));
+    let resolver =
+        ResolverGeneric::new_with_file_system(Arc::clone(&fs), ResolveOptions::default());
+    (fs, resolver)
+}
+
+/// A file in the temporary directory, removed on drop.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("oxc_resolver_{name}_{}", std::process::id())))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+#[cfg_attr(target_os = "wasi", ignore)]
+fn no_metadata_queries_for_covered_paths() {
+    let f = super::fixture();
+    let requests = ["./a", "m1/a", "./main-field-self", "complex"];
+    let artifact = TempFile::new("cache_snapshot");
+
+    let resolver = Resolver::default();
+    let expected = requests.map(|request| resolver.resolve(&f, request).map(|r| r.full_path()));
+    let saved = resolver.save_cache_snapshot_mmap(&artifact.0, "lockfile-hash").unwrap();
+    assert!(saved > 0);
+
+    let (fs, resolver) = counting_resolver();
+    // SAFETY: The artifact is not modified while it is mapped.
+    let loaded = unsafe { resolver.load_cache_snapshot_mmap(&artifact.0, "lockfile-hash") };
+    assert_eq!(loaded, Ok(true));
+    let mapped = requests.map(|request| resolver.resolve(&f, request).map(|r| r.full_path()));
+    assert_eq!(mapped, expected);
+    assert_eq!(fs.queries.load(Ordering::Relaxed), 0);
+
+    // Paths which are not in the artifact go to the file system.
+    assert_eq!(resolver.resolve(&f, "./b").map(|r| r.full_path()), Ok(f.join("b.js")));
+    assert!(fs.queries.load(Ordering::Relaxed) > 0);
+
+    // Invalidated paths too.
+    fs.queries.store(0, Ordering::Relaxed);
+    resolver.invalidate_path(f.join("a.js"));
+    assert_eq!(resolver.resolve(&f, "./a").map(|r| r.full_path()), Ok(f.join("a.js")));
+    assert!(fs.queries.load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+#[cfg_attr(target_os = "wasi", ignore)]
+fn token() {
+    let f = super::fixture();
+    let artifact = TempFile::new("cache_snapshot_token");
+    let resolver = Resolver::default();
+    resolver.resolve(&f, "./a").unwrap();
+    resolver.save_cache_snapshot_mmap(&artifact.0, "old").unwrap();
+
+    let (fs, resolver) = counting_resolver();
+    // SAFETY: The artifact is not modified while it is mapped.
+    assert_eq!(unsafe { resolver.load_cache_snapshot_mmap(&artifact.0, "new") }, Ok(false));
+    resolver.resolve(&f, "./a").unwrap();
+    assert!(fs.queries.load(Ordering::Relaxed) > 0);
+
+    // Not an artifact.
+    let resolver = Resolver::default();
+    // SAFETY: The fixture is not modified.
+    let loaded = unsafe { resolver.load_cache_snapshot_mmap(f.join("a.js"), "old") };
+    assert!(matches!(loaded, Err(ResolveError::IOError(_))));
+}
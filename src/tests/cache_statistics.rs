@@ -0,0 +1,45 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{CacheStatistics, ResolveOptions, Resolver, TsconfigDiscovery};
+
+#[test]
+fn statistics() {
+    let app = super::fixture_root().join("cache-statistics/app");
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Auto),
+        ..ResolveOptions::default()
+    });
+    // Nothing is counted until enabled.
+    resolver.resolve_file(app.join("a/index.js"), "pkg").unwrap();
+    assert_eq!(resolver.cache_statistics(), CacheStatistics::default());
+    resolver.clear_cache();
+
+    resolver.enable_cache_statistics();
+    resolver.resolve_file(app.join("a/index.js"), "pkg").unwrap();
+    let cold = resolver.cache_statistics();
+    assert!(cold.file_misses > 0 && cold.package_json_misses > 0, "{cold:?}");
+    // The tsconfig and the one it extends.
+    assert_eq!((cold.tsconfig_hits, cold.tsconfig_misses), (0, 2));
+
+    resolver.resolve_file(app.join("b/index.js"), "pkg").unwrap();
+    let warm = resolver.cache_statistics();
+    assert!(warm.file_hits > cold.file_hits, "{warm:?}");
+    assert!(warm.package_json_hits > cold.package_json_hits, "{warm:?}");
+    // Only the tsconfig of `b` is new, the extended one is cached.
+    assert_eq!((warm.tsconfig_hits, warm.tsconfig_misses), (1, 3));
+
+    resolver.resolve_file(app.join("b/index.js"), "pkg").unwrap();
+    let hot = resolver.cache_statistics();
+    assert_eq!(hot.file_misses, warm.file_misses);
+    assert_eq!(hot.package_json_misses, warm.package_json_misses);
+    assert_eq!(hot.tsconfig_misses, warm.tsconfig_misses);
+
+    // Clearing the cache keeps the counts, the next resolution misses again.
+    resolver.clear_cache();
+    assert_eq!(resolver.cache_statistics(), hot);
+    resolver.resolve(app.join("a"), "./index.js").unwrap();
+    assert!(resolver.cache_statistics().file_misses > hot.file_misses);
+
+    resolver.reset_cache_statistics();
+    assert_eq!(resolver.cache_statistics(), CacheStatistics::default());
+}
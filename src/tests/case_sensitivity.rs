@@ -0,0 +1,85 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{CaseSensitivity, MemoryFileSystem, ResolveError, ResolveOptions, ResolverGeneric};
+
+fn file_system() -> MemoryFileSystem {
+    MemoryFileSystem::new([
+        ("/project/src/index.js", ""),
+        ("/project/src/Components/Button.js", ""),
+        ("/project/src/utils.js", ""),
+        ("/project/node_modules/react/package.json", r#"{ "main": "./index.js" }"#),
+        ("/project/node_modules/react/index.js", ""),
+        ("/project/both/foo.js", ""),
+        ("/project/both/Foo.js", ""),
+    ])
+}
+
+fn resolver(case_sensitivity: CaseSensitivity) -> ResolverGeneric<MemoryFileSystem> {
+    ResolverGeneric::new_with_file_system(
+        file_system(),
+        ResolveOptions { case_sensitivity, ..ResolveOptions::default() },
+    )
+}
+
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+#[test]
+fn insensitive() {
+    let resolver = resolver(CaseSensitivity::Insensitive);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exact", "/project/src", "./utils", "/project/src/utils.js"),
+        ("file", "/project/src", "./Utils", "/project/src/utils.js"),
+        ("extension", "/project/src", "./utils.JS", "/project/src/utils.js"),
+        ("directory", "/project/src", "./components/button", "/project/src/Components/Button.js"),
+        ("importer", "/project/SRC", "./utils", "/project/src/utils.js"),
+        ("package", "/project/src", "React", "/project/node_modules/react/index.js"),
+        ("exact match among several", "/project/both", "./Foo", "/project/both/Foo.js"),
+        ("exact match among several", "/project/both", "./foo", "/project/both/foo.js"),
+    ];
+
+    for (comment, directory, request, expected) in pass {
+        let resolution = resolver.resolve(directory, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {directory} {request}");
+    }
+
+    // Names which match several entries are ambiguous.
+    assert_eq!(
+        resolver.resolve("/project/both", "./FOO").map(|r| r.full_path()),
+        Err(ResolveError::NotFound("./FOO".into()))
+    );
+}
+
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+#[test]
+fn sensitive() {
+    for case_sensitivity in [CaseSensitivity::Sensitive, CaseSensitivity::Auto] {
+        let resolver = resolver(case_sensitivity);
+        resolver.resolve("/project/src", "./utils").unwrap();
+        for request in ["./Utils", "./components/button", "React"] {
+            assert_eq!(
+                resolver.resolve("/project/src", request).map(|r| r.full_path()),
+                Err(ResolveError::NotFound(request.into())),
+                "{case_sensitivity:?} {request}"
+            );
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+#[test]
+fn clone_with_options() {
+    let resolver = resolver(CaseSensitivity::Sensitive);
+    resolver.resolve("/project/src", "./Utils").unwrap_err();
+    let insensitive = resolver.clone_with_options(ResolveOptions {
+        case_sensitivity: CaseSensitivity::Insensitive,
+        ..ResolveOptions::default()
+    });
+    assert_eq!(
+        insensitive.resolve("/project/src", "./Utils").map(|r| r.full_path()),
+        Ok(PathBuf::from("/project/src/utils.js"))
+    );
+    resolver.resolve("/project/src", "./Utils").unwrap_err();
+}
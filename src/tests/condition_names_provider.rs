@@ -0,0 +1,84 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::{ConditionNamesProvider, ResolveOptions, Resolver};
+
+fn repo() -> PathBuf {
+    super::fixture_root().join("condition-names-provider/repo")
+}
+
+fn provider(calls: Arc<AtomicUsize>) -> ConditionNamesProvider {
+    let apps = repo().join("apps");
+    ConditionNamesProvider::new(move |directory: &Path| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        if directory.starts_with(apps.join("modern")) {
+            Some(vec!["es2020".into()])
+        } else if directory.starts_with(apps.join("legacy")) {
+            Some(vec!["legacy".into()])
+        } else {
+            None
+        }
+    })
+}
+
+#[test]
+fn per_directory() {
+    let repo = repo();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let options = ResolveOptions {
+        condition_names: vec!["node".into()],
+        condition_names_provider: Some(provider(Arc::clone(&calls))),
+        ..ResolveOptions::default()
+    };
+    let resolver = Resolver::new(options);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("modern", "apps/modern/src", "pkg", "node_modules/pkg/modern.js"),
+        ("legacy", "apps/legacy/src", "pkg", "node_modules/pkg/legacy.js"),
+        ("provider returns None", "tools", "pkg", "node_modules/pkg/index.js"),
+        ("imports", "apps/modern/src", "#polyfill", "apps/modern/empty.js"),
+    ];
+
+    for (comment, directory, request, expected) in pass {
+        let resolution = resolver.resolve(repo.join(directory), request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(repo.join(expected)), "{comment} {directory} {request}");
+    }
+
+    // The provider is called once per directory.
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+    resolver.resolve(repo.join("apps/legacy/src"), "pkg").unwrap();
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn new_provider() {
+    let repo = repo();
+    let options = ResolveOptions {
+        condition_names_provider: Some(provider(Arc::default())),
+        ..ResolveOptions::default()
+    };
+    let resolver = Resolver::new(options);
+    let resolution = resolver.resolve(repo.join("apps/modern/src"), "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(repo.join("node_modules/pkg/modern.js")));
+
+    // A resolver sharing the cache does not see the conditions of the other provider.
+    let legacy = ConditionNamesProvider::new(|_: &Path| Some(vec!["legacy".into()]));
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        condition_names_provider: Some(legacy),
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(repo.join("apps/modern/src"), "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(repo.join("node_modules/pkg/legacy.js")));
+
+    let resolver = resolver.clone_with_options(ResolveOptions::default());
+    let resolution = resolver.resolve(repo.join("apps/modern/src"), "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(repo.join("node_modules/pkg/index.js")));
+}
@@ -0,0 +1,100 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+fn resolver(description_files: &[&str]) -> Resolver {
+    Resolver::new(ResolveOptions {
+        description_files: description_files.iter().map(ToString::to_string).collect(),
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn description_files() {
+    let f = super::fixture_root().join("description-files");
+    let resolver = resolver(&["package.json", "component.json"]);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("only component.json", "./component", "component/lib/component.js"),
+        ("package.json first", "./both", "both/package.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{comment} {request}");
+    }
+
+    let resolution = resolver.resolve(f.join("component"), "./index.js").unwrap();
+    let package_json = resolution.package_json().unwrap();
+    assert_eq!(package_json.path(), f.join("component/component.json"));
+    assert_eq!(package_json.directory(), f.join("component"));
+
+    let resolver = self::resolver(&["component.json", "package.json"]);
+    let resolution = resolver.resolve(&f, "./both").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("both/component.js")));
+}
+
+#[test]
+fn shared_cache() {
+    let f = super::fixture_root().join("description-files");
+    let resolver = resolver(&["package.json"]);
+    let resolution = resolver.resolve(&f, "./component").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("component/index.js")));
+
+    let options = ResolveOptions {
+        description_files: vec!["component.json".into()],
+        ..ResolveOptions::default()
+    };
+    let component_resolver = resolver.clone_with_options(options);
+    for (resolver, expected) in
+        [(&component_resolver, "both/component.js"), (&resolver, "both/package.js")]
+    {
+        let resolution = resolver.resolve(&f, "./both").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)));
+    }
+}
+
+#[test]
+fn exports_and_imports() {
+    let app = super::fixture_root().join("description-files/exports-and-imports/app");
+    let resolver = resolver(&["deno.json"]);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("", "std", "node_modules/std/mod.js"),
+        ("", "std/path", "node_modules/std/path/mod.js"),
+        ("node_modules/std", "#internal", "node_modules/std/internal.js"),
+    ];
+
+    for (directory, request, expected) in pass {
+        let resolution = resolver.resolve(app.join(directory), request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(app.join(expected)), "{directory} {request}");
+    }
+}
+
+#[test]
+fn errors_name_the_description_file() {
+    let app = super::fixture_root().join("description-files/errors/app");
+    let resolver = resolver(&["deno.json"]);
+
+    let deno_json = |package: &str| app.join(format!("node_modules/{package}/deno.json"));
+    match resolver.resolve(&app, "std/missing") {
+        Err(ResolveError::PackagePathNotExported { package_json_path, .. }) => {
+            assert_eq!(package_json_path, deno_json("std"));
+        }
+        result => panic!("{result:?}"),
+    }
+    assert_eq!(
+        resolver.resolve(&app, "std/lib/a"),
+        Err(ResolveError::InvalidPackageTarget(
+            "../outside/*.js".into(),
+            "./lib/*".into(),
+            deno_json("std")
+        ))
+    );
+    assert_eq!(
+        resolver.resolve(&app, "mixed"),
+        Err(ResolveError::InvalidPackageConfig(deno_json("mixed")))
+    );
+}
@@ -0,0 +1,40 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{AliasValue, EslintResolveOutcome, ResolveOptions, Resolver, into_eslint_result};
+
+#[test]
+fn eslint_import() {
+    let f = super::fixture_root().join("eslint/app");
+    let resolver = Resolver::new(ResolveOptions {
+        alias: vec![("#ignored".into(), vec![AliasValue::Ignore])],
+        alias_fields: vec![vec!["browser".into()]],
+        ..ResolveOptions::default_for_eslint_import()
+    });
+    let importer = f.join("src/index.ts");
+
+    #[rustfmt::skip]
+    let cases = [
+        ("./utils", EslintResolveOutcome::Found(f.join("src/utils.ts"))),
+        ("./utils.js", EslintResolveOutcome::Found(f.join("src/utils.ts"))),
+        ("./component.jsx", EslintResolveOutcome::Found(f.join("src/component.tsx"))),
+        ("./legacy.cjs", EslintResolveOutcome::Found(f.join("src/legacy.cjs"))),
+        ("./worker.mjs", EslintResolveOutcome::Found(f.join("src/worker.mts"))),
+        ("./data.json?raw", EslintResolveOutcome::Found(f.join("src/data.json"))),
+        ("typed", EslintResolveOutcome::Found(f.join("node_modules/typed/lib/index.d.ts"))),
+        ("fs", EslintResolveOutcome::Builtin("node:fs".into())),
+        ("node:path", EslintResolveOutcome::Builtin("node:path".into())),
+        ("#ignored", EslintResolveOutcome::Ignored),
+        ("browser-only", EslintResolveOutcome::Ignored),
+        ("./missing", EslintResolveOutcome::NotFound),
+        ("missing", EslintResolveOutcome::NotFound),
+    ];
+    for (request, expected) in cases {
+        let outcome = into_eslint_result(resolver.resolve_file(&importer, request));
+        assert_eq!(outcome, expected, "{request}");
+    }
+}
+
+#[test]
+fn options_are_valid() {
+    assert_eq!(ResolveOptions::default_for_eslint_import().validate(), vec![]);
+}
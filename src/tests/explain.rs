@@ -0,0 +1,73 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::Path;
+
+use crate::{AliasValue, ResolutionStep, ResolveError, ResolveOptions, Resolver, StepOutcome};
+
+fn step(operation: &'static str, path: impl AsRef<Path>, outcome: StepOutcome) -> ResolutionStep {
+    ResolutionStep { operation, path: path.as_ref().to_path_buf(), outcome }
+}
+
+fn resolver() -> Resolver {
+    Resolver::new(ResolveOptions {
+        alias: vec![("@pkg".into(), vec![AliasValue::from("pkg")])],
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn explain() {
+    let app = super::fixture_root().join("explain/app");
+    let src = app.join("src");
+    let resolver = resolver();
+
+    let explanation = resolver.explain(&src, "./util");
+    assert_eq!(explanation.result.map(|r| r.full_path()), Ok(src.join("util.json")));
+    assert_eq!(
+        explanation.steps,
+        [
+            step("file", src.join("util"), StepOutcome::NotFound),
+            step("file", src.join("util.js"), StepOutcome::NotFound),
+            step("file", src.join("util.json"), StepOutcome::Found),
+            step("resolved", src.join("util.json"), StepOutcome::Found),
+        ]
+    );
+
+    let package_json = app.join("node_modules/pkg/package.json");
+    let cjs = app.join("node_modules/pkg/cjs.js");
+    let explanation = resolver.explain(&src, "@pkg");
+    assert_eq!(
+        explanation.steps,
+        [
+            step("alias", "pkg", StepOutcome::Matched("@pkg".into())),
+            step("exports condition", &package_json, StepOutcome::Skipped("import".into())),
+            step("exports condition", &package_json, StepOutcome::Matched("require".into())),
+            step("exports target", &cjs, StepOutcome::Matched(".".into())),
+            step("file", &cjs, StepOutcome::Found),
+            step("resolved", &cjs, StepOutcome::Found),
+        ]
+    );
+    assert_eq!(
+        explanation.to_string(),
+        [
+            format!(r#"resolve "@pkg" in {}"#, src.display()),
+            "  alias @pkg: pkg".to_string(),
+            format!("  exports condition import skipped: {}", package_json.display()),
+            format!("  exports condition require: {}", package_json.display()),
+            format!("  exports target .: {}", cjs.display()),
+            format!("  file: {} found", cjs.display()),
+            format!("  resolved: {} found", cjs.display()),
+            format!("=> {}", cjs.display()),
+        ]
+        .join("\n")
+    );
+
+    // Steps are recorded for cached paths too.
+    assert_eq!(resolver.explain(&src, "@pkg").steps, explanation.steps);
+
+    let explanation = resolver.explain(&src, "./missing");
+    assert_eq!(explanation.result, Err(ResolveError::NotFound("./missing".into())));
+    assert!(explanation.steps.iter().all(|step| step.outcome == StepOutcome::NotFound));
+    assert!(explanation.to_string().ends_with("=> error: Cannot find module './missing'"));
+}
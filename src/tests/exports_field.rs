@@ -2,12 +2,13 @@
 //!
 //! The huge exports field test cases are at the bottom of this file.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde_json::json;
 
+use super::memory_fs::MemoryFS;
 use crate::{
-    Ctx, PathUtil, ResolveError, ResolveOptions, Resolver,
+    Ctx, PackageJson, PathUtil, ResolveError, ResolveOptions, Resolver,
     package_json::{ImportsExportsEntry, ImportsExportsEntryGeneric},
 };
 
@@ -70,7 +71,8 @@ fn test_simple() {
     let fail = [
         // ("throw error if extension not provided", f2.clone(), "exports-field/dist/main", ResolveError::NotFound(f2.join("node_modules/exports-field/lib/lib2/main"))),
         ("relative path should not work with exports field", f.clone(), "./node_modules/exports-field/dist/main.js", ResolveError::NotFound("./node_modules/exports-field/dist/main.js".into())),
-        ("backtracking should not work for request", f.clone(), "exports-field/dist/../../../a.js", ResolveError::InvalidPackageTarget("./lib/../../../a.js".to_string(), "./dist/".to_string(), p.clone())),
+        // enhanced-resolve reports `InvalidPackageTarget`, Node.js rejects the `..` segments of the request itself.
+        ("backtracking should not work for request", f.clone(), "exports-field/dist/../../../a.js", ResolveError::InvalidModuleSpecifier("./dist/../../../a.js".to_string(), p.clone())),
         ("backtracking should not work for exports field target", f.clone(), "exports-field/dist/a.js", ResolveError::InvalidPackageTarget("./../../a.js".to_string(), "./dist/a.js".to_string(), p.clone())),
         ("not exported error", f.clone(), "exports-field/anything/else", ResolveError::PackagePathNotExported { subpath: "./anything/else".to_string(), package_path: f.join("node_modules/exports-field"), package_json_path: p.clone(), conditions: vec!["webpack".into()].into() }),
         ("request ending with slash #1", f.clone(), "exports-field/", ResolveError::PackagePathNotExported { subpath: "./".to_string(), package_path: f.join("node_modules/exports-field"), package_json_path: p.clone(), conditions: vec!["webpack".into()].into() }),
@@ -298,6 +300,290 @@ fn directory() {
     assert_eq!(path, f.join("exports-field").join("a.js"));
 }
 
+// Not part of enhanced-resolve's test suite.
+// Deprecated folder mappings whose target is a conditions object, modeled on `@babel/runtime@7.0`.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn folder_mapping_with_conditions() {
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/@babel/runtime/package.json",
+            r#"{
+                "name": "@babel/runtime",
+                "exports": {
+                    "./helpers/": { "node": "./helpers/node/", "default": "./helpers/" },
+                    "./regenerator/": {
+                        "node": { "import": "./regenerator/esm/", "default": "./regenerator/cjs/" },
+                        "default": ["./regenerator/"]
+                    }
+                }
+            }"#,
+        ),
+        ("/node_modules/@babel/runtime/helpers/extends.js", ""),
+        ("/node_modules/@babel/runtime/helpers/node/extends.js", ""),
+        ("/node_modules/@babel/runtime/helpers/esm/extends.js", ""),
+        ("/node_modules/@babel/runtime/regenerator/index.js", ""),
+        ("/node_modules/@babel/runtime/regenerator/esm/index.js", ""),
+        ("/node_modules/@babel/runtime/regenerator/cjs/index.js", ""),
+    ]);
+
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let pass = [
+        ("node", vec!["node"], "@babel/runtime/helpers/extends.js", "/node_modules/@babel/runtime/helpers/node/extends.js"),
+        ("default", vec![], "@babel/runtime/helpers/extends.js", "/node_modules/@babel/runtime/helpers/extends.js"),
+        ("nested directory", vec![], "@babel/runtime/helpers/esm/extends.js", "/node_modules/@babel/runtime/helpers/esm/extends.js"),
+        ("nested conditions", vec!["node", "import"], "@babel/runtime/regenerator/index.js", "/node_modules/@babel/runtime/regenerator/esm/index.js"),
+        ("nested default", vec!["node"], "@babel/runtime/regenerator/index.js", "/node_modules/@babel/runtime/regenerator/cjs/index.js"),
+        ("array of folders", vec![], "@babel/runtime/regenerator/index.js", "/node_modules/@babel/runtime/regenerator/index.js"),
+    ];
+
+    for (comment, condition_names, request, expected) in pass {
+        let resolver = resolver.clone_with_options(ResolveOptions {
+            condition_names: condition_names.into_iter().map(Into::into).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolution = resolver.resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+}
+
+// Not part of enhanced-resolve's test suite.
+// The mixed keys validation of an `exports` field runs once per parsed package.json.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn mixed_keys_validated_once() {
+    use std::{
+        path::Path,
+        sync::{Arc, atomic::Ordering},
+    };
+
+    use crate::{ResolverGeneric, context::ResolveContext as Ctx};
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/valid/package.json",
+            r#"{ "exports": { ".": "./a.js", "./b": "./b.js" } }"#,
+        ),
+        ("/node_modules/valid/a.js", ""),
+        ("/node_modules/valid/b.js", ""),
+        (
+            "/node_modules/mixed/package.json",
+            r#"{ "exports": { ".": "./a.js", "import": "./a.js" } }"#,
+        ),
+        ("/node_modules/mixed/a.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+    let package_json = |directory: &str| {
+        let cached_path = resolver.cache.value(Path::new(directory));
+        resolver
+            .cache
+            .get_package_json(&cached_path, &resolver.options, &mut Ctx::default())
+            .unwrap()
+            .unwrap()
+    };
+
+    let valid = package_json("/node_modules/valid");
+    let mixed = package_json("/node_modules/mixed");
+    assert_eq!(valid.exports_mixed_keys.get(), None);
+    assert_eq!(mixed.exports_mixed_keys.get(), None);
+
+    for _ in 0..10 {
+        assert_eq!(
+            resolver.resolve("/", "valid").map(|r| r.full_path()),
+            Ok(PathBuf::from("/node_modules/valid/a.js"))
+        );
+        assert_eq!(
+            resolver.resolve("/", "valid/b").map(|r| r.full_path()),
+            Ok(PathBuf::from("/node_modules/valid/b.js"))
+        );
+        assert_eq!(
+            resolver.resolve("/", "mixed"),
+            Err(ResolveError::InvalidPackageConfig(PathBuf::from(
+                "/node_modules/mixed/package.json"
+            )))
+        );
+    }
+
+    // The validation is kept on the package.json, which is parsed once.
+    assert_eq!(valid.exports_mixed_keys.get(), Some(&false));
+    assert_eq!(mixed.exports_mixed_keys.get(), Some(&true));
+    assert_eq!(valid.exports_mixed_keys_scans.load(Ordering::Relaxed), 1);
+    assert_eq!(mixed.exports_mixed_keys_scans.load(Ordering::Relaxed), 1);
+    assert!(Arc::ptr_eq(&valid, &package_json("/node_modules/valid")));
+    assert!(Arc::ptr_eq(&mixed, &package_json("/node_modules/mixed")));
+}
+
+// Not part of enhanced-resolve's test suite.
+// Node.js requires `exports` targets to exist as written, opt-in via `exports_require_exact_target`.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn exports_require_exact_target() {
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/pkg/package.json",
+            r#"{
+                "name": "pkg",
+                "exports": { ".": "./lib/index", "./exact": "./lib/exact.js", "./dir": "./lib" }
+            }"#,
+        ),
+        ("/node_modules/pkg/lib/index.js", ""),
+        ("/node_modules/pkg/lib/exact.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    // Lenient by default.
+    #[rustfmt::skip]
+    let pass = [
+        ("pkg", "/node_modules/pkg/lib/index.js"),
+        ("pkg/exact", "/node_modules/pkg/lib/exact.js"),
+        ("pkg/dir", "/node_modules/pkg/lib/index.js"),
+    ];
+    for (request, expected) in pass {
+        let resolution = resolver.resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(expected)), "{request}");
+    }
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        exports_require_exact_target: true,
+        ..ResolveOptions::default()
+    });
+
+    let resolution = resolver.resolve("/", "pkg/exact").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/node_modules/pkg/lib/exact.js")));
+
+    #[rustfmt::skip]
+    let fail = [
+        ("pkg", ".", "/node_modules/pkg/lib/index"),
+        ("pkg/dir", "./dir", "/node_modules/pkg/lib"),
+    ];
+    for (request, subpath, target) in fail {
+        let resolution = resolver.resolve("/", request);
+        let error = ResolveError::ExportsTargetNotFound {
+            subpath: subpath.to_string(),
+            package_json_path: PathBuf::from("/node_modules/pkg/package.json"),
+            target: PathBuf::from(target),
+        };
+        assert_eq!(resolution, Err(error), "{request}");
+    }
+}
+
+// Not part of enhanced-resolve's test suite.
+// Native addons are gated behind the `node-addons` condition and must be reported to bundlers.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn via_node_addons() {
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/pkg/package.json",
+            r#"{
+                "name": "pkg",
+                "exports": {
+                    ".": { "node-addons": "./native.node", "default": "./fallback.js" },
+                    "./nested": { "node": { "node-addons": "./native.node" }, "default": "./fallback.js" }
+                }
+            }"#,
+        ),
+        ("/node_modules/pkg/native.node", ""),
+        ("/node_modules/pkg/fallback.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let cases = [
+        (vec![], "pkg", "/node_modules/pkg/fallback.js", false),
+        (vec!["node"], "pkg", "/node_modules/pkg/fallback.js", false),
+        (vec!["node", "node-addons"], "pkg", "/node_modules/pkg/native.node", true),
+        (vec!["node-addons"], "pkg/nested", "/node_modules/pkg/fallback.js", false),
+        (vec!["node", "node-addons"], "pkg/nested", "/node_modules/pkg/native.node", true),
+    ];
+    for (condition_names, request, expected, via_node_addons) in cases {
+        let resolver = resolver.clone_with_options(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolution = resolver.resolve("/", request).unwrap();
+        assert_eq!(resolution.path(), Path::new(expected), "{condition_names:?} {request}");
+        assert_eq!(resolution.via_node_addons(), via_node_addons, "{condition_names:?} {request}");
+    }
+}
+
+// Not part of enhanced-resolve's test suite.
+// A top-level fallback array of condition objects and strings is the main export.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn top_level_array() {
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/pkg/package.json",
+            r#"{ "name": "pkg", "exports": [{ "import": "./index.mjs" }, "./index.js"] }"#,
+        ),
+        ("/node_modules/pkg/index.mjs", ""),
+        ("/node_modules/pkg/index.js", ""),
+        ("/node_modules/pkg/feature.js", ""),
+        (
+            "/node_modules/invalid/package.json",
+            r#"{ "name": "invalid", "exports": ["../escape.js", { "import": "./index.mjs" }, "./index.js"] }"#,
+        ),
+        ("/node_modules/invalid/index.mjs", ""),
+        ("/node_modules/invalid/index.js", ""),
+        (
+            "/node_modules/unmatched/package.json",
+            r#"{ "name": "unmatched", "exports": ["../escape.js", { "import": "./index.mjs" }] }"#,
+        ),
+        ("/node_modules/unmatched/index.mjs", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let pass = [
+        (vec!["import"], "pkg", "/node_modules/pkg/index.mjs"),
+        (vec!["require"], "pkg", "/node_modules/pkg/index.js"),
+        (vec![], "pkg", "/node_modules/pkg/index.js"),
+        (vec!["import"], "invalid", "/node_modules/invalid/index.mjs"),
+        (vec!["require"], "invalid", "/node_modules/invalid/index.js"),
+        (vec!["import"], "unmatched", "/node_modules/unmatched/index.mjs"),
+    ];
+    for (condition_names, request, expected) in pass {
+        let resolver = resolver.clone_with_options(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolution = resolver.resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(expected)), "{condition_names:?} {request}");
+    }
+
+    // Subpaths are not exported by a main export, like in Node.js.
+    let resolution = resolver.resolve("/", "pkg/feature.js");
+    assert!(
+        matches!(resolution, Err(ResolveError::PackagePathNotExported { .. })),
+        "{resolution:?}"
+    );
+
+    // The last error is surfaced when no element matches.
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    });
+    assert_eq!(
+        resolver.resolve("/", "unmatched"),
+        Err(ResolveError::InvalidPackageTarget(
+            "../escape.js".into(),
+            ".".into(),
+            PathBuf::from("/node_modules/unmatched/package.json"),
+        ))
+    );
+}
+
 // Small script for generating the test cases from enhanced-resolve
 // for (c of testCases) {
 //  console.log("TestCase {")
@@ -2540,6 +2826,15 @@ fn test_cases() {
         },
     ];
 
+    // The exports field under test is not part of this package.json.
+    let package_json = PackageJson::parse(
+        &MemoryFS::default(),
+        PathBuf::from("package.json"),
+        PathBuf::from("package.json"),
+        b"{}".to_vec(),
+    )
+    .unwrap();
+
     for case in test_cases {
         let resolver = Resolver::new(ResolveOptions {
             condition_names: case
@@ -2554,6 +2849,7 @@ fn test_cases() {
             .package_exports_resolve(
                 &cached_path,
                 case.request,
+                &package_json,
                 &case.exports_field,
                 None,
                 &mut Ctx::default(),
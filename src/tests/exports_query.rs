@@ -0,0 +1,57 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{MemoryFileSystem, ResolveError, ResolveOptions, Resolver, ResolverGeneric};
+
+fn resolve(directory: &str, specifier: &str) -> (PathBuf, Option<String>, Option<String>) {
+    let f = super::fixture_root().join("exports-query");
+    let resolution = Resolver::default().resolve(f.join(directory), specifier).unwrap();
+    (resolution.path().to_path_buf(), resolution.query, resolution.fragment)
+}
+
+#[test]
+fn pattern_target_with_query() {
+    let f = super::fixture_root().join("exports-query");
+    let logo = f.join("node_modules/pkg/dist/icons/logo.svg");
+    let component = Some("?component".to_string());
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exact", "", "pkg/logo", logo.clone(), component.clone(), None),
+        ("pattern", "", "pkg/icons/logo", logo.clone(), component.clone(), None),
+        ("pattern with fragment", "", "pkg/raw/logo", logo.clone(), Some("?raw".into()), Some("#top".into())),
+        ("imports pattern", "node_modules/pkg", "#icons/logo", logo, component, None),
+    ];
+
+    for (comment, directory, request, path, query, fragment) in pass {
+        assert_eq!(resolve(directory, request), (path, query, fragment), "{comment} {request}");
+    }
+}
+
+// The query and fragment of a target are split off before `*` is substituted, so the matched
+// segment is kept whole even when it contains `?` or `#`.
+#[test]
+fn special_characters_in_pattern_match() {
+    // A `#` of the request is tried as part of the path first.
+    let f = super::fixture_root().join("exports-query");
+    let path = f.join("node_modules/pkg/dist/icons/a#b.svg");
+    assert_eq!(resolve("", "pkg/icons/a#b"), (path, Some("?component".into()), None));
+}
+
+// A `?` of the request always starts its query, it never reaches the pattern. `a?b.svg` is not a
+// valid file name on Windows, so it is not committed to the fixture.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn question_mark_in_pattern_match() {
+    let fs = MemoryFileSystem::new([
+        (
+            "/node_modules/pkg/package.json",
+            r#"{ "exports": { "./icons/*": "./dist/icons/*.svg?component" } }"#,
+        ),
+        ("/node_modules/pkg/dist/icons/a?b.svg", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    let resolution = resolver.resolve("/", "pkg/icons/a?b");
+    assert_eq!(resolution, Err(ResolveError::NotFound("pkg/icons/a".into())));
+}
@@ -0,0 +1,91 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveError, ResolveOptions, Resolver, ResolverGeneric};
+
+#[test]
+fn invalid_segments() {
+    let f = super::fixture_root().join("exports-subpath");
+    let resolver = Resolver::default();
+    let package_json = f.join("node_modules/pkg/package.json");
+
+    #[rustfmt::skip]
+    let fail = [
+        ("dot dot", "pkg/lib/../feature", "./lib/../feature"),
+        ("dot", "pkg/lib/./x", "./lib/./x"),
+        ("empty", "pkg//double", ".//double"),
+        ("node_modules", "pkg/lib/node_modules/x", "./lib/node_modules/x"),
+        ("percent encoded dot dot", "pkg/lib/%2e%2e/feature", "./lib/%2e%2e/feature"),
+        ("percent encoded dot", "pkg/lib/%2E/x", "./lib/%2E/x"),
+    ];
+
+    for (comment, request, subpath) in fail {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::InvalidModuleSpecifier(subpath.into(), package_json.clone());
+        assert_eq!(resolution, Err(error), "{comment} {request}");
+    }
+}
+
+#[test]
+fn valid_segments() {
+    let f = super::fixture_root().join("exports-subpath");
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exports", "pkg/feature", "node_modules/pkg/feature.js"),
+        ("exports pattern", "pkg/lib/x", "node_modules/pkg/lib/x.js"),
+        // Packages without `exports` resolve the normalized path.
+        ("legacy dot dot", "legacy/lib/../feature", "node_modules/legacy/feature.js"),
+        ("legacy empty", "legacy//feature", "node_modules/legacy/feature.js"),
+        ("legacy dot", "legacy/lib/./x", "node_modules/legacy/lib/x.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{comment} {request}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn pattern_key_order() {
+    use crate::MemoryFileSystem;
+
+    // The same keys in both orders.
+    let exports = [
+        [
+            r#""./*": "./dist/*/index.js""#,
+            r#""./*/index": "./dist/*/index.js""#,
+            r#""./*.js": "./dist/*.js""#,
+        ],
+        [
+            r#""./*.js": "./dist/*.js""#,
+            r#""./*/index": "./dist/*/index.js""#,
+            r#""./*": "./dist/*/index.js""#,
+        ],
+    ];
+
+    for exports in exports {
+        let package_json = format!(r#"{{ "exports": {{ {} }} }}"#, exports.join(", "));
+        let fs = MemoryFileSystem::new([
+            ("/node_modules/pkg/package.json", package_json.as_str()),
+            ("/node_modules/pkg/dist/button/index.js", ""),
+            ("/node_modules/pkg/dist/button.js", ""),
+        ]);
+        let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+        #[rustfmt::skip]
+        let pass = [
+            ("shortest pattern", "pkg/button", "/node_modules/pkg/dist/button/index.js"),
+            ("longer pattern with the same base", "pkg/button/index", "/node_modules/pkg/dist/button/index.js"),
+            ("pattern with a trailer", "pkg/button.js", "/node_modules/pkg/dist/button.js"),
+        ];
+
+        for (comment, request, expected) in pass {
+            let resolution = resolver.resolve("/", request).map(|r| r.full_path());
+            assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {exports:?}");
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{AliasValue, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("extension-probe-report/app")
+}
+
+fn options() -> ResolveOptions {
+    let app = app();
+    ResolveOptions {
+        extension_probe_report: true,
+        alias: vec![
+            ("@exact".into(), vec![AliasValue::Path(app.join("foo.js").to_string_lossy().into())]),
+            ("@probed".into(), vec![AliasValue::Path(app.join("foo").to_string_lossy().into())]),
+        ],
+        ..ResolveOptions::default()
+    }
+}
+
+#[test]
+fn probed() {
+    let resolver = Resolver::new(options());
+
+    #[rustfmt::skip]
+    let cases = [
+        ("extensionless relative", "./foo", true),
+        ("fully specified relative", "./foo.js", false),
+        ("index fallback", "./dir", true),
+        ("fully specified index", "./dir/index.js", false),
+        ("exact exports match", "exports", false),
+        ("extensionless main field", "main", true),
+        ("alias to a file", "@exact", false),
+        ("alias to an extensionless path", "@probed", true),
+    ];
+
+    for (comment, request, probed) in cases {
+        let resolution = resolver.resolve(app(), request).unwrap();
+        assert_eq!(resolution.probed(), probed, "{comment} {request}");
+    }
+}
+
+#[test]
+fn disabled() {
+    let resolver = Resolver::new(ResolveOptions { extension_probe_report: false, ..options() });
+    for request in ["./foo", "./dir", "main", "@probed"] {
+        let resolution = resolver.resolve(app(), request).unwrap();
+        assert!(!resolution.probed(), "{request}");
+    }
+}
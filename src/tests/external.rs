@@ -0,0 +1,107 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{AliasValue, ResolveError, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("external/app")
+}
+
+fn resolver(options: ResolveOptions) -> Resolver {
+    Resolver::new(ResolveOptions {
+        external: vec![
+            "react$".into(),
+            "lodash".into(),
+            "@scope/*".into(),
+            "preact".into(),
+            "*-polyfill".into(),
+        ],
+        ..options
+    })
+}
+
+#[test]
+fn external() {
+    let app = app();
+    let src = app.join("src");
+    let resolver = resolver(ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let external = [
+        "react",
+        "lodash",
+        "lodash/fp",
+        "@scope/pkg",
+        "@scope/pkg/sub",
+        "buffer-polyfill",
+        "react?query",
+    ];
+
+    for request in external {
+        let resolved_name = request.split('?').next().unwrap();
+        assert_eq!(
+            resolver.resolve(&src, request),
+            Err(ResolveError::External {
+                specifier: request.into(),
+                resolved_name: resolved_name.into()
+            }),
+            "{request}"
+        );
+    }
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exact match only", "react/jsx-runtime", "node_modules/react/jsx-runtime.js"),
+        ("prefix matches whole segments", "lodash-es", "node_modules/lodash-es/index.js"),
+        ("relative", "./lodash", "src/lodash.js"),
+        ("not listed", "local", "node_modules/local/index.js"),
+        ("wildcard suffix differs", "lodash-es/index.js", "node_modules/lodash-es/index.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve(&src, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(app.join(expected)), "{comment} {request}");
+    }
+
+    // An absolute path is never external.
+    let lodash = src.join("lodash.js");
+    let resolution = resolver.resolve(&src, lodash.to_str().unwrap()).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(lodash));
+
+    let error = resolver.resolve(&src, "react").unwrap_err();
+    assert!(error.is_external());
+    assert!(!error.is_ignore());
+}
+
+#[test]
+fn alias() {
+    let src = app().join("src");
+    let lodash = src.join("lodash.js");
+    let resolver = resolver(ResolveOptions {
+        alias: vec![
+            ("react-dom".into(), vec![AliasValue::from("preact/compat")]),
+            ("lodash".into(), vec![AliasValue::Path(lodash.to_string_lossy().into())]),
+        ],
+        fallback: vec![("@scope/pkg".into(), vec![AliasValue::from("local")])],
+        ..ResolveOptions::default()
+    });
+
+    assert_eq!(
+        resolver.resolve(&src, "react-dom"),
+        Err(ResolveError::External {
+            specifier: "react-dom".into(),
+            resolved_name: "preact/compat".into()
+        })
+    );
+
+    // Aliased to a file, and never falling back.
+    assert_eq!(resolver.resolve(&src, "lodash").map(|r| r.full_path()), Ok(lodash));
+    assert_eq!(
+        resolver.resolve(&src, "@scope/pkg"),
+        Err(ResolveError::External {
+            specifier: "@scope/pkg".into(),
+            resolved_name: "@scope/pkg".into()
+        })
+    );
+}
@@ -0,0 +1,81 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{path::PathBuf, sync::Arc};
+
+use crate::{AmbiguityWinner, FileDirectoryAmbiguity, ResolveOptions, Resolver, Restriction};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("file-directory-ambiguity/app")
+}
+
+fn options() -> ResolveOptions {
+    ResolveOptions {
+        extensions: vec![".ts".into(), ".js".into()],
+        report_file_directory_ambiguity: true,
+        ..ResolveOptions::default()
+    }
+}
+
+#[test]
+fn file_wins() {
+    let app = app();
+    let resolver = Resolver::new(options());
+    let resolution = resolver.resolve(&app, "./thing").unwrap();
+    assert_eq!(resolution.path(), app.join("thing.js"));
+    assert_eq!(
+        resolution.file_directory_ambiguity(),
+        Some(&FileDirectoryAmbiguity {
+            file: app.join("thing.js"),
+            directory: app.join("thing"),
+            winner: AmbiguityWinner::File,
+        })
+    );
+
+    // Not recorded unless enabled.
+    let resolver =
+        Resolver::new(ResolveOptions { report_file_directory_ambiguity: false, ..options() });
+    let resolution = resolver.resolve(&app, "./thing").unwrap();
+    assert_eq!(resolution.path(), app.join("thing.js"));
+    assert_eq!(resolution.file_directory_ambiguity(), None);
+}
+
+#[test]
+fn excluded_file() {
+    let app = app();
+    let resolver = Resolver::new(ResolveOptions {
+        restrictions: vec![Restriction::Fn(Arc::new(|path| {
+            path.extension().is_none_or(|extension| extension != "js")
+        }))],
+        ..options()
+    });
+    let resolution = resolver.resolve(&app, "./thing").unwrap();
+    assert_eq!(resolution.path(), app.join("thing/index.ts"));
+    assert_eq!(
+        resolution.file_directory_ambiguity(),
+        Some(&FileDirectoryAmbiguity {
+            file: app.join("thing.js"),
+            directory: app.join("thing"),
+            winner: AmbiguityWinner::Directory,
+        })
+    );
+}
+
+#[test]
+fn unambiguous() {
+    let app = app();
+    let resolver = Resolver::new(options());
+
+    #[rustfmt::skip]
+    let data = [
+        ("./other", "other.js"),
+        ("./dir", "dir/index.js"),
+        ("./thing/", "thing/index.ts"),
+        ("./thing.js", "thing.js"),
+    ];
+
+    for (specifier, expected) in data {
+        let resolution = resolver.resolve(&app, specifier).unwrap();
+        assert_eq!(resolution.path(), app.join(expected), "{specifier}");
+        assert_eq!(resolution.file_directory_ambiguity(), None, "{specifier}");
+    }
+}
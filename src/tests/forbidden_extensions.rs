@@ -0,0 +1,42 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{AliasValue, ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn forbidden_extensions() {
+    let f = super::fixture_root().join("forbidden-extensions");
+    let secret = f.join("secrets/server.pem");
+    let resolver = Resolver::new(ResolveOptions {
+        alias: vec![("secret".into(), vec![AliasValue::Path(secret.to_string_lossy().into())])],
+        forbidden_extensions: vec![".env".into(), ".env.local".into(), ".pem".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("suffix of the file name only", "./.environment", ".environment.js"),
+        ("suffix of the file name only", "./.environment.js", ".environment.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{comment} {request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("exports", "pkg/config", "node_modules/pkg/.env", ".env"),
+        ("relative multi-dot suffix", "./config.env.local", "config.env.local", ".env.local"),
+        ("alias", "secret", "secrets/server.pem", ".pem"),
+        ("main field", "key", "node_modules/key/key.pem", ".pem"),
+    ];
+
+    for (comment, request, path, extension) in fail {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::ForbiddenExtension {
+            path: f.join(path),
+            extension: extension.to_string(),
+        };
+        assert_eq!(resolution, Err(error), "{comment} {request}");
+    }
+}
@@ -0,0 +1,166 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{
+    AliasValue, EnforceExtension, ResolveError, ResolveOptions, Restriction, UnsupportedOption,
+};
+
+// A webpack `resolve` configuration, serialized as JSON.
+const WEBPACK_RESOLVE: &str = r#"{
+    "alias": {
+        "@": "/project/src",
+        "lodash$": "lodash-es",
+        "fs": false
+    },
+    "aliasFields": ["browser"],
+    "byDependency": {
+        "esm": {
+            "conditionNames": ["import", "..."],
+            "fullySpecified": true,
+            "alias": { "lodash$": "lodash-es/index.js" }
+        },
+        "commonjs": {
+            "conditionNames": ["require", "node"],
+            "unsafeCache": false
+        }
+    },
+    "cacheWithContext": false,
+    "conditionNames": ["webpack", "production", "browser"],
+    "descriptionFiles": ["package.json"],
+    "enforceExtension": false,
+    "exportsFields": ["exports"],
+    "extensionAlias": { ".js": [".ts", ".js"], ".mjs": ".mts" },
+    "extensions": [".tsx", ".ts", "..."],
+    "fallback": [{ "name": "crypto", "alias": "crypto-browserify", "onlyModule": true }],
+    "fullySpecified": false,
+    "importsFields": ["imports"],
+    "mainFields": ["browser", "module", "main"],
+    "mainFiles": ["index"],
+    "modules": ["node_modules", "/project/shared"],
+    "plugins": [],
+    "preferAbsolute": true,
+    "preferRelative": true,
+    "resolveToContext": false,
+    "restrictions": ["/project"],
+    "roots": ["/project"],
+    "symlinks": false,
+    "unsafeCache": true,
+    "useSyncFileSystemCalls": "yes",
+    "unknownOption": 1
+}"#;
+
+fn unsupported(name: &str, reason: &str) -> UnsupportedOption {
+    UnsupportedOption { name: name.into(), reason: reason.into() }
+}
+
+#[test]
+fn webpack_resolve() {
+    let (options, unsupported_options) =
+        ResolveOptions::from_enhanced_resolve(WEBPACK_RESOLVE).unwrap();
+
+    assert_eq!(
+        options.alias,
+        vec![
+            ("@".into(), vec![AliasValue::from("/project/src")]),
+            ("lodash$".into(), vec![AliasValue::from("lodash-es")]),
+            ("fs".into(), vec![AliasValue::Ignore]),
+        ]
+    );
+    assert_eq!(options.alias_fields, vec![vec!["browser".to_string()]]);
+    assert_eq!(options.condition_names, vec!["webpack", "production", "browser"]);
+    assert_eq!(options.enforce_extension, EnforceExtension::Disabled);
+    assert_eq!(options.exports_fields, vec![vec!["exports".to_string()]]);
+    assert_eq!(
+        options.extension_alias,
+        vec![
+            (".js".into(), vec![".ts".into(), ".js".into()]),
+            (".mjs".into(), vec![".mts".into()]),
+        ]
+    );
+    assert_eq!(options.extensions, vec![".tsx", ".ts", ".js", ".json", ".node"]);
+    assert_eq!(
+        options.fallback,
+        vec![("crypto$".into(), vec![AliasValue::from("crypto-browserify")])]
+    );
+    assert!(!options.fully_specified);
+    assert_eq!(options.imports_fields, vec![vec!["imports".to_string()]]);
+    assert_eq!(options.main_fields, vec!["browser", "module", "main"]);
+    assert_eq!(options.main_files, vec!["index"]);
+    assert_eq!(options.modules, vec!["node_modules", "/project/shared"]);
+    assert!(options.prefer_absolute);
+    assert!(options.prefer_relative);
+    assert!(!options.resolve_to_context);
+    assert!(
+        matches!(options.restrictions.as_slice(), [Restriction::Path(path)] if path == &PathBuf::from("/project"))
+    );
+    assert_eq!(options.roots, vec![PathBuf::from("/project")]);
+    assert!(!options.symlinks);
+
+    let cached = "file system lookups are always cached, see `ResolverGeneric::clear_cache`";
+    assert_eq!(
+        unsupported_options,
+        vec![
+            unsupported("byDependency.commonjs.unsafeCache", cached),
+            unsupported("cacheWithContext", cached),
+            unsupported("plugins", "plugins are not supported"),
+            unsupported("unsafeCache", cached),
+            unsupported("useSyncFileSystemCalls", "resolution is always synchronous"),
+            unsupported("unknownOption", "unknown option"),
+        ]
+    );
+}
+
+#[test]
+fn by_dependency() {
+    let by_dependency =
+        ResolveOptions::from_enhanced_resolve_by_dependency(WEBPACK_RESOLVE).unwrap();
+    let names = by_dependency.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["esm", "commonjs"]);
+
+    let esm = &by_dependency[0].1;
+    assert_eq!(esm.condition_names, vec!["import", "webpack", "production", "browser"]);
+    assert!(esm.fully_specified);
+    assert_eq!(
+        esm.alias,
+        vec![
+            ("@".into(), vec![AliasValue::from("/project/src")]),
+            ("lodash$".into(), vec![AliasValue::from("lodash-es/index.js")]),
+            ("fs".into(), vec![AliasValue::Ignore]),
+        ]
+    );
+    assert_eq!(esm.main_fields, vec!["browser", "module", "main"]);
+
+    let commonjs = &by_dependency[1].1;
+    assert_eq!(commonjs.condition_names, vec!["require", "node"]);
+    assert!(!commonjs.fully_specified);
+}
+
+#[test]
+fn unsupported_values() {
+    #[rustfmt::skip]
+    let cases = [
+        (r#"{ "extensions": ".js" }"#, None),
+        (r#"{ "extensions": 1 }"#, Some(unsupported("extensions", "invalid value"))),
+        (r#"{ "mainFields": [["exports", "browser"]] }"#, Some(unsupported("mainFields", "invalid value"))),
+        (r#"{ "symlinks": "true" }"#, Some(unsupported("symlinks", "invalid value"))),
+        (r#"{ "alias": { "a": 1 } }"#, Some(unsupported("alias", "invalid value"))),
+        (r#"{ "descriptionFiles": ["package.json", "component.json"] }"#, None),
+        (r#"{ "descriptionFiles": [1] }"#, Some(unsupported("descriptionFiles", "invalid value"))),
+        (r#"{ "fileSystem": {} }"#, Some(unsupported("fileSystem", "use `ResolverGeneric::new_with_file_system` for a custom file system"))),
+        (r#"{ "pnpApi": null }"#, Some(unsupported("pnpApi", "use the `yarn_pnp` feature and option"))),
+        (r#"{ "byDependency": { "esm": [] } }"#, Some(unsupported("byDependency.esm", "invalid value"))),
+        (r#"{ "byDependency": { "esm": { "byDependency": {} } } }"#, Some(unsupported("byDependency.esm.byDependency", "nested `byDependency` is not supported"))),
+    ];
+
+    for (json, expected) in cases {
+        let (_, unsupported_options) = ResolveOptions::from_enhanced_resolve(json).unwrap();
+        assert_eq!(unsupported_options, expected.into_iter().collect::<Vec<_>>(), "{json}");
+    }
+}
+
+#[test]
+fn invalid_json() {
+    assert!(matches!(ResolveOptions::from_enhanced_resolve("{"), Err(ResolveError::Json(_))));
+    assert!(matches!(ResolveOptions::from_enhanced_resolve("[]"), Err(ResolveError::Json(_))));
+}
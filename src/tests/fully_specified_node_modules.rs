@@ -0,0 +1,60 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("fully-specified-node-modules/app")
+}
+
+fn resolver(fully_specified_applies_to_node_modules: bool) -> Resolver {
+    Resolver::new(ResolveOptions {
+        fully_specified: true,
+        fully_specified_applies_to_node_modules,
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn relaxed_in_packages_without_exports() {
+    let app = app();
+    let src = app.join("src");
+    let resolver = resolver(false);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("extension", "lodash/get", "node_modules/lodash/get.js"),
+        ("index", "lodash/fp", "node_modules/lodash/fp/index.js"),
+        ("fully specified", "lodash/get.js", "node_modules/lodash/get.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&src, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(app.join(expected)), "{comment} {request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("first-party relative", "./util"),
+        ("exports target", "exports/get"),
+    ];
+
+    for (comment, request) in fail {
+        let resolution = resolver.resolve(&src, request);
+        assert!(resolution.is_err(), "{comment} {request} {resolution:?}");
+    }
+}
+
+#[test]
+fn strict_by_default() {
+    let src = app().join("src");
+    let resolver = resolver(true);
+
+    for request in ["lodash/get", "lodash/fp", "./util", "exports/get"] {
+        let resolution = resolver.resolve(&src, request);
+        assert!(resolution.is_err(), "{request} {resolution:?}");
+    }
+    resolver.resolve(&src, "lodash/get.js").unwrap();
+    resolver.resolve(&src, "./util.js").unwrap();
+}
@@ -0,0 +1,86 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+
+use crate::{AliasValue, ImportMap, ResolveError, ResolveOptions, Resolver};
+
+fn specifier_map<const N: usize>(entries: [(&str, &str); N]) -> IndexMap<String, String> {
+    entries.into_iter().map(|(key, address)| (key.to_string(), address.to_string())).collect()
+}
+
+fn app() -> PathBuf {
+    super::fixture_root().join("import-map/app")
+}
+
+fn resolver() -> Resolver {
+    let app = app();
+    let import_map = ImportMap {
+        imports: specifier_map([
+            ("react", "./vendor/react.js"),
+            ("lodash/", "./vendor/lodash/"),
+            ("lodash/fp/", "./vendor/lodash-fp/"),
+            ("lodash/get.js", "./vendor/get-override.js"),
+            ("./src/config.js", "./src/config.prod.js"),
+            ("utils", "pkg"),
+            ("bad/", "./vendor/bad.js"),
+        ]),
+        scopes: IndexMap::from([(
+            "./legacy/".to_string(),
+            specifier_map([("react", "./vendor/react-16.js")]),
+        )]),
+        base: app.clone(),
+    };
+    let other = AliasValue::Path(app.join("other.js").to_string_lossy().into());
+    Resolver::new(ResolveOptions {
+        import_map: Some(import_map),
+        alias: vec![("react".into(), vec![other])],
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn import_map() {
+    let app = app();
+    let resolver = resolver();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exact match before alias", "src", "react", "vendor/react.js"),
+        ("prefix match", "src", "lodash/get", "vendor/lodash/get.js"),
+        ("longest prefix wins", "src", "lodash/fp/map.js", "vendor/lodash-fp/map.js"),
+        ("exact match before prefix", "src", "lodash/get.js", "vendor/get-override.js"),
+        ("relative specifier", "src", "./config.js", "src/config.prod.js"),
+        ("bare address", "src", "utils", "node_modules/pkg/index.js"),
+        ("scope", "legacy/components", "react", "vendor/react-16.js"),
+        ("scope falls back to imports", "legacy", "lodash/get", "vendor/lodash/get.js"),
+        ("unmapped", "src", "unmapped", "node_modules/unmapped/index.js"),
+    ];
+
+    for (comment, path, request, expected) in pass {
+        let resolved_path = resolver.resolve(app.join(path), request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(app.join(expected)), "{comment} {path} {request}");
+    }
+
+    let resolution = resolver.resolve(app.join("src"), "react").unwrap();
+    assert_eq!(resolution.resolved_specifier(), app.join("vendor/react.js").to_string_lossy());
+}
+
+#[test]
+fn blocked() {
+    let src = app().join("src");
+    let resolver = resolver();
+
+    #[rustfmt::skip]
+    let data = [
+        ("address without trailing slash", "bad/x.js", "bad/"),
+        ("back tracking", "lodash/../x.js", "lodash/"),
+    ];
+
+    for (comment, request, key) in data {
+        let resolution = resolver.resolve(&src, request);
+        let error = ResolveError::ImportMapBlocked { specifier: request.into(), key: key.into() };
+        assert_eq!(resolution, Err(error), "{comment} {request}");
+    }
+}
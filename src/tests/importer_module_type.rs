@@ -0,0 +1,36 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveContext, ResolveOptions, Resolver};
+
+#[test]
+fn importer_module_type() {
+    let app = super::fixture_root().join("importer-module-type/app");
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".cts".into(), ".ts".into(), ".mts".into(), ".cjs".into(), ".mjs".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("esm importer", "index.mts", "./util", "util.mts"),
+        ("cjs importer", "index.cts", "./util", "util.cts"),
+        ("js esm importer", "index.mjs", "./lib", "lib/index.mjs"),
+        ("js cjs importer", "index.cjs", "./lib", "lib/index.cjs"),
+        ("no hint", "index.ts", "./util", "util.cts"),
+        ("no hint", "index.js", "./lib", "lib/index.cjs"),
+    ];
+
+    for (comment, importer, request, expected) in pass {
+        let (importer, expected) = (app.join(importer), app.join(expected));
+        let resolution = resolver.resolve_file(&importer, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(expected.clone()), "{comment} {importer:?} {request}");
+        let mut ctx = ResolveContext::default();
+        let resolution =
+            resolver.resolve_file_with_context(&importer, request, &mut ctx).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(expected), "{comment} {importer:?} {request}");
+    }
+
+    // Directories carry no hint.
+    let resolution = resolver.resolve(&app, "./util").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("util.cts")));
+}
@@ -0,0 +1,68 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("imports-fallback/app")
+}
+
+fn resolver(condition_names: &[&str]) -> Resolver {
+    Resolver::new(ResolveOptions {
+        condition_names: condition_names.iter().map(ToString::to_string).collect(),
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn imports_array() {
+    let app = app();
+    let resolver = resolver(&["node", "import"]);
+    let resolution = resolver.resolve(&app, "#dep").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("impl-node.cjs")));
+
+    let resolution = self::resolver(&["import"]).resolve(&app, "#dep").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("impl-browser.mjs")));
+
+    // The first entry is kept when no entry exists.
+    let resolution = resolver.resolve(&app, "#missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("#missing".into())));
+    let resolution = resolver.resolve(&app, "#invalid");
+    assert_eq!(resolution, Err(ResolveError::NotFound("#invalid".into())));
+}
+
+#[test]
+fn exports_array() {
+    let app = app();
+    let resolver = resolver(&["node", "import"]);
+
+    // The first valid target is the match, whether it exists or not.
+    let resolution = resolver.resolve(&app, "pkg");
+    assert_eq!(resolution, Err(ResolveError::NotFound("pkg".into())));
+
+    // The error of the last entry is returned when no entry is valid.
+    let resolution = resolver.resolve(&app, "pkg/invalid");
+    let error = ResolveError::InvalidPackageTarget(
+        "/absolute.js".into(),
+        "./invalid".into(),
+        app.join("node_modules/pkg/package.json"),
+    );
+    assert_eq!(resolution, Err(error));
+}
+
+#[test]
+fn imports_array_skipped_entries() {
+    let app = super::fixture_root().join("imports-fallback/skipped-entries");
+    let resolver = Resolver::default();
+
+    // The query and fragment of a skipped entry are not kept.
+    let resolution = resolver.resolve(&app, "#query").unwrap();
+    assert_eq!(resolution.full_path(), app.join("impl.js"));
+    assert_eq!(resolution.query(), None);
+    assert_eq!(resolution.fragment(), None);
+
+    // An entry which fails to load moves on to the next entry.
+    let resolution = resolver.resolve(&app, "#broken").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("impl.js?kept")));
+}
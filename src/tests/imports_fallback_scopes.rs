@@ -0,0 +1,73 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+fn workspace() -> PathBuf {
+    super::fixture_root().join("imports-fallback-scopes/workspace")
+}
+
+fn resolver(imports_fallback_scopes: Vec<PathBuf>) -> Resolver {
+    Resolver::new(ResolveOptions { imports_fallback_scopes, ..ResolveOptions::default() })
+}
+
+#[test]
+fn fallback_scopes() {
+    let workspace = workspace();
+    let resolver = resolver(vec![workspace.clone()]);
+
+    #[rustfmt::skip]
+    let data = [
+        ("member without imports", "packages/app/src", "#shared/utils", "shared/utils.js"),
+        ("member without a match", "packages/lib", "#shared/utils", "shared/utils.js"),
+        ("member mapping wins", "packages/lib", "#config", "packages/lib/config.lib.js"),
+        ("root itself", "", "#config", "config.root.js"),
+    ];
+
+    for (comment, directory, request, expected) in data {
+        let resolution =
+            resolver.resolve(workspace.join(directory), request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(workspace.join(expected)), "{comment} {directory} {request}");
+    }
+}
+
+#[test]
+fn not_defined() {
+    let workspace = workspace();
+    let resolver = resolver(vec![workspace.clone()]);
+
+    #[rustfmt::skip]
+    let data = [
+        ("packages/app/src", "#missing", "packages/app/package.json"),
+        ("packages/lib", "#missing", "packages/lib/package.json"),
+    ];
+
+    for (directory, request, package_json) in data {
+        let resolution =
+            resolver.resolve(workspace.join(directory), request).map(|r| r.full_path());
+        assert_eq!(
+            resolution,
+            Err(ResolveError::PackageImportNotDefined(
+                request.into(),
+                workspace.join(package_json)
+            )),
+            "{directory} {request}"
+        );
+    }
+}
+
+#[test]
+fn disabled() {
+    let workspace = workspace();
+    let resolver = resolver(vec![]);
+
+    assert_eq!(
+        resolver.resolve(workspace.join("packages/lib"), "#shared/utils").map(|r| r.full_path()),
+        Err(ResolveError::PackageImportNotDefined(
+            "#shared/utils".into(),
+            workspace.join("packages/lib/package.json")
+        ))
+    );
+    resolver.resolve(workspace.join("packages/app/src"), "#shared/utils").unwrap_err();
+}
@@ -0,0 +1,101 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    DependencyKind, DependencySink, ResolveContext, ResolveContextInterned, ResolveOptions,
+    Resolver,
+};
+
+/// `node_modules/pkg{0..50}` and `src/dir{0..50}` are in the fixture.
+const PACKAGES: usize = 50;
+const IMPORTERS: usize = 50;
+
+fn app() -> PathBuf {
+    super::fixture_root().join("interned-dependencies/app")
+}
+
+fn paths<'a>(paths: impl Iterator<Item = &'a Path>) -> FxHashSet<PathBuf> {
+    paths.map(Path::to_path_buf).collect()
+}
+
+#[test]
+fn interned() {
+    // A package.json is only recorded by the first resolution reading it, so both runs get a
+    // resolver of their own.
+    let app = app();
+    let resolver = Resolver::default();
+    let fresh_resolver = Resolver::default();
+
+    let mut interned = ResolveContextInterned::default();
+    let mut union = ResolveContext::default();
+    let mut recorded = 0;
+    for importer in 0..IMPORTERS {
+        let directory = app.join(format!("src/dir{importer}"));
+        for package in 0..PACKAGES {
+            let specifier = format!("pkg{package}");
+            resolver.resolve_with_context(&directory, &specifier, None, &mut interned).unwrap();
+            let mut ctx = ResolveContext::default();
+            fresh_resolver.resolve_with_context(&directory, &specifier, None, &mut ctx).unwrap();
+            recorded += ctx.file_dependencies.len() + ctx.missing_dependencies.len();
+            union.file_dependencies.extend(ctx.file_dependencies);
+            union.missing_dependencies.extend(ctx.missing_dependencies);
+        }
+    }
+
+    assert_eq!(paths(interned.iter_file_dependencies()), union.file_dependencies);
+    assert_eq!(paths(interned.iter_missing_dependencies()), union.missing_dependencies);
+
+    // Each unique path is stored once, by the cache, however often it was recorded.
+    let unique = interned.file_dependencies.len() + interned.missing_dependencies.len();
+    assert_eq!(unique, union.file_dependencies.len() + union.missing_dependencies.len());
+    assert!(recorded > unique * 10, "{recorded} recorded, {unique} unique");
+    for path in interned.file_dependencies.iter().chain(&interned.missing_dependencies) {
+        assert!(Arc::ptr_eq(&path.0, &resolver.cache.value(path.path()).0), "{path}");
+    }
+
+    let converted = ResolveContext::from(interned);
+    assert_eq!(converted.file_dependencies, union.file_dependencies);
+    assert_eq!(converted.missing_dependencies, union.missing_dependencies);
+}
+
+#[test]
+fn dependency_sink() {
+    let streamed = Arc::new(Mutex::new((FxHashSet::default(), FxHashSet::default())));
+    let sink = {
+        let streamed = Arc::clone(&streamed);
+        DependencySink::new(move |kind, path| {
+            let mut streamed = streamed.lock().unwrap();
+            match kind {
+                DependencyKind::File => streamed.0.insert(path.to_path_buf()),
+                DependencyKind::Missing => streamed.1.insert(path.to_path_buf()),
+            };
+        })
+    };
+    let options = ResolveOptions { dependency_sink: Some(sink), ..ResolveOptions::default() };
+    let app = app();
+    let resolver = Resolver::new(options);
+
+    // Resolutions without a context are streamed too.
+    resolver.resolve(app.join("src/dir0"), "pkg0").unwrap();
+    resolver.resolve_file(app.join("src/dir1/index.js"), "pkg1").unwrap();
+    resolver.resolve(app.join("src/dir0"), "missing").unwrap_err();
+
+    // The same resolutions with a context, on a resolver with a cold cache as well.
+    let resolver = Resolver::default();
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context(app.join("src/dir0"), "pkg0", None, &mut ctx).unwrap();
+    resolver.resolve_file_with_context(app.join("src/dir1/index.js"), "pkg1", &mut ctx).unwrap();
+    resolver.resolve_with_context(app.join("src/dir0"), "missing", None, &mut ctx).unwrap_err();
+
+    let (files, missing) = std::mem::take(&mut *streamed.lock().unwrap());
+    assert_eq!(files, ctx.file_dependencies);
+    assert_eq!(missing, ctx.missing_dependencies);
+    assert!(files.contains(&app.join("src/dir1/index.js")));
+    assert!(missing.contains(&app.join("node_modules/missing")));
+}
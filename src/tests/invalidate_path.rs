@@ -0,0 +1,204 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    FileMetadata, FileSystem, MemoryFileSystem, ResolveError, ResolveOptions, ResolverGeneric,
+    TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
+};
+
+/// Counts the metadata queries of each path.
+#[derive(Default)]
+struct CountingFS {
+    fs: Arc<MemoryFileSystem>,
+    queries: Mutex<FxHashMap<PathBuf, usize>>,
+}
+
+impl CountingFS {
+    fn count(&self, path: &Path) {
+        *self.queries.lock().unwrap().entry(path.to_path_buf()).or_default() += 1;
+    }
+
+    fn queries(&self, path: &str) -> usize {
+        self.queries.lock().unwrap().get(Path::new(path)).copied().unwrap_or_default()
+    }
+}
+
+impl FileSystem for CountingFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.fs.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.count(path);
+        self.fs.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.count(path);
+        self.fs.symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.fs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+}
+
+fn file_system() -> Arc<MemoryFileSystem> {
+    Arc::new(MemoryFileSystem::new([
+        ("/app/node_modules/pkg/package.json", r#"{ "main": "./a.js" }"#),
+        ("/app/node_modules/pkg/a.js", ""),
+        ("/app/node_modules/pkg/b.js", ""),
+        ("/app/tsconfig.json", r#"{ "compilerOptions": { "paths": { "@/*": ["./a/*"] } } }"#),
+        ("/app/a/util.js", ""),
+        ("/app/b/util.js", ""),
+        ("/app/index.js", ""),
+    ]))
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn created_file() {
+    let fs = file_system();
+    let resolver =
+        ResolverGeneric::new_with_file_system(Arc::clone(&fs), ResolveOptions::default());
+    resolver.resolve("/app", "./new").unwrap_err();
+
+    fs.add_file("/app/new.js", "");
+    resolver.resolve("/app", "./new").unwrap_err();
+    assert!(resolver.invalidate_path("/app/new.js"));
+    let resolution = resolver.resolve("/app", "./new").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/new.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn changed_package_json() {
+    let fs = file_system();
+    let resolver =
+        ResolverGeneric::new_with_file_system(Arc::clone(&fs), ResolveOptions::default());
+    let resolution = resolver.resolve("/app", "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/pkg/a.js")));
+
+    fs.add_file("/app/node_modules/pkg/package.json", r#"{ "main": "./b.js" }"#);
+    assert!(resolver.invalidate_path("/app/node_modules/pkg/package.json"));
+    let resolution = resolver.resolve("/app", "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/pkg/b.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn changed_tsconfig() {
+    let fs = file_system();
+    let resolver = ResolverGeneric::new_with_file_system(
+        Arc::clone(&fs),
+        ResolveOptions {
+            tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+                config_file: PathBuf::from("/app/tsconfig.json"),
+                references: TsconfigReferences::Disabled,
+            })),
+            ..ResolveOptions::default()
+        },
+    );
+    let resolution = resolver.resolve("/app", "@/util").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/a/util.js")));
+
+    fs.add_file(
+        "/app/tsconfig.json",
+        r#"{ "compilerOptions": { "paths": { "@/*": ["./b/*"] } } }"#,
+    );
+    assert!(resolver.invalidate_path("/app/tsconfig.json"));
+    let resolution = resolver.resolve("/app", "@/util").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/b/util.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn keeps_unrelated_entries() {
+    let resolver = ResolverGeneric::new_with_file_system(file_system(), ResolveOptions::default());
+    resolver.resolve("/app", "./index").unwrap();
+    resolver.resolve("/app", "pkg").unwrap();
+
+    assert!(!resolver.invalidate_path("/elsewhere/file.js"));
+    assert!(resolver.invalidate_path("/app/index.js"));
+    assert!(!resolver.invalidate_path("/app/index.js"));
+
+    resolver.enable_cache_statistics();
+    resolver.reset_cache_statistics();
+    resolver.resolve("/app", "pkg").unwrap();
+    let statistics = resolver.cache_statistics();
+    assert_eq!((statistics.file_misses, statistics.package_json_misses), (0, 0));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn restats_only_invalidated_path() {
+    let fs = Arc::new(CountingFS { fs: file_system(), ..CountingFS::default() });
+    let resolver =
+        ResolverGeneric::new_with_file_system(Arc::clone(&fs), ResolveOptions::default());
+    resolver.resolve("/app", "./index.js").unwrap();
+    resolver.resolve("/app", "pkg").unwrap();
+    let index_queries = fs.queries("/app/index.js");
+    let package_json_queries = fs.queries("/app/node_modules/pkg/package.json");
+
+    fs.fs.add_file("/app/node_modules/pkg/package.json", r#"{ "main": "./b.js" }"#);
+    assert!(resolver.invalidate_path("/app/node_modules/pkg/package.json"));
+    let resolution = resolver.resolve("/app", "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/pkg/b.js")));
+    resolver.resolve("/app", "./index.js").unwrap();
+
+    assert!(fs.queries("/app/node_modules/pkg/package.json") > package_json_queries);
+    assert_eq!(fs.queries("/app/index.js"), index_queries);
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn invalidate_dir_recursive() {
+    let fs = Arc::new(CountingFS { fs: file_system(), ..CountingFS::default() });
+    let resolver =
+        ResolverGeneric::new_with_file_system(Arc::clone(&fs), ResolveOptions::default());
+    resolver.resolve("/app", "./a/util.js").unwrap();
+    resolver.resolve("/app", "./a/new").unwrap_err();
+    resolver.resolve("/app", "./index.js").unwrap();
+    let util_queries = fs.queries("/app/a/util.js");
+    let index_queries = fs.queries("/app/index.js");
+
+    // The paths below a directory are kept when only the directory is invalidated.
+    fs.fs.add_file("/app/a/new.js", "");
+    assert!(resolver.invalidate_path("/app/a"));
+    resolver.resolve("/app", "./a/new").unwrap_err();
+    resolver.resolve("/app", "./a/util.js").unwrap();
+    assert_eq!(fs.queries("/app/a/util.js"), util_queries);
+
+    assert!(resolver.invalidate_dir_recursive("/app/a"));
+    let resolution = resolver.resolve("/app", "./a/new").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/a/new.js")));
+    resolver.resolve("/app", "./a/util.js").unwrap();
+    resolver.resolve("/app", "./index.js").unwrap();
+    assert!(fs.queries("/app/a/util.js") > util_queries);
+    assert_eq!(fs.queries("/app/index.js"), index_queries);
+}
@@ -0,0 +1,68 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions, TsconfigReferences};
+
+fn manual(config_file: std::path::PathBuf) -> Resolver {
+    Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file,
+            references: TsconfigReferences::Auto,
+        })),
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn paths() {
+    let f = super::fixture_root().join("tsconfig/cases");
+
+    #[rustfmt::skip]
+    let pass = [
+        ("jsconfig only", f.join("jsconfig-only"), "jsconfig-only/src/util.js"),
+        ("jsconfig file", f.join("jsconfig-only/jsconfig.json"), "jsconfig-only/src/util.js"),
+        ("tsconfig wins", f.join("jsconfig-both"), "jsconfig-both/ts/util.js"),
+        ("jsconfig file next to tsconfig", f.join("jsconfig-both/jsconfig.json"), "jsconfig-both/js/util.js"),
+        ("jsconfig extending tsconfig", f.join("jsconfig-extends"), "jsconfig-extends/src/util.js"),
+    ];
+
+    for (comment, config_file, expected) in pass {
+        let directory = config_file.parent().unwrap().to_path_buf();
+        let resolution = manual(config_file).resolve(&directory, "@/util").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{comment}");
+    }
+}
+
+#[test]
+fn discovery() {
+    let f = super::fixture_root().join("tsconfig/cases");
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Auto),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("jsconfig only", "jsconfig-only/index.js", "jsconfig-only/src/util.js"),
+        ("tsconfig wins", "jsconfig-both/index.ts", "jsconfig-both/ts/util.js"),
+        ("jsconfig extending tsconfig", "jsconfig-extends/index.js", "jsconfig-extends/src/util.js"),
+    ];
+
+    for (comment, importer, expected) in pass {
+        let resolution = resolver.resolve_file(f.join(importer), "@/util").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{comment}");
+    }
+}
+
+#[test]
+fn allow_js() {
+    let f = super::fixture_root().join("tsconfig/cases");
+    let resolver = Resolver::default();
+
+    let tsconfig = resolver.resolve_tsconfig(f.join("jsconfig-only")).unwrap();
+    assert_eq!(tsconfig.path, f.join("jsconfig-only/jsconfig.json"));
+    assert_eq!(tsconfig.compiler_options.allow_js, Some(true));
+
+    let tsconfig = resolver.resolve_tsconfig(f.join("jsconfig-both")).unwrap();
+    assert_eq!(tsconfig.path, f.join("jsconfig-both/tsconfig.json"));
+    assert_eq!(tsconfig.compiler_options.allow_js, None);
+}
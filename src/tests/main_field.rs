@@ -1,6 +1,9 @@
 //! Not part of enhanced_resolve's test suite
 
-use crate::{ResolveOptions, Resolver};
+use std::path::PathBuf;
+
+use super::memory_fs::MemoryFS;
+use crate::{ResolveOptions, Resolver, ResolverGeneric};
 
 #[test]
 fn test() {
@@ -36,3 +39,37 @@ fn test_fallback() {
     let resolution = resolver1.resolve(&f, "main_field_fallback").map(|r| r.full_path());
     assert_eq!(resolution, Ok(f.join("node_modules/main_field_fallback/exist.js")));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn directory() {
+    let fs = MemoryFS::new(&[
+        ("/node_modules/lib-index/package.json", r#"{ "browser": "./lib" }"#),
+        ("/node_modules/lib-index/lib/index.js", ""),
+        ("/node_modules/lib-main/package.json", r#"{ "browser": "./lib", "main": "./main.js" }"#),
+        ("/node_modules/lib-main/main.js", ""),
+        ("/node_modules/lib-main/lib/package.json", r#"{ "main": "./browser.js" }"#),
+        ("/node_modules/lib-main/lib/browser.js", ""),
+        ("/node_modules/lib-main/lib/index.js", ""),
+        ("/node_modules/self/package.json", r#"{ "browser": "./", "main": "../self/main.js" }"#),
+        ("/node_modules/self/main.js", ""),
+        ("/node_modules/self/index.js", ""),
+    ]);
+    let options = ResolveOptions {
+        main_fields: vec!["browser".into(), "main".into()],
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("index.js of the directory", "lib-index", "/node_modules/lib-index/lib/index.js"),
+        ("package.json of the directory before index.js", "lib-main", "/node_modules/lib-main/lib/browser.js"),
+        ("the package itself", "self", "/node_modules/self/index.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+}
@@ -0,0 +1,78 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveContext, ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn main_files_outer_extensions_inner() {
+    let f = super::fixture_root().join("main-files");
+    let resolver = Resolver::new(ResolveOptions {
+        main_files: vec!["index".into(), "main".into()],
+        extensions: vec![".js".into(), ".ts".into()],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "./ordering").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("ordering/index.ts")));
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        main_files: vec!["main".into(), "index".into()],
+        extensions: vec![".ts".into(), ".js".into()],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "./ordering").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("ordering/main.js")));
+}
+
+#[test]
+fn main_field_skips_main_files() {
+    let f = super::fixture_root().join("main-files");
+    let resolver = Resolver::new(ResolveOptions {
+        main_files: vec!["index".into(), "main".into()],
+        ..ResolveOptions::default()
+    });
+    let mut ctx = ResolveContext::default();
+    let resolution =
+        resolver.resolve_with_context(&f, "./main_field", None, &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("main_field/entry.js")));
+    let probed = ctx.file_dependencies.iter().chain(ctx.missing_dependencies.iter());
+    for path in probed {
+        assert!(
+            !path.starts_with(f.join("main_field/index"))
+                && !path.starts_with(f.join("main_field/main")),
+            "unexpected main file probe {}",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn directories_lib() {
+    let f = super::fixture_root().join("main-files");
+    let resolver = Resolver::default();
+    let resolution = resolver.resolve(&f, "./legacy");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./legacy".into())));
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        use_directories_lib: true,
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("lib/index", "./legacy", "legacy/lib/index.js"),
+        ("index in the directory comes first", "./legacy_with_index", "legacy_with_index/index.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{comment} {request}");
+    }
+
+    // An absolute `directories.lib` stays inside the package.
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        use_directories_lib: true,
+        main_files: vec!["main".into()],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "./legacy_absolute").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("legacy_absolute/lib/main.js")));
+}
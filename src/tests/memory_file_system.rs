@@ -0,0 +1,111 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{
+    FileSystem, MemoryFileSystem, ResolveOptions, ResolverGeneric, TsconfigDiscovery,
+    TsconfigOptions, TsconfigReferences,
+};
+
+fn file_system() -> MemoryFileSystem {
+    MemoryFileSystem::new([
+        (
+            "/app/node_modules/pkg/package.json",
+            r#"{ "name": "pkg", "exports": { ".": { "import": "./esm.js", "default": "./cjs.js" }, "./feature": "./feature.js" } }"#,
+        ),
+        ("/app/node_modules/pkg/esm.js", ""),
+        ("/app/node_modules/pkg/cjs.js", ""),
+        ("/app/node_modules/pkg/feature.js", ""),
+        ("/app/tsconfig.json", r#"{ "compilerOptions": { "paths": { "@/*": ["./src/*"] } } }"#),
+        ("/app/src/util.ts", ""),
+        ("/app/src/index.ts", ""),
+    ])
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's paths in these tests are unix paths.
+fn exports_and_tsconfig_paths() {
+    let resolver = ResolverGeneric::new_with_file_system(
+        file_system(),
+        ResolveOptions {
+            condition_names: vec!["import".into()],
+            extensions: vec![".ts".into(), ".js".into()],
+            tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+                config_file: PathBuf::from("/app/tsconfig.json"),
+                references: TsconfigReferences::Disabled,
+            })),
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exports", "pkg", "/app/node_modules/pkg/esm.js"),
+        ("exports subpath", "pkg/feature", "/app/node_modules/pkg/feature.js"),
+        ("tsconfig paths", "@/util", "/app/src/util.ts"),
+        ("relative", "./src/index", "/app/src/index.ts"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolution = resolver.resolve("/app", request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's paths in these tests are unix paths.
+fn symlinks() {
+    let fs = file_system();
+    fs.add_symlink("/app/node_modules/linked", "../packages/linked");
+    fs.add_file("/app/packages/linked/index.js", "");
+    fs.add_symlink("/app/src/alias.ts", "util.ts");
+
+    assert!(fs.symlink_metadata("/app/node_modules/linked".as_ref()).unwrap().is_symlink());
+    assert!(fs.metadata("/app/node_modules/linked".as_ref()).unwrap().is_dir());
+    assert_eq!(
+        fs.read_link("/app/node_modules/linked".as_ref()),
+        Ok(PathBuf::from("/app/packages/linked"))
+    );
+    assert_eq!(
+        fs.canonicalize("/app/node_modules/linked/index.js".as_ref()).unwrap(),
+        PathBuf::from("/app/packages/linked/index.js")
+    );
+
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    let resolution = resolver.resolve("/app", "linked").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/packages/linked/index.js")));
+    let resolution = resolver.resolve("/app", "./src/alias.ts").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/src/util.ts")));
+
+    let resolver = resolver
+        .clone_with_options(ResolveOptions { symlinks: false, ..ResolveOptions::default() });
+    let resolution = resolver.resolve("/app", "linked").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/linked/index.js")));
+}
+
+#[test]
+fn symlink_loop() {
+    let fs = MemoryFileSystem::default();
+    fs.add_symlink("/a", "/b");
+    fs.add_symlink("/b", "/a");
+    fs.metadata("/a".as_ref()).unwrap_err();
+    fs.canonicalize("/a/index.js".as_ref()).unwrap_err();
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's paths in these tests are unix paths.
+fn add_file_after_resolution() {
+    let fs = std::sync::Arc::new(file_system());
+    let resolver = ResolverGeneric::new_with_file_system(
+        std::sync::Arc::clone(&fs),
+        ResolveOptions::default(),
+    );
+    resolver.resolve("/app", "./new").unwrap_err();
+
+    fs.add_file("/app/new.js", "");
+    // The miss is cached until the cache is cleared.
+    resolver.resolve("/app", "./new").unwrap_err();
+    resolver.clear_cache();
+    let resolution = resolver.resolve("/app", "./new").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/new.js")));
+}
@@ -1,13 +1,18 @@
 use std::{
     io,
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 
+#[cfg(feature = "async")]
+use crate::{AsyncFileSystem, FileSystemFuture};
 use crate::{FileMetadata, FileSystem, ResolveError};
 
 #[derive(Default)]
 pub struct MemoryFS {
     fs: vfs::MemoryFS,
+    /// Paths of special files (e.g. FIFOs), which are neither a file nor a directory.
+    special_files: Vec<PathBuf>,
 }
 
 impl MemoryFS {
@@ -16,7 +21,7 @@ impl MemoryFS {
     /// * Fails to create directory
     /// * Fails to write file
     pub fn new(data: &[(&'static str, &'static str)]) -> Self {
-        let mut fs = Self { fs: vfs::MemoryFS::default() };
+        let mut fs = Self::default();
         for (path, content) in data {
             fs.add_file(Path::new(path), content);
         }
@@ -37,6 +42,25 @@ impl MemoryFS {
         let mut file = fs.create_file(path.to_string_lossy().as_ref()).unwrap();
         file.write_all(content.as_bytes()).unwrap();
     }
+
+    pub fn add_dir(&mut self, path: &Path) {
+        use vfs::FileSystem;
+        let fs = &mut self.fs;
+        for path in path.ancestors().collect::<Vec<_>>().iter().rev() {
+            let path = path.to_string_lossy();
+            if !fs.exists(path.as_ref()).unwrap() {
+                fs.create_dir(path.as_ref()).unwrap();
+            }
+        }
+    }
+
+    /// Add a special file such as a FIFO, reading it panics.
+    pub fn add_special_file(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            self.add_dir(parent);
+        }
+        self.special_files.push(path.to_path_buf());
+    }
 }
 
 impl FileSystem for MemoryFS {
@@ -52,6 +76,11 @@ impl FileSystem for MemoryFS {
 
     fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
         use vfs::FileSystem;
+        assert!(
+            !self.special_files.iter().any(|p| p == path),
+            "reading special file {}",
+            path.display()
+        );
         let mut file = self
             .fs
             .open_file(path.to_string_lossy().as_ref())
@@ -68,13 +97,19 @@ impl FileSystem for MemoryFS {
 
     fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
         use vfs::FileSystem;
+        if self.special_files.iter().any(|p| p == path) {
+            return Ok(FileMetadata::new(false, false, false));
+        }
         let metadata = self
             .fs
             .metadata(path.to_string_lossy().as_ref())
             .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
         let is_file = metadata.file_type == vfs::VfsFileType::File;
         let is_dir = metadata.file_type == vfs::VfsFileType::Directory;
-        Ok(FileMetadata::new(is_file, is_dir, false))
+        // Deterministic modification time so tests can assert on it.
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(metadata.len);
+        Ok(FileMetadata::new(is_file, is_dir, false)
+            .with_size_and_modified(metadata.len, Some(modified)))
     }
 
     fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
@@ -93,4 +128,45 @@ impl FileSystem for MemoryFS {
             .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
         Ok(path.to_path_buf())
     }
+
+    #[cfg(feature = "async")]
+    fn as_async(&self) -> Option<&dyn AsyncFileSystem> {
+        Some(self)
+    }
+}
+
+/// Completes immediately with the result of the [FileSystem] method.
+#[cfg(feature = "async")]
+impl AsyncFileSystem for MemoryFS {
+    fn read_async<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, io::Result<Vec<u8>>> {
+        Box::pin(std::future::ready(FileSystem::read(self, path)))
+    }
+
+    fn metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        Box::pin(std::future::ready(FileSystem::metadata(self, path)))
+    }
+
+    fn symlink_metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        Box::pin(std::future::ready(FileSystem::symlink_metadata(self, path)))
+    }
+
+    fn read_link_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<PathBuf, ResolveError>> {
+        Box::pin(std::future::ready(FileSystem::read_link(self, path)))
+    }
+
+    fn canonicalize_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<PathBuf>> {
+        Box::pin(std::future::ready(FileSystem::canonicalize(self, path)))
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::Resolver;
+use crate::{Resolver, context::ResolveContext as Ctx};
 
 /// Test to prove memory leak in `CachedPath` Arc cycles
 #[test]
@@ -13,8 +13,9 @@ fn test_memory_leak_arc_cycles() {
 
     resolver.resolve(&f, "package-json-nested").unwrap();
 
-    // Populated cache - path is now owned in multiple places.
-    assert_eq!(Arc::strong_count(&path.0), 2);
+    // Populated cache - path is now owned in multiple places,
+    // the paths and the memoized module directories of the cache.
+    assert_eq!(Arc::strong_count(&path.0), 3);
 
     // Drop the resolver.
     drop(resolver);
@@ -36,10 +37,10 @@ fn test_canonicalized_path_not_dropped() {
     let path = resolver.cache.value(&f);
 
     // This should work without "Canonicalized path was dropped" error
-    let canonicalized = resolver.cache.canonicalize(&path).unwrap();
+    let canonicalized = resolver.cache.canonicalize(&path, &mut Ctx::default()).unwrap();
 
     // Try canonicalizing again - should still work
-    let canonicalized2 = resolver.cache.canonicalize(&path).unwrap();
+    let canonicalized2 = resolver.cache.canonicalize(&path, &mut Ctx::default()).unwrap();
     assert_eq!(canonicalized, canonicalized2);
 }
 
@@ -58,12 +59,12 @@ fn test_canonicalized_path_weak_reference() {
 
     // Canonicalize a path that doesn't exist in the cache's hashmap yet
     // This might fail with "Canonicalized path was dropped" if the implementation is wrong
-    match resolver.cache.canonicalize(&path) {
+    match resolver.cache.canonicalize(&path, &mut Ctx::default()) {
         Ok(_) => {
             // If canonicalization succeeded, try again to ensure consistency
-            let result2 = resolver.cache.canonicalize(&path);
+            let result2 = resolver.cache.canonicalize(&path, &mut Ctx::default());
             assert_eq!(
-                resolver.cache.canonicalize(&path).ok(),
+                resolver.cache.canonicalize(&path, &mut Ctx::default()).ok(),
                 result2.ok(),
                 "Canonicalization results should be consistent"
             );
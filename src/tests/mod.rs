@@ -1,38 +1,93 @@
+mod adaptive_extension_probing;
 mod alias;
 mod browser_field;
 mod builtins;
+mod bundled_dependencies;
+mod cache_snapshot;
+mod cache_statistics;
+mod case_sensitivity;
+mod condition_names_provider;
 mod dependencies;
+mod description_files;
 mod dts_resolver;
+mod eslint;
+mod explain;
 mod exports_field;
+mod exports_query;
+mod exports_subpath;
 mod extension_alias;
+mod extension_probe_report;
 mod extensions;
+mod external;
 mod fallback;
+mod file_directory_ambiguity;
+mod forbidden_extensions;
+#[cfg(feature = "serde")]
+mod from_enhanced_resolve;
 mod full_specified;
+mod fully_specified_node_modules;
+mod import_map;
+mod importer_module_type;
+mod imports_fallback;
+mod imports_fallback_scopes;
 mod imports_field;
 mod incorrect_description_file;
+mod interned_dependencies;
+mod invalidate_path;
+mod jsconfig;
 mod main_field;
+mod main_files;
+mod memory_file_system;
 mod memory_fs;
 mod memory_leak;
 mod missing;
+mod module_directories;
 mod module_type;
 mod modules;
+#[cfg(target_os = "linux")]
+mod non_utf8_path;
+mod optional_peers;
+mod package_entry;
 mod package_json;
+mod package_json_chain;
+mod package_root;
+mod path_too_long;
 #[cfg(feature = "yarn_pnp")]
 mod pnp;
+mod prefer_module_field_over_exports;
+mod queries;
+mod recursion_limit;
 mod resolution;
+mod resolution_order;
 mod resolve;
+#[cfg(feature = "async")]
+mod resolve_async;
+mod resolve_file;
+#[cfg(feature = "rayon")]
+mod resolve_many;
+mod resolve_overrides;
 mod restrictions;
 mod roots;
 mod scoped_packages;
 mod simple;
 mod symlink;
+mod target;
+mod timings;
+mod trailing_slash;
 mod tsconfck;
 mod tsconfig_discovery;
 mod tsconfig_extends;
 mod tsconfig_lookup;
 mod tsconfig_paths;
 mod tsconfig_project_references;
+mod tsconfig_respect_include;
 mod tsconfig_root_dirs;
+mod types_versions;
+mod update_options;
+mod url_scheme;
+mod validate;
+mod warm_from_directory;
+mod watch;
 #[cfg(target_os = "windows")]
 mod windows;
 
@@ -0,0 +1,145 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashMap;
+
+use super::memory_fs::MemoryFS;
+use crate::{
+    FileMetadata, FileSystem, ResolveContext, ResolveError, ResolveOptions, ResolverGeneric,
+};
+
+const DEPTH: usize = 25;
+const PACKAGES: usize = 20;
+
+/// Counts the metadata queries of each path.
+struct CountingFS {
+    fs: MemoryFS,
+    queries: Arc<Mutex<FxHashMap<PathBuf, usize>>>,
+}
+
+impl CountingFS {
+    fn count(&self, path: &Path) {
+        *self.queries.lock().unwrap().entry(path.to_path_buf()).or_default() += 1;
+    }
+}
+
+impl FileSystem for CountingFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.fs.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.count(path);
+        self.fs.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.count(path);
+        self.fs.symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.fs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+}
+
+/// `/project/d0/.../d24` with packages in `/project/node_modules` and `/project/web_modules`.
+fn file_system() -> (MemoryFS, PathBuf) {
+    let mut fs = MemoryFS::default();
+    let nested = (0..DEPTH).fold(PathBuf::from("/project"), |dir, i| dir.join(format!("d{i}")));
+    fs.add_file(&nested.join("index.js"), "");
+    for i in 0..PACKAGES {
+        fs.add_file(&PathBuf::from(format!("/project/node_modules/pkg{i}/index.js")), "");
+    }
+    fs.add_file(Path::new("/project/web_modules/web/index.js"), "");
+    (fs, nested)
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn ancestors_are_checked_once() {
+    let (fs, nested) = file_system();
+    let queries = Arc::new(Mutex::new(FxHashMap::default()));
+    let fs = CountingFS { fs, queries: Arc::clone(&queries) };
+    let resolver = ResolverGeneric::new_with_file_system(
+        fs,
+        ResolveOptions {
+            modules: vec!["node_modules".into(), "web_modules".into()],
+            ..ResolveOptions::default()
+        },
+    );
+
+    for i in 0..PACKAGES {
+        let resolution = resolver.resolve(&nested, &format!("pkg{i}")).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from(format!("/project/node_modules/pkg{i}/index.js"))));
+    }
+    let resolution = resolver.resolve(&nested, "web").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/project/web_modules/web/index.js")));
+
+    let queries = std::mem::take(&mut *queries.lock().unwrap());
+    for ancestor in nested.ancestors() {
+        for path in [ancestor.to_path_buf(), ancestor.join("node_modules")] {
+            assert!(queries.get(&path).is_some_and(|count| *count == 1), "{}", path.display());
+        }
+    }
+
+    // One memo for each ancestor and module name, all ancestors share the memo of `/project`.
+    let module_directories = |module_name: &str| {
+        let key = (resolver.cache.value(&nested), module_name.to_string().into());
+        let chain = resolver.cache.module_directories.get(&key).unwrap().clone().unwrap();
+        assert!(chain.parent.is_none(), "{module_name}");
+        chain.directory.to_path_buf()
+    };
+    assert_eq!(module_directories("node_modules"), PathBuf::from("/project/node_modules"));
+    assert_eq!(module_directories("web_modules"), PathBuf::from("/project/web_modules"));
+    assert_eq!(resolver.cache.module_directories.len(), 2 * nested.ancestors().count());
+
+    resolver.clear_cache();
+    assert!(resolver.cache.module_directories.is_empty());
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn dependencies_bypass_memo() {
+    let (fs, nested) = file_system();
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    // Every missing module directory is reported and nothing is memoized.
+    let mut ctx = ResolveContext::default();
+    let resolution = resolver.resolve_with_context(&nested, "pkg0", None, &mut ctx);
+    assert_eq!(
+        resolution.map(|r| r.full_path()),
+        Ok(PathBuf::from("/project/node_modules/pkg0/index.js"))
+    );
+    for ancestor in nested.ancestors().take(DEPTH) {
+        let path = ancestor.join("node_modules");
+        assert!(ctx.missing_dependencies.contains(&path), "{}", path.display());
+    }
+    assert!(resolver.cache.module_directories.is_empty());
+
+    resolver.resolve(&nested, "pkg1").unwrap();
+    assert!(!resolver.cache.module_directories.is_empty());
+}
@@ -1,4 +1,4 @@
-use crate::{ModuleType, ResolveOptions, Resolver};
+use crate::{MemoryFileSystem, ModuleType, ResolveOptions, Resolver, ResolverGeneric};
 
 #[test]
 fn test() {
@@ -54,3 +54,45 @@ fn module_type_disabled() {
     let resolution = resolver.resolve(&f, "./file.cjs").unwrap();
     assert_eq!(resolution.module_type(), None);
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn module_type_detection() {
+    let fs = MemoryFileSystem::new([
+        ("/app/esm.js", "import foo from 'foo';\nexport default foo;"),
+        ("/app/cjs.js", "const foo = require('foo');\nmodule.exports = foo;"),
+        ("/app/string.js", "const code = 'import foo from \"foo\"';"),
+        ("/app/bin", "#!/usr/bin/env node\nimport.meta.url;"),
+        ("/app/esm.ts", "export {};"),
+        ("/app/typed/package.json", r#"{ "type": "commonjs" }"#),
+        ("/app/typed/esm.js", "export default 1;"),
+    ]);
+    let options = ResolveOptions {
+        module_type: true,
+        module_type_detection: true,
+        extensions: vec![".js".into(), ".ts".into()],
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options.clone());
+
+    #[rustfmt::skip]
+    let data = [
+        ("./esm.js", Some(ModuleType::Module)),
+        ("./cjs.js", Some(ModuleType::CommonJs)),
+        ("./string.js", Some(ModuleType::CommonJs)),
+        ("./bin", Some(ModuleType::Module)),
+        // Only `.js` and extensionless files are detected.
+        ("./esm.ts", None),
+        // The package type takes precedence.
+        ("./typed/esm.js", Some(ModuleType::CommonJs)),
+    ];
+    for (specifier, module_type) in data {
+        let resolution = resolver.resolve("/app", specifier).unwrap();
+        assert_eq!(resolution.module_type(), module_type, "{specifier}");
+    }
+
+    let resolver =
+        resolver.clone_with_options(ResolveOptions { module_type_detection: false, ..options });
+    let resolution = resolver.resolve("/app", "./esm.js").unwrap();
+    assert_eq!(resolution.module_type(), None);
+}
@@ -5,7 +5,7 @@ mod tests {
     use std::path::PathBuf;
 
     use super::super::memory_fs::MemoryFS;
-    use crate::{ResolveOptions, ResolverGeneric};
+    use crate::{ResolveContext, ResolveError, ResolveOptions, ResolverGeneric};
 
     #[test]
     fn custom_module_directory_name() {
@@ -72,4 +72,72 @@ mod tests {
         let result = resolver.resolve("/project", "pkg");
         result.unwrap_err();
     }
+
+    fn web_modules() -> MemoryFS {
+        MemoryFS::new(&[
+            (
+                "/project/web_modules/@acme/ui/package.json",
+                r#"{ "name": "@acme/ui", "main": "./main.js", "exports": { ".": "./esm/index.js", "./button": "./esm/button.js" } }"#,
+            ),
+            ("/project/web_modules/@acme/ui/main.js", ""),
+            ("/project/web_modules/@acme/ui/button.js", ""),
+            ("/project/web_modules/@acme/ui/esm/index.js", ""),
+            ("/project/web_modules/@acme/ui/esm/button.js", ""),
+            ("/project/node_modules/@acme/ui/index.js", ""),
+            ("/project/src/app.js", ""),
+        ])
+    }
+
+    #[test]
+    fn custom_module_directory_scoped_exports() {
+        let resolver = ResolverGeneric::new_with_file_system(
+            web_modules(),
+            ResolveOptions {
+                modules: vec!["web_modules".into(), "node_modules".into()],
+                ..ResolveOptions::default()
+            },
+        );
+
+        #[rustfmt::skip]
+        let pass = [
+            ("exports win over main", "/project/src", "@acme/ui", "/project/web_modules/@acme/ui/esm/index.js"),
+            ("exports subpath", "/project/src", "@acme/ui/button", "/project/web_modules/@acme/ui/esm/button.js"),
+            ("inside the module directory", "/project/web_modules", "@acme/ui", "/project/web_modules/@acme/ui/esm/index.js"),
+        ];
+
+        for (comment, path, request, expected) in pass {
+            let resolution = resolver.resolve(path, request).map(|r| r.full_path());
+            assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {path} {request}");
+        }
+
+        // Same treatment as `node_modules`: a subpath that is not exported is an error.
+        let resolution = resolver.resolve("/project/src", "@acme/ui/button.js");
+        assert!(
+            matches!(resolution, Err(ResolveError::PackagePathNotExported { .. })),
+            "{resolution:?}"
+        );
+    }
+
+    #[test]
+    fn custom_module_directory_missing_scope() {
+        let resolver = ResolverGeneric::new_with_file_system(
+            web_modules(),
+            ResolveOptions { modules: vec!["web_modules".into()], ..ResolveOptions::default() },
+        );
+        let mut ctx = ResolveContext::default();
+        let resolution =
+            resolver.resolve_with_context("/project/src", "@missing/ui", None, &mut ctx);
+        assert_eq!(resolution, Err(ResolveError::NotFound("@missing/ui".into())));
+        // The missing `@missing` scope directory short-circuits probing the package as a file.
+        let probed = ctx
+            .missing_dependencies
+            .iter()
+            .filter(|path| path.starts_with("/project/web_modules/@missing"))
+            .collect::<Vec<_>>();
+        assert_eq!(probed.len(), 2, "{probed:?}");
+        assert!(ctx.missing_dependencies.contains(&PathBuf::from("/project/web_modules/@missing")));
+        assert!(
+            ctx.missing_dependencies.contains(&PathBuf::from("/project/web_modules/@missing/ui"))
+        );
+    }
 }
@@ -0,0 +1,77 @@
+//! Not part of enhanced_resolve's test suite
+//!
+//! Linux allows any bytes except `/` and NUL in file names, macOS and Windows file systems
+//! reject names which are not valid UTF-8.
+
+use std::{ffi::OsStr, fs, os::unix::ffi::OsStrExt, path::PathBuf};
+
+use crate::{AliasValue, ResolveError, ResolveOptions, Resolver, TsconfigDiscovery};
+
+fn create_fixture(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target").join(name);
+    _ = fs::remove_dir_all(&root);
+    let dir = root.join(OsStr::from_bytes(b"vendor-\xff\xfe"));
+    let files = [
+        ("index.js", ""),
+        ("lib.js", ""),
+        ("types.d.ts", ""),
+        ("src/util.ts", ""),
+        ("tsconfig.json", r#"{ "compilerOptions": { "paths": { "@/*": ["./src/*"] } } }"#),
+        (
+            "node_modules/pkg/package.json",
+            r#"{ "name": "pkg", "exports": { ".": "./main.js", "./feature/*": "./features/*.js" } }"#,
+        ),
+        ("node_modules/pkg/main.js", ""),
+        ("node_modules/pkg/features/a.js", ""),
+    ];
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn non_utf8_path() {
+    let dir = create_fixture("non_utf8_path");
+    assert!(dir.to_str().is_none());
+
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".js".into(), ".ts".into()],
+        tsconfig: Some(TsconfigDiscovery::Auto),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("relative", "./lib", dir.join("lib.js")),
+        ("directory", ".", dir.join("index.js")),
+        ("exports", "pkg", dir.join("node_modules/pkg/main.js")),
+        ("exports pattern", "pkg/feature/a", dir.join("node_modules/pkg/features/a.js")),
+        ("tsconfig paths", "@/util", dir.join("src/util.ts")),
+    ];
+    for (comment, request, expected) in pass {
+        let resolution =
+            resolver.resolve_file(dir.join("index.ts"), request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(expected), "{comment} {request}");
+    }
+
+    let resolution =
+        resolver.resolve_dts(dir.join("index.ts"), "./types.js").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(dir.join("types.d.ts")));
+}
+
+// The path of a file matched by an alias key becomes the specifier, which requires UTF-8.
+#[test]
+fn non_utf8_path_as_alias() {
+    let dir = create_fixture("non_utf8_path_as_alias");
+    let alias_key = dir.parent().unwrap().to_str().unwrap();
+
+    let resolver = Resolver::new(ResolveOptions {
+        alias: vec![(alias_key.into(), vec![AliasValue::from("./lib")])],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&dir, "./index.js");
+    assert_eq!(resolution, Err(ResolveError::NonUtf8Path(dir.join("index.js"))));
+}
@@ -0,0 +1,72 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{AliasValue, ResolveError, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("optional-peers/app")
+}
+
+fn resolver(options: ResolveOptions) -> Resolver {
+    Resolver::new(ResolveOptions { ignore_missing_optional_peers: true, ..options })
+}
+
+#[test]
+fn ignore_missing_optional_peers() {
+    let app = app();
+    let resolver = resolver(ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let data = [
+        ("scoped optional peer", "@scope/optional", Err(ResolveError::Ignored(app.clone()))),
+        ("subpath of an optional peer", "@scope/optional/sub", Err(ResolveError::Ignored(app.clone()))),
+        ("optional dependency", "fsevents", Err(ResolveError::Ignored(app.clone()))),
+        ("non-optional peer", "required", Err(ResolveError::NotFound("required".into()))),
+        ("undeclared", "unknown", Err(ResolveError::NotFound("unknown".into()))),
+        ("relative", "./@scope/optional", Err(ResolveError::NotFound("./@scope/optional".into()))),
+        ("installed", "installed", Ok(app.join("node_modules/installed/index.js"))),
+    ];
+
+    for (comment, request, expected) in data {
+        let resolved_path = resolver.resolve(&app, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, expected, "{comment} {request}");
+    }
+
+    let resolver = resolver.clone_with_options(ResolveOptions::default());
+    assert_eq!(
+        resolver.resolve(&app, "@scope/optional").map(|r| r.full_path()),
+        Err(ResolveError::NotFound("@scope/optional".into())),
+        "disabled"
+    );
+}
+
+#[test]
+fn fallback() {
+    let app = app();
+    let shim = app.join("shim.js");
+    let resolver = resolver(ResolveOptions {
+        fallback: vec![(
+            "@scope/optional".into(),
+            vec![AliasValue::Path(shim.to_string_lossy().into())],
+        )],
+        ..ResolveOptions::default()
+    });
+    assert_eq!(resolver.resolve(&app, "@scope/optional").map(|r| r.full_path()), Ok(shim));
+    assert_eq!(
+        resolver.resolve(&app, "fsevents").map(|r| r.full_path()),
+        Err(ResolveError::Ignored(app))
+    );
+}
+
+#[test]
+fn package_json() {
+    let resolver = resolver(ResolveOptions::default());
+    let resolution = resolver.resolve(app(), "./index.js").unwrap();
+    let package_json = resolution.package_json().unwrap();
+    assert_eq!(
+        package_json.peer_dependencies_meta(),
+        Some(vec![("@scope/optional", true), ("required", false)])
+    );
+    assert_eq!(package_json.optional_dependencies(), Some(vec!["fsevents"]));
+}
@@ -0,0 +1,47 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{PackageEntryInfo, PackageEntryKind, ResolveOptions, Resolver};
+
+fn resolver(record_matched_entry: bool) -> Resolver {
+    Resolver::new(ResolveOptions {
+        condition_names: vec!["node".into(), "require".into()],
+        main_fields: vec!["module".into(), "main".into()],
+        alias_fields: vec![vec!["browser".into()]],
+        record_matched_entry,
+        ..ResolveOptions::default()
+    })
+}
+
+fn entry(kind: PackageEntryKind, key: &str, conditions: &[&str]) -> PackageEntryInfo {
+    let conditions = conditions.iter().map(ToString::to_string).collect();
+    PackageEntryInfo { kind, key: key.to_string(), conditions }
+}
+
+#[test]
+fn package_entry() {
+    use PackageEntryKind::{Browser, Exports, Imports, Main};
+    let app = super::fixture_root().join("package-entry/app");
+    let resolver = resolver(true);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("", "exports/feature", entry(Exports, "./feature", &["node", "require"])),
+        ("", "exports", entry(Exports, ".", &["require"])),
+        ("", "exports/lib/util", entry(Exports, "./lib/*", &[])),
+        ("", "#dep", entry(Imports, "#dep", &["node"])),
+        ("", "main", entry(Main, "module", &[])),
+        ("", "browser", entry(Browser, "./lib/node.js", &[])),
+        ("node_modules/browser", "module-a", entry(Browser, "module-a", &[])),
+    ];
+
+    for (directory, request, expected) in pass {
+        let resolution = resolver.resolve(app.join(directory), request).unwrap();
+        assert_eq!(resolution.package_entry(), Some(&expected), "{directory} {request}");
+    }
+
+    let resolution = resolver.resolve(&app, "./dep.js").unwrap();
+    assert_eq!(resolution.package_entry(), None);
+
+    let resolution = self::resolver(false).resolve(&app, "exports/feature").unwrap();
+    assert_eq!(resolution.package_entry(), None);
+}
@@ -123,3 +123,101 @@ fn test_corrupted_package_json() {
         }
     }
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn test_package_json_not_a_regular_file() {
+    use std::path::{Path, PathBuf};
+
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveContext, ResolveOptions, ResolverGeneric};
+
+    let mut fs = MemoryFS::default();
+    // A directory left behind by a failed extraction.
+    fs.add_dir(Path::new("/node_modules/dir/package.json"));
+    fs.add_file(Path::new("/node_modules/dir/index.js"), "");
+    // A FIFO, reading it would block; `MemoryFS` panics instead.
+    fs.add_special_file(Path::new("/node_modules/fifo/package.json"));
+    fs.add_file(Path::new("/node_modules/fifo/index.js"), "");
+
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    for name in ["dir", "fifo"] {
+        let mut ctx = ResolveContext::default();
+        let resolution = resolver.resolve_with_context("/", name, None, &mut ctx).unwrap();
+        assert_eq!(resolution.full_path(), PathBuf::from(format!("/node_modules/{name}/index.js")));
+        assert!(resolution.package_json().is_none());
+        let package_json_path = PathBuf::from(format!("/node_modules/{name}/package.json"));
+        assert!(ctx.missing_dependencies.contains(&package_json_path));
+        assert!(!ctx.file_dependencies.contains(&package_json_path));
+
+        // The negative result is cached like a missing package.json.
+        let resolution = resolver.resolve("/", &format!("{name}/index.js")).unwrap();
+        assert!(resolution.package_json().is_none());
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn side_effects() {
+    use crate::{MemoryFileSystem, ResolveOptions, ResolverGeneric, SideEffects};
+
+    let fs = MemoryFileSystem::new([
+        ("/node_modules/none/package.json", r#"{ "sideEffects": false }"#),
+        ("/node_modules/all/package.json", r#"{ "sideEffects": true }"#),
+        ("/node_modules/glob/package.json", r#"{ "sideEffects": "*.css" }"#),
+        ("/node_modules/list/package.json", r#"{ "sideEffects": ["*.css", "./src/polyfill.js"] }"#),
+        ("/node_modules/unset/package.json", "{}"),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let data = [
+        ("none", Some(SideEffects::Bool(false))),
+        ("all", Some(SideEffects::Bool(true))),
+        ("glob", Some(SideEffects::String("*.css"))),
+        ("list", Some(SideEffects::Array(vec!["*.css", "./src/polyfill.js"]))),
+        ("unset", None),
+    ];
+
+    for (name, expected) in data {
+        let (_, package_json) = resolver.resolve_package_root("/", name).unwrap();
+        assert_eq!(package_json.side_effects(), expected, "{name}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn workspaces() {
+    use crate::{MemoryFileSystem, ResolveOptions, ResolverGeneric};
+
+    let fs = MemoryFileSystem::new([
+        ("/npm/package.json", r#"{ "workspaces": ["packages/*", "apps/web"] }"#),
+        (
+            "/yarn/package.json",
+            r#"{ "workspaces": { "packages": ["packages/*"], "nohoist": ["**/react"] } }"#,
+        ),
+        ("/unset/package.json", "{}"),
+        ("/invalid/package.json", r#"{ "workspaces": "packages/*" }"#),
+    ]);
+    for directory in ["/npm", "/yarn", "/unset", "/invalid"] {
+        fs.add_file(format!("{directory}/index.js"), "");
+    }
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let data = [
+        ("/npm", Some(&["packages/*", "apps/web"][..])),
+        ("/yarn", Some(&["packages/*"][..])),
+        ("/unset", None),
+        ("/invalid", None),
+    ];
+
+    for (directory, expected) in data {
+        let resolution = resolver.resolve(directory, "./index.js").unwrap();
+        let workspaces = resolution.package_json().unwrap().workspaces();
+        let workspaces =
+            workspaces.map(|patterns| patterns.iter().map(String::as_str).collect::<Vec<_>>());
+        assert_eq!(workspaces.as_deref(), expected, "{directory}");
+    }
+}
@@ -0,0 +1,44 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveContext, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("package-json-chain/app")
+}
+
+fn resolve(options: ResolveOptions, directory: &str, specifier: &str) -> (PathBuf, Vec<PathBuf>) {
+    let resolver = Resolver::new(options);
+    let mut ctx = ResolveContext::default();
+    let resolution =
+        resolver.resolve_with_context(app().join(directory), specifier, None, &mut ctx).unwrap();
+    (resolution.full_path(), ctx.package_json_chain)
+}
+
+#[test]
+fn nested_node_modules() {
+    let app = app();
+    let options = ResolveOptions { collect_package_json_chain: true, ..ResolveOptions::default() };
+
+    // `a/node_modules/b` shadows `node_modules/b` for requests from `a`.
+    let (path, chain) = resolve(options.clone(), "node_modules/a", "b");
+    assert_eq!(path, app.join("node_modules/a/node_modules/b/inner.js"));
+    assert_eq!(
+        chain,
+        vec![
+            app.join("node_modules/a/node_modules/b/package.json"),
+            app.join("node_modules/a/package.json"),
+        ]
+    );
+
+    let (path, chain) = resolve(options, "src", "b");
+    assert_eq!(path, app.join("node_modules/b/outer.js"));
+    assert_eq!(chain, vec![app.join("node_modules/b/package.json"), app.join("package.json")]);
+}
+
+#[test]
+fn disabled() {
+    let (_, chain) = resolve(ResolveOptions::default(), "node_modules/a", "b");
+    assert_eq!(chain, Vec::<PathBuf>::new());
+}
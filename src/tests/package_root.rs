@@ -0,0 +1,146 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{Resolution, ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn package_root() {
+    let f = super::fixture_root().join("package-root");
+    let resolver = Resolver::default();
+
+    // The entry point can not be resolved.
+    assert!(matches!(
+        resolver.resolve(f.join("app"), "restricted/package.json"),
+        Err(ResolveError::PackagePathNotExported { .. })
+    ));
+
+    #[rustfmt::skip]
+    let pass = [
+        ("exports without package.json", "app", "restricted", "node_modules/restricted"),
+        ("scoped", "app", "@scope/pkg", "node_modules/@scope/pkg"),
+        ("closest", "app/src", "pkg", "app/node_modules/pkg"),
+        ("parent", "", "pkg", "node_modules/pkg"),
+    ];
+    for (comment, directory, package_name, expected) in pass {
+        let (root, package_json) =
+            resolver.resolve_package_root(f.join(directory), package_name).unwrap();
+        assert_eq!(root, f.join(expected), "{comment}");
+        assert_eq!(package_json.path(), f.join(expected).join("package.json"), "{comment}");
+        assert_eq!(package_json.name(), Some(package_name), "{comment}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("not installed", "missing"),
+        ("subpath", "pkg/package.json"),
+        ("relative", "./pkg"),
+        ("absolute", "/node_modules/pkg"),
+        ("imports", "#pkg"),
+        ("other module directory", "web"),
+    ];
+    for (comment, package_name) in fail {
+        let error = resolver.resolve_package_root(f.join("app"), package_name).unwrap_err();
+        assert_eq!(error, ResolveError::NotFound(package_name.into()), "{comment}");
+    }
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        modules: vec!["web_modules".into()],
+        ..ResolveOptions::default()
+    });
+    let (root, _) = resolver.resolve_package_root(f.join("app"), "web").unwrap();
+    assert_eq!(root, f.join("app/web_modules/web"));
+}
+
+#[test]
+fn package_entries() {
+    let f = super::fixture_root().join("package-root");
+    let resolver = Resolver::default();
+
+    let subpaths = [".", "./utils", "./features/a", "./features/b", "./features/c", "./private"];
+    let entries =
+        resolver.resolve_package_entries(f.join("app"), "multi", &subpaths, None).unwrap();
+    let paths = entries
+        .iter()
+        .map(|entry| (entry.subpath.as_str(), entry.resolution.clone().map(|r| r.full_path())))
+        .collect::<Vec<_>>();
+    #[rustfmt::skip]
+    let expected = [
+        (".", Ok(f.join("node_modules/multi/cjs/index.js"))),
+        ("./utils", Ok(f.join("node_modules/multi/utils.js"))),
+        ("./features/a", Ok(f.join("node_modules/multi/src/features/a.js"))),
+        ("./features/b", Ok(f.join("node_modules/multi/src/features/b.js"))),
+        ("./features/c", Err(ResolveError::NotFound("multi/features/c".into()))),
+    ];
+    assert_eq!(paths[..expected.len()], expected);
+    assert!(matches!(entries[5].resolution, Err(ResolveError::PackagePathNotExported { .. })));
+    // The same as resolving each subpath on its own.
+    for entry in &entries[..4] {
+        let specifier = format!("multi{}", &entry.subpath[1..]);
+        assert_eq!(resolver.resolve(f.join("app"), &specifier).as_ref(), entry.resolution.as_ref());
+    }
+
+    // The conditions replace the configured ones.
+    let conditions = ["import".to_string()];
+    let entries = resolver
+        .resolve_package_entries(f.join("app"), "multi", &["."], Some(&conditions))
+        .unwrap();
+    assert_eq!(
+        entries[0].resolution.as_ref().map(Resolution::full_path),
+        Ok(f.join("node_modules/multi/esm/index.mjs"))
+    );
+
+    // Packages without `exports` are probed.
+    let entries = resolver
+        .resolve_package_entries(f.join("app"), "legacy", &[".", "./lib/util"], None)
+        .unwrap();
+    let paths = entries
+        .iter()
+        .map(|entry| entry.resolution.as_ref().unwrap().full_path())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [f.join("node_modules/legacy/lib/main.js"), f.join("node_modules/legacy/lib/util.js")]
+    );
+
+    let error =
+        resolver.resolve_package_entries(f.join("app"), "missing", &["."], None).unwrap_err();
+    assert_eq!(error, ResolveError::NotFound("missing".into()));
+}
+
+#[test]
+fn bin() {
+    let f = super::fixture_root().join("package-root");
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("named", "typescript", Some("tsc"), "node_modules/typescript/bin/tsc"),
+        ("named", "typescript", Some("tsserver"), "node_modules/typescript/bin/tsserver"),
+        ("single, named after the unscoped package", "@scope/cli", None, "node_modules/@scope/cli/cli.js"),
+        ("single, named after the unscoped package", "@scope/cli", Some("cli"), "node_modules/@scope/cli/cli.js"),
+        ("the only executable", "only", None, "node_modules/only/only.js"),
+    ];
+    for (comment, package_name, bin_name, expected) in pass {
+        let bin_path = resolver.resolve_bin(f.join("app"), package_name, bin_name);
+        assert_eq!(bin_path, Ok(f.join(expected)), "{comment} {package_name} {bin_name:?}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("ambiguous", "typescript", None, "typescript bin"),
+        ("unknown name", "typescript", Some("tsx"), "typescript bin tsx"),
+        ("unknown name", "@scope/cli", Some("other"), "@scope/cli bin other"),
+        ("no bin field", "legacy", None, "legacy bin"),
+        ("missing file", "broken", None, "broken bin"),
+        ("not installed", "missing", None, "missing"),
+    ];
+    for (comment, package_name, bin_name, expected) in fail {
+        let bin_path = resolver.resolve_bin(f.join("app"), package_name, bin_name);
+        assert_eq!(bin_path, Err(ResolveError::NotFound(expected.into())), "{comment}");
+    }
+
+    let (_, package_json) = resolver.resolve_package_root(f.join("app"), "typescript").unwrap();
+    assert_eq!(
+        package_json.bin(),
+        Some(crate::BinField::Map(vec![("tsc", "./bin/tsc"), ("tsserver", "./bin/tsserver")]))
+    );
+}
@@ -0,0 +1,152 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    FileMetadata, FileSystem, FileSystemOs, ResolveError, ResolveOptions, ResolverGeneric,
+};
+
+/// Fails the metadata queries of `too_long` paths with the error kind of `ENAMETOOLONG`.
+struct TooLongFS {
+    fs: FileSystemOs,
+    too_long: Vec<PathBuf>,
+}
+
+impl TooLongFS {
+    fn check(&self, path: &Path) -> io::Result<()> {
+        if self.too_long.iter().any(|p| p == path) {
+            return Err(io::ErrorKind::InvalidFilename.into());
+        }
+        Ok(())
+    }
+}
+
+impl FileSystem for TooLongFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.fs.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.check(path)?;
+        self.fs.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.check(path)?;
+        self.fs.symlink_metadata(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+        self.fs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.fs.canonicalize(path)
+    }
+}
+
+#[test]
+fn path_too_long() {
+    let app = super::fixture_root().join("path-too-long/app");
+    let too_long = [app.join("node_modules/pkg/lib/index.js"), app.join("util.ts")];
+    #[cfg(not(feature = "yarn_pnp"))]
+    let fs = FileSystemOs::new();
+    #[cfg(feature = "yarn_pnp")]
+    let fs = FileSystemOs::new(false);
+    let fs = TooLongFS { fs, too_long: too_long.to_vec() };
+    let options = ResolveOptions {
+        extensions: vec![".ts".into(), ".js".into()],
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options);
+
+    let resolution = resolver.resolve(&app, "pkg");
+    assert_eq!(resolution, Err(ResolveError::PathTooLong(too_long[0].clone())));
+
+    // Another candidate resolves.
+    let resolution = resolver.resolve(&app, "./util").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("util.js")));
+
+    // No candidate was too long.
+    let resolution = resolver.resolve(&app, "missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("missing".into())));
+}
+
+/// Only has the files in `files`, which exceed `MAX_PATH` unless in their `\\?\` form.
+#[cfg(target_os = "windows")]
+struct VerbatimFS {
+    files: Vec<PathBuf>,
+}
+
+#[cfg(target_os = "windows")]
+impl FileSystem for VerbatimFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!()
+    }
+
+    fn read(&self, _path: &Path) -> io::Result<Vec<u8>> {
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    fn read_to_string(&self, _path: &Path) -> io::Result<String> {
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.symlink_metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let verbatim = path.to_str().and_then(|path| path.strip_prefix(r"\\?\"));
+        match verbatim {
+            Some(path) if self.files.iter().any(|p| p == Path::new(path)) => {
+                Ok(FileMetadata::new(true, false, false))
+            }
+            None if self.files.iter().any(|p| p == path) => {
+                Err(io::Error::from_raw_os_error(206)) // ERROR_FILENAME_EXCED_RANGE
+            }
+            _ => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read_link(&self, _path: &Path) -> Result<PathBuf, ResolveError> {
+        Err(io::Error::from(io::ErrorKind::NotFound).into())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        Ok(path.to_path_buf())
+    }
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn verbatim_retry() {
+    let file = PathBuf::from(r"C:\app\long.js");
+    let fs = VerbatimFS { files: vec![file.clone()] };
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    let resolution = resolver.resolve(r"C:\app", "./long.js").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(file));
+}
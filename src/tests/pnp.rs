@@ -351,3 +351,111 @@ fn test_cache_recreated_when_toggling_yarn_pnp_off() {
         Err(crate::ResolveError::NotFound("is-even".to_string()))
     );
 }
+
+#[test]
+fn stray_parent_manifest() {
+    // Plain npm projects below a directory holding an unrelated PnP install.
+    let dir = super::fixture_root().join("pnp/stray");
+
+    // Without a lockfile, the project is considered part of the PnP install.
+    let project = dir.join("project");
+    let resolver = Resolver::new(ResolveOptions {
+        cwd: Some(project.clone()),
+        yarn_pnp: true,
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&project, "dep");
+    assert!(matches!(resolution, Err(crate::ResolveError::YarnPnpError(_))), "{resolution:?}");
+
+    // With a lockfile, the project has its own root which the manifest does not cover.
+    let project = dir.join("locked");
+    let resolver = Resolver::new(ResolveOptions {
+        cwd: Some(project.clone()),
+        yarn_pnp: true,
+        ..ResolveOptions::default()
+    });
+    for _ in 0..2 {
+        assert_eq!(
+            resolver.resolve(&project, "dep").map(|r| r.full_path()),
+            Ok(project.join("node_modules/dep/index.js"))
+        );
+    }
+}
+
+#[test]
+fn yarn_pnp_root() {
+    let project = super::fixture_root().join("pnp/stray/project");
+    let resolver = Resolver::new(ResolveOptions {
+        cwd: Some(project.clone()),
+        yarn_pnp: true,
+        yarn_pnp_root: Some(project.clone()),
+        ..ResolveOptions::default()
+    });
+
+    assert_eq!(
+        resolver.resolve(&project, "dep").map(|r| r.full_path()),
+        Ok(project.join("node_modules/dep/index.js"))
+    );
+    assert_eq!(
+        resolver.resolve(&project, "pnpapi"),
+        Err(crate::ResolveError::FailedToFindYarnPnpManifest(project.clone()))
+    );
+
+    // The search stops at the root, and finding no manifest is cached per `cwd`.
+    assert!(resolver.cache.yarn_pnp_manifests.get(&project).is_some_and(|m| m.is_none()));
+}
+
+#[test]
+fn resolve_package_root() {
+    let dir = super::fixture_root().join("pnp/package-root");
+    let resolver = Resolver::new(ResolveOptions {
+        cwd: Some(dir.clone()),
+        yarn_pnp: true,
+        ..ResolveOptions::default()
+    });
+
+    let (root, package_json) = resolver.resolve_package_root(&dir, "dep").unwrap();
+    assert_eq!(root, dir.join("vendor/dep"));
+    assert_eq!(package_json.name(), Some("dep"));
+
+    // Undeclared dependencies are rejected by PnP.
+    let error = resolver.resolve_package_root(&dir, "undeclared").unwrap_err();
+    assert!(matches!(error, crate::ResolveError::YarnPnpError(_)), "{error:?}");
+}
+
+#[test]
+fn pnp_manifest_paths() {
+    // `dep` is a different package in the nested `inner` install.
+    let dir = super::fixture_root().join("pnp/manifest-paths");
+    let inner = dir.join("inner");
+
+    let resolver = Resolver::new(ResolveOptions {
+        cwd: Some(dir.clone()),
+        yarn_pnp: true,
+        pnp_manifest_paths: vec![inner.join(".pnp.cjs"), dir.join(".pnp.cjs")],
+        ..ResolveOptions::default()
+    });
+    assert_eq!(
+        resolver.resolve(&dir, "dep").map(|r| r.full_path()),
+        Ok(dir.join("vendor/dep-1.0.0/index.js"))
+    );
+    assert_eq!(
+        resolver.resolve(&inner, "dep").map(|r| r.full_path()),
+        Ok(inner.join("vendor/dep-2.0.0/index.js"))
+    );
+    assert_eq!(
+        resolver.resolve(&inner, "pnpapi").map(|r| r.full_path()),
+        Ok(inner.join(".pnp.cjs"))
+    );
+
+    // Only the manifest found from `cwd` is used without `pnp_manifest_paths`.
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        cwd: Some(dir.clone()),
+        yarn_pnp: true,
+        ..ResolveOptions::default()
+    });
+    assert_eq!(
+        resolver.resolve(&inner, "dep").map(|r| r.full_path()),
+        Ok(dir.join("vendor/dep-1.0.0/index.js"))
+    );
+}
@@ -0,0 +1,79 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ModuleFieldConflict, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("prefer-module-field-over-exports/app")
+}
+
+fn resolver(prefer_module_field_over_exports: bool) -> Resolver {
+    Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        prefer_module_field_over_exports,
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn package_root() {
+    #[rustfmt::skip]
+    let data = [
+        ("dual", false, "node_modules/dual/cjs/index.js"),
+        ("dual", true, "node_modules/dual/esm/legacy.js"),
+        ("agreeing", true, "node_modules/agreeing/index.mjs"),
+        ("missing", true, "node_modules/missing/index.js"),
+    ];
+
+    let app = app();
+    for (request, prefer_module_field_over_exports, expected) in data {
+        let resolution = resolver(prefer_module_field_over_exports)
+            .resolve(app.join("src"), request)
+            .map(|r| r.full_path());
+        assert_eq!(
+            resolution,
+            Ok(app.join(expected)),
+            "{request} {prefer_module_field_over_exports}"
+        );
+    }
+}
+
+#[test]
+fn subpath() {
+    let app = app();
+    for prefer_module_field_over_exports in [false, true] {
+        let resolver = resolver(prefer_module_field_over_exports);
+        let resolution = resolver.resolve(app.join("src"), "dual/utils").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(app.join("node_modules/dual/utils.js")));
+        assert_eq!(resolver.module_field_conflicts(), vec![]);
+    }
+}
+
+#[test]
+fn conflicts() {
+    let app = app();
+    let resolver = resolver(true);
+    for directory in ["src", "src", "src/nested"] {
+        let directory = app.join(directory);
+        resolver.resolve(&directory, "dual").unwrap();
+        resolver.resolve(&directory, "agreeing").unwrap();
+        resolver.resolve(&directory, "missing").unwrap();
+    }
+    resolver.clear_cache();
+    resolver.resolve(app.join("src"), "dual").unwrap();
+
+    assert_eq!(
+        resolver.module_field_conflicts(),
+        vec![ModuleFieldConflict {
+            package_json: app.join("node_modules/dual/package.json"),
+            module: app.join("node_modules/dual/esm/legacy.js"),
+            exports: app.join("node_modules/dual/cjs/index.js"),
+        }]
+    );
+
+    // Without the option the `module` field is not consulted.
+    let resolver = self::resolver(false);
+    resolver.resolve(app.join("src"), "dual").unwrap();
+    assert_eq!(resolver.module_field_conflicts(), vec![]);
+}
@@ -0,0 +1,99 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    ConditionNamesProvider, MemoryFileSystem, ResolveContext, ResolveOptions, Resolver,
+    ResolverGeneric,
+};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("queries/app")
+}
+
+#[test]
+fn consistent_with_resolution() {
+    let app = app();
+    let resolver = Resolver::default();
+
+    let path = resolver.resolve(&app, "pkg").unwrap().into_path_buf();
+    assert!(resolver.is_file(&path));
+    assert!(!resolver.is_dir(&path));
+    assert!(resolver.is_dir(path.parent().unwrap()));
+    assert_eq!(resolver.canonicalize(&path), Ok(path));
+
+    resolver.resolve(&app, "./missing").unwrap_err();
+    assert!(!resolver.is_file(app.join("missing")));
+    assert!(!resolver.is_dir(app.join("missing")));
+
+    // Queries before resolution share the cache with it.
+    let resolver = Resolver::default();
+    assert!(resolver.is_file(app.join("index.js")));
+    assert!(resolver.is_dir(app.join("node_modules/pkg")));
+    let path = resolver.resolve(&app, "./index.js").map(|r| r.full_path());
+    assert_eq!(path, Ok(app.join("index.js")));
+}
+
+#[test]
+fn context() {
+    let app = app();
+    let resolver = Resolver::default();
+    let mut ctx = ResolveContext::default();
+
+    assert!(resolver.is_file_with_context(app.join("index.js"), &mut ctx));
+    assert!(!resolver.is_file_with_context(app.join("index.ts"), &mut ctx));
+    assert!(!resolver.is_file_with_context(app.join("node_modules"), &mut ctx));
+    assert!(resolver.is_dir_with_context(app.join("node_modules"), &mut ctx));
+    assert!(!resolver.is_dir_with_context(app.join("src"), &mut ctx));
+    resolver.canonicalize_with_context(app.join("node_modules/pkg"), &mut ctx).unwrap();
+    resolver.canonicalize_with_context(app.join("lib"), &mut ctx).unwrap_err();
+
+    let paths = |paths: &[&str]| paths.iter().map(|path| app.join(path)).collect();
+    assert_eq!(ctx.file_dependencies, paths(&["index.js", "node_modules/pkg"]));
+    assert_eq!(ctx.missing_dependencies, paths(&["index.ts", "node_modules", "src", "lib"]));
+}
+
+#[test]
+fn can_resolve() {
+    let app = app();
+    let resolver = Resolver::default();
+    let absolute = app.join("node_modules/pkg");
+    let absolute = absolute.to_str().unwrap();
+    for specifier in ["pkg", "./index", "./index.js", absolute, "pkg/lib/main"] {
+        resolver.resolve(&app, specifier).unwrap();
+        assert!(resolver.can_resolve(&app, specifier), "{specifier}");
+    }
+    for specifier in ["missing", "./missing", "pkg/missing", "fs", "#imports"] {
+        resolver.resolve(&app, specifier).unwrap_err();
+        assert!(!resolver.can_resolve(&app, specifier), "{specifier}");
+    }
+
+    resolver.update_options(|options| options.forbidden_extensions = vec![".js".into()]);
+    assert!(!resolver.can_resolve(&app, "./index"));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFileSystem's path separator is always `/` so the test will not pass in windows.
+fn can_resolve_as_resolve() {
+    let fs = MemoryFileSystem::new([
+        ("/app/.env", ""),
+        ("/app/node_modules/pkg/package.json", r#"{ "exports": { "custom": "./custom.js" } }"#),
+        ("/app/node_modules/pkg/custom.js", ""),
+    ]);
+    fs.add_symlink("/app/config.js", "/app/.env");
+    let options = ResolveOptions {
+        forbidden_extensions: vec![".env".into()],
+        condition_names_provider: Some(ConditionNamesProvider::new(|_: &Path| {
+            Some(vec!["custom".into()])
+        })),
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options);
+
+    // The conditions of the provider apply.
+    resolver.resolve("/app", "pkg").unwrap();
+    assert!(resolver.can_resolve("/app", "pkg"));
+    // The extension of the symlink target is forbidden.
+    resolver.resolve("/app", "./config.js").unwrap_err();
+    assert!(!resolver.can_resolve("/app", "./config.js"));
+}
@@ -0,0 +1,55 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("recursion-limit/app")
+}
+
+// Each package is aliased to the next one, looping back to the first.
+fn options(max_resolve_depth: usize) -> ResolveOptions {
+    ResolveOptions {
+        alias: vec![
+            ("a".into(), vec!["b".into()]),
+            ("b".into(), vec!["c".into()]),
+            ("c".into(), vec!["a".into()]),
+        ],
+        max_resolve_depth,
+        ..ResolveOptions::default()
+    }
+}
+
+#[test]
+fn three_package_loop() {
+    let resolver = Resolver::new(options(64));
+    let Err(ResolveError::RecursionLimitExceeded { limit, trail }) = resolver.resolve(app(), "a")
+    else {
+        panic!("expected RecursionLimitExceeded");
+    };
+    assert_eq!(limit, 64);
+    assert_eq!(trail.len(), 8);
+    for specifier in ["a", "b", "c"] {
+        assert!(
+            trail.iter().any(|entry| entry.starts_with(&format!("{specifier} from "))),
+            "{trail:?}"
+        );
+    }
+}
+
+#[test]
+fn custom_limit() {
+    let app = app();
+    let resolver = Resolver::new(options(4));
+    let resolution = resolver.resolve(&app, "a");
+    let trail = ["c", "a", "b"].map(|specifier| format!("{specifier} from {}", app.display()));
+    assert_eq!(
+        resolution,
+        Err(ResolveError::RecursionLimitExceeded { limit: 4, trail: trail.to_vec() })
+    );
+    assert_eq!(
+        resolution.unwrap_err().to_string(),
+        format!("Recursion limit of 4 exceeded in resolving, last resolving {trail:?}")
+    );
+}
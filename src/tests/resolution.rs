@@ -6,15 +6,128 @@ use crate::Resolution;
 fn test() {
     let resolution = Resolution {
         path: PathBuf::from("foo"),
+        specifier: "./foo?query#fragment".to_string(),
+        aliased_specifier: None,
         query: Some("?query".to_string()),
         fragment: Some("#fragment".to_string()),
         package_json: None,
         module_type: None,
+        probed: false,
+        via_node_addons: false,
+        file_metadata: None,
+        package_entry: None,
+        file_directory_ambiguity: None,
     };
     assert_eq!(resolution.path(), Path::new("foo"));
+    assert_eq!(resolution.specifier(), "./foo?query#fragment");
+    assert_eq!(resolution.resolved_specifier(), "./foo?query#fragment");
     assert_eq!(resolution.query(), Some("?query"));
     assert_eq!(resolution.fragment(), Some("#fragment"));
     assert_eq!(resolution.full_path(), PathBuf::from("foo?query#fragment"));
+    assert_eq!(resolution.full_path_string(), "foo?query#fragment");
     assert_eq!(resolution.module_type(), None);
+    assert!(!resolution.probed());
+    assert!(!resolution.via_node_addons());
+    assert_eq!(resolution.file_metadata(), None);
     assert_eq!(resolution.into_path_buf(), PathBuf::from("foo"));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn file_metadata() {
+    use super::memory_fs::MemoryFS;
+    use crate::{FileSystem, ResolveOptions, ResolverGeneric};
+
+    let fs = MemoryFS::new(&[("/node_modules/pkg/index.js", "export {}")]);
+    let expected = fs.metadata(Path::new("/node_modules/pkg/index.js")).unwrap();
+    assert_eq!(expected.size(), 9);
+    assert!(expected.modified().is_some());
+
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    let resolution = resolver.resolve("/", "pkg").unwrap();
+    assert_eq!(resolution.file_metadata(), Some(expected));
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        resolve_to_context: true,
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve("/", "pkg").unwrap();
+    assert_eq!(resolution.file_metadata(), None);
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn key() {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveOptions, ResolverGeneric};
+
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let fs =
+        MemoryFS::new(&[("/src/shared.js", ""), ("/src/a/index.js", ""), ("/src/b/index.js", "")]);
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    // The same file through different importers.
+    let a = resolver.resolve_file("/src/a/index.js", "../shared.js").unwrap();
+    let b = resolver.resolve_file("/src/b/index.js", "../shared").unwrap();
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(a.key(), b.key());
+    assert_eq!(hash(&a.key()), hash(&b.key()));
+    assert_eq!(a.key().full_path(), a.full_path());
+
+    // Differing queries or fragments.
+    let query = resolver.resolve("/src", "./shared.js?raw").unwrap();
+    let fragment = resolver.resolve("/src", "./shared.js#raw").unwrap();
+    assert_ne!(a, query);
+    assert_ne!(a.key(), query.key());
+    assert_ne!(query.key(), fragment.key());
+    assert_eq!(query.key().query(), Some("?raw"));
+    assert_eq!(fragment.key().fragment(), Some("#raw"));
+    assert_eq!(query.key().path(), a.path());
+    assert_eq!(query.key().full_path(), PathBuf::from("/src/shared.js?raw"));
+    assert_eq!(query.full_path_string(), "/src/shared.js?raw");
+    assert_eq!(fragment.full_path_string(), "/src/shared.js#raw");
+    assert_eq!(a.full_path_string(), "/src/shared.js");
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn specifier() {
+    use super::memory_fs::MemoryFS;
+    use crate::{AliasValue, ResolveOptions, ResolverGeneric};
+
+    let fs = MemoryFS::new(&[("/src/shared.js", ""), ("/src/lib/util.js", "")]);
+    let options = ResolveOptions {
+        alias: vec![
+            ("@lib".into(), vec![AliasValue::from("/src/lib")]),
+            ("util".into(), vec![AliasValue::from("missing"), AliasValue::from("@lib/util")]),
+        ],
+        fallback: vec![("fallback".into(), vec![AliasValue::from("./shared")])],
+        ..ResolveOptions::default()
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, options);
+
+    #[rustfmt::skip]
+    let pass = [
+        ("no alias", "./shared.js?raw", "./shared.js?raw"),
+        ("alias", "@lib/util", "/src/lib/util"),
+        ("chained alias", "util", "/src/lib/util"),
+        ("fallback", "fallback", "./shared"),
+    ];
+
+    for (comment, request, resolved_specifier) in pass {
+        let resolution = resolver.resolve("/src", request).unwrap();
+        assert_eq!(resolution.specifier(), request, "{comment}");
+        assert_eq!(resolution.resolved_specifier(), resolved_specifier, "{comment}");
+    }
+}
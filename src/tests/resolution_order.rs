@@ -0,0 +1,189 @@
+//! Not part of enhanced_resolve's test suite
+//!
+//! Locks the priority between resolution mechanisms that can satisfy the same specifier,
+//! see [crate::REQUEST_PHASES], [crate::ABSOLUTE_PHASES] and [crate::BARE_PHASES].
+//! A change to the phase order must update the expected priorities below.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    AliasValue, ResolveError, ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions,
+    TsconfigReferences,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mechanism {
+    TsconfigPaths,
+    Alias,
+    PackageSelf,
+    BrowserField,
+    NodeModules,
+    Fallback,
+}
+
+/// Expected priority for the bare specifier `pkg`, highest first.
+const PRIORITY: [Mechanism; 6] = [
+    Mechanism::TsconfigPaths,
+    Mechanism::Alias,
+    Mechanism::PackageSelf,
+    Mechanism::BrowserField,
+    Mechanism::NodeModules,
+    Mechanism::Fallback,
+];
+
+impl Mechanism {
+    /// The file resolved in the fixture `app`.
+    fn expected(self) -> &'static str {
+        match self {
+            Self::TsconfigPaths => "paths.js",
+            Self::Alias => "alias.js",
+            Self::PackageSelf => "self.js",
+            Self::BrowserField => "browser.js",
+            Self::NodeModules => "node_modules/pkg/index.js",
+            Self::Fallback => "fallback.js",
+        }
+    }
+}
+
+fn app() -> PathBuf {
+    super::fixture_root().join("resolution-order/app")
+}
+
+fn options(mechanisms: &[Mechanism]) -> ResolveOptions {
+    let app = app();
+    let enabled = |m| mechanisms.contains(&m);
+    ResolveOptions {
+        tsconfig: enabled(Mechanism::TsconfigPaths).then(|| {
+            TsconfigDiscovery::Manual(TsconfigOptions {
+                config_file: app.join("tsconfig.json"),
+                references: TsconfigReferences::Disabled,
+            })
+        }),
+        alias: if enabled(Mechanism::Alias) {
+            vec![("pkg".into(), vec![alias_value(&app.join("alias.js"))])]
+        } else {
+            vec![]
+        },
+        exports_fields: if enabled(Mechanism::PackageSelf) {
+            vec![vec!["exports".into()]]
+        } else {
+            vec![]
+        },
+        alias_fields: if enabled(Mechanism::BrowserField) {
+            vec![vec!["browser".into()]]
+        } else {
+            vec![]
+        },
+        modules: if enabled(Mechanism::NodeModules) { vec!["node_modules".into()] } else { vec![] },
+        fallback: if enabled(Mechanism::Fallback) {
+            vec![("pkg".into(), vec![alias_value(&app.join("fallback.js"))])]
+        } else {
+            vec![]
+        },
+        ..ResolveOptions::default()
+    }
+}
+
+#[test]
+fn each_mechanism_alone() {
+    let resolver = Resolver::new(options(&[]));
+    let resolution = resolver.resolve(app(), "pkg");
+    assert_eq!(resolution, Err(ResolveError::NotFound("pkg".into())));
+
+    for mechanism in PRIORITY {
+        let resolver = resolver.clone_with_options(options(&[mechanism]));
+        let resolution = resolver.resolve(app(), "pkg").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(app().join(mechanism.expected())), "{mechanism:?}");
+    }
+}
+
+#[test]
+fn pairwise() {
+    let resolver = Resolver::new(options(&[]));
+    for (i, winner) in PRIORITY.iter().enumerate() {
+        for loser in &PRIORITY[i + 1..] {
+            let resolver = resolver.clone_with_options(options(&[*winner, *loser]));
+            let resolution = resolver.resolve(app(), "pkg").map(|r| r.full_path());
+            assert_eq!(
+                resolution,
+                Ok(app().join(winner.expected())),
+                "{winner:?} should win over {loser:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn all_mechanisms() {
+    let resolver = Resolver::new(options(&PRIORITY));
+    let resolution = resolver.resolve(app(), "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app().join(PRIORITY[0].expected())));
+}
+
+#[test]
+fn prefer_relative() {
+    // Relative wins over the package phases, but not over tsconfig paths and alias.
+    // (The browser field is left out, it also applies to the relative file `./pkg.js`.)
+    let resolver = Resolver::new(options(&[]));
+    #[rustfmt::skip]
+    let cases = [
+        (vec![Mechanism::PackageSelf, Mechanism::NodeModules, Mechanism::Fallback], "pkg.js"),
+        (vec![Mechanism::Alias, Mechanism::NodeModules], "alias.js"),
+        (vec![Mechanism::TsconfigPaths, Mechanism::NodeModules], "paths.js"),
+    ];
+    for (mechanisms, expected) in cases {
+        let resolver = resolver
+            .clone_with_options(ResolveOptions { prefer_relative: true, ..options(&mechanisms) });
+        let resolution = resolver.resolve(app(), "pkg").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(app().join(expected)), "{mechanisms:?}");
+    }
+}
+
+#[test]
+fn absolute() {
+    let f = super::fixture_root().join("resolution-order");
+    let app = app();
+    let roots = vec![app.join("root")];
+    let resolver = Resolver::new(options(&[]));
+
+    // File system only.
+    let absolute = f.join("lib/a.js");
+    let resolution = resolver.resolve(&app, absolute.to_str().unwrap()).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(absolute.clone()));
+
+    // Roots win over the file system.
+    let resolver =
+        resolver.clone_with_options(ResolveOptions { roots: roots.clone(), ..options(&[]) });
+    let resolution = resolver.resolve(&app, "/lib/a.js").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("root/lib/a.js")));
+
+    // Alias wins over roots.
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        roots: roots.clone(),
+        alias: vec![("/lib/a.js".into(), vec![alias_value(&app.join("alias.js"))])],
+        ..options(&[])
+    });
+    let resolution = resolver.resolve(&app, "/lib/a.js").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("alias.js")));
+
+    // Fallback loses to roots and the file system.
+    let fallback = vec![alias_value(&app.join("fallback.js"))];
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        roots,
+        fallback: vec![
+            ("/lib/a.js".into(), fallback.clone()),
+            (absolute.to_string_lossy().into_owned(), fallback),
+        ],
+        ..options(&[])
+    });
+    let resolution = resolver.resolve(&app, "/lib/a.js").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("root/lib/a.js")));
+    let resolution = resolver.resolve(&app, absolute.to_str().unwrap()).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(absolute));
+    let resolution = resolver.resolve(&app, "/lib/b.js").map(|r| r.full_path());
+    assert_eq!(resolution, Err(ResolveError::NotFound("/lib/b.js".into())));
+}
+
+fn alias_value(path: &Path) -> AliasValue {
+    AliasValue::Path(path.to_string_lossy().to_string())
+}
@@ -312,3 +312,46 @@ fn file_protocol() {
 
     assert_eq!(resolver.resolve(f, "file://./main.js"), Err(resolve_error));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn resolve_from_any() {
+    use std::path::{Path, PathBuf};
+
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+
+    let fs = MemoryFS::new(&[
+        ("/project/a.js", ""),
+        ("/config/a.js", ""),
+        ("/config/b.js", ""),
+        ("/broken/node_modules/pkg/package.json", "{"),
+        ("/valid/node_modules/pkg/index.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    let directories = [Path::new("/project"), Path::new("/config"), Path::new("/cwd")];
+
+    let resolution =
+        resolver.resolve_from_any(&directories, "./a").map(|(i, r)| (i, r.full_path()));
+    assert_eq!(resolution, Ok((0, PathBuf::from("/project/a.js"))));
+
+    let resolution =
+        resolver.resolve_from_any(&directories, "./b").map(|(i, r)| (i, r.full_path()));
+    assert_eq!(resolution, Ok((1, PathBuf::from("/config/b.js"))));
+
+    let resolution = resolver.resolve_from_any(&directories, "./c");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::NotFoundInAny {
+            specifier: "./c".into(),
+            tried: directories.iter().map(|d| d.to_path_buf()).collect(),
+        })
+    );
+
+    // A JSON error from an earlier directory is not masked by a later match.
+    let resolution = resolver.resolve_from_any(&["/broken", "/valid"], "pkg");
+    assert!(matches!(resolution, Err(ResolveError::Json(_))), "{resolution:?}");
+    let resolution = resolver.resolve_from_any(&["/valid", "/broken"], "pkg").map(|(i, _)| i);
+    assert_eq!(resolution, Ok(0));
+}
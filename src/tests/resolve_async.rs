@@ -0,0 +1,271 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    AliasValue, AsyncFileSystem, FileMetadata, FileSystem, FileSystemFuture, FileSystemOs,
+    ResolveContext, ResolveError, ResolveOptions, ResolveTimings, Resolver, ResolverGeneric,
+};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("resolve-async/app")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resolve_async() {
+    let app = app();
+    let resolver = Resolver::default();
+    let future = resolver.resolve_async(&app, "pkg");
+    let resolution = tokio::spawn(future).await.unwrap().map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/pkg/lib/main.js")));
+
+    let resolution = resolver.resolve_async(&app, "./missing").await;
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+
+    // Options updated after the future was created do not apply to it.
+    let future = resolver.resolve_async(&app, "./index");
+    resolver.update_options(|options| options.extensions = vec![".ts".into()]);
+    assert_eq!(future.await.map(|r| r.full_path()), Ok(app.join("index.js")));
+    assert!(matches!(
+        resolver.resolve_async(&app, "./index").await,
+        Err(ResolveError::NotFound(_))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resolve_with_context_async() {
+    let app = app();
+    let resolver = Resolver::default();
+    let mut ctx = ResolveContext::default();
+    let resolution = resolver.resolve_with_context_async(&app, "pkg", None, &mut ctx).await;
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(app.join("node_modules/pkg/lib/main.js")));
+
+    let mut expected = ResolveContext::default();
+    Resolver::default().resolve_with_context(&app, "pkg", None, &mut expected).unwrap();
+    assert_eq!(ctx.file_dependencies, expected.file_dependencies);
+    assert_eq!(ctx.missing_dependencies, expected.missing_dependencies);
+    assert!(ctx.file_dependencies.contains(&app.join("node_modules/pkg/lib/main.js")));
+
+    resolver.update_options(|options| options.collect_package_json_chain = true);
+    let mut ctx = ResolveContext { timings: Some(ResolveTimings::default()), ..Default::default() };
+    resolver.resolve_with_context_async(&app, "pkg", None, &mut ctx).await.unwrap();
+    let mut expected = ResolveContext::default();
+    resolver.resolve_with_context(&app, "pkg", None, &mut expected).unwrap();
+    assert_eq!(ctx.package_json_chain, expected.package_json_chain);
+    assert_eq!(ctx.package_json_chain[0], app.join("node_modules/pkg/package.json"));
+    assert!(ctx.timings.unwrap().total > std::time::Duration::ZERO);
+
+    resolver.update_options(|options| {
+        options.alias = vec![("alias".into(), vec![AliasValue::from("pkg")])];
+    });
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context_async(&app, "alias", None, &mut ctx).await.unwrap();
+    assert_eq!(ctx.alias_chain, vec![("alias".into(), "pkg".into())]);
+}
+
+/// Counts the async operations, which yield to the runtime once before completing.
+struct YieldingFS {
+    fs: FileSystemOs,
+    operations: AtomicUsize,
+}
+
+impl YieldingFS {
+    fn run<'a, T: Send + 'a>(&'a self, result: T) -> FileSystemFuture<'a, T> {
+        self.operations.fetch_add(1, Ordering::Relaxed);
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            result
+        })
+    }
+}
+
+impl FileSystem for YieldingFS {
+    #[cfg(not(feature = "yarn_pnp"))]
+    fn new() -> Self {
+        unreachable!()
+    }
+
+    #[cfg(feature = "yarn_pnp")]
+    fn new(_yarn_pnp: bool) -> Self {
+        unreachable!()
+    }
+
+    fn read(&self, _path: &Path) -> io::Result<Vec<u8>> {
+        unreachable!("async resolutions only")
+    }
+
+    fn read_to_string(&self, _path: &Path) -> io::Result<String> {
+        unreachable!("async resolutions only")
+    }
+
+    fn metadata(&self, _path: &Path) -> io::Result<FileMetadata> {
+        unreachable!("async resolutions only")
+    }
+
+    fn symlink_metadata(&self, _path: &Path) -> io::Result<FileMetadata> {
+        unreachable!("async resolutions only")
+    }
+
+    fn read_link(&self, _path: &Path) -> Result<PathBuf, ResolveError> {
+        unreachable!("async resolutions only")
+    }
+
+    fn canonicalize(&self, _path: &Path) -> io::Result<PathBuf> {
+        unreachable!("async resolutions only")
+    }
+
+    fn as_async(&self) -> Option<&dyn AsyncFileSystem> {
+        Some(self)
+    }
+}
+
+impl AsyncFileSystem for YieldingFS {
+    fn read_async<'a>(&'a self, path: &'a Path) -> FileSystemFuture<'a, io::Result<Vec<u8>>> {
+        self.run(self.fs.read(path))
+    }
+
+    fn metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        self.run(self.fs.metadata(path))
+    }
+
+    fn symlink_metadata_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<FileMetadata>> {
+        self.run(self.fs.symlink_metadata(path))
+    }
+
+    fn read_link_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, Result<PathBuf, ResolveError>> {
+        self.run(self.fs.read_link(path))
+    }
+
+    fn canonicalize_async<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> FileSystemFuture<'a, io::Result<PathBuf>> {
+        self.run(self.fs.canonicalize(path))
+    }
+}
+
+fn yielding_resolver() -> (ResolverGeneric<Arc<YieldingFS>>, Arc<YieldingFS>) {
+    #[cfg(not(feature = "yarn_pnp"))]
+    let fs = FileSystemOs::new();
+    #[cfg(feature = "yarn_pnp")]
+    let fs = FileSystemOs::new(false);
+    let fs = Arc::new(YieldingFS { fs, operations: AtomicUsize::new(0) });
+    (ResolverGeneric::new_with_file_system(Arc::clone(&fs), ResolveOptions::default()), fs)
+}
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn async_file_system() {
+    let app = app();
+    let (resolver, fs) = yielding_resolver();
+    let resolution = resolver.resolve_async(&app, "pkg").await.map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/pkg/lib/main.js")));
+    let operations = fs.operations.load(Ordering::Relaxed);
+    assert!(operations > 0);
+
+    // A cache hit completes without the runtime.
+    let resolution = poll_once(resolver.resolve_async(&app, "pkg"));
+    let Poll::Ready(resolution) = resolution else { panic!("{resolution:?}") };
+    assert_eq!(resolution.unwrap().full_path(), app.join("node_modules/pkg/lib/main.js"));
+    assert_eq!(fs.operations.load(Ordering::Relaxed), operations);
+
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context_async(&app, "pkg", None, &mut ctx).await.unwrap();
+    assert!(ctx.file_dependencies.contains(&app.join("node_modules/pkg/lib/main.js")));
+
+    let resolution = resolver.resolve_async(&app, "./missing").await;
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+
+    // The results of the operations are cleared with the cache.
+    let operations = fs.operations.load(Ordering::Relaxed);
+    resolver.clear_cache();
+    resolver.resolve_async(&app, "pkg").await.unwrap();
+    assert!(fs.operations.load(Ordering::Relaxed) > operations);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn shared_cache() {
+    let app = app();
+    let (resolver, _) = yielding_resolver();
+    resolver.enable_cache_statistics();
+    resolver.resolve_async(&app, "pkg").await.unwrap();
+    let statistics = resolver.cache_statistics();
+    assert!(statistics.file_misses > 0);
+
+    // `YieldingFS` has no sync operations, the cache of the async resolution answers instead.
+    let resolution = resolver.resolve(&app, "pkg").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/pkg/lib/main.js")));
+    assert!(resolver.cache_statistics().file_hits > statistics.file_hits);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cancellation() {
+    let app = app();
+    let (resolver, _) = yielding_resolver();
+    for _ in 0..3 {
+        // Dropped while awaiting an operation.
+        let resolution = poll_once(resolver.resolve_async(&app, "pkg"));
+        assert!(resolution.is_pending());
+    }
+    let resolution = resolver.resolve_async(&app, "pkg").await.map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/pkg/lib/main.js")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn os_file_system() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+    for specifier in ["m1/a", "./main1.js", "complexm"] {
+        let expected = resolver.resolve(&f, specifier).map(|r| r.full_path());
+        let resolution = resolver.resolve_async(&f, specifier).await.map(|r| r.full_path());
+        assert_eq!(resolution, expected, "{specifier}");
+    }
+    let resolution = resolver.resolve_async(&f, "./missing").await;
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resolve_tsconfig_async() {
+    let app = app();
+    let (resolver, fs) = yielding_resolver();
+    let tsconfig = resolver.resolve_tsconfig_async(&app, None).await.unwrap();
+    assert_eq!(tsconfig.path(), app.join("tsconfig.json"));
+    assert_eq!(tsconfig.compiler_options.base_url, Some(app.join("src")));
+    assert!(fs.operations.load(Ordering::Relaxed) > 0);
+
+    let cancel = Arc::new(AtomicBool::new(true));
+    let tsconfig = resolver.resolve_tsconfig_async(&app, Some(cancel)).await;
+    assert_eq!(
+        tsconfig.map(|tsconfig| tsconfig.path().to_path_buf()),
+        Err(ResolveError::Cancelled)
+    );
+
+    let f = super::fixture_root().join("tsconfig/cases/extends-chain");
+    let resolver = Resolver::default();
+    let expected = resolver.resolve_tsconfig(&f).unwrap();
+    let tsconfig = resolver.resolve_tsconfig_async(&f, None).await.unwrap();
+    assert_eq!(tsconfig.path(), expected.path());
+    assert_eq!(tsconfig.compiler_options.base_url, expected.compiler_options.base_url);
+}
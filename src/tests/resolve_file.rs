@@ -0,0 +1,57 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ResolveContext, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("resolve-file/app")
+}
+
+#[test]
+fn parity_with_directory() {
+    let app = app();
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("existing file", "src/index.js", "src"),
+        ("missing file", "src/virtual.js", "src"),
+        ("trailing slash", "src/virtual/", "src"),
+        ("existing directory", "src/", ""),
+    ];
+
+    for (comment, file, directory) in pass {
+        let (file, directory) = (app.join(file), app.join(directory));
+        for specifier in ["./util", "pkg", "../src/util.js"] {
+            let expected = resolver.resolve(&directory, specifier).map(|r| r.full_path());
+            let resolution = resolver.resolve_file(&file, specifier).map(|r| r.full_path());
+            assert_eq!(resolution, expected, "{comment} {file:?} {specifier}");
+            let mut ctx = ResolveContext::default();
+            let resolution = resolver
+                .resolve_file_with_context(&file, specifier, &mut ctx)
+                .map(|r| r.full_path());
+            assert_eq!(resolution, expected, "{comment} {file:?} {specifier}");
+        }
+    }
+}
+
+#[test]
+fn dependencies() {
+    let app = app();
+    let resolver = Resolver::default();
+
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_file_with_context(app.join("src/index.js"), "./util", &mut ctx).unwrap();
+    assert!(ctx.file_dependencies.contains(&app.join("src/index.js")));
+    assert!(ctx.file_dependencies.contains(&app.join("src/util.js")));
+
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_file_with_context(app.join("src/virtual.js"), "./util", &mut ctx).unwrap();
+    assert!(!ctx.file_dependencies.contains(&app.join("src/virtual.js")));
+    assert!(ctx.missing_dependencies.contains(&app.join("src/virtual.js")));
+
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_file_with_context("/", "./util", &mut ctx).unwrap_err();
+    assert!(ctx.file_dependencies.is_empty());
+}
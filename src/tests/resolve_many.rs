@@ -0,0 +1,38 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::{Path, PathBuf};
+
+use super::memory_fs::MemoryFS;
+use crate::{ResolveError, ResolveOptions, ResolverGeneric};
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn resolve_many() {
+    let mut fs = MemoryFS::default();
+    fs.add_file(Path::new("/app/index.js"), "");
+    for i in 0..50 {
+        fs.add_file(&PathBuf::from(format!("/app/node_modules/pkg{i}/index.js")), "");
+    }
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    let mut specifiers = (0..50).map(|i| format!("pkg{i}")).collect::<Vec<_>>();
+    specifiers.insert(10, "./missing".into());
+    specifiers.insert(20, "./index".into());
+    let specifiers = specifiers.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let results = resolver.resolve_many("/app", &specifiers);
+    assert_eq!(results.len(), specifiers.len());
+    for (specifier, result) in specifiers.iter().zip(results) {
+        let expected = resolver.resolve("/app", specifier).map(|r| r.full_path());
+        assert_eq!(result.map(|r| r.full_path()), expected, "{specifier}");
+    }
+    let results = resolver.resolve_many("/app", &["./missing", "pkg3"]);
+    assert_eq!(
+        results.into_iter().map(|r| r.map(|r| r.full_path())).collect::<Vec<_>>(),
+        vec![
+            Err(ResolveError::NotFound("./missing".into())),
+            Ok(PathBuf::from("/app/node_modules/pkg3/index.js"))
+        ]
+    );
+    assert!(resolver.resolve_many("/app", &[]).is_empty());
+}
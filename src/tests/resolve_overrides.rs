@@ -0,0 +1,82 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{ConditionNamesProvider, ResolveOptions, ResolveOverrides, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("resolve-overrides/app")
+}
+
+fn strings(strings: &[&str]) -> Vec<String> {
+    strings.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn condition_names() {
+    let app = app();
+    let resolver = Resolver::default();
+    let node = ResolveOverrides {
+        condition_names: Some(strings(&["node", "require"])),
+        ..ResolveOverrides::default()
+    };
+    let browser = ResolveOverrides {
+        condition_names: Some(strings(&["browser", "import"])),
+        ..ResolveOverrides::default()
+    };
+
+    // Alternate between the condition sets so that a mixed up cache would show.
+    for _ in 0..2 {
+        let data = [
+            (&node, "node_modules/dual/node.cjs"),
+            (&browser, "node_modules/dual/browser.mjs"),
+            (&ResolveOverrides::default(), "node_modules/dual/default.js"),
+        ];
+        for (overrides, expected) in data {
+            let resolution = resolver
+                .resolve_with_options_override(&app, "dual", overrides)
+                .map(|r| r.full_path());
+            assert_eq!(resolution, Ok(app.join(expected)), "{overrides:?}");
+        }
+        let resolution = resolver.resolve(&app, "dual").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(app.join("node_modules/dual/default.js")));
+    }
+}
+
+#[test]
+fn condition_names_provider() {
+    let app = app();
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names_provider: Some(ConditionNamesProvider::new(|_| {
+            Some(vec!["node".into(), "require".into()])
+        })),
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&app, "dual").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/dual/node.cjs")));
+
+    let browser = ResolveOverrides {
+        condition_names: Some(strings(&["browser", "import"])),
+        ..ResolveOverrides::default()
+    };
+    let resolution =
+        resolver.resolve_with_options_override(&app, "dual", &browser).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/dual/browser.mjs")));
+}
+
+#[test]
+fn main_fields() {
+    let app = app();
+    let resolver = Resolver::default();
+    let browser = ResolveOverrides {
+        main_fields: Some(strings(&["browser", "main"])),
+        ..ResolveOverrides::default()
+    };
+
+    let resolution =
+        resolver.resolve_with_options_override(&app, "legacy", &browser).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/legacy/browser.js")));
+
+    let resolution = resolver.resolve(&app, "legacy").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(app.join("node_modules/legacy/main.js")));
+}
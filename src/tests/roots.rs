@@ -2,7 +2,8 @@
 
 use std::path::PathBuf;
 
-use crate::{AliasValue, ResolveError, ResolveOptions, Resolver};
+use super::memory_fs::MemoryFS;
+use crate::{AliasValue, ResolveError, ResolveOptions, Resolver, ResolverGeneric};
 
 fn dirname() -> PathBuf {
     super::fixture_root().join("enhanced-resolve").join("test")
@@ -121,3 +122,84 @@ fn should_resolve_slash() {
         assert_eq!(resolution, Err(ResolveError::NotFound("/".into())), "{comment} {roots:?}");
     }
 }
+
+fn package_root_resolver(
+    allow_package_exports_in_directory_resolve: bool,
+) -> ResolverGeneric<MemoryFS> {
+    let fs = MemoryFS::new(&[
+        (
+            "/app/dist/package.json",
+            r#"{
+                "main": "./main.js",
+                "exports": {
+                    ".": "./lib/index.js",
+                    "./components/*": "./lib/components/*.js"
+                }
+            }"#,
+        ),
+        ("/app/dist/main.js", ""),
+        ("/app/dist/lib/index.js", ""),
+        ("/app/dist/lib/components/Button.js", ""),
+        ("/app/dist/components/Button.js", ""),
+        ("/app/dist/plain.js", ""),
+        ("/app/src/index.js", ""),
+    ]);
+    ResolverGeneric::new_with_file_system(
+        fs,
+        ResolveOptions {
+            extensions: vec![".js".into()],
+            roots: vec![PathBuf::from("/app/dist")],
+            allow_package_exports_in_directory_resolve,
+            ..ResolveOptions::default()
+        },
+    )
+}
+
+// Not part of enhanced_resolve's test suite
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn package_exports() {
+    #[rustfmt::skip]
+    let data = [
+        ("/components/Button", true, "/app/dist/lib/components/Button.js"),
+        ("/components/Button", false, "/app/dist/components/Button.js"),
+        ("/plain", true, "/app/dist/plain.js"),
+        ("/plain", false, "/app/dist/plain.js"),
+        ("/", true, "/app/dist/lib/index.js"),
+        ("/", false, "/app/dist/main.js"),
+    ];
+
+    for (request, allow_package_exports_in_directory_resolve, expected) in data {
+        let resolver = package_root_resolver(allow_package_exports_in_directory_resolve);
+        let resolution = resolver.resolve("/app/dist", request).map(|r| r.full_path());
+        assert_eq!(
+            resolution,
+            Ok(PathBuf::from(expected)),
+            "{request} {allow_package_exports_in_directory_resolve}"
+        );
+    }
+}
+
+// Not part of enhanced_resolve's test suite
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn query_fragment() {
+    #[rustfmt::skip]
+    let data = [
+        ("/components/Button?raw", true, "/app/dist/lib/components/Button.js?raw"),
+        ("/components/Button?raw#hash", false, "/app/dist/components/Button.js?raw#hash"),
+        ("/plain#hash", true, "/app/dist/plain.js#hash"),
+        ("/plain.js?raw", false, "/app/dist/plain.js?raw"),
+        ("/?raw", true, "/app/dist/lib/index.js?raw"),
+    ];
+
+    for (request, allow_package_exports_in_directory_resolve, expected) in data {
+        let resolver = package_root_resolver(allow_package_exports_in_directory_resolve);
+        let resolution = resolver.resolve("/app/dist", request).map(|r| r.full_path());
+        assert_eq!(
+            resolution,
+            Ok(PathBuf::from(expected)),
+            "{request} {allow_package_exports_in_directory_resolve}"
+        );
+    }
+}
@@ -10,7 +10,9 @@ use super::fixture_root;
 use crate::PathUtil;
 #[cfg(target_os = "windows")]
 use crate::tests::windows::get_dos_device_path;
-use crate::{ResolveOptions, Resolver};
+use crate::{
+    FileSystemOs, ResolveContext, ResolveOptions, Resolver, context::ResolveContext as Ctx,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum FileType {
@@ -177,6 +179,76 @@ fn test() {
     }
 }
 
+#[test]
+#[cfg_attr(target_family = "wasm", ignore)]
+fn symlinks_are_file_dependencies() {
+    let Some(SymlinkFixturePaths { root, temp_path }) =
+        prepare_symlinks("temp.dependencies").unwrap()
+    else {
+        return;
+    };
+    let that = temp_path.join("that");
+    let this = temp_path.join("this");
+
+    let resolver = Resolver::default();
+    // The second resolution is answered from the canonicalization cache.
+    for _ in 0..2 {
+        let mut ctx = ResolveContext::default();
+        let resolution =
+            resolver.resolve_with_context(&temp_path, "./that/lib/index.js", None, &mut ctx);
+        assert_eq!(resolution.map(|r| r.full_path()), Ok(root.join("lib/index.js")));
+        assert!(ctx.file_dependencies.contains(&that), "{:?}", ctx.file_dependencies);
+        assert!(ctx.file_dependencies.contains(&this), "{:?}", ctx.file_dependencies);
+    }
+
+    let resolver = Resolver::new(ResolveOptions { symlinks: false, ..ResolveOptions::default() });
+    let mut ctx = ResolveContext::default();
+    let resolution =
+        resolver.resolve_with_context(&temp_path, "./that/lib/index.js", None, &mut ctx);
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(that.join("lib/index.js")));
+    assert!(!ctx.file_dependencies.contains(&that), "{:?}", ctx.file_dependencies);
+    assert!(!ctx.file_dependencies.contains(&this), "{:?}", ctx.file_dependencies);
+}
+
+// Files reached through a symlink are watched at their real path and at the link.
+#[test]
+#[cfg_attr(target_family = "wasm", ignore)]
+fn watch_targets() {
+    let Some(SymlinkFixturePaths { root, temp_path }) = prepare_symlinks("temp.watch").unwrap()
+    else {
+        return;
+    };
+    let resolver = Resolver::default();
+    let mut ctx = ResolveContext::default();
+    let resolution = resolver.resolve_with_context(&temp_path, "./lib", None, &mut ctx);
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(root.join("lib/index.js")));
+    assert!(ctx.file_dependencies.contains(&temp_path.join("lib/index.js")));
+
+    let targets = ctx.watch_targets(&resolver);
+    for path in [root.join("lib/index.js"), temp_path.join("lib")] {
+        assert!(targets.files.contains(&path), "{path:?}");
+    }
+    assert!(!targets.files.contains(&temp_path.join("lib/index.js")));
+}
+
+// The metadata of a symlink is the metadata of its target.
+#[test]
+#[cfg_attr(target_family = "wasm", ignore)]
+fn file_metadata_of_symlink_target() {
+    let Some(SymlinkFixturePaths { root, temp_path }) =
+        prepare_symlinks("temp.file_metadata").unwrap()
+    else {
+        return;
+    };
+    let expected = FileSystemOs::metadata(&root.join("lib/index.js")).unwrap();
+    assert!(expected.size() > 0);
+    assert!(expected.modified().is_some());
+
+    let resolution = Resolver::default().resolve(&temp_path, "./index.js").unwrap();
+    assert_eq!(resolution.path(), root.join("lib/index.js"));
+    assert_eq!(resolution.file_metadata(), Some(expected));
+}
+
 #[cfg(target_os = "windows")]
 #[test]
 fn test_unsupported_targets() {
@@ -295,9 +367,10 @@ fn canonicalize_matches_os_for_all_node_modules() {
             #[cfg(target_os = "windows")]
             let Ok(expected) = crate::windows::strip_windows_prefix(expected) else { continue };
             let cached = resolver.cache.value(path);
-            let actual = resolver.cache.canonicalize(&cached).unwrap_or_else(|err| {
-                panic!("{combo}: resolver canonicalize({}) failed: {err}", path.display())
-            });
+            let actual =
+                resolver.cache.canonicalize(&cached, &mut Ctx::default()).unwrap_or_else(|err| {
+                    panic!("{combo}: resolver canonicalize({}) failed: {err}", path.display())
+                });
             assert_eq!(actual, expected, "{combo}: canonicalize mismatch for {}", path.display());
             paths_checked += 1;
         }
@@ -340,7 +413,7 @@ fn canonicalize_dirty_cache_keys() {
         #[cfg(target_os = "windows")]
         let expected = crate::windows::strip_windows_prefix(expected).unwrap();
         let cached = resolver.cache.value(&path);
-        let actual = resolver.cache.canonicalize(&cached).unwrap();
+        let actual = resolver.cache.canonicalize(&cached, &mut Ctx::default()).unwrap();
         assert_eq!(actual.as_os_str(), expected.as_os_str(), "{}", path.display());
     }
 }
@@ -367,7 +440,7 @@ fn symlinked_package_anchor_walks_suffix_symlinks() {
 
     let resolver = Resolver::new(ResolveOptions::default());
     let cached = resolver.cache.value(&path);
-    let actual = resolver.cache.canonicalize(&cached).unwrap();
+    let actual = resolver.cache.canonicalize(&cached, &mut Ctx::default()).unwrap();
 
     assert_eq!(actual, expected);
     assert_eq!(expected, root.join("packages/pkg/real/file.js"));
@@ -396,7 +469,7 @@ fn real_package_anchor_walks_internal_symlinks() {
         #[cfg(target_os = "windows")]
         let expected = crate::windows::strip_windows_prefix(expected).unwrap();
         let cached = resolver.cache.value(&path);
-        let actual = resolver.cache.canonicalize(&cached).unwrap();
+        let actual = resolver.cache.canonicalize(&cached, &mut Ctx::default()).unwrap();
         assert_eq!(actual, expected, "{}", path.display());
         assert_eq!(expected, real, "{}", path.display());
     }
@@ -427,14 +500,14 @@ fn nested_monorepo_canonicalize_matches_os() {
         #[cfg(target_os = "windows")]
         let Ok(expected) = crate::windows::strip_windows_prefix(expected) else { continue };
         let cached = resolver.cache.value(path);
-        let actual = resolver.cache.canonicalize(&cached).unwrap();
+        let actual = resolver.cache.canonicalize(&cached, &mut Ctx::default()).unwrap();
         assert_eq!(actual, expected, "canonicalize mismatch for {}", path.display());
     }
 
     // The conflicting versions resolve to their respective stores.
     let resolve = |p: &Path| {
         let cached = resolver.cache.value(p);
-        resolver.cache.canonicalize(&cached).unwrap()
+        resolver.cache.canonicalize(&cached, &mut Ctx::default()).unwrap()
     };
     assert_eq!(
         resolve(&nested.join("index.js")),
@@ -445,3 +518,62 @@ fn nested_monorepo_canonicalize_matches_os() {
         root.join("node_modules/.pnpm/dep@2.0.0/node_modules/dep/index.js")
     );
 }
+
+/// A pnpm style layout of `app/node_modules/pkg` linking to `packages/pkg`.
+fn prepare_linked_package(temp_path: &Path) -> io::Result<bool> {
+    _ = fs::remove_dir_all(temp_path);
+    let package = temp_path.join("packages/pkg");
+    fs::create_dir_all(package.join("src"))?;
+    fs::create_dir_all(package.join("lib"))?;
+    fs::create_dir_all(temp_path.join("app/node_modules"))?;
+    fs::write(
+        package.join("package.json"),
+        r##"{
+            "name": "pkg",
+            "exports": { ".": "./lib/index.js", "./sub": "./lib/sub.js" },
+            "imports": { "#internal": "./lib/internal.js" }
+        }"##,
+    )?;
+    for file in ["src/index.js", "lib/index.js", "lib/sub.js", "lib/internal.js"] {
+        fs::write(package.join(file), "")?;
+    }
+    if let Err(err) =
+        symlink(package.canonicalize()?, temp_path.join("app/node_modules/pkg"), FileType::Dir)
+    {
+        println!(
+            "Skipped test: Failed to create symlinks. You may need administrator privileges. Error: {err}"
+        );
+        _ = fs::remove_dir_all(temp_path);
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", ignore)]
+fn package_self_reference_without_symlinks() {
+    let temp_path = fixture_root().join("enhanced-resolve/test/temp_self_reference");
+    if !prepare_linked_package(&temp_path).unwrap() {
+        return;
+    }
+    let linked = temp_path.join("app/node_modules/pkg");
+    let directory = linked.join("src");
+
+    let resolver_with_symlinks = Resolver::default();
+    // The cache is shared, so the package.json is first read by a resolution following symlinks.
+    let resolver_without_symlinks = resolver_with_symlinks
+        .clone_with_options(ResolveOptions { symlinks: false, ..ResolveOptions::default() });
+    let real = temp_path.join("packages/pkg").canonicalize().unwrap();
+    for (request, file) in
+        [("pkg", "lib/index.js"), ("pkg/sub", "lib/sub.js"), ("#internal", "lib/internal.js")]
+    {
+        let resolution = resolver_with_symlinks.resolve(&directory, request).unwrap();
+        assert_eq!(resolution.path(), real.join(file), "{request}");
+
+        let resolution = resolver_without_symlinks.resolve(&directory, request).unwrap();
+        assert_eq!(resolution.path(), linked.join(file), "{request}");
+        let package_json = resolution.package_json().unwrap();
+        assert_eq!(package_json.directory(), linked, "{request}");
+    }
+    _ = fs::remove_dir_all(&temp_path);
+}
@@ -0,0 +1,47 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveOptions, ResolveTarget, Resolver};
+
+// `platforms` only has the platform keys, runtimes fall back to the platform they are compatible with.
+#[rustfmt::skip]
+const MATRIX: [(ResolveTarget, &str, &str); 6] = [
+    (ResolveTarget::Node, "node_modules/runtimes/node.js", "node_modules/platforms/node.js"),
+    (ResolveTarget::Browser, "node_modules/runtimes/browser.js", "node_modules/platforms/browser.js"),
+    (ResolveTarget::Workerd, "node_modules/runtimes/workerd.js", "node_modules/platforms/worker.js"),
+    (ResolveTarget::EdgeLight, "node_modules/runtimes/edge-light.js", "node_modules/platforms/worker.js"),
+    (ResolveTarget::Bun, "node_modules/runtimes/bun.js", "node_modules/platforms/node.js"),
+    (ResolveTarget::Deno, "node_modules/runtimes/deno.js", "node_modules/platforms/node.js"),
+];
+
+fn assert_matrix(options: impl Fn(ResolveTarget) -> ResolveOptions) {
+    assert_eq!(MATRIX.map(|(target, _, _)| target), ResolveTarget::ALL);
+    let f = super::fixture_root().join("target");
+    for (target, runtimes, platforms) in MATRIX {
+        let resolver = Resolver::new(options(target));
+        for (specifier, expected) in [("runtimes", runtimes), ("platforms", platforms)] {
+            let resolution = resolver.resolve(&f, specifier).map(|r| r.full_path());
+            assert_eq!(resolution, Ok(f.join(expected)), "{target:?} {specifier}");
+        }
+    }
+}
+
+#[test]
+fn matrix() {
+    assert_matrix(ResolveOptions::default_for_target);
+}
+
+#[test]
+fn unknown_condition() {
+    assert_matrix(|target| {
+        let mut options = ResolveOptions::default_for_target(target);
+        options.condition_names.push("some-other-runtime".into());
+        options
+    });
+}
+
+#[test]
+fn valid() {
+    for target in ResolveTarget::ALL {
+        assert_eq!(ResolveOptions::default_for_target(target).validate(), vec![], "{target:?}");
+    }
+}
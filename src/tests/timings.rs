@@ -0,0 +1,157 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{path::PathBuf, time::Duration};
+
+use crate::{AliasValue, ResolveContext, ResolveOptions, ResolveStats, ResolveTimings, Resolver};
+
+fn app() -> PathBuf {
+    super::fixture_root().join("timings/app")
+}
+
+#[test]
+fn phases() {
+    let options = ResolveOptions {
+        alias: vec![("alias".into(), vec![AliasValue::from("pkg/a")])],
+        ..ResolveOptions::default()
+    };
+    let src = app().join("src");
+    let resolve_all = |resolver: &Resolver| {
+        let mut ctx =
+            ResolveContext { timings: Some(ResolveTimings::default()), ..Default::default() };
+        for specifier in ["pkg", "pkg/a", "pkg/b", "alias", "main", "./index.js"] {
+            resolver.resolve_with_context(&src, specifier, None, &mut ctx).unwrap();
+        }
+        ctx.timings.unwrap()
+    };
+    let resolver = Resolver::new(options.clone());
+    let timings = resolve_all(&resolver);
+    for (phase, duration) in [
+        ("parse", timings.parse),
+        ("alias", timings.alias),
+        ("node_modules_walk", timings.node_modules_walk),
+        ("exports_resolution", timings.exports_resolution),
+        ("package_json_io", timings.package_json_io),
+        ("canonicalize", timings.canonicalize),
+    ] {
+        assert!(duration > Duration::ZERO, "{phase}");
+    }
+    assert!(timings.phases() <= timings.total, "{timings:?}");
+    // A preempted thread only adds to the total, so the best of a few fresh runs is compared.
+    let covered = std::iter::once(timings)
+        .chain((0..4).map(|_| resolve_all(&Resolver::new(options.clone()))))
+        .find(|timings| timings.phases() * 2 >= timings.total);
+    assert!(covered.is_some(), "{timings:?}");
+
+    // Timings are accumulated across calls.
+    let mut next = ResolveContext { timings: Some(timings), ..Default::default() };
+    resolver.resolve_with_context(&src, "pkg", None, &mut next).unwrap();
+    assert!(next.timings.unwrap().total > timings.total);
+}
+
+#[test]
+fn package_exports_timings() {
+    let app = app();
+    let src = app.join("src");
+    let resolver = Resolver::default();
+
+    // Not measured without timings.
+    resolver.resolve(&src, "pkg").unwrap();
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context(&src, "pkg/a", None, &mut ctx).unwrap();
+    assert!(ctx.timings.is_none());
+    assert_eq!(resolver.package_exports_timings(), vec![]);
+
+    let mut ctx = ResolveContext { timings: Some(ResolveTimings::default()), ..Default::default() };
+    resolver.resolve_with_context(&src, "pkg", None, &mut ctx).unwrap();
+    resolver.resolve_with_context(&src, "main", None, &mut ctx).unwrap();
+    let timings = resolver.package_exports_timings();
+    assert_eq!(timings.len(), 1);
+    assert_eq!(timings[0].0, app.join("node_modules/pkg/package.json"));
+    assert!(timings[0].1 > Duration::ZERO);
+
+    resolver.resolve_with_context(&src, "pkg/b", None, &mut ctx).unwrap();
+    assert!(resolver.package_exports_timings()[0].1 > timings[0].1);
+    assert!(ctx.timings.unwrap().exports_resolution <= resolver.package_exports_timings()[0].1);
+}
+
+#[test]
+fn timing() {
+    let options = ResolveOptions {
+        alias: vec![("alias".into(), vec![AliasValue::from("pkg/a")])],
+        ..ResolveOptions::default()
+    };
+    let src = app().join("src");
+    let resolver = Resolver::new(options);
+
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context(&src, "pkg", None, &mut ctx).unwrap();
+    assert_eq!(ctx.timings, None);
+
+    ctx.enable_timing();
+    for specifier in ["pkg", "alias", "main", "./index.js"] {
+        resolver.resolve_with_context(&src, specifier, None, &mut ctx).unwrap();
+    }
+    let timings = ctx.timings.unwrap();
+    assert!(timings.alias > Duration::ZERO, "{timings:?}");
+    assert!(timings.node_modules_walk > Duration::ZERO, "{timings:?}");
+    assert!(timings.exports_resolution > Duration::ZERO, "{timings:?}");
+    assert!(timings.file_probes > Duration::ZERO, "{timings:?}");
+
+    // Enabling again keeps the collected timings.
+    ctx.enable_timing();
+    assert_eq!(ctx.timings, Some(timings));
+}
+
+#[test]
+fn stats() {
+    let options = ResolveOptions {
+        alias: vec![("alias".into(), vec![AliasValue::from("pkg/a")])],
+        ..ResolveOptions::default()
+    };
+    let src = app().join("src");
+    let resolver = Resolver::new(options);
+
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context(&src, "pkg", None, &mut ctx).unwrap();
+    assert_eq!(ctx.stats, None);
+    assert_eq!(ctx.timings, None);
+
+    // Relative specifiers do not walk `node_modules`.
+    ctx.collect_stats();
+    resolver.resolve_with_context(&src, "./index.js", None, &mut ctx).unwrap();
+    let stats = ctx.stats.unwrap();
+    assert!(stats.metadata_lookups > 0, "{stats:?}");
+    assert_eq!(stats.node_modules_directories, 0, "{stats:?}");
+    assert_eq!(stats.node_modules_walk_calls, 0, "{stats:?}");
+    assert!(ctx.timings.is_some());
+
+    // Bare specifiers look into `src/node_modules` and `node_modules`.
+    let mut ctx = ResolveContext::default();
+    ctx.collect_stats();
+    resolver.resolve_with_context(&src, "main", None, &mut ctx).unwrap();
+    let stats = ctx.stats.unwrap();
+    assert_eq!(stats.node_modules_walk_calls, 1, "{stats:?}");
+    assert!(stats.node_modules_directories >= 1, "{stats:?}");
+    assert!(stats.package_json_parses >= 1, "{stats:?}");
+    assert!(stats.metadata_lookups >= stats.node_modules_directories, "{stats:?}");
+
+    // The counts are accumulated across calls, the package.json files are cached by now.
+    for specifier in ["main", "pkg", "alias"] {
+        resolver.resolve_with_context(&src, specifier, None, &mut ctx).unwrap();
+    }
+    let accumulated = ctx.stats.unwrap();
+    assert_eq!(accumulated.node_modules_walk_calls, 4, "{accumulated:?}");
+    // Once per specifier, including `pkg/a` from `alias`.
+    assert_eq!(accumulated.alias_calls, 5, "{accumulated:?}");
+    assert_eq!(accumulated.exports_resolution_calls, 2, "{accumulated:?}");
+    assert_eq!(accumulated.package_json_parses, stats.package_json_parses, "{accumulated:?}");
+    assert!(accumulated.metadata_lookups > stats.metadata_lookups);
+
+    let mut interned = crate::ResolveContextInterned {
+        stats: Some(ResolveStats::default()),
+        ..Default::default()
+    };
+    resolver.resolve_with_context(&src, "main", None, &mut interned).unwrap();
+    assert!(interned.stats.unwrap().node_modules_directories >= 1);
+    assert_eq!(interned.timings, None);
+}
@@ -0,0 +1,61 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{EnforceExtension, ResolveError, ResolveOptions, Resolver};
+
+// The matrix is documented at `ResolverImpl::load_as_file_or_directory`.
+#[test]
+fn trailing_slash() {
+    let f = super::fixture_root().join("trailing-slash");
+    let resolver = Resolver::default();
+
+    let default = ResolveOptions::default();
+    let enforce_extension = ResolveOptions {
+        enforce_extension: EnforceExtension::Enabled,
+        ..ResolveOptions::default()
+    };
+    let fully_specified = ResolveOptions { fully_specified: true, ..ResolveOptions::default() };
+    let resolve_to_context =
+        ResolveOptions { resolve_to_context: true, ..ResolveOptions::default() };
+
+    #[rustfmt::skip]
+    let cases = [
+        (&default, "./dir", Some("dir.js")),
+        (&default, "./dir?query", Some("dir.js?query")),
+        (&default, "./dir/", Some("dir/main.js")),
+        (&default, "./dir/?query", Some("dir/main.js?query")),
+        (&default, ".//dir//", Some("dir/main.js")),
+        (&default, ".//dir//?query", Some("dir/main.js?query")),
+        // `./dir` is `./dir.js` with an appended extension, `dir/main.js` can't be loaded as is.
+        (&enforce_extension, "./dir", Some("dir.js")),
+        (&enforce_extension, "./dir/", Some("dir/main.js")),
+        (&enforce_extension, "./dir/?query", Some("dir/main.js?query")),
+        (&enforce_extension, ".//dir//", Some("dir/main.js")),
+        (&fully_specified, "./dir", None),
+        (&fully_specified, "./dir?query", None),
+        (&fully_specified, "./dir/", None),
+        (&fully_specified, "./dir/?query", None),
+        (&fully_specified, ".//dir//", None),
+        (&fully_specified, "./dir.js", Some("dir.js")),
+        (&fully_specified, ".//dir.js?query", Some("dir.js?query")),
+        (&resolve_to_context, "./dir", Some("dir")),
+        (&resolve_to_context, "./dir/", Some("dir")),
+        (&resolve_to_context, "./dir/?query", Some("dir?query")),
+        (&resolve_to_context, ".//dir//", Some("dir")),
+        (&resolve_to_context, "./dir.js", None),
+    ];
+
+    for (options, request, expected) in cases {
+        let resolver = resolver.clone_with_options(options.clone());
+        let resolution = resolver.resolve(&f, request).map(|r| r.full_path());
+        let path = request.split_once('?').map_or(request, |(path, _)| path);
+        let expected =
+            expected.map(|p| f.join(p)).ok_or_else(|| ResolveError::NotFound(path.to_string()));
+        assert_eq!(resolution, expected, "{request} {options}");
+    }
+
+    // A directory without a trailing slash keeps the extension requirement for its main field.
+    let f = f.join("directory-only");
+    let resolver = Resolver::new(enforce_extension);
+    assert_eq!(resolver.resolve(&f, "./dir"), Err(ResolveError::NotFound("./dir".into())));
+    assert_eq!(resolver.resolve(&f, "./dir/").map(|r| r.full_path()), Ok(f.join("dir/main.js")));
+}
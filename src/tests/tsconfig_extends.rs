@@ -437,3 +437,95 @@ fn test_extend_tsconfig_via_symlink_relative() {
     let f = super::fixture_root().join("tsconfig/cases/extends-symlink");
     assert_extends_symlink_resolves_to_canonical(&f.join("project/tsconfig.relative.json"));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn test_resolve_tsconfig_with_cancellation() {
+    use std::{
+        io,
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicBool, Ordering},
+        },
+    };
+
+    use super::memory_fs::MemoryFS;
+    use crate::{FileMetadata, FileSystem, ResolveError, ResolverGeneric};
+
+    /// Records every read and flips `cancel` once the first file has been read.
+    struct CancellingFS {
+        fs: MemoryFS,
+        reads: Arc<Mutex<Vec<PathBuf>>>,
+        cancel: Arc<AtomicBool>,
+    }
+
+    impl FileSystem for CancellingFS {
+        #[cfg(not(feature = "yarn_pnp"))]
+        fn new() -> Self {
+            unreachable!()
+        }
+
+        #[cfg(feature = "yarn_pnp")]
+        fn new(_yarn_pnp: bool) -> Self {
+            unreachable!()
+        }
+
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            self.reads.lock().unwrap().push(path.to_path_buf());
+            self.cancel.store(true, Ordering::Relaxed);
+            self.fs.read(path)
+        }
+
+        fn read_to_string(&self, path: &Path) -> io::Result<String> {
+            self.reads.lock().unwrap().push(path.to_path_buf());
+            self.cancel.store(true, Ordering::Relaxed);
+            self.fs.read_to_string(path)
+        }
+
+        fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.fs.metadata(path)
+        }
+
+        fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.fs.symlink_metadata(path)
+        }
+
+        fn read_link(&self, path: &Path) -> Result<PathBuf, ResolveError> {
+            self.fs.read_link(path)
+        }
+
+        fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+            self.fs.canonicalize(path)
+        }
+    }
+
+    let reads = Arc::new(Mutex::new(vec![]));
+    let cancel = Arc::new(AtomicBool::new(false));
+    let fs = CancellingFS {
+        fs: MemoryFS::new(&[
+            ("/project/tsconfig.json", r#"{ "extends": "./base.json" }"#),
+            ("/project/base.json", r#"{ "extends": "./root.json" }"#),
+            ("/project/root.json", r#"{ "compilerOptions": { "baseUrl": "src" } }"#),
+        ]),
+        reads: Arc::clone(&reads),
+        cancel: Arc::clone(&cancel),
+    };
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+
+    let result = resolver.resolve_tsconfig_with("/project/tsconfig.json", Some(cancel));
+    assert_eq!(result.map(|_| ()), Err(ResolveError::Cancelled));
+    assert_eq!(*reads.lock().unwrap(), vec![PathBuf::from("/project/tsconfig.json")]);
+
+    // Nothing is cached for the cancelled load.
+    reads.lock().unwrap().clear();
+    let tsconfig = resolver.resolve_tsconfig_with("/project/tsconfig.json", None).unwrap();
+    assert_eq!(tsconfig.compiler_options.base_url, Some(PathBuf::from("/project/src")));
+    assert_eq!(reads.lock().unwrap().len(), 3);
+
+    // An already set token cancels before anything is read.
+    reads.lock().unwrap().clear();
+    let result = resolver
+        .resolve_tsconfig_with("/project/other.json", Some(Arc::new(AtomicBool::new(true))));
+    assert_eq!(result.map(|_| ()), Err(ResolveError::Cancelled));
+    assert!(reads.lock().unwrap().is_empty());
+}
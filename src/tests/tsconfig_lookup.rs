@@ -233,6 +233,46 @@ fn extends_diamond() {
     assert_eq!(compiler_options.module, Some("ESNext".to_string()));
 }
 
+// Each compiler option is taken from the last config defining it, `paths` is never merged.
+#[test]
+fn extends_array_split_options() {
+    let f = super::fixture_root().join("tsconfig/cases/extends-array-split");
+
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Auto),
+        extensions: vec![".ts".into()],
+        ..ResolveOptions::default()
+    });
+
+    let tsconfig = resolver.find_tsconfig(f.join("main.ts")).unwrap().unwrap();
+    assert_eq!(tsconfig.compiler_options.jsx, Some("react-jsx".to_string()));
+    assert_eq!(tsconfig.compiler_options.target, Some("ES2022".to_string()));
+
+    // `${configDir}` is the directory of the extending config.
+    let resolution = resolver.resolve_file(f.join("main.ts"), "@/util").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("src/util.ts")));
+}
+
+#[test]
+fn extends_array_conflict() {
+    let f = super::fixture_root().join("tsconfig/cases/extends-array-conflict");
+
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Auto),
+        extensions: vec![".ts".into()],
+        ..ResolveOptions::default()
+    });
+
+    let tsconfig = resolver.find_tsconfig(f.join("main.ts")).unwrap().unwrap();
+    assert_eq!(tsconfig.compiler_options.jsx, Some("react-jsx".to_string()));
+
+    let resolution = resolver.resolve_file(f.join("main.ts"), "@/util").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("second/util.ts")));
+    // Keys of the replaced `paths` are gone.
+    let resolution = resolver.resolve_file(f.join("main.ts"), "~/util").map(|r| r.full_path());
+    assert_eq!(resolution, Err(ResolveError::NotFound("~/util".into())));
+}
+
 // ---------------------------------------------------------------------------
 // Group 4: paths semantics
 // ---------------------------------------------------------------------------
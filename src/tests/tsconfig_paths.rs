@@ -3,8 +3,8 @@
 //! Fixtures copied from <https://github.com/parcel-bundler/parcel/tree/v2/packages/utils/node-resolver-core/test/fixture/tsconfig>.
 
 use crate::{
-    JSONError, ResolveError, ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions,
-    TsconfigReferences,
+    EnforceExtension, JSONError, ResolveError, ResolveOptions, Resolver, TsconfigDiscovery,
+    TsconfigOptions, TsconfigReferences,
 };
 
 // <https://github.com/parcel-bundler/parcel/blob/b6224fd519f95e68d8b93ba90376fd94c8b76e69/packages/utils/node-resolver-rs/src/lib.rs#L2303>
@@ -275,6 +275,34 @@ fn test_paths_nested_base() {
     }
 }
 
+#[test]
+fn paths_package_targets() {
+    let f = super::fixture_root().join("tsconfig/cases/paths-package");
+
+    #[rustfmt::skip]
+    let pass = [
+        ("lodash", Ok(f.join("node_modules/lodash-es/lodash.js"))),
+        ("x", Ok(f.join("node_modules/@scope/pkg/lib/x.js"))),
+        ("utils/y", Ok(f.join("node_modules/@scope/pkg/lib/y.js"))),
+        // Targets which name a directory under the base path still resolve there first.
+        ("@/foo", Ok(f.join("src/foo.ts"))),
+        ("utils/z", Err(ResolveError::NotFound("utils/z".into()))),
+    ];
+
+    for (request, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+                config_file: f.join("tsconfig.json"),
+                references: TsconfigReferences::Disabled,
+            })),
+            ..ResolveOptions::default().with_extension(String::from(".ts"))
+        });
+        let resolved_path =
+            resolver.resolve_file(f.join("src/index.ts"), request).map(|f| f.full_path());
+        assert_eq!(resolved_path, expected, "{request}");
+    }
+}
+
 #[test]
 fn test_parent_base_url() {
     let f = super::fixture_root().join("tsconfig");
@@ -640,3 +668,133 @@ mod windows_test {
         }
     }
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn browser_field_applies_to_paths_candidates() {
+    use std::path::PathBuf;
+
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+
+    const PACKAGE_JSON: &str = r#"{
+        "name": "@ws/lib",
+        "main": "./index.js",
+        "browser": { "./debug.js": "./noop.js", "./index.js": "./browser.js", "./dir": "./noop" }
+    }"#;
+    let fs = MemoryFS::new(&[
+        (
+            "/ws/app/tsconfig.json",
+            r#"{ "compilerOptions": { "paths": { "@ws/lib": ["../lib"], "@ws/lib/*": ["../lib/*"] } } }"#,
+        ),
+        ("/ws/lib/package.json", PACKAGE_JSON),
+        ("/ws/lib/index.js", ""),
+        ("/ws/lib/browser.js", ""),
+        ("/ws/lib/debug.js", ""),
+        ("/ws/lib/noop.js", ""),
+        ("/ws/lib/dir/index.js", ""),
+        ("/ws/app/node_modules/@ws/lib/package.json", PACKAGE_JSON),
+        ("/ws/app/node_modules/@ws/lib/index.js", ""),
+        ("/ws/app/node_modules/@ws/lib/browser.js", ""),
+        ("/ws/app/node_modules/@ws/lib/debug.js", ""),
+        ("/ws/app/node_modules/@ws/lib/noop.js", ""),
+        ("/ws/app/node_modules/@ws/lib/dir/index.js", ""),
+    ]);
+    let node_modules = ResolverGeneric::new_with_file_system(
+        fs,
+        ResolveOptions { alias_fields: vec![vec!["browser".into()]], ..ResolveOptions::default() },
+    );
+    let tsconfig_paths = node_modules.clone_with_options(ResolveOptions {
+        alias_fields: vec![vec!["browser".into()]],
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: PathBuf::from("/ws/app/tsconfig.json"),
+            references: TsconfigReferences::Disabled,
+        })),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("@ws/lib", "browser.js"),
+        ("@ws/lib/index", "browser.js"),
+        ("@ws/lib/debug", "noop.js"),
+        ("@ws/lib/debug.js", "noop.js"),
+        ("@ws/lib/noop", "noop.js"),
+        ("@ws/lib/dir", "noop.js"),
+        ("@ws/lib/dir/", "noop.js"),
+    ];
+
+    for (request, expected) in pass {
+        let resolution = node_modules.resolve("/ws/app", request).map(|r| r.full_path());
+        let expected_path = PathBuf::from("/ws/app/node_modules/@ws/lib").join(expected);
+        assert_eq!(resolution, Ok(expected_path), "node_modules {request}");
+        let resolution = tsconfig_paths.resolve("/ws/app", request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(PathBuf::from("/ws/lib").join(expected)), "paths {request}");
+    }
+
+    // The directory itself is looked up in the browser field even when extensions are enforced.
+    let enforce_extension = |resolver: &ResolverGeneric<MemoryFS>| {
+        resolver.clone_with_options(ResolveOptions {
+            enforce_extension: EnforceExtension::Enabled,
+            ..resolver.options().clone()
+        })
+    };
+    let resolution = enforce_extension(&node_modules).resolve("/ws/app", "@ws/lib/dir");
+    assert_eq!(
+        resolution.map(|r| r.full_path()),
+        Ok(PathBuf::from("/ws/app/node_modules/@ws/lib/noop.js"))
+    );
+    let resolution = enforce_extension(&tsconfig_paths).resolve("/ws/app", "@ws/lib/dir");
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(PathBuf::from("/ws/lib/noop.js")));
+}
+
+// Not part of enhanced-resolve.
+// tsc only falls back to `baseUrl` when no `paths` pattern matched,
+// opt-in via `tsconfig_paths_strict_fallback`.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn paths_strict_fallback() {
+    use std::path::PathBuf;
+
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveError, ResolverGeneric};
+
+    let fs = MemoryFS::new(&[
+        (
+            "/app/tsconfig.json",
+            r#"{ "compilerOptions": { "baseUrl": "./src", "paths": { "bar/*": ["./test/*"] } } }"#,
+        ),
+        ("/app/src/test/found.js", ""),
+        // Only reachable through `baseUrl`, the `bar/*` candidate `src/test/hi` is missing.
+        ("/app/src/bar/hi.js", ""),
+        ("/app/src/foo.js", ""),
+    ]);
+    let options = ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: PathBuf::from("/app/tsconfig.json"),
+            references: TsconfigReferences::Disabled,
+        })),
+        ..ResolveOptions::default()
+    };
+    let lenient = ResolverGeneric::new_with_file_system(fs, options.clone());
+    let strict = lenient
+        .clone_with_options(ResolveOptions { tsconfig_paths_strict_fallback: true, ..options });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("matched pattern", "bar/found", "/app/src/test/found.js"),
+        ("unmatched specifier uses baseUrl", "foo", "/app/src/foo.js"),
+    ];
+    for (comment, request, expected) in pass {
+        for resolver in [&lenient, &strict] {
+            let resolution = resolver.resolve("/app", request).map(|r| r.full_path());
+            assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {request}");
+        }
+    }
+
+    // Matched but missing.
+    let resolution = lenient.resolve("/app", "bar/hi").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/src/bar/hi.js")));
+    let resolution = strict.resolve("/app", "bar/hi").map(|r| r.full_path());
+    assert_eq!(resolution, Err(ResolveError::NotFound("bar/hi".into())));
+}
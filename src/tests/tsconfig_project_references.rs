@@ -1,7 +1,8 @@
 //! Tests for tsconfig project references
 
 use crate::{
-    ResolveError, ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
+    MissingProjectReference, ProjectGraphReport, ResolveError, ResolveOptions, Resolver,
+    TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
 };
 
 #[test]
@@ -376,3 +377,51 @@ fn solution_style_nested_non_ts_walks_up() {
     // config for it (it would land in an inferred project).
     assert!(resolver.find_tsconfig(f.join("src/feature/legacy.js")).unwrap().is_none());
 }
+
+#[test]
+fn validate_project() {
+    let f = super::fixture_root().join("tsconfig/cases");
+    let resolver = Resolver::new(ResolveOptions::default());
+
+    let report = resolver.validate_tsconfig_project(f.join("references-composite")).unwrap();
+    let expected = ProjectGraphReport {
+        references: vec![
+            f.join("references-composite/app/tsconfig.json"),
+            f.join("references-composite/core/tsconfig.json"),
+        ],
+        ..ProjectGraphReport::default()
+    };
+    assert_eq!(report, expected);
+
+    let report = resolver.validate_tsconfig_project(f.join("references-not-found")).unwrap();
+    let missing = MissingProjectReference {
+        path: "./nonexistent".into(),
+        referenced_by: f.join("references-not-found/tsconfig.json"),
+    };
+    assert_eq!(
+        report,
+        ProjectGraphReport { missing: vec![missing], ..ProjectGraphReport::default() }
+    );
+
+    let report = resolver.validate_tsconfig_project(f.join("references-cycle")).unwrap();
+    let a = f.join("references-cycle/a/tsconfig.json");
+    let b = f.join("references-cycle/b/tsconfig.json");
+    let expected = ProjectGraphReport {
+        references: vec![a.clone(), b.clone()],
+        cycles: vec![vec![a, b]],
+        ..ProjectGraphReport::default()
+    };
+    assert_eq!(report, expected);
+
+    let report = resolver.validate_tsconfig_project(f.join("references-self")).unwrap();
+    let root = f.join("references-self/tsconfig.json");
+    assert_eq!(report.cycles, vec![vec![root.clone()]]);
+    assert_eq!(report.non_composite, vec![root]);
+
+    let report = resolver.validate_tsconfig_project(f.join("references-multi")).unwrap();
+    assert_eq!(report.references, report.non_composite);
+    assert_eq!(report.references.len(), 2);
+
+    let error = resolver.validate_tsconfig_project(f.join("nonexistent")).unwrap_err();
+    assert_eq!(error, ResolveError::TsconfigNotFound(f.join("nonexistent")));
+}
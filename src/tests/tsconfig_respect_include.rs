@@ -0,0 +1,70 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{
+    ResolveError, ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
+};
+
+fn fixture() -> PathBuf {
+    super::fixture_root().join("tsconfig/cases/respect-include")
+}
+
+fn resolver(respect_include: bool) -> Resolver {
+    Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: fixture().join("tsconfig.json"),
+            references: TsconfigReferences::Disabled,
+        })),
+        tsconfig_respect_include: respect_include,
+        extensions: vec![".ts".into(), ".js".into()],
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn respect_include() {
+    let f = fixture();
+    let resolver = resolver(true);
+
+    #[rustfmt::skip]
+    let data = [
+        ("src", "@/utils", Ok("src/utils.ts")),
+        ("src/app", "@/utils", Ok("src/utils.ts")),
+        ("src/app", "src/utils", Ok("src/utils.ts")),
+        ("src/app", "shared", Ok("shared/index.ts")),
+        ("scripts", "@/utils", Err("@/utils")),
+        ("scripts", "src/utils", Err("src/utils")),
+        ("scripts", "shared", Ok("node_modules/shared/index.js")),
+        ("src/fixtures", "@/utils", Err("@/utils")),
+    ];
+
+    // Twice, the second time from the cached coverage.
+    for _ in 0..2 {
+        for (directory, request, expected) in data {
+            let resolution = resolver.resolve(f.join(directory), request).map(|r| r.full_path());
+            let expected = expected
+                .map(|path| f.join(path))
+                .map_err(|request| ResolveError::NotFound(request.into()));
+            assert_eq!(resolution, expected, "{directory} {request}");
+        }
+    }
+}
+
+#[test]
+fn disabled() {
+    let f = fixture();
+    let resolver = resolver(false);
+
+    #[rustfmt::skip]
+    let data = [
+        ("scripts", "@/utils", "src/utils.ts"),
+        ("scripts", "shared", "shared/index.ts"),
+        ("src/fixtures", "@/utils", "src/utils.ts"),
+    ];
+
+    for (directory, request, expected) in data {
+        let resolution = resolver.resolve(f.join(directory), request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(f.join(expected)), "{directory} {request}");
+    }
+}
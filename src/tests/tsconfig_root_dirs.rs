@@ -118,3 +118,21 @@ fn test_root_dirs_via_extends() {
     let expected = f.join(".svelte-kit/types/src/routes/$types.d.ts");
     assert_eq!(path, Ok(expected), "from {from:?} resolve ./$types");
 }
+
+#[test]
+fn test_root_dirs_auto_discovery() {
+    let f = super::fixture_root().join("tsconfig/cases/root-dirs");
+    let options = ResolveOptions { extensions: vec![".ts".into()], ..ResolveOptions::default() };
+    let from = f.join("src/index.ts");
+
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Auto),
+        ..options.clone()
+    });
+    let path = resolver.resolve_file(&from, "./foo").map(|r| r.full_path());
+    assert_eq!(path, Ok(f.join("generated/foo.ts")));
+
+    // rootDirs are not applied without the tsconfig option.
+    let resolver = Resolver::new(options);
+    assert!(matches!(resolver.resolve_file(&from, "./foo"), Err(ResolveError::NotFound(_))));
+}
@@ -0,0 +1,70 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use semver::Version;
+
+use crate::{ResolveOptions, Resolver};
+
+fn fixture() -> PathBuf {
+    super::fixture_root().join("types-versions")
+}
+
+fn resolver(typescript_version: Option<&str>) -> Resolver {
+    Resolver::new(ResolveOptions {
+        extensions: vec![".js".into(), ".d.ts".into()],
+        typescript_version: typescript_version.map(|version| Version::parse(version).unwrap()),
+        ..ResolveOptions::default()
+    })
+}
+
+#[test]
+fn types_versions() {
+    #[rustfmt::skip]
+    let data = [
+        ("not applied without a version", None, "pkg", "node_modules/pkg/index.js"),
+        ("not applied without a version", None, "pkg/sub", "node_modules/pkg/sub.js"),
+        ("matching range", Some("5.4.2"), "pkg", "node_modules/pkg/ts4.2/index.d.ts"),
+        ("matching range", Some("5.4.2"), "pkg/sub", "node_modules/pkg/ts4.2/sub.d.ts"),
+        ("prerelease", Some("4.2.0-beta"), "pkg/sub", "node_modules/pkg/ts4.2/sub.d.ts"),
+        ("fallback range", Some("4.1.0"), "pkg", "node_modules/pkg/ts-old/index.d.ts"),
+        ("fallback range", Some("4.1.0"), "pkg/sub", "node_modules/pkg/ts-old/sub.d.ts"),
+        ("exports take precedence", Some("5.4.2"), "exported", "node_modules/exported/index.js"),
+    ];
+
+    let f = fixture();
+    for (comment, typescript_version, specifier, expected) in data {
+        let resolved_path =
+            resolver(typescript_version).resolve(&f, specifier).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(f.join(expected)), "{comment} {specifier}");
+    }
+}
+
+#[test]
+fn resolve_dts() {
+    #[rustfmt::skip]
+    let data = [
+        ("first entry without a version", None, "node_modules/pkg/ts4.2/sub.d.ts"),
+        ("matching range", Some("5.4.2"), "node_modules/pkg/ts4.2/sub.d.ts"),
+        ("fallback range", Some("4.1.0"), "node_modules/pkg/ts-old/sub.d.ts"),
+    ];
+
+    let f = fixture();
+    for (comment, typescript_version, expected) in data {
+        let resolved_path = resolver(typescript_version)
+            .resolve_dts(f.join("index.ts"), "pkg/sub")
+            .map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(f.join(expected)), "{comment}");
+    }
+}
+
+#[test]
+fn package_json() {
+    let resolver = resolver(None);
+    let (_, package_json) = resolver.resolve_package_root(fixture(), "pkg").unwrap();
+    let types_versions = package_json.types_versions().unwrap();
+    let ranges = types_versions.iter().map(|(range, _)| range).collect::<Vec<_>>();
+    assert_eq!(ranges, [">=4.2", "*"]);
+    let paths = types_versions.paths_for(Some(&Version::new(4, 0, 0))).unwrap();
+    assert_eq!(paths, &[("*".to_string(), vec!["ts-old/*".to_string()])]);
+}
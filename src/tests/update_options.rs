@@ -0,0 +1,116 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use crate::{
+    AliasValue, ResolveError, ResolveOptions, Resolver, TsconfigDiscovery, TsconfigOptions,
+    TsconfigReferences,
+};
+
+fn target_alias(path: &Path) -> Vec<(String, Vec<AliasValue>)> {
+    vec![("target".into(), vec![AliasValue::Path(path.to_string_lossy().into())])]
+}
+
+#[test]
+fn update_options() {
+    let f = super::fixture_root().join("update-options");
+    let (old, new) = (f.join("old.js"), f.join("new.js"));
+    let resolver =
+        Resolver::new(ResolveOptions { alias: target_alias(&old), ..ResolveOptions::default() });
+    assert_eq!(resolver.resolve(&f, "target").map(|r| r.full_path()), Ok(old));
+
+    resolver.update_options(|options| options.alias = target_alias(&new));
+    assert_eq!(resolver.resolve(&f, "target").map(|r| r.full_path()), Ok(new.clone()));
+    assert_eq!(resolver.options().alias, target_alias(&new));
+
+    // Updates build on the current options.
+    resolver.update_options(|options| options.main_files = vec!["main".into()]);
+    assert_eq!(resolver.options().alias, target_alias(&new));
+    assert_eq!(
+        resolver.resolve(&f, "./lib").map(|r| r.full_path()),
+        Err(ResolveError::NotFound("./lib".into()))
+    );
+
+    // Clones of an updated resolver start from the current options.
+    let cloned = resolver.clone_with_options(resolver.options().clone());
+    assert_eq!(cloned.resolve(&f, "target").map(|r| r.full_path()), Ok(new));
+}
+
+// Resolutions racing with an update see either the old or the new options, never a mix.
+#[test]
+fn update_options_concurrently() {
+    let f = super::fixture_root().join("update-options");
+    let (old, new) = (f.join("old.js"), f.join("new.js"));
+    let resolver = Resolver::new(ResolveOptions {
+        alias: target_alias(&old),
+        fallback: target_alias(&old),
+        ..ResolveOptions::default()
+    });
+    let updated = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let readers = (0..2)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut seen_new = false;
+                    loop {
+                        let done = updated.load(Ordering::Acquire);
+                        let path = resolver.resolve(&f, "target").unwrap().full_path();
+                        if path == new {
+                            seen_new = true;
+                        } else {
+                            assert_eq!(path, old);
+                            assert!(!seen_new, "resolved with old options after new ones");
+                        }
+                        if done {
+                            assert!(seen_new);
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        scope.spawn(|| {
+            thread::yield_now();
+            resolver.update_options(|options| {
+                options.alias = target_alias(&new);
+                options.fallback = target_alias(&new);
+            });
+            updated.store(true, Ordering::Release);
+        });
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    });
+}
+
+// The manual tsconfig is memoized for its config file, not for the resolver sharing the cache.
+#[test]
+fn update_manual_tsconfig() {
+    let f = super::fixture_root().join("tsconfig");
+    let manual = |case: &str| {
+        Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: f.join("cases").join(case),
+            references: TsconfigReferences::Disabled,
+        }))
+    };
+    let resolver =
+        Resolver::new(ResolveOptions { tsconfig: manual("index"), ..ResolveOptions::default() });
+    let index = f.join("node_modules/tsconfig-index/foo.js");
+    let extends = f.join("cases/extends-multiple/foo.js");
+    assert_eq!(resolver.resolve(&f, "foo").map(|r| r.full_path()), Ok(index.clone()));
+
+    let cloned = resolver.clone_with_options(ResolveOptions {
+        tsconfig: manual("extends-multiple"),
+        ..ResolveOptions::default()
+    });
+    assert_eq!(cloned.resolve(&f, "foo").map(|r| r.full_path()), Ok(extends.clone()));
+    assert_eq!(resolver.resolve(&f, "foo").map(|r| r.full_path()), Ok(index));
+
+    resolver.update_options(|options| options.tsconfig = manual("extends-multiple"));
+    assert_eq!(resolver.resolve(&f, "foo").map(|r| r.full_path()), Ok(extends));
+}
@@ -0,0 +1,89 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{AliasValue, ModuleType, ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn unsupported() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let data = [
+        ("http", "http://example.com/index.js"),
+        ("https", "https://example.com/index.js"),
+        ("jsr", "jsr:@std/path"),
+        ("npm", "npm:lodash@4"),
+    ];
+
+    for (scheme, specifier) in data {
+        let resolved_path = resolver.resolve(&f, specifier);
+        let error = ResolveError::UnsupportedUrlScheme {
+            scheme: scheme.to_string(),
+            specifier: specifier.to_string(),
+        };
+        assert_eq!(resolved_path, Err(error), "{specifier}");
+    }
+}
+
+#[test]
+fn alias() {
+    let f = super::fixture();
+    let resolver = Resolver::new(ResolveOptions {
+        alias: vec![("https://example.com/a.js".into(), vec![AliasValue::from("./a.js")])],
+        fallback: vec![("https://example.com/b.js".into(), vec![AliasValue::Ignore])],
+        ..ResolveOptions::default()
+    });
+
+    // Aliases are applied before the scheme is rejected.
+    let resolved_path = resolver.resolve(&f, "https://example.com/a.js").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("a.js")));
+
+    let resolved_path = resolver.resolve(&f, "https://example.com/b.js");
+    assert_eq!(resolved_path, Err(ResolveError::Ignored(f.join("https://example.com/b.js"))));
+}
+
+#[test]
+fn data_url() {
+    let f = super::fixture();
+    let resolver = Resolver::new(ResolveOptions { module_type: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let data = [
+        ("data:text/javascript,export default 42", Some(ModuleType::Module)),
+        ("data:application/json,{\"a\":1}", Some(ModuleType::Json)),
+        ("data:application/wasm;base64,AGFzbQEAAAA=", Some(ModuleType::Wasm)),
+        ("data:text/plain,hello", None),
+    ];
+
+    for (specifier, module_type) in data {
+        let resolution = resolver.resolve(&f, specifier).unwrap();
+        assert_eq!(resolution.module_type(), module_type, "{specifier}");
+        assert!(resolution.package_json().is_none(), "{specifier}");
+        // The path is stable for a URL and does not exist.
+        assert_eq!(resolver.resolve(&f, specifier).unwrap().path(), resolution.path());
+        assert!(!resolution.path().exists(), "{specifier}");
+    }
+
+    let a = resolver.resolve(&f, "data:text/javascript,export default 1").unwrap();
+    let b = resolver.resolve(&f, "data:text/javascript,export default 2").unwrap();
+    assert_ne!(a.path(), b.path());
+
+    // A trailing fragment is not part of the URL's body, as in Node.js.
+    let resolution = resolver.resolve(&f, "data:text/javascript,export default 1#a").unwrap();
+    assert_eq!(resolution.path(), a.path());
+    assert_eq!(resolution.fragment(), Some("#a"));
+
+    // `?` and `#` inside the body are part of it.
+    let bodies = ["export default 'a?1'", "export default 'a?2'", "export default 'a#1'"];
+    let resolutions =
+        bodies.map(|body| resolver.resolve(&f, &format!("data:text/javascript,{body}")).unwrap());
+    for resolution in &resolutions {
+        assert_eq!(resolution.query(), None);
+        assert_eq!(resolution.fragment(), None);
+    }
+    assert_ne!(resolutions[0].path(), resolutions[1].path());
+    assert_ne!(resolutions[0].path(), resolutions[2].path());
+
+    let resolved_path = resolver.resolve(&f, "data:text/javascript");
+    assert_eq!(resolved_path, Err(ResolveError::DataUrl("data:text/javascript".into())));
+}
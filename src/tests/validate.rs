@@ -0,0 +1,252 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use crate::{
+    AliasValue, DiagnosticSeverity, EnforceExtension, MemoryFileSystem, ResolveOptions, Resolver,
+    ResolverBuilder, Restriction, TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
+};
+
+fn diagnostics(options: &ResolveOptions) -> Vec<(DiagnosticSeverity, Vec<&'static str>)> {
+    options.validate().into_iter().map(|d| (d.severity, d.fields)).collect()
+}
+
+#[test]
+fn valid() {
+    assert_eq!(ResolveOptions::default().validate(), vec![]);
+    let options = ResolveOptions {
+        alias: vec![
+            ("foo$".into(), vec![AliasValue::from("./foo")]),
+            ("foo".into(), vec![AliasValue::from("./bar")]),
+        ],
+        alias_fields: vec![vec!["browser".into()]],
+        main_fields: vec!["browser".into(), "main".into()],
+        condition_names: vec!["node".into(), "import".into()],
+        extensions: vec![".js".into(), String::new()],
+        enforce_extension: EnforceExtension::Enabled,
+        roots: vec![PathBuf::from("/public")],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(options.validate(), vec![]);
+}
+
+#[test]
+fn extensions() {
+    use DiagnosticSeverity::Error;
+
+    let options = ResolveOptions { extensions: vec!["js".into()], ..ResolveOptions::default() };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["extensions"])]);
+    let diagnostic = &options.validate()[0];
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("use \".js\""));
+    assert_eq!(
+        diagnostic.to_string(),
+        "error [extensions]: Extension \"js\" does not start with a dot (use \".js\")"
+    );
+
+    let options = ResolveOptions {
+        extensions: vec![],
+        enforce_extension: EnforceExtension::Enabled,
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["enforce_extension", "extensions"])]);
+
+    let options = ResolveOptions {
+        fully_specified: true,
+        enforce_extension: EnforceExtension::Enabled,
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["fully_specified", "enforce_extension"])]);
+}
+
+#[test]
+fn empty_extension() {
+    let options = ResolveOptions { extensions: vec![".".into()], ..ResolveOptions::default() };
+    assert_eq!(diagnostics(&options), vec![(DiagnosticSeverity::Error, vec!["extensions"])]);
+}
+
+#[test]
+fn prefer_relative_and_absolute() {
+    let options = ResolveOptions {
+        prefer_relative: true,
+        prefer_absolute: true,
+        ..ResolveOptions::default()
+    };
+    assert_eq!(
+        diagnostics(&options),
+        vec![(DiagnosticSeverity::Warning, vec!["prefer_relative", "prefer_absolute"])]
+    );
+}
+
+#[test]
+fn resolve_to_context() {
+    let options = ResolveOptions {
+        resolve_to_context: true,
+        extension_alias: vec![(".js".into(), vec![".ts".into()])],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(
+        diagnostics(&options),
+        vec![(DiagnosticSeverity::Warning, vec!["resolve_to_context", "extension_alias"])]
+    );
+}
+
+#[test]
+fn fields() {
+    use DiagnosticSeverity::Warning;
+
+    let options =
+        ResolveOptions { alias_fields: vec![vec!["browser".into()]], ..ResolveOptions::default() };
+    assert_eq!(diagnostics(&options), vec![(Warning, vec!["alias_fields", "main_fields"])]);
+
+    // A nested field can not be a main field.
+    let options = ResolveOptions {
+        alias_fields: vec![vec!["config".into(), "browser".into()]],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![]);
+
+    let options = ResolveOptions {
+        condition_names: vec!["import".into(), "default".into()],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Warning, vec!["condition_names"])]);
+}
+
+#[test]
+fn paths() {
+    use DiagnosticSeverity::{Error, Warning};
+
+    let options = ResolveOptions {
+        restrictions: vec![Restriction::Path(PathBuf::new())],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["restrictions"])]);
+
+    let options = ResolveOptions {
+        restrictions: vec![Restriction::Path(PathBuf::from("src"))],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["restrictions"])]);
+
+    let options = ResolveOptions { roots: vec!["public".into()], ..ResolveOptions::default() };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["roots"])]);
+
+    let options = ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: PathBuf::from("/missing/tsconfig"),
+            references: TsconfigReferences::Disabled,
+        })),
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["tsconfig"])]);
+    // A directory holding the tsconfig.
+    let options = ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: super::fixture_root().join("tsconfig"),
+            references: TsconfigReferences::Disabled,
+        })),
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![]);
+    // The directory is looked up in the file system of the resolver.
+    let options = ResolveOptions {
+        tsconfig: Some(TsconfigDiscovery::Manual(TsconfigOptions {
+            config_file: PathBuf::from("/app/tsconfig"),
+            references: TsconfigReferences::Disabled,
+        })),
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Error, vec!["tsconfig"])]);
+    let file_system = MemoryFileSystem::new([("/app/tsconfig/tsconfig.json", "{}")]);
+    assert_eq!(options.validate_with_file_system(&file_system), vec![]);
+    ResolverBuilder::new(options).build_with_file_system(file_system).unwrap();
+
+    let tsconfig = Some(TsconfigDiscovery::Manual(TsconfigOptions {
+        config_file: PathBuf::from("tsconfig.json"),
+        references: TsconfigReferences::Disabled,
+    }));
+    let options = ResolveOptions { tsconfig: tsconfig.clone(), ..ResolveOptions::default() };
+    assert_eq!(diagnostics(&options), vec![(Warning, vec!["tsconfig", "cwd"])]);
+    let options =
+        ResolveOptions { tsconfig, cwd: Some(PathBuf::from("/")), ..ResolveOptions::default() };
+    assert_eq!(diagnostics(&options), vec![]);
+}
+
+#[test]
+fn alias() {
+    use DiagnosticSeverity::Warning;
+
+    let options = ResolveOptions {
+        alias: vec![
+            ("foo".into(), vec![AliasValue::from("./foo")]),
+            ("foo$".into(), vec![AliasValue::from("./bar")]),
+            ("bar".into(), vec![AliasValue::Ignore]),
+        ],
+        fallback: vec![
+            ("bar".into(), vec![AliasValue::from("./bar")]),
+            ("bar".into(), vec![AliasValue::from("./baz")]),
+        ],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(diagnostics(&options), vec![(Warning, vec!["alias"]), (Warning, vec!["fallback"])]);
+    let messages = options.validate().into_iter().map(|d| d.message).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Key \"foo$\" is shadowed by the earlier key \"foo\"",
+            "Key \"bar\" is declared more than once, only the first entry is used",
+        ]
+    );
+}
+
+#[test]
+fn empty_alias_key() {
+    let options = ResolveOptions {
+        alias: vec![(String::new(), vec![AliasValue::from("./foo")])],
+        fallback: vec![("$".into(), vec![AliasValue::from("./foo")])],
+        ..ResolveOptions::default()
+    };
+    assert_eq!(
+        diagnostics(&options),
+        vec![
+            (DiagnosticSeverity::Error, vec!["alias"]),
+            (DiagnosticSeverity::Error, vec!["fallback"])
+        ]
+    );
+}
+
+#[test]
+fn resolver_builder() {
+    let f = super::fixture();
+
+    let resolver = ResolverBuilder::new(ResolveOptions::default()).build().unwrap();
+    assert_eq!(resolver.resolve(&f, "./a").map(|r| r.full_path()), Ok(f.join("a.js")));
+
+    // Warnings are accepted.
+    let options = ResolveOptions {
+        prefer_relative: true,
+        prefer_absolute: true,
+        ..ResolveOptions::default()
+    };
+    let builder = ResolverBuilder::new(options);
+    assert_eq!(builder.warnings().len(), 1);
+    assert!(builder.build().is_ok_and(|resolver| resolver.options().prefer_relative));
+
+    let options = ResolveOptions {
+        extensions: vec!["js".into(), ".".into()],
+        roots: vec!["public".into()],
+        prefer_relative: true,
+        prefer_absolute: true,
+        ..ResolveOptions::default()
+    };
+    let errors = ResolverBuilder::new(options.clone()).build().unwrap_err();
+    let fields = errors.iter().map(|error| error.fields.clone()).collect::<Vec<_>>();
+    assert_eq!(fields, [vec!["extensions"], vec!["extensions"], vec!["roots"]]);
+    assert!(errors.iter().all(|error| error.severity == DiagnosticSeverity::Error));
+
+    // `Resolver::new` uses the options as given.
+    let options = ResolveOptions { extensions: vec![".js".into(), ".".into()], ..options };
+    let resolver = Resolver::new(options);
+    assert_eq!(resolver.options().roots, [PathBuf::from("public")]);
+    assert_eq!(resolver.resolve(&f, "./a").map(|r| r.full_path()), Ok(f.join("a.js")));
+}
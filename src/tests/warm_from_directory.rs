@@ -0,0 +1,39 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveError, Resolver};
+
+#[test]
+fn warm() {
+    let app = super::fixture_root().join("warm-from-directory/app");
+    let resolver = Resolver::default();
+
+    // 5 directories, the symlink and 5 files, and the two valid package.json files.
+    // The linked directory is not walked into and the invalid package.json is not counted.
+    assert_eq!(resolver.warm_from_directory(&app), Ok(13));
+    assert_eq!(resolver.warm_from_directory(&app), Ok(0));
+
+    resolver.enable_cache_statistics();
+    resolver.reset_cache_statistics();
+    resolver.resolve(app.join("src"), "pkg").unwrap();
+    resolver.resolve(app.join("src"), "./index.js").unwrap();
+    let statistics = resolver.cache_statistics();
+    assert_eq!(statistics.package_json_misses, 0);
+}
+
+#[test]
+fn missing_root() {
+    let f = super::fixture_root().join("warm-from-directory");
+    let resolver = Resolver::default();
+    let missing = resolver.warm_from_directory(f.join("missing"));
+    assert!(matches!(missing, Err(ResolveError::IOError(_))));
+    let file = resolver.warm_from_directory(f.join("app/package.json"));
+    assert!(matches!(file, Err(ResolveError::IOError(_))));
+}
+
+#[test]
+fn file_system_os() {
+    let f = super::fixture().join("extensions");
+    let resolver = Resolver::default();
+    assert!(resolver.warm_from_directory(&f).unwrap() > 0);
+    assert_eq!(resolver.warm_from_directory(&f), Ok(0));
+}
@@ -0,0 +1,33 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::path::PathBuf;
+
+use rustc_hash::FxHashSet;
+
+use crate::{ResolveContext, Resolver, WatchTargets};
+
+fn paths(paths: &[PathBuf]) -> FxHashSet<PathBuf> {
+    paths.iter().cloned().collect()
+}
+
+#[test]
+fn missing() {
+    let f = super::fixture_root().join("watch");
+    let app = f.join("app");
+    let resolver = Resolver::default();
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context(&app, "missing", None, &mut ctx).unwrap_err();
+    assert!(ctx.missing_dependencies.contains(&app.join("node_modules/missing")));
+    assert!(ctx.missing_dependencies.contains(&f.join("node_modules")));
+
+    let targets = ctx.watch_targets(&resolver);
+    assert!(targets.directories.is_superset(&paths(&[app.join("node_modules"), f])));
+    assert!(!targets.directories.contains(&app.join("node_modules/missing")));
+
+    // Nested missing directories are watched from the closest existing ancestor.
+    let mut ctx = ResolveContext::default();
+    resolver.resolve_with_context(&app, "./src/lib/util", None, &mut ctx).unwrap_err();
+    assert_eq!(ctx.watch_targets(&resolver).directories, paths(&[app]));
+
+    assert_eq!(ResolveContext::default().watch_targets(&resolver), WatchTargets::default());
+}
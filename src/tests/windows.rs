@@ -7,7 +7,7 @@ use std::{
 
 use thiserror::Error;
 
-use crate::{ResolveOptions, Resolver};
+use crate::{ResolveError, ResolveOptions, Resolver};
 
 /// Converts a Win32 drive letter or mounted folder into DOS device path, e.g.:
 /// `\\?\Volume{GUID}\`
@@ -109,3 +109,22 @@ fn forward_slash_path_resolved_to_backslash() {
         .map(|r| r.into_path_buf().to_string_lossy().to_string());
     assert_eq!(resolved, Ok(expected), "symlinks: false");
 }
+
+#[test]
+fn prefixed_specifiers() {
+    let expected = super::fixture_root().join("enhanced-resolve").join("lib").join("index.js");
+    let path = expected.to_string_lossy().to_string();
+    let dir = super::fixture_root();
+    let resolver = Resolver::default();
+
+    for specifier in [format!(r"\\?\{path}"), format!(r"\\.\{path}"), format!("//?/{path}")] {
+        let resolved = resolver.resolve(&dir, &specifier).map(|r| r.into_path_buf());
+        assert_eq!(resolved, Ok(expected.clone()), "{specifier}");
+    }
+
+    // Drive-relative paths are relative to the current directory of the drive.
+    let drive = &path[..2];
+    let specifier = format!("{drive}index.js");
+    let resolved = resolver.resolve(&dir, &specifier);
+    assert_eq!(resolved, Err(ResolveError::PathNotSupported(PathBuf::from(specifier))));
+}
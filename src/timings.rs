@@ -0,0 +1,181 @@
+//! Phase durations and counters of resolutions, see [ResolveTimings] and [ResolveStats].
+
+use std::time::{Duration, Instant};
+
+/// Time spent in the phases of the resolutions made with a [crate::ResolveContext].
+///
+/// Collected only when [crate::ResolveContext::timings] is set before resolving, the durations
+/// are added to the ones already present.
+///
+/// A phase does not include the time of phases nested in it, e.g. reading a `package.json`
+/// during the `node_modules` walk counts towards [ResolveTimings::package_json_io] only.
+/// The sum of the phases is therefore at most [ResolveTimings::total], the difference being
+/// the time spent outside of any phase such as looking up the `browser` field.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResolveTimings {
+    /// Parsing the query and fragment of specifiers.
+    pub parse: Duration,
+
+    /// Matching tsconfig `compilerOptions.paths`.
+    pub tsconfig_paths: Duration,
+
+    /// Applying [crate::ResolveOptions::alias] and [crate::ResolveOptions::fallback].
+    pub alias: Duration,
+
+    /// Looking up packages in [crate::ResolveOptions::modules] and loading their entry points.
+    pub node_modules_walk: Duration,
+
+    /// Matching the `exports` field of packages.
+    pub exports_resolution: Duration,
+
+    /// Finding and reading `package.json` files.
+    pub package_json_io: Duration,
+
+    /// Resolving the symbolic links of resolved paths.
+    pub canonicalize: Duration,
+
+    /// Checking whether candidate files exist, e.g. the specifier with each of
+    /// [crate::ResolveOptions::extensions] appended.
+    pub file_probes: Duration,
+
+    /// The whole resolutions.
+    pub total: Duration,
+}
+
+impl ResolveTimings {
+    /// The sum of all phases, excluding [ResolveTimings::total].
+    #[must_use]
+    pub fn phases(&self) -> Duration {
+        self.parse
+            + self.tsconfig_paths
+            + self.alias
+            + self.node_modules_walk
+            + self.exports_resolution
+            + self.package_json_io
+            + self.canonicalize
+            + self.file_probes
+    }
+
+    const fn phase_mut(&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::Parse => &mut self.parse,
+            Phase::TsconfigPaths => &mut self.tsconfig_paths,
+            Phase::Alias => &mut self.alias,
+            Phase::NodeModulesWalk => &mut self.node_modules_walk,
+            Phase::ExportsResolution => &mut self.exports_resolution,
+            Phase::PackageJsonIo => &mut self.package_json_io,
+            Phase::Canonicalize => &mut self.canonicalize,
+            Phase::FileProbes => &mut self.file_probes,
+        }
+    }
+}
+
+/// Counts of the work done by the resolutions made with a [crate::ResolveContext].
+///
+/// Collected only when [crate::ResolveContext::stats] is set before resolving, see
+/// [crate::ResolveContext::collect_stats], the counts are added to the ones already present.
+/// The durations of the phases are in [crate::ResolveContext::timings].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResolveStats {
+    /// Metadata lookups of files and directories, whether answered from the cache or not.
+    /// [crate::CacheStatistics] tells how many went to the file system.
+    pub metadata_lookups: u64,
+
+    /// `package.json` files read and parsed, a cached `package.json` is not counted.
+    pub package_json_parses: u64,
+
+    /// Directories of [crate::ResolveOptions::modules] looked into for a package.
+    pub node_modules_directories: u64,
+
+    /// Times a specifier was matched against tsconfig `compilerOptions.paths`.
+    pub tsconfig_paths_calls: u64,
+
+    /// Times a specifier was matched against [crate::ResolveOptions::alias] or
+    /// [crate::ResolveOptions::fallback].
+    pub alias_calls: u64,
+
+    /// Times a package was looked up in [crate::ResolveOptions::modules].
+    pub node_modules_walk_calls: u64,
+
+    /// Times the `exports` field of a package was matched.
+    pub exports_resolution_calls: u64,
+}
+
+impl ResolveStats {
+    const fn counter_mut(&mut self, counter: Counter) -> &mut u64 {
+        match counter {
+            Counter::MetadataLookup => &mut self.metadata_lookups,
+            Counter::PackageJsonParse => &mut self.package_json_parses,
+            Counter::NodeModulesDirectory => &mut self.node_modules_directories,
+        }
+    }
+
+    /// Count an entry into `phase`.
+    pub(crate) const fn enter(&mut self, phase: Phase) {
+        let calls = match phase {
+            Phase::TsconfigPaths => &mut self.tsconfig_paths_calls,
+            Phase::Alias => &mut self.alias_calls,
+            Phase::NodeModulesWalk => &mut self.node_modules_walk_calls,
+            Phase::ExportsResolution => &mut self.exports_resolution_calls,
+            Phase::Parse | Phase::PackageJsonIo | Phase::Canonicalize | Phase::FileProbes => {
+                return;
+            }
+        };
+        *calls += 1;
+    }
+
+    pub(crate) const fn count(&mut self, counter: Counter) {
+        *self.counter_mut(counter) += 1;
+    }
+}
+
+/// A counter of [ResolveStats].
+#[derive(Debug, Clone, Copy)]
+pub enum Counter {
+    MetadataLookup,
+    PackageJsonParse,
+    NodeModulesDirectory,
+}
+
+/// A field of [ResolveTimings].
+#[derive(Debug, Clone, Copy)]
+pub enum Phase {
+    Parse,
+    TsconfigPaths,
+    Alias,
+    NodeModulesWalk,
+    ExportsResolution,
+    PackageJsonIo,
+    Canonicalize,
+    FileProbes,
+}
+
+/// A started [Phase], see [crate::context::ResolveContext::start_phase].
+///
+/// The phase is a `trace` level `tracing` span named `phase` until the timer is stopped.
+#[derive(Debug)]
+pub struct PhaseTimer {
+    phase: Phase,
+    start: Instant,
+    /// [ResolveTimings::phases] at the start, to exclude the nested phases.
+    phases: Duration,
+    _span: tracing::span::EnteredSpan,
+}
+
+impl PhaseTimer {
+    pub fn start(phase: Phase, timings: &ResolveTimings) -> Self {
+        let span = tracing::trace_span!("phase", phase = ?phase).entered();
+        Self { phase, start: Instant::now(), phases: timings.phases(), _span: span }
+    }
+
+    /// Add the time since the start, without the nested phases, to `timings`.
+    ///
+    /// Returns the time since the start including the nested phases.
+    pub fn stop(self, timings: &mut ResolveTimings) -> Duration {
+        let elapsed = self.start.elapsed();
+        let nested = timings.phases().saturating_sub(self.phases);
+        *timings.phase_mut(self.phase) += elapsed.saturating_sub(nested);
+        elapsed
+    }
+}
@@ -1,8 +1,9 @@
 use std::{
     borrow::Cow,
+    ffi::{OsStr, OsString},
     fmt::Debug,
     hash::BuildHasherDefault,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     sync::Arc,
 };
 
@@ -11,7 +12,9 @@ use indexmap::IndexMap;
 use rustc_hash::FxHasher;
 use serde::Deserialize;
 
-use crate::{TsconfigReferences, path::PathUtil, replace_bom_with_whitespace};
+use crate::{
+    TsconfigReferences, path::PathUtil, prefix_trie::PrefixTrie, replace_bom_with_whitespace,
+};
 
 /// Template variable `${configDir}` for substitution of config files
 /// directory path.
@@ -33,6 +36,32 @@ pub struct ProjectReference {
     pub path: PathBuf,
 }
 
+/// The reference graph of a tsconfig project, see [crate::ResolverImpl::validate_tsconfig_project].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProjectGraphReport {
+    /// Paths of the referenced tsconfigs, in the order they are first referenced.
+    pub references: Vec<PathBuf>,
+
+    /// References to tsconfigs which do not exist.
+    pub missing: Vec<MissingProjectReference>,
+
+    /// Each cycle of references, starting from the tsconfig which is referenced again.
+    pub cycles: Vec<Vec<PathBuf>>,
+
+    /// Referenced tsconfigs without `compilerOptions.composite` set to `true`.
+    pub non_composite: Vec<PathBuf>,
+}
+
+/// A project reference to a tsconfig which does not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingProjectReference {
+    /// The referenced path, relative to the directory of [MissingProjectReference::referenced_by].
+    pub path: PathBuf,
+
+    /// Path of the tsconfig containing the reference.
+    pub referenced_by: PathBuf,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TsConfig {
@@ -164,6 +193,11 @@ impl TsConfig {
         &self.path
     }
 
+    /// Whether this is a `jsconfig.json`, which defaults to `allowJs`.
+    pub(crate) fn is_jsconfig(&self) -> bool {
+        self.path.file_name().is_some_and(|name| name == "jsconfig.json")
+    }
+
     /// Directory to `tsconfig.json`.
     ///
     /// # Panics
@@ -203,6 +237,9 @@ impl TsConfig {
     }
 
     /// Inherits settings from the given tsconfig into `self`.
+    ///
+    /// Each field is only taken when `self` does not define it, so a field which is not defined
+    /// never erases an inherited value. `paths` is taken as a whole, its keys are not merged.
     #[expect(clippy::too_many_lines, reason = "field-by-field merge of every inherited setting")]
     pub(crate) fn extend_tsconfig(&mut self, tsconfig: &Self) {
         if self.files.is_none()
@@ -369,6 +406,12 @@ impl TsConfig {
         {
             compiler_options.check_js = Some(*check_js);
         }
+
+        if compiler_options.composite.is_none()
+            && let Some(composite) = &tsconfig.compiler_options.composite
+        {
+            compiler_options.composite = Some(*composite);
+        }
     }
 
     /// "Build" the root tsconfig, resolve:
@@ -434,6 +477,14 @@ impl TsConfig {
             *declaration_dir = config_dir.join(stripped_path.trim_start_matches('/'));
         }
 
+        self.compiler_options.package_paths =
+            self.compiler_options.paths.as_ref().and_then(package_paths);
+        self.compiler_options.compiled_package_paths = self
+            .compiler_options
+            .package_paths
+            .as_ref()
+            .map(|package_paths| Arc::new(CompiledTsconfigPaths::new(package_paths)));
+
         if let Some(paths_map) = &mut self.compiler_options.paths {
             // Substitute template variable in `tsconfig.compilerOptions.paths`.
             for paths in paths_map.values_mut() {
@@ -465,52 +516,61 @@ impl TsConfig {
         }
     }
 
-    /// Resolves the given `specifier` within project references and then [CompilerOptions::paths].
-    ///
-    /// `specifier` can be either a real path or an alias.
+    /// The tsconfig whose [CompilerOptions::paths] apply to `path`: the project reference
+    /// containing it, or this tsconfig.
     #[must_use]
-    pub(crate) fn resolve_references_then_self_paths(
-        &self,
-        path: &Path,
-        specifier: &str,
-    ) -> Vec<PathBuf> {
-        for tsconfig in &self.references_resolved {
-            if path.starts_with(&tsconfig.compiler_options.paths_base) {
-                return tsconfig.resolve_path_alias(specifier);
-            }
-        }
-        self.resolve_path_alias(specifier)
+    pub(crate) fn paths_owner(&self, path: &Path) -> &Self {
+        self.references_resolved
+            .iter()
+            .find(|tsconfig| path.starts_with(&tsconfig.compiler_options.paths_base))
+            .map_or(self, |tsconfig| tsconfig)
     }
 
     /// Resolves the given `specifier` within the project configured by this
     /// tsconfig.
     ///
     /// `specifier` is expected to be a path alias.
+    ///
+    /// Returns the candidates of the matched `paths` pattern, or `None` when no pattern matched,
+    /// which is when tsc falls back to `baseUrl`.
     // Copied from parcel
     // <https://github.com/parcel-bundler/parcel/blob/b6224fd519f95e68d8b93ba90376fd94c8b76e69/packages/utils/node-resolver-rs/src/tsconfig.rs#L93>
     #[must_use]
-    pub(crate) fn resolve_path_alias(&self, specifier: &str) -> Vec<PathBuf> {
+    pub(crate) fn resolve_path_alias(&self, specifier: &str) -> Option<Vec<PathBuf>> {
         if specifier.starts_with('.') {
-            return Vec::new();
+            return None;
         }
 
         let compiler_options = &self.compiler_options;
 
-        let Some(paths_map) = &compiler_options.paths else {
-            return vec![];
-        };
+        let paths_map = compiler_options.paths.as_ref()?;
 
         if let Some(paths) = paths_map.get(specifier) {
-            return paths.clone();
+            return Some(paths.clone());
         }
 
-        if let Some(compiled_paths) = &compiler_options.compiled_paths
-            && let Some(paths) = compiled_paths.resolve(specifier)
-        {
-            return paths;
-        }
+        compiler_options.compiled_paths.as_ref()?.resolve(specifier)
+    }
 
-        Vec::new()
+    /// The targets of the `paths` pattern matching `specifier` which name a package, e.g.
+    /// `lodash-es` for `"lodash": ["lodash-es"]`, with the wildcard substituted.
+    ///
+    /// [TsConfig::resolve_path_alias] returns them too, joined onto the base path.
+    #[must_use]
+    pub(crate) fn resolve_package_path_alias(&self, specifier: &str) -> Vec<PathBuf> {
+        let compiler_options = &self.compiler_options;
+        let Some(package_paths) = &compiler_options.package_paths else { return vec![] };
+        if specifier.starts_with('.') {
+            return vec![];
+        }
+        if let Some(paths) = package_paths.get(specifier) {
+            return paths.clone();
+        }
+        compiler_options
+            .compiled_package_paths
+            .as_ref()
+            .and_then(|compiled| compiled.resolve(specifier))
+            .unwrap_or_default()
     }
 
     pub(crate) fn resolve_base_url(&self, specifier: &str) -> Option<PathBuf> {
@@ -534,7 +594,7 @@ impl TsConfig {
     /// which resolves project references so the returned config is the one that owns the file.
     #[must_use]
     pub fn resolve_path_alias_or_base_url(&self, specifier: &str) -> Vec<PathBuf> {
-        let mut paths = self.resolve_path_alias(specifier);
+        let mut paths = self.resolve_path_alias(specifier).unwrap_or_default();
         if paths.is_empty()
             && let Some(base_url_path) = self.resolve_base_url(specifier)
         {
@@ -559,6 +619,15 @@ pub struct CompilerOptions {
     #[serde(skip)]
     compiled_paths: Option<Arc<CompiledTsconfigPaths>>,
 
+    /// The `paths` targets which name a package rather than a path, e.g. `lodash-es` or
+    /// `@scope/pkg/lib/*`, as written, keyed like [CompilerOptions::paths].
+    #[serde(skip)]
+    package_paths: Option<CompilerOptionsPathsMap>,
+
+    /// Pre-compiled wildcard patterns of [CompilerOptions::package_paths].
+    #[serde(skip)]
+    compiled_package_paths: Option<Arc<CompiledTsconfigPaths>>,
+
     /// The "base_url" at which this tsconfig is defined.
     #[serde(skip)]
     pub(crate) paths_base: PathBuf,
@@ -625,6 +694,9 @@ pub struct CompilerOptions {
 
     /// <https://www.typescriptlang.org/tsconfig/#checkJs>
     pub check_js: Option<bool>,
+
+    /// <https://www.typescriptlang.org/tsconfig/#composite>
+    pub composite: Option<bool>,
 }
 
 /// Value for the "extends" field.
@@ -688,6 +760,62 @@ impl TsConfig {
         self.is_file_included_in_tsconfig(path)
     }
 
+    /// Whether the `files`, `include` and `exclude` of this tsconfig cover files in `directory`,
+    /// see [crate::ResolveOptions::tsconfig_respect_include].
+    ///
+    /// File patterns such as `src/**/*.ts` cover the directories of their directory part, here
+    /// `src` and the directories below it. Only `exclude` patterns naming directories, such as
+    /// `scripts` or `**/fixtures`, exclude a directory.
+    #[must_use]
+    pub fn includes_directory(&self, directory: &Path) -> bool {
+        if self
+            .files
+            .as_ref()
+            .is_some_and(|files| files.iter().any(|file| file.parent() == Some(directory)))
+        {
+            return true;
+        }
+        let directory_str = to_forward_slashes(directory.to_string_lossy());
+        let is_included = self.include.as_ref().map_or_else(
+            || self.files.is_none() && directory.starts_with(self.directory()),
+            |patterns| {
+                patterns.iter().any(|pattern| {
+                    let pattern = to_forward_slashes(pattern.to_string_lossy());
+                    Self::is_include_directory_match(&pattern, directory_str.as_ref())
+                })
+            },
+        );
+        is_included
+            && self.exclude.as_ref().is_none_or(|patterns| {
+                !patterns.iter().any(|pattern| {
+                    let pattern = to_forward_slashes(pattern.to_string_lossy());
+                    let name = pattern.rsplit('/').next().unwrap_or(&pattern);
+                    !name.contains(['.', '*', '?'])
+                        && Self::is_directory_glob_match(&pattern, directory_str.as_ref())
+                })
+            })
+    }
+
+    /// Whether the `include` `pattern` covers files in `directory`.
+    fn is_include_directory_match(pattern: &str, directory: &str) -> bool {
+        let (parent, name) = pattern.rsplit_once('/').unwrap_or(("", pattern));
+        if !name.contains(['.', '*', '?']) {
+            return Self::is_directory_glob_match(pattern, directory);
+        }
+        // `src/**/*.ts` covers `src` and the directories below it.
+        if let Some(parent) = parent.strip_suffix("/**") {
+            return Self::is_directory_glob_match(parent, directory);
+        }
+        fast_glob::glob_match(parent, directory)
+    }
+
+    /// Whether the directory `pattern` matches `directory` or one of its ancestors.
+    fn is_directory_glob_match(pattern: &str, directory: &str) -> bool {
+        let pattern = pattern.trim_end_matches('/');
+        fast_glob::glob_match(pattern, directory)
+            || fast_glob::glob_match(format!("{pattern}/**").as_str(), directory)
+    }
+
     fn is_file_included_in_tsconfig(&self, path: &Path) -> bool {
         // 1. Check files array (highest priority - overrides exclude)
         if self.files.as_ref().is_some_and(|files| files.iter().any(|file| Path::new(file) == path))
@@ -793,11 +921,12 @@ impl TsConfig {
 #[derive(Clone, Debug, Default)]
 struct CompiledTsconfigPaths {
     wildcard_patterns: Vec<CompiledTsconfigPathPattern>,
+    /// Indices into `wildcard_patterns` by pattern prefix.
+    prefixes: PrefixTrie,
 }
 
 #[derive(Clone, Debug)]
 struct CompiledTsconfigPathPattern {
-    prefix: CompactString,
     suffix: CompactString,
     prefix_len: usize,
     suffix_len: usize,
@@ -807,13 +936,37 @@ struct CompiledTsconfigPathPattern {
 #[derive(Clone, Debug)]
 enum CompiledTsconfigPathTarget {
     Static(PathBuf),
-    Wildcard { prefix: CompactString, suffix: CompactString },
+    /// Kept as `OsString`, the prefix contains the tsconfig directory which may not be UTF-8.
+    Wildcard {
+        prefix: OsString,
+        suffix: OsString,
+    },
+}
+
+/// The package name targets of each `paths` pattern, `None` when there are none.
+///
+/// A target names a package when it is neither relative, absolute nor starts with
+/// `${configDir}`, e.g. `lodash-es` or `@scope/pkg/lib/*`.
+fn package_paths(paths_map: &CompilerOptionsPathsMap) -> Option<CompilerOptionsPathsMap> {
+    let is_package = |path: &PathBuf| {
+        matches!(path.components().next(), Some(Component::Normal(_)))
+            && !path.to_string_lossy().starts_with(TEMPLATE_VARIABLE)
+    };
+    paths_map.values().flatten().any(is_package).then(|| {
+        paths_map
+            .iter()
+            .map(|(key, paths)| {
+                (key.clone(), paths.iter().filter(|p| is_package(p)).cloned().collect())
+            })
+            .collect()
+    })
 }
 
 impl CompiledTsconfigPaths {
     fn new(paths_map: &CompilerOptionsPathsMap) -> Self {
         let mut wildcard_patterns =
             Vec::<CompiledTsconfigPathPattern>::with_capacity(paths_map.len());
+        let mut prefixes = PrefixTrie::default();
         for (key, paths) in paths_map {
             let Some((prefix, suffix)) = key.split_once('*') else {
                 continue;
@@ -821,46 +974,60 @@ impl CompiledTsconfigPaths {
             let targets = paths
                 .iter()
                 .map(|path| {
-                    let path_str = path.to_string_lossy();
-                    path_str.split_once('*').map_or_else(
+                    let bytes = path.as_os_str().as_encoded_bytes();
+                    bytes.iter().position(|&b| b == b'*').map_or_else(
                         || CompiledTsconfigPathTarget::Static(path.clone()),
-                        |(target_prefix, target_suffix)| CompiledTsconfigPathTarget::Wildcard {
-                            prefix: CompactString::new(target_prefix),
-                            suffix: CompactString::new(target_suffix),
+                        |index| {
+                            // SAFETY: `*` is ASCII, so the bytes around it are valid encoded bytes.
+                            let target_prefix =
+                                unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[..index]) };
+                            // SAFETY: as above.
+                            let target_suffix =
+                                unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[index + 1..]) };
+                            CompiledTsconfigPathTarget::Wildcard {
+                                prefix: target_prefix.to_os_string(),
+                                suffix: target_suffix.to_os_string(),
+                            }
                         },
                     )
                 })
                 .collect::<Vec<_>>();
-            let pattern = CompiledTsconfigPathPattern {
-                prefix: CompactString::new(prefix),
+            #[expect(clippy::cast_possible_truncation, reason = "less than u32::MAX paths")]
+            prefixes.insert(prefix.as_bytes(), wildcard_patterns.len() as u32);
+            wildcard_patterns.push(CompiledTsconfigPathPattern {
                 suffix: CompactString::new(suffix),
                 prefix_len: prefix.len(),
                 suffix_len: suffix.len(),
                 targets,
-            };
-
-            // Match longer prefixes first. Equal-length prefixes keep insertion order.
-            let index = wildcard_patterns
-                .iter()
-                .position(|existing| existing.prefix_len < pattern.prefix_len)
-                .unwrap_or(wildcard_patterns.len());
-            wildcard_patterns.insert(index, pattern);
+            });
         }
 
-        Self { wildcard_patterns }
+        Self { wildcard_patterns, prefixes }
     }
 
     fn resolve(&self, specifier: &str) -> Option<Vec<PathBuf>> {
-        self.wildcard_patterns.iter().find_map(|pattern| {
-            if !specifier.starts_with(pattern.prefix.as_str())
-                || !specifier.ends_with(pattern.suffix.as_str())
-                || specifier.len() < pattern.prefix_len + pattern.suffix_len
+        // Match longer prefixes first, prefixes are visited shortest first so the last hit wins.
+        // Equal-length prefixes are the same prefix, which keeps insertion order.
+        let mut best = None;
+        for indices in self.prefixes.prefixes_of(specifier.as_bytes()) {
+            if let Some(&index) = indices
+                .iter()
+                .find(|&&index| self.wildcard_patterns[index as usize].matches(specifier))
             {
-                return None;
+                best = Some(index);
             }
-            let wildcard = &specifier[pattern.prefix_len..specifier.len() - pattern.suffix_len];
-            Some(pattern.targets.iter().map(|target| target.resolve(wildcard)).collect())
-        })
+        }
+        let pattern = &self.wildcard_patterns[best? as usize];
+        let wildcard = &specifier[pattern.prefix_len..specifier.len() - pattern.suffix_len];
+        Some(pattern.targets.iter().map(|target| target.resolve(wildcard)).collect())
+    }
+}
+
+impl CompiledTsconfigPathPattern {
+    /// Whether `specifier`, which starts with the prefix of this pattern, matches the rest.
+    fn matches(&self, specifier: &str) -> bool {
+        specifier.ends_with(self.suffix.as_str())
+            && specifier.len() >= self.prefix_len + self.suffix_len
     }
 }
 
@@ -870,10 +1037,10 @@ impl CompiledTsconfigPathTarget {
             Self::Static(path) => path.clone(),
             Self::Wildcard { prefix, suffix } => {
                 let mut resolved =
-                    String::with_capacity(prefix.len() + wildcard.len() + suffix.len());
-                resolved.push_str(prefix.as_str());
-                resolved.push_str(wildcard);
-                resolved.push_str(suffix.as_str());
+                    OsString::with_capacity(prefix.len() + wildcard.len() + suffix.len());
+                resolved.push(prefix);
+                resolved.push(wildcard);
+                resolved.push(suffix);
                 PathBuf::from(resolved)
             }
         }
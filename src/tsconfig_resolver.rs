@@ -1,20 +1,34 @@
 use std::{
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
+use rustc_hash::FxHashSet;
+
 use crate::{
-    CachedPath, Ctx, ResolveError, ResolveOptions, ResolveResult, ResolverImpl, Specifier,
-    SpecifierError, TsConfig, TsconfigDiscovery, TsconfigOptions, TsconfigReferences,
-    path::PathUtil,
+    CachedPath, Ctx, MissingProjectReference, ProjectGraphReport, ResolveError, ResolveOptions,
+    ResolveResult, ResolverImpl, Specifier, SpecifierError, StepOutcome, TsConfig,
+    TsconfigDiscovery, TsconfigOptions, TsconfigReferences, path::PathUtil,
 };
 
 #[derive(Default)]
 pub struct TsconfigResolveContext {
     extended_configs: Vec<PathBuf>,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl TsconfigResolveContext {
+    /// Returns [ResolveError::Cancelled] once the cancellation token is set.
+    pub fn check_cancelled(&self) -> Result<(), ResolveError> {
+        if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err(ResolveError::Cancelled);
+        }
+        Ok(())
+    }
+
     pub fn with_extended_file<R, T: FnOnce(&mut Self) -> R>(&mut self, path: PathBuf, cb: T) -> R {
         self.extended_configs.push(path);
         let result = cb(self);
@@ -53,10 +67,14 @@ impl ResolverImpl {
         &self,
         path: P,
     ) -> Result<Option<Arc<TsConfig>>, ResolveError> {
-        let path = path.as_ref().to_string_lossy();
-        let specifier = Specifier::parse(path.as_ref()).map_err(ResolveError::Specifier)?;
-        let path = Path::new(specifier.path());
-        let cached_path = self.cache.value(path);
+        let path = path.as_ref();
+        // A query or fragment can only be stripped from a UTF-8 path, other paths are used as is.
+        let cached_path = if let Some(path) = path.to_str() {
+            let specifier = Specifier::parse(path).map_err(ResolveError::Specifier)?;
+            self.cache.value(Path::new(specifier.path()))
+        } else {
+            self.cache.value(path)
+        };
         self.find_tsconfig_tracing(&cached_path)
     }
 
@@ -112,22 +130,22 @@ impl ResolverImpl {
         let mut cache_value = Some(cached_path.clone());
         while let Some(cv) = cache_value {
             if let Some(tsconfig) = cv.tsconfig.get_or_try_init(|| {
-                let tsconfig_path = cv.path.join("tsconfig.json");
-                let tsconfig_path = self.cache.value(&tsconfig_path);
-                if self.is_file(&tsconfig_path, &mut ctx) {
-                    match self.resolve_tsconfig(tsconfig_path.path()) {
-                        Ok(tsconfig) => Ok(Some(tsconfig)),
-                        // Skip unreadable tsconfig files (e.g. permission denied)
-                        // and continue walking parent directories
-                        Err(ResolveError::TsconfigLoadFailed { ref source, .. })
-                            if matches!(source.as_ref(), ResolveError::IOError(_)) =>
-                        {
-                            Ok(None)
-                        }
-                        Err(e) => Err(e),
+                // `jsconfig.json` is ignored when there is a `tsconfig.json`.
+                let tsconfig_path = ["tsconfig.json", "jsconfig.json"]
+                    .into_iter()
+                    .map(|name| self.cache.value(&cv.path.join(name)))
+                    .find(|path| self.is_file_impl(path, &mut ctx));
+                let Some(tsconfig_path) = tsconfig_path else { return Ok(None) };
+                match self.resolve_tsconfig(tsconfig_path.path()) {
+                    Ok(tsconfig) => Ok(Some(tsconfig)),
+                    // Skip unreadable tsconfig files (e.g. permission denied)
+                    // and continue walking parent directories
+                    Err(ResolveError::TsconfigLoadFailed { ref source, .. })
+                        if matches!(source.as_ref(), ResolveError::IOError(_)) =>
+                    {
+                        Ok(None)
                     }
-                } else {
-                    Ok(None)
+                    Err(e) => Err(e),
                 }
             })? {
                 // Return the nearest tsconfig that owns the file (directly via
@@ -158,21 +176,23 @@ impl ResolverImpl {
         &self,
         tsconfig_options: &TsconfigOptions,
     ) -> Result<Option<Arc<TsConfig>>, ResolveError> {
-        // Cache the loaded tsconfig in /
-        self.cache
-            .value(Path::new("/"))
-            .tsconfig
-            .get_or_try_init(|| {
-                let mut ctx = TsconfigResolveContext::default();
-                self.load_tsconfig(
-                    true,
-                    &tsconfig_options.config_file,
-                    tsconfig_options.references,
-                    &mut ctx,
-                )
-                .map(Some)
-            })
-            .cloned()
+        // Keyed by the options rather than memoized on a path, so that resolvers with other
+        // options sharing the cache do not see this tsconfig.
+        let key = (
+            tsconfig_options.config_file.clone(),
+            matches!(tsconfig_options.references, TsconfigReferences::Auto),
+        );
+        if let Some(tsconfig) = self.cache.manual_tsconfigs.get(&key) {
+            return Ok(Some(Arc::clone(&tsconfig)));
+        }
+        let mut ctx = TsconfigResolveContext::default();
+        let tsconfig = self.load_tsconfig(
+            true,
+            &tsconfig_options.config_file,
+            tsconfig_options.references,
+            &mut ctx,
+        )?;
+        Ok(Some(Arc::clone(&self.cache.manual_tsconfigs.entry(key).or_insert(tsconfig))))
     }
 
     /// Resolve `tsconfig`.
@@ -196,6 +216,100 @@ impl ResolverImpl {
         self.load_tsconfig(true, path, references, &mut TsconfigResolveContext::default())
     }
 
+    /// Resolve `tsconfig` like [ResolverImpl::resolve_tsconfig], checking `cancel` before
+    /// each `extends` hop and each referenced project is loaded.
+    ///
+    /// Nothing is cached for a cancelled load, so a later call starts over.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::Cancelled] when `cancel` is set before the load completes.
+    /// * See [ResolveError]
+    pub fn resolve_tsconfig_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Arc<TsConfig>, ResolveError> {
+        let path = path.as_ref();
+        let references = match &self.options.tsconfig {
+            Some(TsconfigDiscovery::Manual(o)) => o.references,
+            Some(TsconfigDiscovery::Auto) => TsconfigReferences::Auto,
+            None => TsconfigReferences::Disabled,
+        };
+        let mut ctx = TsconfigResolveContext { cancel, ..TsconfigResolveContext::default() };
+        ctx.check_cancelled()?;
+        self.load_tsconfig(true, path, references, &mut ctx)
+    }
+
+    /// Load the tsconfig at `path` and the closure of its project references, and report
+    /// missing references, reference cycles and referenced projects which are not `composite`.
+    ///
+    /// References are followed regardless of [TsconfigOptions::references]. Nothing is
+    /// cached, so resolution is not affected.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::TsconfigNotFound] when the tsconfig at `path` does not exist.
+    /// * Any other error loading a tsconfig in the closure, including its `extends`.
+    pub fn validate_tsconfig_project<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<ProjectGraphReport, ResolveError> {
+        let tsconfig = self.read_project_tsconfig(path.as_ref())?;
+        let mut report = ProjectGraphReport::default();
+        let mut visited = FxHashSet::default();
+        self.validate_project_references(&tsconfig, &mut vec![], &mut visited, &mut report)?;
+        Ok(report)
+    }
+
+    /// Read a tsconfig with its `extends` applied, which may set `composite`.
+    fn read_project_tsconfig(&self, path: &Path) -> Result<TsConfig, ResolveError> {
+        let mut tsconfig = self.cache.read_tsconfig(/* root */ true, path)?;
+        let directory = self.cache.value(tsconfig.directory());
+        self.extend_tsconfig(&directory, &mut tsconfig, &mut TsconfigResolveContext::default())?;
+        Ok(tsconfig)
+    }
+
+    /// Depth first search of the references of `tsconfig`, where `stack` holds the path to it.
+    fn validate_project_references(
+        &self,
+        tsconfig: &TsConfig,
+        stack: &mut Vec<PathBuf>,
+        visited: &mut FxHashSet<PathBuf>,
+        report: &mut ProjectGraphReport,
+    ) -> Result<(), ResolveError> {
+        visited.insert(tsconfig.path().to_path_buf());
+        stack.push(tsconfig.path().to_path_buf());
+        for reference in &tsconfig.references {
+            let reference_path = tsconfig.directory().normalize_with(&reference.path);
+            let referenced = match self.read_project_tsconfig(&reference_path) {
+                Ok(referenced) => referenced,
+                Err(ResolveError::TsconfigNotFound(path)) if path == reference_path => {
+                    report.missing.push(MissingProjectReference {
+                        path: reference.path.clone(),
+                        referenced_by: tsconfig.path().to_path_buf(),
+                    });
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            let path = referenced.path();
+            if !report.references.iter().any(|p| p == path) {
+                report.references.push(path.to_path_buf());
+                if referenced.compiler_options.composite != Some(true) {
+                    report.non_composite.push(path.to_path_buf());
+                }
+            }
+            if let Some(position) = stack.iter().position(|p| p == path) {
+                report.cycles.push(stack[position..].to_vec());
+            } else if !visited.contains(path) {
+                self.validate_project_references(&referenced, stack, visited, report)?;
+            }
+        }
+        stack.pop();
+        Ok(())
+    }
+
     fn load_tsconfig(
         &self,
         root: bool,
@@ -227,6 +341,7 @@ impl ResolverImpl {
             if !extended_tsconfig_paths.is_empty() {
                 ctx.with_extended_file(tsconfig.path().to_owned(), |ctx| {
                     for extended_tsconfig_path in extended_tsconfig_paths.into_iter().rev() {
+                        ctx.check_cancelled()?;
                         let extended_tsconfig = self.load_tsconfig(
                             /* root */ false,
                             &extended_tsconfig_path,
@@ -239,10 +354,16 @@ impl ResolverImpl {
                 })?;
             }
 
+            // Defaults apply to the loaded config only, after the configs it extends.
+            if root && tsconfig.is_jsconfig() {
+                tsconfig.compiler_options.allow_js.get_or_insert(true);
+            }
+
             if tsconfig.load_references(references) {
                 let path = tsconfig.path().to_path_buf();
                 let directory = tsconfig.directory().to_path_buf();
                 for reference in &tsconfig.references {
+                    ctx.check_cancelled()?;
                     let reference_tsconfig_path = directory.normalize_with(&reference.path);
                     let referenced_tsconfig = self.cache.get_tsconfig(
                         /* root */ true,
@@ -281,6 +402,7 @@ impl ResolverImpl {
         // Iterate in reverse so that later `extends` entries take precedence —
         // see comment in `load_tsconfig`.
         for extended_tsconfig_path in extended_tsconfig_paths.into_iter().rev() {
+            ctx.check_cancelled()?;
             let extended_tsconfig = self.load_tsconfig(
                 /* root */ false,
                 &extended_tsconfig_path,
@@ -308,32 +430,51 @@ impl ResolverImpl {
             return Ok(None);
         }
         let Some(tsconfig) = tsconfig else { return Ok(None) };
-        let paths = match &self.options.tsconfig {
+        let paths_owner = match &self.options.tsconfig {
             // Do not resolve against project references because its already resolved during
             // initialization phase.
-            Some(TsconfigDiscovery::Auto) => tsconfig.resolve_path_alias(specifier),
+            Some(TsconfigDiscovery::Auto) => tsconfig,
             Some(TsconfigDiscovery::Manual(o))
                 if matches!(o.references, TsconfigReferences::Disabled) =>
             {
-                tsconfig.resolve_path_alias(specifier)
+                tsconfig
             }
             Some(TsconfigDiscovery::Manual(o))
                 if matches!(o.references, TsconfigReferences::Auto) =>
             {
                 if ctx.resolve_file {
                     // This is the solution tsconfig, resolve directly.
-                    tsconfig.resolve_path_alias(specifier)
+                    tsconfig
                 } else {
                     // This is the manually provided tsconfig, resolve against project references..
-                    tsconfig.resolve_references_then_self_paths(cached_path.path(), specifier)
+                    tsconfig.paths_owner(cached_path.path())
                 }
             }
             None | Some(TsconfigDiscovery::Manual(_)) => return Ok(None),
         };
-        for path in paths {
+        if self.options.tsconfig_respect_include
+            && !self.cache.tsconfig_includes_directory(paths_owner, cached_path)
+        {
+            return Ok(None);
+        }
+        let paths = paths_owner.resolve_path_alias(specifier);
+        // tsc only falls back to `baseUrl` when no `paths` pattern matched.
+        let skip_base_url = paths.is_some() && self.options.tsconfig_paths_strict_fallback;
+        for path in paths.into_iter().flatten() {
+            ctx.add_step("tsconfig paths", &path, StepOutcome::Matched(specifier.to_string()));
             let resolved_path = self.cache.value(&path);
             if let Some(resolution) =
-                self.load_as_file_or_directory(&resolved_path, ".", Some(tsconfig), ctx)?
+                self.load_tsconfig_paths_candidate(&resolved_path, tsconfig, ctx)?
+            {
+                return Ok(Some(resolution));
+            }
+        }
+        // Targets naming a package, e.g. `"lodash": ["lodash-es"]`, are looked up in
+        // `node_modules` when they are not a path under the base path either.
+        for package_path in paths_owner.resolve_package_path_alias(specifier) {
+            let Some(package_specifier) = package_path.to_str() else { continue };
+            if let Some(resolution) =
+                self.load_tsconfig_package_path(paths_owner.directory(), package_specifier, ctx)?
             {
                 return Ok(Some(resolution));
             }
@@ -344,10 +485,10 @@ impl ResolverImpl {
             {
                 return Ok(Some(path));
             }
-        } else if let Some(path) = tsconfig.resolve_base_url(specifier) {
+        } else if !skip_base_url && let Some(path) = tsconfig.resolve_base_url(specifier) {
             let resolved_path = self.cache.value(&path);
             if let Some(resolution) =
-                self.load_as_file_or_directory(&resolved_path, ".", Some(tsconfig), ctx)?
+                self.load_tsconfig_paths_candidate(&resolved_path, tsconfig, ctx)?
             {
                 return Ok(Some(resolution));
             }
@@ -355,6 +496,52 @@ impl ResolverImpl {
         Ok(None)
     }
 
+    /// Load a `paths` or `baseUrl` candidate the same way as a package directory in
+    /// `node_modules`, so the alias fields of the package the candidate lands in also apply to
+    /// the directory itself, not only to the files tried within it.
+    fn load_tsconfig_paths_candidate(
+        &self,
+        cached_path: &CachedPath,
+        tsconfig: &TsConfig,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        if self.options.resolve_to_context {
+            return self.load_as_file_or_directory(cached_path, ".", Some(tsconfig), ctx);
+        }
+        if let Some(path) = self.load_as_file(cached_path, Some(tsconfig), ctx)? {
+            return Ok(Some(path));
+        }
+        if self.is_dir_impl(cached_path, ctx) {
+            if let Some(path) =
+                self.load_browser_field_or_alias(cached_path, Some(tsconfig), ctx)?
+            {
+                return Ok(Some(path));
+            }
+            return self.load_as_directory(cached_path, Some(tsconfig), ctx);
+        }
+        Ok(None)
+    }
+
+    /// Resolve the `paths` target `specifier`, a package name, through `node_modules` from
+    /// `directory`.
+    fn load_tsconfig_package_path(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let cached_path = self.cache.value(directory);
+        let fully_specified = ctx.fully_specified;
+        // Without the tsconfig, so `"*": ["*"]` does not map the target onto itself again.
+        let resolved = self.require_bare(&cached_path, specifier, None, ctx);
+        ctx.with_fully_specified(fully_specified);
+        match resolved {
+            Ok(path) => Ok(Some(path)),
+            Err(ResolveError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     pub(crate) fn load_tsconfig_root_dirs(
         &self,
         cached_path: &CachedPath,
@@ -367,7 +554,7 @@ impl ResolverImpl {
         let Some(root_dirs) = &tsconfig.compiler_options.root_dirs else { return Ok(None) };
 
         // Use the containing directory, not the file itself
-        let containing_directory = if self.is_dir(cached_path, ctx) {
+        let containing_directory = if self.is_dir_impl(cached_path, ctx) {
             cached_path.clone()
         } else {
             cached_path.parent(&self.cache).unwrap_or_else(|| cached_path.clone())
@@ -425,13 +612,10 @@ impl ResolverImpl {
             #[cfg(feature = "yarn_pnp")]
             cwd: self.options.cwd.clone(),
             ..ResolveOptions::default()
-        }
-        .sanitize();
-        let alias = crate::alias::compile_alias(&options.alias);
-        let fallback = crate::alias::compile_alias(&options.fallback);
+        };
         // Extends-resolution never toggles `yarn_pnp`, so reuse the same cache (and thus the
         // same underlying filesystem) rather than rebuilding it.
-        Self { options, cache: Arc::clone(&self.cache), alias, fallback }
+        Self::new(options, Arc::clone(&self.cache))
     }
 
     fn get_extended_tsconfig_path(
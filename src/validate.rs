@@ -0,0 +1,319 @@
+//! Diagnostics for [ResolveOptions] combinations which are accepted but behave surprisingly,
+//! see [ResolveOptions::validate], and the [ResolverBuilder] rejecting them.
+
+use std::{cfg_select, fmt};
+
+use crate::{
+    Alias, FileSystem, FileSystemOs, ResolveOptions, Resolver, ResolverGeneric, Restriction,
+    TsconfigDiscovery,
+};
+
+/// Severity of an [OptionsDiagnostic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    /// The options are redundant or misleading, resolution works as configured.
+    Warning,
+    /// The options can not work as intended, e.g. an option is ignored or no file resolves.
+    Error,
+}
+
+/// A problem with a combination of [ResolveOptions], returned by [ResolveOptions::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsDiagnostic {
+    pub severity: DiagnosticSeverity,
+
+    /// Names of the involved [ResolveOptions] fields, e.g. `["fully_specified", "enforce_extension"]`.
+    pub fields: Vec<&'static str>,
+
+    pub message: String,
+
+    /// How to fix the options.
+    pub suggestion: Option<String>,
+}
+
+impl OptionsDiagnostic {
+    fn warning(fields: &[&'static str], message: String, suggestion: Option<String>) -> Self {
+        Self { severity: DiagnosticSeverity::Warning, fields: fields.to_vec(), message, suggestion }
+    }
+
+    fn error(fields: &[&'static str], message: String, suggestion: Option<String>) -> Self {
+        Self { severity: DiagnosticSeverity::Error, fields: fields.to_vec(), message, suggestion }
+    }
+}
+
+impl fmt::Display for OptionsDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Error => "error",
+        };
+        write!(f, "{severity} [{}]: {}", self.fields.join(", "), self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({suggestion})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a resolver from [ResolveOptions] without the errors [ResolveOptions::validate] finds.
+///
+/// [ResolverGeneric::new] keeps using the options as given, this is the strict alternative.
+/// Warnings do not fail the build, see [ResolverBuilder::warnings].
+///
+/// ## Examples
+///
+/// ```
+/// use oxc_resolver::{ResolveOptions, ResolverBuilder};
+///
+/// let options = ResolveOptions { extensions: vec!["js".into()], ..ResolveOptions::default() };
+/// let errors = ResolverBuilder::new(options).build().unwrap_err();
+/// assert_eq!(errors[0].fields, ["extensions"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResolverBuilder {
+    options: ResolveOptions,
+}
+
+impl ResolverBuilder {
+    #[must_use]
+    pub const fn new(options: ResolveOptions) -> Self {
+        Self { options }
+    }
+
+    /// The diagnostics of [DiagnosticSeverity::Warning], which are accepted by the build.
+    #[must_use]
+    pub fn warnings(&self) -> Vec<OptionsDiagnostic> {
+        let mut diagnostics = self.options.validate();
+        diagnostics.retain(|diagnostic| diagnostic.severity == DiagnosticSeverity::Warning);
+        diagnostics
+    }
+
+    /// Build the same resolver as [ResolverGeneric::new].
+    ///
+    /// # Errors
+    ///
+    /// * The diagnostics of [DiagnosticSeverity::Error] when there are any.
+    pub fn build(self) -> Result<Resolver, Vec<OptionsDiagnostic>> {
+        let file_system = self.options.os_file_system();
+        self.build_with_file_system(file_system)
+    }
+
+    /// Build the same resolver as [ResolverGeneric::new_with_file_system].
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolverBuilder::build]
+    pub fn build_with_file_system<Fs: FileSystem + 'static>(
+        self,
+        file_system: Fs,
+    ) -> Result<ResolverGeneric<Fs>, Vec<OptionsDiagnostic>> {
+        self.check(&file_system)?;
+        Ok(ResolverGeneric::new_with_file_system(file_system, self.options))
+    }
+
+    fn check(&self, file_system: &dyn FileSystem) -> Result<(), Vec<OptionsDiagnostic>> {
+        let mut diagnostics = self.options.validate_with_file_system(file_system);
+        diagnostics.retain(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error);
+        if diagnostics.is_empty() { Ok(()) } else { Err(diagnostics) }
+    }
+}
+
+impl ResolveOptions {
+    /// Check for option combinations which are accepted but do not behave as their author likely
+    /// intended. The resolver never calls this, options are used as given; [ResolverBuilder]
+    /// rejects the errors.
+    ///
+    /// Returns an empty list for sound options.
+    #[must_use]
+    pub fn validate(&self) -> Vec<OptionsDiagnostic> {
+        self.validate_with_file_system(&self.os_file_system())
+    }
+
+    /// [ResolveOptions::validate] looking up the configured paths in `file_system`.
+    #[must_use]
+    pub fn validate_with_file_system(
+        &self,
+        file_system: &dyn FileSystem,
+    ) -> Vec<OptionsDiagnostic> {
+        let mut diagnostics = vec![];
+        self.validate_extensions(&mut diagnostics);
+        self.validate_fields(&mut diagnostics);
+        self.validate_paths(file_system, &mut diagnostics);
+        validate_alias("alias", &self.alias, &mut diagnostics);
+        validate_alias("fallback", &self.fallback, &mut diagnostics);
+        diagnostics
+    }
+
+    fn validate_extensions(&self, diagnostics: &mut Vec<OptionsDiagnostic>) {
+        for extension in &self.extensions {
+            if extension == "." {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["extensions"],
+                    "Extension \".\" has no name, it only appends a dot".into(),
+                    Some("use \"\" to also try the request as written".into()),
+                ));
+            } else if !extension.is_empty() && !extension.starts_with('.') {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["extensions"],
+                    format!("Extension {extension:?} does not start with a dot"),
+                    Some(format!("use \".{extension}\"")),
+                ));
+            }
+        }
+        if self.enforce_extension.is_enabled() {
+            if self.extensions.is_empty() {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["enforce_extension", "extensions"],
+                    "No file can be resolved, `enforce_extension` requires an extension from the empty `extensions`".into(),
+                    Some("add extensions or disable `enforce_extension`".into()),
+                ));
+            }
+            if self.fully_specified {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["fully_specified", "enforce_extension"],
+                    "Requests can not resolve to a file, they are not given the extension `enforce_extension` requires".into(),
+                    Some("disable `enforce_extension`".into()),
+                ));
+            }
+        }
+        if self.resolve_to_context && !self.extension_alias.is_empty() {
+            diagnostics.push(OptionsDiagnostic::warning(
+                &["resolve_to_context", "extension_alias"],
+                "`extension_alias` is not applied, `resolve_to_context` resolves directories"
+                    .into(),
+                Some("remove `extension_alias`".into()),
+            ));
+        }
+    }
+
+    fn validate_fields(&self, diagnostics: &mut Vec<OptionsDiagnostic>) {
+        for alias_field in &self.alias_fields {
+            if let [field] = alias_field.as_slice()
+                && !self.main_fields.contains(field)
+            {
+                diagnostics.push(OptionsDiagnostic::warning(
+                    &["alias_fields", "main_fields"],
+                    format!(
+                        "A string {field:?} field in package.json is not used as the entry point, {field:?} is not in `main_fields`"
+                    ),
+                    Some(format!("add {field:?} to `main_fields` before \"main\"")),
+                ));
+            }
+        }
+        if self.prefer_relative && self.prefer_absolute {
+            diagnostics.push(OptionsDiagnostic::warning(
+                &["prefer_relative", "prefer_absolute"],
+                "`prefer_relative` tries bare requests as relative paths first while `prefer_absolute` tries absolute requests in `roots` first".into(),
+                Some("enable only the preference matching how requests are written".into()),
+            ));
+        }
+        if self.condition_names.iter().any(|name| name == "default") {
+            diagnostics.push(OptionsDiagnostic::warning(
+                &["condition_names"],
+                "The \"default\" condition always matches, listing it has no effect".into(),
+                Some("remove \"default\" from `condition_names`".into()),
+            ));
+        }
+    }
+
+    fn os_file_system(&self) -> FileSystemOs {
+        cfg_select! {
+            feature = "yarn_pnp" => FileSystemOs::new(self.yarn_pnp),
+            _ => FileSystemOs::new(),
+        }
+    }
+
+    fn validate_paths(
+        &self,
+        file_system: &dyn FileSystem,
+        diagnostics: &mut Vec<OptionsDiagnostic>,
+    ) {
+        for restriction in &self.restrictions {
+            let Restriction::Path(path) = restriction else { continue };
+            if path.as_os_str().is_empty() {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["restrictions"],
+                    "No path can pass an empty path restriction".into(),
+                    Some("use an absolute directory".into()),
+                ));
+            } else if path.is_relative() {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["restrictions"],
+                    format!(
+                        "No path can pass the relative path restriction \"{}\", resolved paths are absolute",
+                        path.display()
+                    ),
+                    Some("use an absolute directory".into()),
+                ));
+            }
+        }
+        if let Some(TsconfigDiscovery::Manual(tsconfig)) = &self.tsconfig
+            && tsconfig.config_file.is_relative()
+            && self.cwd.is_none()
+        {
+            diagnostics.push(OptionsDiagnostic::warning(
+                &["tsconfig", "cwd"],
+                format!(
+                    "The relative tsconfig \"{}\" is read from the working directory of the process",
+                    tsconfig.config_file.display()
+                ),
+                Some("use an absolute `config_file`".into()),
+            ));
+        }
+        if let Some(TsconfigDiscovery::Manual(tsconfig)) = &self.tsconfig {
+            let config_file = &tsconfig.config_file;
+            let path =
+                self.cwd.as_ref().map_or_else(|| config_file.clone(), |cwd| cwd.join(config_file));
+            if config_file.extension().is_none_or(|extension| extension != "json")
+                && file_system.metadata(&path).is_err()
+            {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["tsconfig"],
+                    format!(
+                        "The tsconfig \"{}\" is neither a json file nor an existing directory",
+                        config_file.display()
+                    ),
+                    Some("use the path of a tsconfig.json or of its directory".into()),
+                ));
+            }
+        }
+        for root in &self.roots {
+            if root.is_relative() {
+                diagnostics.push(OptionsDiagnostic::error(
+                    &["roots"],
+                    format!("Root \"{}\" is not an absolute path", root.display()),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+/// An alias entry is never used when an earlier entry matches all of its requests:
+/// the same key, or a prefix key before the exact (`$`) key of the same name.
+fn validate_alias(field: &'static str, alias: &Alias, diagnostics: &mut Vec<OptionsDiagnostic>) {
+    for (index, (key, _)) in alias.iter().enumerate() {
+        let earlier = &alias[..index];
+        if key.is_empty() || key == "$" {
+            diagnostics.push(OptionsDiagnostic::error(
+                &[field],
+                format!("Key {key:?} is empty, it does not name a request to alias"),
+                None,
+            ));
+        } else if earlier.iter().any(|(earlier_key, _)| earlier_key == key) {
+            diagnostics.push(OptionsDiagnostic::warning(
+                &[field],
+                format!("Key {key:?} is declared more than once, only the first entry is used"),
+                Some("merge the values into the first entry".into()),
+            ));
+        } else if let Some(exact_key) = key.strip_suffix('$')
+            && earlier.iter().any(|(earlier_key, _)| earlier_key == exact_key)
+        {
+            diagnostics.push(OptionsDiagnostic::warning(
+                &[field],
+                format!("Key {key:?} is shadowed by the earlier key {exact_key:?}"),
+                Some(format!("move {key:?} before {exact_key:?}")),
+            ));
+        }
+    }
+}
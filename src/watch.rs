@@ -0,0 +1,61 @@
+//! Watchable locations of the dependencies of resolutions, see [ResolveContext::watch_targets].
+
+use std::path::PathBuf;
+
+use rustc_hash::FxHashSet;
+
+use crate::{FileMetadata, ResolveContext, ResolverImpl, context::ResolveContext as Ctx};
+
+/// The locations to watch for changes which may alter the resolutions recorded in a
+/// [ResolveContext].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WatchTargets {
+    /// Real paths of the file dependencies, and the symbolic links through which they were
+    /// reached so that re-pointing a link is noticed.
+    pub files: FxHashSet<PathBuf>,
+
+    /// The closest existing ancestor directory of each missing dependency, where it may be
+    /// created.
+    pub directories: FxHashSet<PathBuf>,
+}
+
+impl ResolveContext {
+    /// Map the [ResolveContext::file_dependencies] and [ResolveContext::missing_dependencies]
+    /// to [WatchTargets], using the cache of the `resolver` which recorded them.
+    ///
+    /// Paths probed during resolution are already in the cache, so this does not hit the
+    /// file system for them.
+    pub fn watch_targets(&self, resolver: &ResolverImpl) -> WatchTargets {
+        let cache = &resolver.cache;
+        let mut targets = WatchTargets::default();
+        for dependency in &self.file_dependencies {
+            let cached_path = cache.value(dependency);
+            match cache.canonicalize(&cached_path, &mut Ctx::default()) {
+                Ok(real_path) if real_path != *dependency => {
+                    targets.files.insert(real_path);
+                    let mut path = Some(cached_path);
+                    while let Some(link) = path {
+                        if link.link_metadata(cache).is_some_and(FileMetadata::is_symlink) {
+                            targets.files.insert(link.to_path_buf());
+                        }
+                        path = link.parent(cache);
+                    }
+                }
+                _ => {
+                    targets.files.insert(dependency.clone());
+                }
+            }
+        }
+        for dependency in &self.missing_dependencies {
+            let mut path = cache.value(dependency).parent(cache);
+            while let Some(directory) = path {
+                if cache.exists(&directory) {
+                    targets.directories.insert(directory.to_path_buf());
+                    break;
+                }
+                path = directory.parent(cache);
+            }
+        }
+        targets
+    }
+}
@@ -1,7 +1,16 @@
-use std::path::PathBuf;
+//! Windows path handling.
+//!
+//! Only `metadata` needs the Windows API, the path conversions are compiled on every platform so
+//! that they are tested everywhere.
 
+#[cfg(target_os = "windows")]
+use std::path::Path;
+use std::{borrow::Cow, path::PathBuf};
+
+#[cfg(target_os = "windows")]
 mod metadata;
 
+#[cfg(target_os = "windows")]
 pub use metadata::{SymlinkMetadata, symlink_metadata};
 
 use crate::ResolveError;
@@ -27,7 +36,7 @@ pub fn strip_windows_prefix(path: PathBuf) -> Result<PathBuf, ResolveError> {
         path_bytes.strip_prefix(br"\\?\").or_else(|| path_bytes.strip_prefix(br"\\.\"))
     {
         // Assuming traditional DOS path "\\?\C:\"
-        if p[1] != b':' {
+        if p.get(1) != Some(&b':') {
             // E.g.,
             // \\?\Volume{b75e2c83-0000-0000-0000-602f00000000}
             // \\?\BootPartition\
@@ -48,6 +57,46 @@ pub fn strip_windows_prefix(path: PathBuf) -> Result<PathBuf, ResolveError> {
     Ok(path)
 }
 
+/// Normalizes a specifier starting with a Windows path prefix for [crate::ResolverImpl::resolve].
+///
+/// UNC paths written with `/` (`//server/share/file.js`) are converted to `\\server\share\file.js`,
+/// and DOS device paths to normal paths with [strip_windows_prefix].
+///
+/// # Errors
+///
+/// * [ResolveError::PathNotSupported] for a drive-relative path such as `C:file.js`, which is
+///   relative to the current directory of the drive, or a DOS device path which cannot be
+///   represented as a normal path.
+pub fn normalize_specifier(specifier: &str) -> Result<Cow<'_, str>, ResolveError> {
+    let is_separator = |b: &u8| matches!(b, b'/' | b'\\');
+    match specifier.as_bytes() {
+        [drive, b':', rest @ ..]
+            if drive.is_ascii_alphabetic() && !rest.first().is_some_and(is_separator) =>
+        {
+            Err(ResolveError::PathNotSupported(PathBuf::from(specifier)))
+        }
+        [a, b, ..] if is_separator(a) && is_separator(b) => {
+            let path = strip_windows_prefix(PathBuf::from(specifier.replace('/', "\\")))?;
+            Ok(Cow::Owned(path.to_string_lossy().into_owned()))
+        }
+        _ => Ok(Cow::Borrowed(specifier)),
+    }
+}
+
+/// Converts an absolute path to its extended-length (`\\?\`) form, which is not subject to
+/// `MAX_PATH`. Returns `None` for a relative path or a path which already is a device path.
+#[cfg(target_os = "windows")]
+pub fn to_verbatim_path(path: &Path) -> Option<PathBuf> {
+    let path = path.to_str()?.replace('/', "\\");
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return None;
+    }
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(PathBuf::from(format!(r"\\?\UNC\{unc}")));
+    }
+    Path::new(&path).is_absolute().then(|| PathBuf::from(format!(r"\\?\{path}")))
+}
+
 #[test]
 fn test_strip_windows_prefix() {
     let pass = [
@@ -75,3 +124,54 @@ fn test_strip_windows_prefix() {
         );
     }
 }
+
+#[test]
+fn test_normalize_specifier() {
+    let pass = [
+        (r"C:\project\src\a.js", r"C:\project\src\a.js"),
+        ("C:/project/src/a.js", "C:/project/src/a.js"),
+        (r"\\?\C:\project\src\a.js", r"C:\project\src\a.js"),
+        ("//?/C:/project/src/a.js", r"C:\project\src\a.js"),
+        (r"\\.\C:\project\src\a.js", r"C:\project\src\a.js"),
+        ("//server/share/file.js", r"\\server\share\file.js"),
+        (r"\\server\share/file.js", r"\\server\share\file.js"),
+        (r"\\?\UNC\server\share\file.js", r"\\server\share\file.js"),
+        ("/project/src/a.js", "/project/src/a.js"),
+    ];
+
+    for (specifier, expected) in pass {
+        assert_eq!(normalize_specifier(specifier).as_deref(), Ok(expected), "{specifier}");
+    }
+
+    let fail = [
+        ("C:a.js", "C:a.js"),
+        ("C:", "C:"),
+        (r"c:..\a.js", r"c:..\a.js"),
+        (
+            r"\\?\Volume{c8ec34d8-3ba6-45c3-9b9d-3e4148e12d00}\a.js",
+            r"\\?\Volume{c8ec34d8-3ba6-45c3-9b9d-3e4148e12d00}\a.js",
+        ),
+        ("//?/C", r"\\?\C"),
+    ];
+
+    for (specifier, path) in fail {
+        let error = ResolveError::PathNotSupported(PathBuf::from(path));
+        assert_eq!(normalize_specifier(specifier), Err(error), "{specifier}");
+    }
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn test_to_verbatim_path() {
+    let pass = [
+        (r"C:\Users\user\file.txt", Some(r"\\?\C:\Users\user\file.txt")),
+        ("C:/Users/user/file.txt", Some(r"\\?\C:\Users\user\file.txt")),
+        (r"\\server\share\file.txt", Some(r"\\?\UNC\server\share\file.txt")),
+        (r"\\?\C:\Users\user\file.txt", None),
+        (r"Users\user\file.txt", None),
+    ];
+
+    for (path, expected) in pass {
+        assert_eq!(to_verbatim_path(Path::new(path)), expected.map(PathBuf::from), "{path}");
+    }
+}